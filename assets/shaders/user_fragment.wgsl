@@ -0,0 +1,19 @@
+#define_import_path hirundo::user_fragment
+
+// What a `HirundoPlugin::with_custom_fragment` shader receives, once every built-in
+// effect has run. Its module declares `#define_import_path hirundo::custom_fragment`
+// and defines `fn custom_fragment(input: UserFragmentInput) -> vec4<f32>`.
+struct UserFragmentInput {
+    // Color after all built-in effects (straight alpha)
+    color: vec4<f32>,
+    // Atlas texel before any effect
+    sampled: vec4<f32>,
+    // 0..1 across the sprite, after UV-warping fragment effects
+    uv: vec2<f32>,
+    // Where in the atlas `sampled` was read
+    atlas_uv: vec2<f32>,
+    // Framebuffer position in pixels
+    frag_coord: vec2<f32>,
+    // Effect time, follows the VfxTimeline when it's frozen
+    time: f32,
+}
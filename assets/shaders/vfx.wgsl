@@ -1,92 +1,20 @@
-#import bevy_render::globals::Globals;
+#ifdef VFX_3D
+#import bevy_pbr::{mesh_functions, mesh_view_bindings::view};
+#else
 #import bevy_sprite::mesh2d_functions;
-
-const PI: f32 = 3.141592653589793;
-const MAX_FX: u32 = 6;
-const MAX_SPATIAL_FX: u32 = 3;
-const MAX_COLOR_FX: u32 = 3;
-
-struct AtlasDimensions {
-    texture_size: vec2<f32>,
-    cell_size: vec2<f32>,
-    sprite_size: vec2<f32>,
-    padding: vec2<f32>,
-}
-
-struct EffectLifetime { 
-    enabled: u32, 
-    looping: u32, 
-    start_time: f32, 
-    duration: f32 
-}
-
-struct Phase { 
-    start: f32, 
-    end: f32, 
-    _padding: vec2<f32> 
-}
-
-struct Envelope { 
-    attack: f32, 
-    hold: f32, 
-    release: f32, 
-    growth_mode: u32, 
-    growth: f32, 
-    enabled: u32, 
-    decay_mode: u32, 
-    decay: f32 
-}
-
-struct Wave {
-    kind: u32, 
-    freq: f32, 
-    amp: f32, 
-    bias: f32, 
-    phase: f32,
-    _pad0: f32,
-    _pad1: f32,
-    _pad2: f32,
-    amp_envelope: Envelope,
-    freq_envelope: Envelope,
-}
-
-struct ColorEffect {
-    phase: Phase,
-    wave: Wave,
-    color: vec4<f32>,
-    blend_mode: u32,
-}
-
-struct AlphaEffect {
-    phase: Phase,
-    wave: Wave,
-    target_alpha: f32,
-    _pad0: f32,
-    _pad1: f32,
-    _pad2: f32,
-}
-
-struct SpatialEffect {
-    phase: Phase,
-    wave: Wave,
-    manipulation: u32,
-    intensity: f32,
-    anchor: vec2<f32>,
-}
-
-struct Effect {
-    lifetime: EffectLifetime,
-    color_effects: array<ColorEffect, MAX_COLOR_FX>,
-    alpha_effect: AlphaEffect,
-    spatial_effects: array<SpatialEffect, MAX_SPATIAL_FX>,
-}
-
-struct EffectStack {
-    sprite_index: u32,
-    _pad0: u32,
-    _pad1: u32,
-    _pad2: u32,
-    effects: array<Effect, MAX_FX>,
+#endif
+#ifdef HIRUNDO_USER_FRAGMENT
+#import hirundo::user_fragment::UserFragmentInput;
+#import hirundo::custom_fragment::custom_fragment;
+#endif
+#import hirundo::vfx_common::{
+    MAX_SPATIAL_FX, MAX_COLOR_FX, MAX_ALPHA_FX, MAX_FRAGMENT_FX, FX_SLOTS, COLOR_SLOTS,
+    ALPHA_SLOTS, FRAGMENT_SLOTS, MAX_PATH_VEC4S, EffectLifetime, Phase, Envelope, Wave,
+    ColorEffect, AlphaEffect, SpatialEffect, FragmentEffect, TileCycle, Effect, EffectStack,
+    texture, texture_sampler, atlas_dims, lod, sprite_px, get_sprite_rect,
+    get_atlas_uv_offset, get_sprite_uv_scale, vfx_time, master_lifetime, phase_lifetime,
+    eval_wave, rgb_to_hsv, hsv_to_rgb, apply_spatial, cycled_sprite_index, apply_alpha_clip,
+    apply_fragment_uv, apply_fragment_effect,
 }
 
 struct Varyings {
@@ -99,298 +27,211 @@ struct Varyings {
     @location(5) @interpolate(flat) hsv: vec4<f32>,
     @location(6) @interpolate(flat) atlas_uv_offset: vec2<f32>,
     @location(7) @interpolate(flat) uv_scale: vec2<f32>,
+    // Resolved fragment effects: kind per slot, color, params (w = wave strength)
+    @location(8) @interpolate(flat) frag_kinds: vec2<u32>,
+    @location(9) @interpolate(flat) frag_color0: vec4<f32>,
+    @location(10) @interpolate(flat) frag_params0: vec4<f32>,
+    @location(11) @interpolate(flat) frag_color1: vec4<f32>,
+    @location(12) @interpolate(flat) frag_params1: vec4<f32>,
+    // Alpha clip: x = mode (0 blend, 1 cutout, 2 dither), y = cutout threshold
+    @location(13) @interpolate(flat) alpha_clip: vec2<f32>,
 }
 
-@group(0) @binding(1) var<uniform> globals: Globals;
-@group(2) @binding(0) var texture: texture_2d<f32>;
-@group(2) @binding(1) var texture_sampler: sampler;
-@group(2) @binding(2) var<storage, read> effect_storage: array<EffectStack>;
-@group(2) @binding(3) var<uniform> atlas_dims: AtlasDimensions;
-
-// Helper to calculate atlas UV offset from sprite index (accounts for padding)
-fn get_atlas_uv_offset(sprite_index: u32) -> vec2<f32> {
-    let sprites_per_row = floor(atlas_dims.texture_size.x / atlas_dims.cell_size.x);
-    let col = f32(sprite_index) % sprites_per_row;
-    let row = floor(f32(sprite_index) / sprites_per_row);
-    
-    // Calculate cell step and padding in UV space
-    let cell_step = atlas_dims.cell_size / atlas_dims.texture_size;
-    let padding_uv = atlas_dims.padding / atlas_dims.texture_size;
-    
-    // Offset to the cell, then add padding to get to actual sprite content
-    return vec2<f32>(
-        col * cell_step.x + padding_uv.x,
-        row * cell_step.y + padding_uv.y
-    );
-}
-
-// Helper to get sprite UV scale
-fn get_sprite_uv_scale() -> vec2<f32> {
-    return atlas_dims.sprite_size / atlas_dims.texture_size;
+#ifdef VFX_DATA_TEXTURES
+// One `Rgba32Uint` row per stack, see `load_effect_stack`
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var effect_storage: texture_2d<u32>;
+#else
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<storage, read> effect_storage: array<EffectStack>;
+#endif
+
+#ifdef VFX_DATA_TEXTURES
+// Every struct in a stack starts on a 16-byte boundary, so a stack's row decodes
+// texel by texel: each texel holds four consecutive u32/f32 fields
+const COLOR_FX_TEXELS: u32 = 10u;
+const ALPHA_FX_TEXELS: u32 = 8u;
+const SPATIAL_FX_TEXELS: u32 = 9u;
+const FRAGMENT_FX_TEXELS: u32 = 10u;
+const EFFECT_TEXELS: u32 = 2u
+    + COLOR_FX_TEXELS * MAX_COLOR_FX
+    + ALPHA_FX_TEXELS * MAX_ALPHA_FX
+    + SPATIAL_FX_TEXELS * MAX_SPATIAL_FX
+    + FRAGMENT_FX_TEXELS * MAX_FRAGMENT_FX
+    + MAX_PATH_VEC4S;
+
+fn stack_texel(row: u32, texel: u32) -> vec4<u32> {
+    return textureLoad(effect_storage, vec2<u32>(texel, row), 0);
 }
 
-// Helper functions
-fn master_lifetime(t: f32, m: EffectLifetime) -> f32 {
-    if (m.enabled == 0u || m.duration <= 0.0) { return 0.0; }
-    let elapsed = t - m.start_time;
-    if (m.looping == 1u) { return fract(elapsed / m.duration); }
-    if (elapsed < 0.0 || elapsed >= m.duration) { return 0.0; }
-    return elapsed / m.duration;
+fn stack_texel_f32(row: u32, texel: u32) -> vec4<f32> {
+    return bitcast<vec4<f32>>(stack_texel(row, texel));
 }
 
-fn phase_lifetime(t: f32, p: Phase) -> f32 {
-    let s = clamp(p.start, 0.0, 1.0);
-    let e = clamp(p.end, 0.0, 1.0);
-    if (s >= e || t < s || t > e) { return 0.0; }
-    return (t - s) / (e - s);
+fn load_phase(row: u32, texel: u32) -> Phase {
+    let v = stack_texel_f32(row, texel);
+    return Phase(v.x, v.y, bitcast<u32>(v.z), v.w);
 }
 
-fn eval_envelope_integral(t: f32, env: Envelope) -> vec2<f32> {
-    if (env.enabled == 0u) {
-        return vec2<f32>(1.0, t);
-    }
-
-    let attack = env.attack;
-    let hold = env.hold;
-    let release = env.release;
-    let total = attack + hold + release;
-
-    if (total <= 0.0) {
-        return vec2<f32>(1.0, t);
-    }
-
-    let nt = clamp(t, 0.0, 1.0) * total;
-
-    var env_val: f32 = 1.0;
-    var integral_nt: f32 = 0.0;
-
-    if (nt <= attack) {
-        let phase_t = select(0.0, nt / attack, attack > 0.0);
-        
-        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
-            let s = env.growth;
-            env_val = (exp(phase_t * s) - 1.0) / (exp(s) - 1.0);
-        } else {
-            env_val = phase_t;
-        }
-        
-        integral_nt = select(0.0, (nt * nt) / (2.0 * attack), attack > 0.0);
-    } 
-    else if (nt <= attack + hold) {
-        env_val = 1.0;
-        integral_nt = (attack * 0.5) + (nt - attack);
-    } 
-    else {
-        let s = nt - attack - hold;
-        let phase_t = select(0.0, s / release, release > 0.0);
-        
-        if (env.decay_mode == 1u && abs(env.decay) > 1e-5) {
-            let d = env.decay;
-            env_val = 1.0 - (exp(phase_t * d) - 1.0) / (exp(d) - 1.0);
-        } else {
-            env_val = 1.0 - phase_t;
-        }
-        
-        integral_nt = (attack * 0.5) + hold +
-                      select(0.0, s - (s * s) / (2.0 * release), release > 0.0);
-    }
-
-    let total_area = (attack * 0.5) + hold + (release * 0.5);
-    let base_integral_norm = integral_nt / max(total_area, 1e-5);
-
-    let inst = clamp(env_val, 0.0, 1.0);
-
-    var integral_with_modulation: f32 = base_integral_norm;
-
-    if (env.growth_mode == 1u && abs(env.growth) > 1e-5 && attack > 0.0) {
-        let s = env.growth;
-        let end_t = min(nt, attack);
-        
-        let integral_attack = attack * ((exp(s * end_t / attack) - 1.0) - s * end_t / attack) / (s * (exp(s) - 1.0));
-        
-        var full_integral = attack * 0.5;
-        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
-            full_integral = attack * ((exp(s) - 1.0) - s) / (s * (exp(s) - 1.0));
-        }
-        
-        integral_with_modulation = integral_attack / max(full_integral + hold + (release * 0.5), 1e-5);
-    }
-    
-    if (nt > attack && nt <= attack + hold) {
-        let hold_contrib = (nt - attack);
-        var attack_contrib = attack * 0.5;
-        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
-            let s = env.growth;
-            attack_contrib = attack * ((exp(s) - 1.0) - s) / (s * (exp(s) - 1.0));
-        }
-        integral_with_modulation = (attack_contrib + hold_contrib) / max(attack_contrib + hold + (release * 0.5), 1e-5);
-    }
-    
-    if (nt > attack + hold && release > 0.0) {
-        let s_decay = env.decay;
-        let release_t = nt - attack - hold;
-        
-        var attack_contrib = attack * 0.5;
-        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
-            let s = env.growth;
-            attack_contrib = attack * ((exp(s) - 1.0) - s) / (s * (exp(s) - 1.0));
-        }
-        
-        var release_contrib = release_t - (release_t * release_t) / (2.0 * release);
-        if (env.decay_mode == 1u && abs(s_decay) > 1e-5) {
-            release_contrib = release * (release_t / release - ((exp(s_decay * release_t / release) - 1.0) - s_decay * release_t / release) / (s_decay * (exp(s_decay) - 1.0)));
-        }
-        
-        var full_release = release * 0.5;
-        if (env.decay_mode == 1u && abs(s_decay) > 1e-5) {
-            full_release = release * (1.0 - ((exp(s_decay) - 1.0) - s_decay) / (s_decay * (exp(s_decay) - 1.0)));
-        }
-        
-        integral_with_modulation = (attack_contrib + hold + release_contrib) / max(attack_contrib + hold + full_release, 1e-5);
-    }
-
-    return vec2<f32>(inst, clamp(integral_with_modulation, 0.0, 1.0));
+fn load_envelope(row: u32, texel: u32) -> Envelope {
+    let a = stack_texel(row, texel);
+    let b = stack_texel(row, texel + 1u);
+    return Envelope(
+        bitcast<f32>(a.x), bitcast<f32>(a.y), bitcast<f32>(a.z), a.w,
+        bitcast<f32>(b.x), b.y, b.z, bitcast<f32>(b.w),
+    );
 }
 
-fn eval_envelope(t: f32, env: Envelope) -> f32 {
-    return eval_envelope_integral(t, env).x;
+fn load_wave(row: u32, texel: u32) -> Wave {
+    let a = stack_texel(row, texel);
+    let b = stack_texel_f32(row, texel + 1u);
+    return Wave(
+        a.x, bitcast<f32>(a.y), bitcast<f32>(a.z), bitcast<f32>(a.w),
+        b.x, b.y, b.z, b.w,
+        load_envelope(row, texel + 2u),
+        load_envelope(row, texel + 4u),
+    );
 }
 
-fn eval_wave(t: f32, w: Wave) -> vec2<f32> {
-    let fi = eval_envelope_integral(t, w.freq_envelope);
-    let freq_integral = fi.y;
-
-    let cycles = w.freq * freq_integral;
-    let phase = fract(w.phase + cycles);
-    let raw_phase = phase * 2.0 * PI;
-
-    var v: f32 = 0.0;
-    switch w.kind {
-        case 0u: { v = cos(raw_phase); }
-        case 1u: { v = select(-1.0, 1.0, phase > 0.5); }
-        case 2u: { v = 2.0 * abs(2.0 * phase - 1.0) - 1.0; }
-        case 3u: { v = phase * 2.0 - 1.0; }
-        case 4u: { v = 1.0; }
-        default: { v = 0.0; }
-    }
-
-    let amp_env = eval_envelope(t, w.amp_envelope);
-    let val = v * w.amp * amp_env + w.bias;
-    return vec2<f32>(saturate(val), val);
+fn load_color_effect(row: u32, texel: u32) -> ColorEffect {
+    let tail = stack_texel(row, texel + 9u);
+    return ColorEffect(
+        load_phase(row, texel),
+        load_wave(row, texel + 1u),
+        stack_texel_f32(row, texel + 7u),
+        stack_texel_f32(row, texel + 8u),
+        tail.x,
+        bitcast<f32>(tail.y),
+    );
 }
 
-fn rgb_to_hsv(c: vec3<f32>) -> vec3<f32> {
-    let max_val = max(max(c.r, c.g), c.b);
-    let min_val = min(min(c.r, c.g), c.b);
-    let delta = max_val - min_val;
-    var hsv = vec3<f32>(0.0, 0.0, max_val);
-    if (delta > 0.0) {
-        if (max_val == c.r) { hsv.x = (c.g - c.b) / delta; }
-        else if (max_val == c.g) { hsv.x = 2.0 + (c.b - c.r) / delta; }
-        else { hsv.x = 4.0 + (c.r - c.g) / delta; }
-        hsv.x = fract(hsv.x / 6.0);
-        hsv.y = delta / max_val;
-    }
-    return hsv;
+fn load_alpha_effect(row: u32, texel: u32) -> AlphaEffect {
+    let tail = stack_texel(row, texel + 7u);
+    return AlphaEffect(
+        load_phase(row, texel),
+        load_wave(row, texel + 1u),
+        bitcast<f32>(tail.x),
+        tail.y,
+        tail.z,
+        bitcast<f32>(tail.w),
+    );
 }
 
-fn hsv_to_rgb(hsv: vec3<f32>) -> vec3<f32> {
-    let i = floor(hsv.x * 6.0);
-    let f = hsv.x * 6.0 - i;
-    let p = hsv.z * (1.0 - hsv.y);
-    let q = hsv.z * (1.0 - f * hsv.y);
-    let t = hsv.z * (1.0 - (1.0 - f) * hsv.y);
-    switch i32(i) {
-        case 0: { return vec3<f32>(hsv.z, t, p); }
-        case 1: { return vec3<f32>(q, hsv.z, p); }
-        case 2: { return vec3<f32>(p, hsv.z, t); }
-        case 3: { return vec3<f32>(p, q, hsv.z); }
-        case 4: { return vec3<f32>(t, p, hsv.z); }
-        default: { return vec3<f32>(hsv.z, p, q); }
-    }
+fn load_spatial_effect(row: u32, texel: u32) -> SpatialEffect {
+    let tail = stack_texel(row, texel + 7u);
+    return SpatialEffect(
+        load_phase(row, texel),
+        load_wave(row, texel + 1u),
+        tail.x,
+        bitcast<f32>(tail.y),
+        bitcast<vec2<f32>>(tail.zw),
+        stack_texel_f32(row, texel + 8u),
+    );
 }
 
-fn apply_hsv_shift(base: vec3<f32>, shift: vec3<f32>, a: f32) -> vec3<f32> {
-    var hsv = rgb_to_hsv(base);
-    hsv.x = fract(hsv.x + shift.r * a);
-    hsv.y = clamp(hsv.y * (1.0 + shift.g * a), 0.0, 1.0);
-    hsv.z = clamp(hsv.z * (1.0 + shift.b * a), 0.0, 1.0);
-    return hsv_to_rgb(hsv);
+fn load_fragment_effect(row: u32, texel: u32) -> FragmentEffect {
+    let tail = stack_texel(row, texel + 7u);
+    return FragmentEffect(
+        load_phase(row, texel),
+        load_wave(row, texel + 1u),
+        tail.x, tail.y, tail.z, tail.w,
+        stack_texel_f32(row, texel + 8u),
+        stack_texel_f32(row, texel + 9u),
+    );
 }
 
-fn apply_blend_mode(base: vec3<f32>, blend_color: vec3<f32>, a_clamped: f32, a_raw: f32, mode: u32) -> vec3<f32> {
-    switch mode {
-        case 0u: { return mix(base, blend_color, a_clamped); }
-        case 1u: { return base + blend_color * a_clamped; }
-        case 2u: { return mix(base, base * blend_color, a_clamped); }
-        case 3u: { 
-            let s = vec3<f32>(1.0) - (vec3<f32>(1.0) - base) * (vec3<f32>(1.0) - blend_color); 
-            return mix(base, s, a_clamped); 
-        }
-        case 4u: { return apply_hsv_shift(base, blend_color, a_raw); }
-        default: { return base; }
+fn load_effect(row: u32, texel: u32) -> Effect {
+    var eff: Effect;
+    let lifetime = stack_texel(row, texel);
+    eff.lifetime = EffectLifetime(lifetime.x, lifetime.y, bitcast<f32>(lifetime.z), bitcast<f32>(lifetime.w));
+    var next = texel + 1u;
+    for (var i: u32 = 0u; i < MAX_COLOR_FX; i = i + 1u) {
+        eff.color_effects[i] = load_color_effect(row, next);
+        next = next + COLOR_FX_TEXELS;
     }
+    for (var i: u32 = 0u; i < MAX_ALPHA_FX; i = i + 1u) {
+        eff.alpha_effects[i] = load_alpha_effect(row, next);
+        next = next + ALPHA_FX_TEXELS;
+    }
+    for (var i: u32 = 0u; i < MAX_SPATIAL_FX; i = i + 1u) {
+        eff.spatial_effects[i] = load_spatial_effect(row, next);
+        next = next + SPATIAL_FX_TEXELS;
+    }
+    for (var i: u32 = 0u; i < MAX_FRAGMENT_FX; i = i + 1u) {
+        eff.fragment_effects[i] = load_fragment_effect(row, next);
+        next = next + FRAGMENT_FX_TEXELS;
+    }
+    for (var i: u32 = 0u; i < MAX_PATH_VEC4S; i = i + 1u) {
+        eff.path_points[i] = stack_texel_f32(row, next);
+        next = next + 1u;
+    }
+    let cycle = stack_texel(row, next);
+    eff.tile_cycle = TileCycle(cycle.x, cycle.y, bitcast<f32>(cycle.z), cycle.w);
+    return eff;
 }
 
-// Spatial effects
-fn apply_spatial(t: f32, pos: vec3<f32>, effect_tag: u32) -> vec3<f32> {
-    var p = pos.xy;
-    let effect_stack = effect_storage[effect_tag];
-
-    for (var i: u32 = 0u; i < MAX_FX; i = i + 1u) {
-        let eff = effect_stack.effects[i];
-        if (eff.lifetime.enabled == 0u) { continue; }
-
-        let mt = master_lifetime(t, eff.lifetime);
-        if (mt == 0.0 && eff.lifetime.looping == 0u) { continue; }
-
-        for (var j: u32 = 0u; j < MAX_SPATIAL_FX; j = j + 1u) {
-            let s = eff.spatial_effects[j];
-            if (s.intensity == 0.0) { continue; }
-
-            let pt = phase_lifetime(mt, s.phase);
-            if (pt == 0.0) { continue; }
-
-            let wave = eval_wave(pt, s.wave);
-            let val = wave.y * s.intensity;
-            let offset = (s.anchor - vec2<f32>(0.5, 0.5)) * atlas_dims.sprite_size;
-            p = p - offset;
-
-            switch s.manipulation {
-                case 0u: { p.x = p.x + val; }
-                case 1u: { p.y = p.y + val; }
-                case 2u: { p.x = p.x * (1.0 + val); }
-                case 3u: { p.y = p.y * (1.0 + val); }
-                case 4u: { 
-                    let c = cos(val); 
-                    let si = sin(val); 
-                    p = vec2<f32>(p.x * c - p.y * si, p.x * si + p.y * c);
-                }
-                case 5u: { p.x = p.x + p.y * val; }
-                case 6u: { p.y = p.y + p.x * val; }
-                default: { }
-            }
-            p = p + offset;
-        }
+// Only the first FX_SLOTS effects are decoded, the rest stay disabled
+fn load_effect_stack(effect_tag: u32) -> EffectStack {
+    var stack: EffectStack;
+    let header = stack_texel(effect_tag, 0u);
+    stack.sprite_index = header.x;
+    stack.hidden = header.y;
+    stack.fx_slots = header.z;
+    stack.intensity = bitcast<f32>(header.w);
+    stack.sprite_rect = stack_texel_f32(effect_tag, 1u);
+    stack.base_tint = stack_texel_f32(effect_tag, 2u);
+    for (var i: u32 = 0u; i < FX_SLOTS; i = i + 1u) {
+        stack.effects[i] = load_effect(effect_tag, 3u + i * EFFECT_TEXELS);
     }
-    return vec3<f32>(p, pos.z);
+    return stack;
+}
+#else
+fn load_effect_stack(effect_tag: u32) -> EffectStack {
+    return effect_storage[effect_tag];
 }
+#endif
 
 // Vertex shader
 @vertex
 fn vertex(
     @location(0) position: vec3<f32>,
     @location(2) uv: vec2<f32>,
+#ifdef VFX_INSTANCED
+    // Per sprite in the batch mesh: world X and Y axes, translation, effect tag
+    @location(3) instance_axes: vec4<f32>,
+    @location(4) instance_translation: vec3<f32>,
+    @location(5) instance_tag: u32,
+#endif
     @builtin(instance_index) instance_index: u32,
 ) -> Varyings {
-    let t = globals.time;
+    let t = vfx_time();
+#ifdef VFX_INSTANCED
+    let effect_tag = instance_tag;
+#else ifdef VFX_3D
+    let effect_tag = mesh_functions::get_tag(instance_index);
+#else
     let effect_tag = mesh2d_functions::get_tag(instance_index);
-    let spatial_pos = apply_spatial(t, position, effect_tag);
-    let effect_stack = effect_storage[effect_tag];
+#endif
+    // Loaded once and handed to the helpers, each load is a full row fetch
+    let effect_stack = load_effect_stack(effect_tag);
+
+    // Hidden: collapse the quad so nothing is rasterized
+    if (effect_stack.hidden != 0u) {
+        var hidden_out: Varyings;
+        hidden_out.position = vec4<f32>(0.0);
+        return hidden_out;
+    }
+
+    // Size the quad (built at the grid sprite size) to this sprite's rect
+    var sprite_rect = effect_stack.sprite_rect;
+    if (sprite_rect.z <= 0.0) {
+        sprite_rect = get_sprite_rect(cycled_sprite_index(t, effect_stack.sprite_index, effect_stack));
+    }
+    sprite_px = sprite_rect.zw;
+    let sized_pos = vec3<f32>(position.xy * sprite_px / atlas_dims.sprite_size, position.z);
+    let spatial_pos = apply_spatial(t, sized_pos, effect_stack);
 
     // Calculate atlas UV offset for this sprite
-    let atlas_uv_offset = get_atlas_uv_offset(effect_stack.sprite_index);
-    let sprite_uv_scale = get_sprite_uv_scale();
+    let atlas_uv_offset = get_atlas_uv_offset(sprite_rect);
+    let sprite_uv_scale = get_sprite_uv_scale(sprite_rect);
 
     // Color effect processing (same as before)
     var tint_acc = vec3<f32>(0.0);
@@ -408,14 +249,17 @@ fn vertex(
     var mult_max_weight = 0.0;
     var mult_is_summed = 0.0;
 
-    for (var i: u32 = 0u; i < MAX_FX; i = i + 1u) {
+    var emissive = 0.0;
+
+    for (var i: u32 = 0u; i < FX_SLOTS; i = i + 1u) {
         let eff = effect_stack.effects[i];
         if (eff.lifetime.enabled == 0u) { continue; }
 
         let mt = master_lifetime(t, eff.lifetime);
         if (mt == 0.0 && eff.lifetime.looping == 0u) { continue; }
+        if (t < eff.lifetime.start_time) { continue; } // Queued, e.g. a later sequence step
 
-        for (var c: u32 = 0u; c < MAX_COLOR_FX; c = c + 1u) {
+        for (var c: u32 = 0u; c < COLOR_SLOTS; c = c + 1u) {
             let color_effect = eff.color_effects[c];
             let composite_mode = color_effect.color.w;
             if (composite_mode < 0.9) { continue; }
@@ -425,6 +269,15 @@ fn vertex(
 
             let rgb_wave = eval_wave(rgb_pt, color_effect.wave);
             let weight = rgb_wave.x;
+            emissive += color_effect.emissive * weight;
+
+            // Gradient: full-strength tint towards a color picked between from/to
+            if (color_effect.blend_mode == 5u) {
+                tint_acc += mix(color_effect.from_color.rgb, color_effect.color.rgb, weight);
+                tint_total_weight += 1.0;
+                tint_max_weight = 1.0;
+                continue;
+            }
             if (weight == 0.0) { continue; }
 
             let is_summed = step(1.9, composite_mode);
@@ -484,15 +337,23 @@ fn vertex(
 
     var alpha_mul = 1.0;
     var alpha_add = 0.0;
+    var alpha_clip = vec2<f32>(0.0, 0.5);
+
+    // First FRAGMENT_SLOTS active fragment effects across the stack
+    var frag_kinds = vec2<u32>(0u);
+    var frag_colors = array<vec4<f32>, MAX_FRAGMENT_FX>();
+    var frag_params = array<vec4<f32>, MAX_FRAGMENT_FX>();
+    var frag_count: u32 = 0u;
 
-    for (var i: u32 = 0u; i < MAX_FX; i = i + 1u) {
+    for (var i: u32 = 0u; i < FX_SLOTS; i = i + 1u) {
         let eff = effect_stack.effects[i];
         if (eff.lifetime.enabled == 0u) { continue; }
 
         let mt = master_lifetime(t, eff.lifetime);
         if (mt == 0.0 && eff.lifetime.looping == 0u) { continue; }
+        if (t < eff.lifetime.start_time) { continue; } // Queued, e.g. a later sequence step
 
-        for (var c: u32 = 0u; c < MAX_COLOR_FX; c = c + 1u) {
+        for (var c: u32 = 0u; c < COLOR_SLOTS; c = c + 1u) {
             let color_effect = eff.color_effects[c];
             let composite_mode = color_effect.color.w;
             if (composite_mode >= 0.9) { continue; }
@@ -503,6 +364,13 @@ fn vertex(
             let rgb_wave = eval_wave(rgb_pt, color_effect.wave);
             let a_clamped = rgb_wave.x;
             let a_raw = rgb_wave.y;
+            emissive += color_effect.emissive * a_clamped;
+
+            if (color_effect.blend_mode == 5u) {
+                seq_mul_rgb = vec3<f32>(0.0);
+                seq_add_rgb = mix(color_effect.from_color.rgb, color_effect.color.rgb, a_clamped);
+                continue;
+            }
             if (a_clamped == 0.0 && abs(a_raw) < 1e-6) { continue; }
 
             if (color_effect.blend_mode == 0u) {
@@ -524,7 +392,7 @@ fn vertex(
                 seq_mul_rgb *= k;
                 seq_add_rgb = seq_add_rgb * k + b;
             }
-            else if (color_effect.blend_mode == 4u) {
+            else if (color_effect.blend_mode == 4u && lod.skip_hsv == 0u) {
                 hsv_enabled = 1.0;
                 hsv_hue_delta += color_effect.color.r * a_raw;
                 hsv_sat_mul *= (1.0 + color_effect.color.g * a_raw);
@@ -532,32 +400,108 @@ fn vertex(
             }
         }
 
-        let alpha_pt = phase_lifetime(mt, eff.alpha_effect.phase);
-        if (alpha_pt > 0.0) {
-            let alpha_wave = eval_wave(alpha_pt, eff.alpha_effect.wave);
+        for (var a_idx: u32 = 0u; a_idx < ALPHA_SLOTS; a_idx = a_idx + 1u) {
+            let alpha_effect = eff.alpha_effects[a_idx];
+            let alpha_pt = phase_lifetime(mt, alpha_effect.phase);
+            if (alpha_pt <= 0.0) { continue; }
+
+            // Clip applies for the whole phase, even while the wave is still at 0
+            if (alpha_effect.clip_mode != 0u) {
+                alpha_clip = vec2<f32>(f32(alpha_effect.clip_mode), alpha_effect.clip_threshold);
+            }
+
+            let alpha_wave = eval_wave(alpha_pt, alpha_effect.wave);
             let a = alpha_wave.x;
-            if (a > 0.0) {
-                let to = saturate(eff.alpha_effect.target_alpha);
+            if (a <= 0.0) { continue; }
+
+            let to = saturate(alpha_effect.target_alpha);
+            if (alpha_effect.composite == 1u) {
+                // Multiply: scale the running alpha
+                let k = mix(1.0, to, a);
+                alpha_mul *= k;
+                alpha_add *= k;
+            } else {
+                // Lerp: pull the running alpha towards the target
                 let k = (1.0 - a);
                 alpha_mul *= k;
                 alpha_add = alpha_add * k + to * a;
             }
         }
+
+        for (var f: u32 = 0u; f < FRAGMENT_SLOTS; f = f + 1u) {
+            let frag = eff.fragment_effects[f];
+            if (frag.kind == 0u || frag_count >= FRAGMENT_SLOTS) { continue; }
+            if (((lod.skip_fragments >> frag.kind) & 1u) != 0u) { continue; } // Zoomed out
+
+            let frag_pt = phase_lifetime(mt, frag.phase);
+            if (frag_pt <= 0.0) { continue; }
+
+            let frag_wave = eval_wave(frag_pt, frag.wave);
+            frag_kinds[frag_count] = frag.kind;
+            frag_colors[frag_count] = frag.color;
+            frag_params[frag_count] = vec4<f32>(frag.params.xyz, frag_wave.x);
+            frag_count += 1u;
+        }
     }
 
+#ifdef VFX_3D
+    // Billboard: the quad faces the camera from the entity's origin, sized by its scale
+    let model = mesh_functions::get_world_from_local(instance_index);
+    let billboard = spatial_pos.xy * vec2<f32>(length(model[0].xyz), length(model[1].xyz));
+    let world_pos = vec4<f32>(
+        model[3].xyz + view.world_from_view[0].xyz * billboard.x + view.world_from_view[1].xyz * billboard.y,
+        1.0,
+    );
+    let clip_pos = view.clip_from_world * world_pos;
+#else
+#ifdef VFX_INSTANCED
+    let model = mat4x4<f32>(
+        vec4<f32>(instance_axes.xy, 0.0, 0.0),
+        vec4<f32>(instance_axes.zw, 0.0, 0.0),
+        vec4<f32>(0.0, 0.0, 1.0, 0.0),
+        vec4<f32>(instance_translation, 1.0),
+    );
+#else
     let model = mesh2d_functions::get_world_from_local(instance_index);
-    let clip_pos = mesh2d_functions::mesh2d_position_local_to_clip(model, vec4<f32>(spatial_pos, 1.0));
+#endif
+    var world_pos = mesh2d_functions::mesh2d_position_local_to_world(model, vec4<f32>(spatial_pos, 1.0));
+#ifdef VFX_Y_SORT_SCALE
+    // Y-sorting: depth from the world Y of the sprite's bottom edge, where effects moved it
+    let sort_local = apply_spatial(t, vec3<f32>(0.0, -0.5 * sprite_px.y, 0.0), effect_stack);
+    let sort_y = mesh2d_functions::mesh2d_position_local_to_world(model, vec4<f32>(sort_local, 1.0)).y;
+    world_pos.z += bitcast<f32>(#{VFX_Y_SORT_OFFSET}u) + sort_y * bitcast<f32>(#{VFX_Y_SORT_SCALE}u);
+#endif
+    let clip_pos = mesh2d_functions::mesh2d_position_world_to_clip(world_pos);
+#endif
+
+    // VfxIntensity: pull the color terms back towards leaving the texel as it is
+    let color_intensity = saturate(effect_stack.intensity);
+    acc_mul_rgb = mix(vec3<f32>(1.0), acc_mul_rgb, color_intensity);
+    acc_add_rgb *= color_intensity;
+    seq_mul_rgb = mix(vec3<f32>(1.0), seq_mul_rgb, color_intensity);
+    seq_add_rgb *= color_intensity;
+    hsv_hue_delta *= color_intensity;
+    hsv_sat_mul = mix(1.0, hsv_sat_mul, color_intensity);
+    hsv_val_mul = mix(1.0, hsv_val_mul, color_intensity);
+    emissive *= color_intensity;
 
     var out: Varyings;
     out.position = clip_pos;
     out.uv = uv;
-    out.acc_mul = vec4<f32>(acc_mul_rgb, alpha_mul);
+    // The base tint scales the texel, and so whatever the accumulated pass keeps of it
+    out.acc_mul = vec4<f32>(acc_mul_rgb, alpha_mul) * effect_stack.base_tint;
     out.acc_add = vec4<f32>(acc_add_rgb, alpha_add);
     out.seq_mul = vec4<f32>(seq_mul_rgb, 0.0);
     out.seq_add = vec4<f32>(seq_add_rgb, hsv_enabled);
-    out.hsv = vec4<f32>(hsv_hue_delta, hsv_sat_mul, hsv_val_mul, 0.0);
+    out.hsv = vec4<f32>(hsv_hue_delta, hsv_sat_mul, hsv_val_mul, emissive);
+    out.alpha_clip = alpha_clip;
     out.atlas_uv_offset = atlas_uv_offset;
     out.uv_scale = sprite_uv_scale;
+    out.frag_kinds = frag_kinds;
+    out.frag_color0 = frag_colors[0];
+    out.frag_params0 = frag_params[0];
+    out.frag_color1 = frag_colors[1];
+    out.frag_params1 = frag_params[1];
 
     return out;
 }
@@ -565,10 +509,14 @@ fn vertex(
 // Fragment shader
 @fragment
 fn fragment(in: Varyings) -> @location(0) vec4<f32> {
+    sprite_px = in.uv_scale * atlas_dims.texture_size + 2.0 * atlas_dims.uv_inset;
+
     // Transform UV coordinates to sample from the correct sprite in the atlas
     // The offset points to the top-left of the sprite content (after padding)
     // We scale the UV by the sprite size to stay within the sprite
-    let atlas_uv = in.atlas_uv_offset + (in.uv * in.uv_scale);
+    var local_uv = apply_fragment_uv(in.uv, in.frag_kinds.x, in.frag_color0, in.frag_params0);
+    local_uv = apply_fragment_uv(local_uv, in.frag_kinds.y, in.frag_color1, in.frag_params1);
+    let atlas_uv = in.atlas_uv_offset + (local_uv * in.uv_scale);
     let sampled = textureSample(texture, texture_sampler, atlas_uv);
     let base_a = saturate(sampled.a);
 
@@ -588,10 +536,37 @@ fn fragment(in: Varyings) -> @location(0) vec4<f32> {
         rgb = hsv_to_rgb(hsv);
     }
 
+    // HDR emissive boost (only survives past 1.0 on an HDR target)
+    rgb *= 1.0 + max(in.hsv.w, 0.0);
+
     // Coverage-gated alpha affine
     let cov = step(1e-4, base_a);
     let baked_alpha = saturate(base_a * in.acc_mul.w + in.acc_add.w);
-    let alpha = mix(base_a, baked_alpha, cov);
+    let alpha = apply_alpha_clip(mix(base_a, baked_alpha, cov), base_a, local_uv, in.alpha_clip);
+
+    var out = vec4<f32>(rgb, alpha);
+    out = apply_fragment_effect(out, base_a, local_uv, in.atlas_uv_offset, in.uv_scale, in.frag_kinds.x, in.frag_color0, in.frag_params0);
+    out = apply_fragment_effect(out, base_a, local_uv, in.atlas_uv_offset, in.uv_scale, in.frag_kinds.y, in.frag_color1, in.frag_params1);
 
-    return vec4<f32>(rgb, alpha);
+#ifdef HIRUNDO_USER_FRAGMENT
+    // User hook, see `HirundoPlugin::with_custom_fragment`
+    out = custom_fragment(UserFragmentInput(out, sampled, local_uv, atlas_uv, in.position.xy, vfx_time()));
+#endif
+
+#ifdef VFX_ALPHA_CUTOFF
+    // `VfxAlphaMode::Mask`: cut out below the cutoff, opaque above it
+    if out.a < bitcast<f32>(#{VFX_ALPHA_CUTOFF}u) {
+        discard;
+    }
+    out.a = 1.0;
+#endif
+
+#ifdef VFX_Y_SORT_SCALE
+    // Y-sorting writes depth, empty pixels mustn't hide the sprites behind them
+    if out.a <= 0.0 {
+        discard;
+    }
+#endif
+
+    return out;
 }
\ No newline at end of file
@@ -1,92 +1,18 @@
-#import bevy_render::globals::Globals;
+#ifdef VFX_UI
+#import bevy_render::view::View;
+#else
 #import bevy_sprite::mesh2d_functions;
-
-const PI: f32 = 3.141592653589793;
-const MAX_FX: u32 = 6;
-const MAX_SPATIAL_FX: u32 = 3;
-const MAX_COLOR_FX: u32 = 3;
-
-struct AtlasDimensions {
-    texture_size: vec2<f32>,
-    cell_size: vec2<f32>,
-    sprite_size: vec2<f32>,
-    padding: vec2<f32>,
-}
-
-struct EffectLifetime { 
-    enabled: u32, 
-    looping: u32, 
-    start_time: f32, 
-    duration: f32 
-}
-
-struct Phase { 
-    start: f32, 
-    end: f32, 
-    _padding: vec2<f32> 
-}
-
-struct Envelope { 
-    attack: f32, 
-    hold: f32, 
-    release: f32, 
-    growth_mode: u32, 
-    growth: f32, 
-    enabled: u32, 
-    decay_mode: u32, 
-    decay: f32 
-}
-
-struct Wave {
-    kind: u32, 
-    freq: f32, 
-    amp: f32, 
-    bias: f32, 
-    phase: f32,
-    _pad0: f32,
-    _pad1: f32,
-    _pad2: f32,
-    amp_envelope: Envelope,
-    freq_envelope: Envelope,
-}
-
-struct ColorEffect {
-    phase: Phase,
-    wave: Wave,
-    color: vec4<f32>,
-    blend_mode: u32,
-}
-
-struct AlphaEffect {
-    phase: Phase,
-    wave: Wave,
-    target_alpha: f32,
-    _pad0: f32,
-    _pad1: f32,
-    _pad2: f32,
-}
-
-struct SpatialEffect {
-    phase: Phase,
-    wave: Wave,
-    manipulation: u32,
-    intensity: f32,
-    anchor: vec2<f32>,
-}
-
-struct Effect {
-    lifetime: EffectLifetime,
-    color_effects: array<ColorEffect, MAX_COLOR_FX>,
-    alpha_effect: AlphaEffect,
-    spatial_effects: array<SpatialEffect, MAX_SPATIAL_FX>,
-}
-
-struct EffectStack {
-    sprite_index: u32,
-    _pad0: u32,
-    _pad1: u32,
-    _pad2: u32,
-    effects: array<Effect, MAX_FX>,
+#endif
+#ifdef HIRUNDO_USER_FRAGMENT
+#import hirundo::user_fragment::UserFragmentInput;
+#import hirundo::custom_fragment::custom_fragment;
+#endif
+#import hirundo::vfx_common::{
+    MAX_FRAGMENT_FX, FX_SLOTS, COLOR_SLOTS, ALPHA_SLOTS, FRAGMENT_SLOTS, EffectStack,
+    texture, texture_sampler, atlas_dims, lod, sprite_px, get_sprite_rect,
+    get_atlas_uv_offset, get_sprite_uv_scale, vfx_time, master_lifetime, phase_lifetime,
+    eval_wave, rgb_to_hsv, hsv_to_rgb, apply_spatial, cycled_sprite_index, apply_alpha_clip,
+    apply_fragment_uv, apply_fragment_effect,
 }
 
 struct Varyings {
@@ -99,337 +25,69 @@ struct Varyings {
     @location(5) @interpolate(flat) hsv: vec4<f32>,
     @location(6) @interpolate(flat) atlas_uv_offset: vec2<f32>,
     @location(7) @interpolate(flat) uv_scale: vec2<f32>,
+    // Resolved fragment effects: kind per slot, color, params (w = wave strength)
+    @location(8) @interpolate(flat) frag_kinds: vec2<u32>,
+    @location(9) @interpolate(flat) frag_color0: vec4<f32>,
+    @location(10) @interpolate(flat) frag_params0: vec4<f32>,
+    @location(11) @interpolate(flat) frag_color1: vec4<f32>,
+    @location(12) @interpolate(flat) frag_params1: vec4<f32>,
+    // Alpha clip: x = mode (0 blend, 1 cutout, 2 dither), y = cutout threshold
+    @location(13) @interpolate(flat) alpha_clip: vec2<f32>,
 }
 
-@group(0) @binding(1) var<uniform> globals: Globals;
-@group(2) @binding(0) var texture: texture_2d<f32>;
-@group(2) @binding(1) var texture_sampler: sampler;
-@group(2) @binding(2) var<uniform> effect_stack: EffectStack;
-@group(2) @binding(3) var<uniform> atlas_dims: AtlasDimensions;
-
-// Helper to calculate atlas UV offset from sprite index (accounts for padding)
-fn get_atlas_uv_offset(sprite_index: u32) -> vec2<f32> {
-    let sprites_per_row = floor(atlas_dims.texture_size.x / atlas_dims.cell_size.x);
-    let col = f32(sprite_index) % sprites_per_row;
-    let row = floor(f32(sprite_index) / sprites_per_row);
-    
-    // Calculate cell step and padding in UV space
-    let cell_step = atlas_dims.cell_size / atlas_dims.texture_size;
-    let padding_uv = atlas_dims.padding / atlas_dims.texture_size;
-    
-    // Offset to the cell, then add padding to get to actual sprite content
-    return vec2<f32>(
-        col * cell_step.x + padding_uv.x,
-        row * cell_step.y + padding_uv.y
-    );
-}
-
-// Helper to get sprite UV scale
-fn get_sprite_uv_scale() -> vec2<f32> {
-    return atlas_dims.sprite_size / atlas_dims.texture_size;
-}
+#ifdef VFX_UI
+@group(0) @binding(0) var<uniform> view: View;
+#endif
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> effect_stack: EffectStack;
 
-// Helper functions
-fn master_lifetime(t: f32, m: EffectLifetime) -> f32 {
-    if (m.enabled == 0u || m.duration <= 0.0) { return 0.0; }
-    let elapsed = t - m.start_time;
-    if (m.looping == 1u) { return fract(elapsed / m.duration); }
-    if (elapsed < 0.0 || elapsed >= m.duration) { return 0.0; }
-    return elapsed / m.duration;
+// The stack's own rect if it has one, otherwise the (cycled) rect of `sprite_index`
+fn stack_sprite_rect(t: f32, sprite_index: u32) -> vec4<f32> {
+    if (effect_stack.sprite_rect.z > 0.0) { return effect_stack.sprite_rect; }
+    return get_sprite_rect(cycled_sprite_index(t, sprite_index, effect_stack));
 }
 
-fn phase_lifetime(t: f32, p: Phase) -> f32 {
-    let s = clamp(p.start, 0.0, 1.0);
-    let e = clamp(p.end, 0.0, 1.0);
-    if (s >= e || t < s || t > e) { return 0.0; }
-    return (t - s) / (e - s);
-}
-
-fn eval_envelope_integral(t: f32, env: Envelope) -> vec2<f32> {
-    if (env.enabled == 0u) {
-        return vec2<f32>(1.0, t);
-    }
-
-    let attack = env.attack;
-    let hold = env.hold;
-    let release = env.release;
-    let total = attack + hold + release;
-
-    if (total <= 0.0) {
-        return vec2<f32>(1.0, t);
-    }
-
-    let nt = clamp(t, 0.0, 1.0) * total;
-
-    var env_val: f32 = 1.0;
-    var integral_nt: f32 = 0.0;
-
-    if (nt <= attack) {
-        let phase_t = select(0.0, nt / attack, attack > 0.0);
-        
-        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
-            let s = env.growth;
-            env_val = (exp(phase_t * s) - 1.0) / (exp(s) - 1.0);
-        } else {
-            env_val = phase_t;
-        }
-        
-        integral_nt = select(0.0, (nt * nt) / (2.0 * attack), attack > 0.0);
-    } 
-    else if (nt <= attack + hold) {
-        env_val = 1.0;
-        integral_nt = (attack * 0.5) + (nt - attack);
-    } 
-    else {
-        let s = nt - attack - hold;
-        let phase_t = select(0.0, s / release, release > 0.0);
-        
-        if (env.decay_mode == 1u && abs(env.decay) > 1e-5) {
-            let d = env.decay;
-            env_val = 1.0 - (exp(phase_t * d) - 1.0) / (exp(d) - 1.0);
-        } else {
-            env_val = 1.0 - phase_t;
-        }
-        
-        integral_nt = (attack * 0.5) + hold +
-                      select(0.0, s - (s * s) / (2.0 * release), release > 0.0);
-    }
-
-    let total_area = (attack * 0.5) + hold + (release * 0.5);
-    let base_integral_norm = integral_nt / max(total_area, 1e-5);
-
-    let inst = clamp(env_val, 0.0, 1.0);
-
-    var integral_with_modulation: f32 = base_integral_norm;
-
-    if (env.growth_mode == 1u && abs(env.growth) > 1e-5 && attack > 0.0) {
-        let s = env.growth;
-        let end_t = min(nt, attack);
-        
-        let integral_attack = attack * ((exp(s * end_t / attack) - 1.0) - s * end_t / attack) / (s * (exp(s) - 1.0));
-        
-        var full_integral = attack * 0.5;
-        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
-            full_integral = attack * ((exp(s) - 1.0) - s) / (s * (exp(s) - 1.0));
-        }
-        
-        integral_with_modulation = integral_attack / max(full_integral + hold + (release * 0.5), 1e-5);
-    }
-    
-    if (nt > attack && nt <= attack + hold) {
-        let hold_contrib = (nt - attack);
-        var attack_contrib = attack * 0.5;
-        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
-            let s = env.growth;
-            attack_contrib = attack * ((exp(s) - 1.0) - s) / (s * (exp(s) - 1.0));
-        }
-        integral_with_modulation = (attack_contrib + hold_contrib) / max(attack_contrib + hold + (release * 0.5), 1e-5);
-    }
-    
-    if (nt > attack + hold && release > 0.0) {
-        let s_decay = env.decay;
-        let release_t = nt - attack - hold;
-        
-        var attack_contrib = attack * 0.5;
-        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
-            let s = env.growth;
-            attack_contrib = attack * ((exp(s) - 1.0) - s) / (s * (exp(s) - 1.0));
-        }
-        
-        var release_contrib = release_t - (release_t * release_t) / (2.0 * release);
-        if (env.decay_mode == 1u && abs(s_decay) > 1e-5) {
-            release_contrib = release * (release_t / release - ((exp(s_decay * release_t / release) - 1.0) - s_decay * release_t / release) / (s_decay * (exp(s_decay) - 1.0)));
-        }
-        
-        var full_release = release * 0.5;
-        if (env.decay_mode == 1u && abs(s_decay) > 1e-5) {
-            full_release = release * (1.0 - ((exp(s_decay) - 1.0) - s_decay) / (s_decay * (exp(s_decay) - 1.0)));
-        }
-        
-        integral_with_modulation = (attack_contrib + hold + release_contrib) / max(attack_contrib + hold + full_release, 1e-5);
-    }
-
-    return vec2<f32>(inst, clamp(integral_with_modulation, 0.0, 1.0));
-}
-
-fn eval_envelope(t: f32, env: Envelope) -> f32 {
-    return eval_envelope_integral(t, env).x;
-}
-
-fn eval_wave(t: f32, w: Wave) -> vec2<f32> {
-    let fi = eval_envelope_integral(t, w.freq_envelope);
-    let freq_integral = fi.y;
-
-    let cycles = w.freq * freq_integral;
-    let phase = fract(w.phase + cycles);
-    let raw_phase = phase * 2.0 * PI;
-
-    var v: f32 = 0.0;
-    switch w.kind {
-        case 0u: { v = cos(raw_phase); }
-        case 1u: { v = select(-1.0, 1.0, phase > 0.5); }
-        case 2u: { v = 2.0 * abs(2.0 * phase - 1.0) - 1.0; }
-        case 3u: { v = phase * 2.0 - 1.0; }
-        case 4u: { v = 1.0; }
-        default: { v = 0.0; }
-    }
-
-    let amp_env = eval_envelope(t, w.amp_envelope);
-    let val = v * w.amp * amp_env + w.bias;
-    return vec2<f32>(saturate(val), val);
-}
-
-fn rgb_to_hsv(c: vec3<f32>) -> vec3<f32> {
-    let max_val = max(max(c.r, c.g), c.b);
-    let min_val = min(min(c.r, c.g), c.b);
-    let delta = max_val - min_val;
-    var hsv = vec3<f32>(0.0, 0.0, max_val);
-    if (delta > 0.0) {
-        if (max_val == c.r) { hsv.x = (c.g - c.b) / delta; }
-        else if (max_val == c.g) { hsv.x = 2.0 + (c.b - c.r) / delta; }
-        else { hsv.x = 4.0 + (c.r - c.g) / delta; }
-        hsv.x = fract(hsv.x / 6.0);
-        hsv.y = delta / max_val;
-    }
-    return hsv;
-}
-
-fn hsv_to_rgb(hsv: vec3<f32>) -> vec3<f32> {
-    let i = floor(hsv.x * 6.0);
-    let f = hsv.x * 6.0 - i;
-    let p = hsv.z * (1.0 - hsv.y);
-    let q = hsv.z * (1.0 - f * hsv.y);
-    let t = hsv.z * (1.0 - (1.0 - f) * hsv.y);
-    switch i32(i) {
-        case 0: { return vec3<f32>(hsv.z, t, p); }
-        case 1: { return vec3<f32>(q, hsv.z, p); }
-        case 2: { return vec3<f32>(p, hsv.z, t); }
-        case 3: { return vec3<f32>(p, q, hsv.z); }
-        case 4: { return vec3<f32>(t, p, hsv.z); }
-        default: { return vec3<f32>(hsv.z, p, q); }
-    }
-}
-
-fn apply_hsv_shift(base: vec3<f32>, shift: vec3<f32>, a: f32) -> vec3<f32> {
-    var hsv = rgb_to_hsv(base);
-    hsv.x = fract(hsv.x + shift.r * a);
-    hsv.y = clamp(hsv.y * (1.0 + shift.g * a), 0.0, 1.0);
-    hsv.z = clamp(hsv.z * (1.0 + shift.b * a), 0.0, 1.0);
-    return hsv_to_rgb(hsv);
-}
-
-fn apply_blend_mode(base: vec3<f32>, blend_color: vec3<f32>, a_clamped: f32, a_raw: f32, mode: u32) -> vec3<f32> {
-    switch mode {
-        case 0u: { return mix(base, blend_color, a_clamped); }
-        case 1u: { return base + blend_color * a_clamped; }
-        case 2u: { return mix(base, base * blend_color, a_clamped); }
-        case 3u: { 
-            let s = vec3<f32>(1.0) - (vec3<f32>(1.0) - base) * (vec3<f32>(1.0) - blend_color); 
-            return mix(base, s, a_clamped); 
-        }
-        case 4u: { return apply_hsv_shift(base, blend_color, a_raw); }
-        default: { return base; }
-    }
-}
-
-fn apply_spatial_broadcast(t: f32, pos: vec3<f32>) -> vec3<f32> {
-    var p = pos.xy;
-
-    for (var i: u32 = 0u; i < MAX_FX; i = i + 1u) {
-        let eff = effect_stack.effects[i];
-        if (eff.lifetime.enabled == 0u) { continue; }
-
-        let mt = master_lifetime(t, eff.lifetime);
-        if (mt == 0.0 && eff.lifetime.looping == 0u) { continue; }
-
-        for (var j: u32 = 0u; j < MAX_SPATIAL_FX; j = j + 1u) {
-            let s = eff.spatial_effects[j];
-            if (s.intensity == 0.0) { continue; }
-
-            let pt = phase_lifetime(mt, s.phase);
-            if (pt == 0.0) { continue; }
-
-            let wave = eval_wave(pt, s.wave);
-            let val = wave.y * s.intensity;
-            let offset = (s.anchor - vec2<f32>(0.5, 0.5)) * atlas_dims.sprite_size;
-            p = p - offset;
-
-            switch (s.manipulation) {
-                case 0u: { p.x = p.x + val; }
-                case 1u: { p.y = p.y + val; }
-                case 2u: { p.x = p.x * (1.0 + val); }
-                case 3u: { p.y = p.y * (1.0 + val); }
-                case 4u: { 
-                    let c = cos(val); 
-                    let si = sin(val); 
-                    p = vec2<f32>(p.x * c - p.y * si, p.x * si + p.y * c);
-                }
-                case 5u: { p.x = p.x + p.y * val; }
-                case 6u: { p.y = p.y + p.x * val; }
-                default: { }
-            }
-            p = p + offset;
-        }
-    }
-    return vec3<f32>(p, pos.z);
-}
-
-
-// Spatial effects
-fn apply_spatial(t: f32, pos: vec3<f32>, effect_tag: u32) -> vec3<f32> {
-    var p = pos.xy;
-
-    for (var i: u32 = 0u; i < MAX_FX; i = i + 1u) {
-        let eff = effect_stack.effects[i];
-        if (eff.lifetime.enabled == 0u) { continue; }
-
-        let mt = master_lifetime(t, eff.lifetime);
-        if (mt == 0.0 && eff.lifetime.looping == 0u) { continue; }
-
-        for (var j: u32 = 0u; j < MAX_SPATIAL_FX; j = j + 1u) {
-            let s = eff.spatial_effects[j];
-            if (s.intensity == 0.0) { continue; }
-
-            let pt = phase_lifetime(mt, s.phase);
-            if (pt == 0.0) { continue; }
-
-            let wave = eval_wave(pt, s.wave);
-            let val = wave.y * s.intensity;
-            let offset = (s.anchor - vec2<f32>(0.5, 0.5)) * atlas_dims.sprite_size;
-            p = p - offset;
-
-            switch s.manipulation {
-                case 0u: { p.x = p.x + val; }
-                case 1u: { p.y = p.y + val; }
-                case 2u: { p.x = p.x * (1.0 + val); }
-                case 3u: { p.y = p.y * (1.0 + val); }
-                case 4u: { 
-                    let c = cos(val); 
-                    let si = sin(val); 
-                    p = vec2<f32>(p.x * c - p.y * si, p.x * si + p.y * c);
-                }
-                case 5u: { p.x = p.x + p.y * val; }
-                case 6u: { p.y = p.y + p.x * val; }
-                default: { }
-            }
-            p = p + offset;
-        }
-    }
-    return vec3<f32>(p, pos.z);
-}
-
-// Broadcast vertex shader - no mesh tag, no instance indexing into storage
+// Broadcast vertex shader - the mesh tag only picks a sprite, no instance indexing into storage
+#ifdef VFX_UI
+@vertex
+fn vertex(
+    @location(0) vertex_position: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) size: vec2<f32>,
+    @location(3) border_widths: vec4<f32>,
+    @location(4) border_radius: vec4<f32>,
+) -> Varyings {
+    let t = vfx_time();
+
+    // UI nodes: the quad spans the node, positions around its center with Y up
+    let sprite_rect = stack_sprite_rect(t, effect_stack.sprite_index);
+    sprite_px = size;
+    let node_center = vertex_position.xy - (uv - 0.5) * size;
+    let sized_pos = vec3<f32>((uv - 0.5) * vec2<f32>(1.0, -1.0) * size, 0.0);
+#else
 @vertex
 fn vertex(
     @location(0) position: vec3<f32>,
     @location(2) uv: vec2<f32>,
+#ifdef VERTEX_COLORS
+    @location(4) vertex_color: vec4<f32>,
+#endif
     @builtin(instance_index) instance_index: u32,
 ) -> Varyings {
-    let t = globals.time;
-    let spatial_pos = apply_spatial_broadcast(t, position);
+    let t = vfx_time();
+
+    // A `BroadcastSprite` tags its instance with its sprite + 1, untagged ones draw the stack's
+    let tag = mesh2d_functions::get_tag(instance_index);
+    let sprite_index = select(effect_stack.sprite_index, tag - 1u, tag > 0u);
 
-    let atlas_uv_offset = get_atlas_uv_offset(effect_stack.sprite_index);
-    let sprite_uv_scale = get_sprite_uv_scale();
+    // Size the quad (built at the grid sprite size) to this sprite's rect
+    let sprite_rect = stack_sprite_rect(t, sprite_index);
+    sprite_px = sprite_rect.zw;
+    let sized_pos = vec3<f32>(position.xy * sprite_px / atlas_dims.sprite_size, position.z);
+#endif
+    let spatial_pos = apply_spatial(t, sized_pos, effect_stack);
+
+    let atlas_uv_offset = get_atlas_uv_offset(sprite_rect);
+    let sprite_uv_scale = get_sprite_uv_scale(sprite_rect);
 
     // Color effect processing
     var tint_acc = vec3<f32>(0.0);
@@ -447,14 +105,17 @@ fn vertex(
     var mult_max_weight = 0.0;
     var mult_is_summed = 0.0;
 
-    for (var i: u32 = 0u; i < MAX_FX; i = i + 1u) {
+    var emissive = 0.0;
+
+    for (var i: u32 = 0u; i < FX_SLOTS; i = i + 1u) {
         let eff = effect_stack.effects[i];
         if (eff.lifetime.enabled == 0u) { continue; }
 
         let mt = master_lifetime(t, eff.lifetime);
         if (mt == 0.0 && eff.lifetime.looping == 0u) { continue; }
+        if (t < eff.lifetime.start_time) { continue; } // Queued, e.g. a later sequence step
 
-        for (var c: u32 = 0u; c < MAX_COLOR_FX; c = c + 1u) {
+        for (var c: u32 = 0u; c < COLOR_SLOTS; c = c + 1u) {
             let color_effect = eff.color_effects[c];
             let composite_mode = color_effect.color.w;
             if (composite_mode < 0.9) { continue; }
@@ -464,6 +125,15 @@ fn vertex(
 
             let rgb_wave = eval_wave(rgb_pt, color_effect.wave);
             let weight = rgb_wave.x;
+            emissive += color_effect.emissive * weight;
+
+            // Gradient: full-strength tint towards a color picked between from/to
+            if (color_effect.blend_mode == 5u) {
+                tint_acc += mix(color_effect.from_color.rgb, color_effect.color.rgb, weight);
+                tint_total_weight += 1.0;
+                tint_max_weight = 1.0;
+                continue;
+            }
             if (weight == 0.0) { continue; }
 
             let is_summed = step(1.9, composite_mode);
@@ -523,15 +193,23 @@ fn vertex(
 
     var alpha_mul = 1.0;
     var alpha_add = 0.0;
+    var alpha_clip = vec2<f32>(0.0, 0.5);
+
+    // First FRAGMENT_SLOTS active fragment effects across the stack
+    var frag_kinds = vec2<u32>(0u);
+    var frag_colors = array<vec4<f32>, MAX_FRAGMENT_FX>();
+    var frag_params = array<vec4<f32>, MAX_FRAGMENT_FX>();
+    var frag_count: u32 = 0u;
 
-    for (var i: u32 = 0u; i < MAX_FX; i = i + 1u) {
+    for (var i: u32 = 0u; i < FX_SLOTS; i = i + 1u) {
         let eff = effect_stack.effects[i];
         if (eff.lifetime.enabled == 0u) { continue; }
 
         let mt = master_lifetime(t, eff.lifetime);
         if (mt == 0.0 && eff.lifetime.looping == 0u) { continue; }
+        if (t < eff.lifetime.start_time) { continue; } // Queued, e.g. a later sequence step
 
-        for (var c: u32 = 0u; c < MAX_COLOR_FX; c = c + 1u) {
+        for (var c: u32 = 0u; c < COLOR_SLOTS; c = c + 1u) {
             let color_effect = eff.color_effects[c];
             let composite_mode = color_effect.color.w;
             if (composite_mode >= 0.9) { continue; }
@@ -542,6 +220,13 @@ fn vertex(
             let rgb_wave = eval_wave(rgb_pt, color_effect.wave);
             let a_clamped = rgb_wave.x;
             let a_raw = rgb_wave.y;
+            emissive += color_effect.emissive * a_clamped;
+
+            if (color_effect.blend_mode == 5u) {
+                seq_mul_rgb = vec3<f32>(0.0);
+                seq_add_rgb = mix(color_effect.from_color.rgb, color_effect.color.rgb, a_clamped);
+                continue;
+            }
             if (a_clamped == 0.0 && abs(a_raw) < 1e-6) { continue; }
 
             if (color_effect.blend_mode == 0u) {
@@ -563,7 +248,7 @@ fn vertex(
                 seq_mul_rgb *= k;
                 seq_add_rgb = seq_add_rgb * k + b;
             }
-            else if (color_effect.blend_mode == 4u) {
+            else if (color_effect.blend_mode == 4u && lod.skip_hsv == 0u) {
                 hsv_enabled = 1.0;
                 hsv_hue_delta += color_effect.color.r * a_raw;
                 hsv_sat_mul *= (1.0 + color_effect.color.g * a_raw);
@@ -571,32 +256,100 @@ fn vertex(
             }
         }
 
-        let alpha_pt = phase_lifetime(mt, eff.alpha_effect.phase);
-        if (alpha_pt > 0.0) {
-            let alpha_wave = eval_wave(alpha_pt, eff.alpha_effect.wave);
+        for (var a_idx: u32 = 0u; a_idx < ALPHA_SLOTS; a_idx = a_idx + 1u) {
+            let alpha_effect = eff.alpha_effects[a_idx];
+            let alpha_pt = phase_lifetime(mt, alpha_effect.phase);
+            if (alpha_pt <= 0.0) { continue; }
+
+            // Clip applies for the whole phase, even while the wave is still at 0
+            if (alpha_effect.clip_mode != 0u) {
+                alpha_clip = vec2<f32>(f32(alpha_effect.clip_mode), alpha_effect.clip_threshold);
+            }
+
+            let alpha_wave = eval_wave(alpha_pt, alpha_effect.wave);
             let a = alpha_wave.x;
-            if (a > 0.0) {
-                let to = saturate(eff.alpha_effect.target_alpha);
+            if (a <= 0.0) { continue; }
+
+            let to = saturate(alpha_effect.target_alpha);
+            if (alpha_effect.composite == 1u) {
+                // Multiply: scale the running alpha
+                let k = mix(1.0, to, a);
+                alpha_mul *= k;
+                alpha_add *= k;
+            } else {
+                // Lerp: pull the running alpha towards the target
                 let k = (1.0 - a);
                 alpha_mul *= k;
                 alpha_add = alpha_add * k + to * a;
             }
         }
+
+        for (var f: u32 = 0u; f < FRAGMENT_SLOTS; f = f + 1u) {
+            let frag = eff.fragment_effects[f];
+            if (frag.kind == 0u || frag_count >= FRAGMENT_SLOTS) { continue; }
+            if (((lod.skip_fragments >> frag.kind) & 1u) != 0u) { continue; } // Zoomed out
+
+            let frag_pt = phase_lifetime(mt, frag.phase);
+            if (frag_pt <= 0.0) { continue; }
+
+            let frag_wave = eval_wave(frag_pt, frag.wave);
+            frag_kinds[frag_count] = frag.kind;
+            frag_colors[frag_count] = frag.color;
+            frag_params[frag_count] = vec4<f32>(frag.params.xyz, frag_wave.x);
+            frag_count += 1u;
+        }
     }
 
+#ifdef VFX_UI
+    // Back to the UI's Y-down pixels
+    let ui_pos = node_center + spatial_pos.xy * vec2<f32>(1.0, -1.0);
+    let clip_pos = view.clip_from_world * vec4<f32>(ui_pos, vertex_position.z, 1.0);
+#else
     let model = mesh2d_functions::get_world_from_local(instance_index);
-    let clip_pos = mesh2d_functions::mesh2d_position_local_to_clip(model, vec4<f32>(spatial_pos, 1.0));
+    var world_pos = mesh2d_functions::mesh2d_position_local_to_world(model, vec4<f32>(spatial_pos, 1.0));
+#ifdef VFX_Y_SORT_SCALE
+    // Y-sorting: depth from the world Y of the sprite's bottom edge, where effects moved it
+    let sort_local = apply_spatial(t, vec3<f32>(0.0, -0.5 * sprite_px.y, 0.0), effect_stack);
+    let sort_y = mesh2d_functions::mesh2d_position_local_to_world(model, vec4<f32>(sort_local, 1.0)).y;
+    world_pos.z += bitcast<f32>(#{VFX_Y_SORT_OFFSET}u) + sort_y * bitcast<f32>(#{VFX_Y_SORT_SCALE}u);
+#endif
+    let clip_pos = mesh2d_functions::mesh2d_position_world_to_clip(world_pos);
+#endif
+
+    // VfxIntensity: pull the color terms back towards leaving the texel as it is
+    let color_intensity = saturate(effect_stack.intensity);
+    acc_mul_rgb = mix(vec3<f32>(1.0), acc_mul_rgb, color_intensity);
+    acc_add_rgb *= color_intensity;
+    seq_mul_rgb = mix(vec3<f32>(1.0), seq_mul_rgb, color_intensity);
+    seq_add_rgb *= color_intensity;
+    hsv_hue_delta *= color_intensity;
+    hsv_sat_mul = mix(1.0, hsv_sat_mul, color_intensity);
+    hsv_val_mul = mix(1.0, hsv_val_mul, color_intensity);
+    emissive *= color_intensity;
+
+    var base_tint = effect_stack.base_tint;
+#ifdef VERTEX_COLORS
+    // A `BroadcastTint` entity's mesh carries its color in every vertex
+    base_tint *= vertex_color;
+#endif
 
     var out: Varyings;
     out.position = clip_pos;
     out.uv = uv;
-    out.acc_mul = vec4<f32>(acc_mul_rgb, alpha_mul);
+    // The base tint scales the texel, and so whatever the accumulated pass keeps of it
+    out.acc_mul = vec4<f32>(acc_mul_rgb, alpha_mul) * base_tint;
     out.acc_add = vec4<f32>(acc_add_rgb, alpha_add);
     out.seq_mul = vec4<f32>(seq_mul_rgb, 0.0);
     out.seq_add = vec4<f32>(seq_add_rgb, hsv_enabled);
-    out.hsv = vec4<f32>(hsv_hue_delta, hsv_sat_mul, hsv_val_mul, 0.0);
+    out.hsv = vec4<f32>(hsv_hue_delta, hsv_sat_mul, hsv_val_mul, emissive);
+    out.alpha_clip = alpha_clip;
     out.atlas_uv_offset = atlas_uv_offset;
     out.uv_scale = sprite_uv_scale;
+    out.frag_kinds = frag_kinds;
+    out.frag_color0 = frag_colors[0];
+    out.frag_params0 = frag_params[0];
+    out.frag_color1 = frag_colors[1];
+    out.frag_params1 = frag_params[1];
 
     return out;
 }
@@ -604,10 +357,14 @@ fn vertex(
 // Fragment shader
 @fragment
 fn fragment(in: Varyings) -> @location(0) vec4<f32> {
+    sprite_px = in.uv_scale * atlas_dims.texture_size + 2.0 * atlas_dims.uv_inset;
+
     // Transform UV coordinates to sample from the correct sprite in the atlas
     // The offset points to the top-left of the sprite content (after padding)
     // We scale the UV by the sprite size to stay within the sprite
-    let atlas_uv = in.atlas_uv_offset + (in.uv * in.uv_scale);
+    var local_uv = apply_fragment_uv(in.uv, in.frag_kinds.x, in.frag_color0, in.frag_params0);
+    local_uv = apply_fragment_uv(local_uv, in.frag_kinds.y, in.frag_color1, in.frag_params1);
+    let atlas_uv = in.atlas_uv_offset + (local_uv * in.uv_scale);
     let sampled = textureSample(texture, texture_sampler, atlas_uv);
     let base_a = saturate(sampled.a);
 
@@ -627,10 +384,37 @@ fn fragment(in: Varyings) -> @location(0) vec4<f32> {
         rgb = hsv_to_rgb(hsv);
     }
 
+    // HDR emissive boost (only survives past 1.0 on an HDR target)
+    rgb *= 1.0 + max(in.hsv.w, 0.0);
+
     // Coverage-gated alpha affine
     let cov = step(1e-4, base_a);
     let baked_alpha = saturate(base_a * in.acc_mul.w + in.acc_add.w);
-    let alpha = mix(base_a, baked_alpha, cov);
+    let alpha = apply_alpha_clip(mix(base_a, baked_alpha, cov), base_a, local_uv, in.alpha_clip);
 
-    return vec4<f32>(rgb, alpha);
+    var out = vec4<f32>(rgb, alpha);
+    out = apply_fragment_effect(out, base_a, local_uv, in.atlas_uv_offset, in.uv_scale, in.frag_kinds.x, in.frag_color0, in.frag_params0);
+    out = apply_fragment_effect(out, base_a, local_uv, in.atlas_uv_offset, in.uv_scale, in.frag_kinds.y, in.frag_color1, in.frag_params1);
+
+#ifdef HIRUNDO_USER_FRAGMENT
+    // User hook, see `HirundoPlugin::with_custom_fragment`
+    out = custom_fragment(UserFragmentInput(out, sampled, local_uv, atlas_uv, in.position.xy, vfx_time()));
+#endif
+
+#ifdef VFX_ALPHA_CUTOFF
+    // `VfxAlphaMode::Mask`: cut out below the cutoff, opaque above it
+    if out.a < bitcast<f32>(#{VFX_ALPHA_CUTOFF}u) {
+        discard;
+    }
+    out.a = 1.0;
+#endif
+
+#ifdef VFX_Y_SORT_SCALE
+    // Y-sorting writes depth, empty pixels mustn't hide the sprites behind them
+    if out.a <= 0.0 {
+        discard;
+    }
+#endif
+
+    return out;
 }
\ No newline at end of file
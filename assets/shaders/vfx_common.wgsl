@@ -0,0 +1,758 @@
+#define_import_path hirundo::vfx_common
+
+// Everything vfx.wgsl and vfx_broadcast.wgsl share: the effect stack layout, the
+// material bindings other than the stack itself (binding 2), and the effect helpers.
+#ifdef VFX_3D
+#import bevy_pbr::mesh_view_bindings::globals;
+#else
+#import bevy_render::globals::Globals;
+#endif
+
+const PI: f32 = 3.141592653589793;
+const MAX_FX: u32 = 6;
+const MAX_SPATIAL_FX: u32 = 6;
+#ifdef SPATIAL_SLOTS
+const SPATIAL_SLOTS: u32 = #{SPATIAL_SLOTS}u;
+#else
+const SPATIAL_SLOTS: u32 = MAX_SPATIAL_FX;
+#endif
+const MAX_COLOR_FX: u32 = 3;
+const MAX_ALPHA_FX: u32 = 2;
+const MAX_FRAGMENT_FX: u32 = 2;
+#ifdef FX_SLOTS
+const FX_SLOTS: u32 = #{FX_SLOTS}u;
+#else
+const FX_SLOTS: u32 = MAX_FX;
+#endif
+#ifdef COLOR_SLOTS
+const COLOR_SLOTS: u32 = #{COLOR_SLOTS}u;
+#else
+const COLOR_SLOTS: u32 = MAX_COLOR_FX;
+#endif
+#ifdef ALPHA_SLOTS
+const ALPHA_SLOTS: u32 = #{ALPHA_SLOTS}u;
+#else
+const ALPHA_SLOTS: u32 = MAX_ALPHA_FX;
+#endif
+#ifdef FRAGMENT_SLOTS
+const FRAGMENT_SLOTS: u32 = #{FRAGMENT_SLOTS}u;
+#else
+const FRAGMENT_SLOTS: u32 = MAX_FRAGMENT_FX;
+#endif
+const MAX_PATH_VEC4S: u32 = 4; // MAX_PATH_POINTS / 2, packed as xy + zw
+
+// Fragment kinds (0 = empty slot)
+const FRAGMENT_OUTLINE: u32 = 1u;
+const FRAGMENT_DISSOLVE: u32 = 2u;
+const FRAGMENT_PIXELATE: u32 = 3u;
+const FRAGMENT_GLITCH: u32 = 4u;
+const FRAGMENT_OVERLAY: u32 = 5u;
+const FRAGMENT_HOLOGRAM: u32 = 6u;
+
+struct AtlasDimensions {
+    texture_size: vec2<f32>,
+    cell_size: vec2<f32>,
+    sprite_size: vec2<f32>,
+    padding: vec2<f32>,
+    uv_inset: vec2<f32>,
+}
+
+struct Timeline {
+    frozen: u32,
+    time: f32,
+    _pad: vec2<f32>,
+}
+
+// Effects skipped when zoomed out, see VfxLod
+struct Lod {
+    skip_fragments: u32, // Bit 1 << kind per skipped fragment kind
+    skip_hsv: u32,
+    spatial_slots: u32,
+    _pad: u32,
+}
+
+// Levels of audio waves (kinds 6 and 7), see VfxAudioDriver
+struct Audio {
+    amplitude: f32,
+    beat: f32,
+    _pad: vec2<f32>,
+}
+
+struct EffectLifetime { 
+    enabled: u32, 
+    looping: u32, 
+    start_time: f32, 
+    duration: f32 
+}
+
+struct Phase { 
+    start: f32, 
+    end: f32, 
+    reversed: u32, // 1 = played backwards through the window
+    _padding: f32 
+}
+
+struct Envelope { 
+    attack: f32, 
+    hold: f32, 
+    release: f32, 
+    growth_mode: u32, 
+    growth: f32, 
+    enabled: u32, 
+    decay_mode: u32, 
+    decay: f32 
+}
+
+struct Wave {
+    kind: u32, 
+    freq: f32, 
+    amp: f32, 
+    bias: f32, 
+    phase: f32,
+    _pad_a: f32,
+    _pad_b: f32,
+    _pad_c: f32,
+    amp_envelope: Envelope,
+    freq_envelope: Envelope,
+}
+
+struct ColorEffect {
+    phase: Phase,
+    wave: Wave,
+    color: vec4<f32>,
+    from_color: vec4<f32>,
+    blend_mode: u32,
+    emissive: f32,
+}
+
+struct AlphaEffect {
+    phase: Phase,
+    wave: Wave,
+    target_alpha: f32,
+    composite: u32,
+    clip_mode: u32,
+    clip_threshold: f32,
+}
+
+struct SpatialEffect {
+    phase: Phase,
+    wave: Wave,
+    manipulation: u32,
+    intensity: f32,
+    anchor: vec2<f32>,
+    params: vec4<f32>,
+}
+
+struct FragmentEffect {
+    phase: Phase,
+    wave: Wave,
+    kind: u32,
+    _pad_a: u32,
+    _pad_b: u32,
+    _pad_c: u32,
+    color: vec4<f32>,
+    params: vec4<f32>,
+}
+
+// Shader-side flipbook: count frames from start at fps (0 = disabled)
+struct TileCycle {
+    start: u32,
+    count: u32,
+    fps: f32,
+    mode: u32,
+}
+
+struct Effect {
+    lifetime: EffectLifetime,
+    color_effects: array<ColorEffect, MAX_COLOR_FX>,
+    alpha_effects: array<AlphaEffect, MAX_ALPHA_FX>,
+    spatial_effects: array<SpatialEffect, MAX_SPATIAL_FX>,
+    fragment_effects: array<FragmentEffect, MAX_FRAGMENT_FX>,
+    path_points: array<vec4<f32>, MAX_PATH_VEC4S>,
+    tile_cycle: TileCycle,
+}
+
+struct EffectStack {
+    sprite_index: u32,
+    hidden: u32,
+    fx_slots: u32,
+    // VfxIntensity, scales spatial amplitudes and color strengths
+    intensity: f32,
+    // Atlas pixel rect (xy = top-left, zw = size) replacing the sprite's, unused while z is 0
+    sprite_rect: vec4<f32>,
+    // Linear RGBA multiplied into the texel before every effect
+    base_tint: vec4<f32>,
+    effects: array<Effect, MAX_FX>,
+}
+
+#ifndef VFX_3D
+@group(0) @binding(1) var<uniform> globals: Globals;
+#endif
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var texture_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var<uniform> atlas_dims: AtlasDimensions;
+@group(#{MATERIAL_BIND_GROUP}) @binding(4) var noise_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(5) var noise_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(6) var overlay_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(7) var overlay_sampler: sampler;
+// Per-sprite pixel rects (xy = top-left, zw = size), zero size = use the uniform grid
+#ifdef VFX_DATA_TEXTURES
+@group(#{MATERIAL_BIND_GROUP}) @binding(8) var sprite_rects: texture_2d<f32>;
+#else
+@group(#{MATERIAL_BIND_GROUP}) @binding(8) var<storage, read> sprite_rects: array<vec4<f32>>;
+#endif
+@group(#{MATERIAL_BIND_GROUP}) @binding(9) var<uniform> timeline: Timeline;
+@group(#{MATERIAL_BIND_GROUP}) @binding(10) var<uniform> lod: Lod;
+@group(#{MATERIAL_BIND_GROUP}) @binding(11) var<uniform> audio: Audio;
+
+// Pixel size of the sprite being drawn, set at the top of each entry point
+var<private> sprite_px: vec2<f32>;
+
+// Pixel rect (xy = top-left, zw = size) of a sprite: its sprite_rects entry if it has
+// one, otherwise its cell on the uniform grid (after padding)
+fn get_sprite_rect(sprite_index: u32) -> vec4<f32> {
+#ifdef VFX_DATA_TEXTURES
+    let rects_size = textureDimensions(sprite_rects);
+    if (sprite_index < rects_size.x * rects_size.y) {
+        let rect = textureLoad(
+            sprite_rects,
+            vec2<u32>(sprite_index % rects_size.x, sprite_index / rects_size.x),
+            0,
+        );
+        if (rect.z > 0.0) { return rect; }
+    }
+#else
+    if (sprite_index < arrayLength(&sprite_rects)) {
+        let rect = sprite_rects[sprite_index];
+        if (rect.z > 0.0) { return rect; }
+    }
+#endif
+
+    let sprites_per_row = floor(atlas_dims.texture_size.x / atlas_dims.cell_size.x);
+    let col = f32(sprite_index) % sprites_per_row;
+    let row = floor(f32(sprite_index) / sprites_per_row);
+    let origin = vec2<f32>(col, row) * atlas_dims.cell_size + atlas_dims.padding;
+    return vec4<f32>(origin, atlas_dims.sprite_size);
+}
+
+// Helper to calculate atlas UV offset from a sprite rect, inset so filtering
+// doesn't reach the neighbouring sprites
+fn get_atlas_uv_offset(rect: vec4<f32>) -> vec2<f32> {
+    return (rect.xy + atlas_dims.uv_inset) / atlas_dims.texture_size;
+}
+
+// Helper to get sprite UV scale, the rect less the inset on both sides
+fn get_sprite_uv_scale(rect: vec4<f32>) -> vec2<f32> {
+    return max(rect.zw - 2.0 * atlas_dims.uv_inset, vec2<f32>(0.0)) / atlas_dims.texture_size;
+}
+
+// Helper functions
+// Effect time: the frozen time while the timeline is scrubbed, otherwise Bevy's clock
+fn vfx_time() -> f32 {
+    if (timeline.frozen != 0u) { return timeline.time; }
+    return globals.time;
+}
+
+fn master_lifetime(t: f32, m: EffectLifetime) -> f32 {
+    if (m.enabled == 0u || m.duration <= 0.0) { return 0.0; }
+    let elapsed = t - m.start_time;
+    if (m.looping == 1u) { return fract(elapsed / m.duration); }
+    if (elapsed < 0.0 || elapsed >= m.duration) { return 0.0; }
+    return elapsed / m.duration;
+}
+
+fn phase_lifetime(t: f32, p: Phase) -> f32 {
+    let s = clamp(p.start, 0.0, 1.0);
+    let e = clamp(p.end, 0.0, 1.0);
+    if (s >= e || t < s || t > e) { return 0.0; }
+    let progress = (t - s) / (e - s);
+    return select(progress, 1.0 - progress, p.reversed != 0u);
+}
+
+fn eval_envelope_integral(t: f32, env: Envelope) -> vec2<f32> {
+    if (env.enabled == 0u) {
+        return vec2<f32>(1.0, t);
+    }
+
+    let attack = env.attack;
+    let hold = env.hold;
+    let release = env.release;
+    let total = attack + hold + release;
+
+    if (total <= 0.0) {
+        return vec2<f32>(1.0, t);
+    }
+
+    let nt = clamp(t, 0.0, 1.0) * total;
+
+    var env_val: f32 = 1.0;
+    var integral_nt: f32 = 0.0;
+
+    if (nt <= attack) {
+        let phase_t = select(0.0, nt / attack, attack > 0.0);
+        
+        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
+            let s = env.growth;
+            env_val = (exp(phase_t * s) - 1.0) / (exp(s) - 1.0);
+        } else {
+            env_val = phase_t;
+        }
+        
+        integral_nt = select(0.0, (nt * nt) / (2.0 * attack), attack > 0.0);
+    } 
+    else if (nt <= attack + hold) {
+        env_val = 1.0;
+        integral_nt = (attack * 0.5) + (nt - attack);
+    } 
+    else {
+        let s = nt - attack - hold;
+        let phase_t = select(0.0, s / release, release > 0.0);
+        
+        if (env.decay_mode == 1u && abs(env.decay) > 1e-5) {
+            let d = env.decay;
+            env_val = 1.0 - (exp(phase_t * d) - 1.0) / (exp(d) - 1.0);
+        } else {
+            env_val = 1.0 - phase_t;
+        }
+        
+        integral_nt = (attack * 0.5) + hold +
+                      select(0.0, s - (s * s) / (2.0 * release), release > 0.0);
+    }
+
+    let total_area = (attack * 0.5) + hold + (release * 0.5);
+    let base_integral_norm = integral_nt / max(total_area, 1e-5);
+
+    let inst = clamp(env_val, 0.0, 1.0);
+
+    var integral_with_modulation: f32 = base_integral_norm;
+
+    if (env.growth_mode == 1u && abs(env.growth) > 1e-5 && attack > 0.0) {
+        let s = env.growth;
+        let end_t = min(nt, attack);
+        
+        let integral_attack = attack * ((exp(s * end_t / attack) - 1.0) - s * end_t / attack) / (s * (exp(s) - 1.0));
+        
+        var full_integral = attack * 0.5;
+        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
+            full_integral = attack * ((exp(s) - 1.0) - s) / (s * (exp(s) - 1.0));
+        }
+        
+        integral_with_modulation = integral_attack / max(full_integral + hold + (release * 0.5), 1e-5);
+    }
+    
+    if (nt > attack && nt <= attack + hold) {
+        let hold_contrib = (nt - attack);
+        var attack_contrib = attack * 0.5;
+        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
+            let s = env.growth;
+            attack_contrib = attack * ((exp(s) - 1.0) - s) / (s * (exp(s) - 1.0));
+        }
+        integral_with_modulation = (attack_contrib + hold_contrib) / max(attack_contrib + hold + (release * 0.5), 1e-5);
+    }
+    
+    if (nt > attack + hold && release > 0.0) {
+        let s_decay = env.decay;
+        let release_t = nt - attack - hold;
+        
+        var attack_contrib = attack * 0.5;
+        if (env.growth_mode == 1u && abs(env.growth) > 1e-5) {
+            let s = env.growth;
+            attack_contrib = attack * ((exp(s) - 1.0) - s) / (s * (exp(s) - 1.0));
+        }
+        
+        var release_contrib = release_t - (release_t * release_t) / (2.0 * release);
+        if (env.decay_mode == 1u && abs(s_decay) > 1e-5) {
+            release_contrib = release * (release_t / release - ((exp(s_decay * release_t / release) - 1.0) - s_decay * release_t / release) / (s_decay * (exp(s_decay) - 1.0)));
+        }
+        
+        var full_release = release * 0.5;
+        if (env.decay_mode == 1u && abs(s_decay) > 1e-5) {
+            full_release = release * (1.0 - ((exp(s_decay) - 1.0) - s_decay) / (s_decay * (exp(s_decay) - 1.0)));
+        }
+        
+        integral_with_modulation = (attack_contrib + hold + release_contrib) / max(attack_contrib + hold + full_release, 1e-5);
+    }
+
+    return vec2<f32>(inst, clamp(integral_with_modulation, 0.0, 1.0));
+}
+
+fn eval_envelope(t: f32, env: Envelope) -> f32 {
+    return eval_envelope_integral(t, env).x;
+}
+
+fn eval_wave(t: f32, w: Wave) -> vec2<f32> {
+    let fi = eval_envelope_integral(t, w.freq_envelope);
+    let freq_integral = fi.y;
+
+    let cycles = w.freq * freq_integral;
+    let phase = fract(w.phase + cycles);
+    let raw_phase = phase * 2.0 * PI;
+
+    var v: f32 = 0.0;
+    switch w.kind {
+        case 0u: { v = cos(raw_phase); }
+        case 1u: { v = select(-1.0, 1.0, phase > 0.5); }
+        case 2u: { v = 2.0 * abs(2.0 * phase - 1.0) - 1.0; }
+        case 3u: { v = phase * 2.0 - 1.0; }
+        case 4u: { v = 1.0; }
+        case 5u: {
+            // Smooth value noise; phase seeds the sequence instead of shifting it
+            let x = cycles + w.phase * 101.0;
+            let i = floor(x);
+            let f = x - i;
+            let a = hash12(vec2<f32>(i, 0.0));
+            let b = hash12(vec2<f32>(i + 1.0, 0.0));
+            v = mix(a, b, f * f * (3.0 - 2.0 * f)) * 2.0 - 1.0;
+        }
+        case 6u: { v = audio.amplitude; }
+        case 7u: { v = audio.beat; }
+        default: { v = 0.0; }
+    }
+
+    let amp_env = eval_envelope(t, w.amp_envelope);
+    let val = v * w.amp * amp_env + w.bias;
+    return vec2<f32>(saturate(val), val);
+}
+
+fn rgb_to_hsv(c: vec3<f32>) -> vec3<f32> {
+    let max_val = max(max(c.r, c.g), c.b);
+    let min_val = min(min(c.r, c.g), c.b);
+    let delta = max_val - min_val;
+    var hsv = vec3<f32>(0.0, 0.0, max_val);
+    if (delta > 0.0) {
+        if (max_val == c.r) { hsv.x = (c.g - c.b) / delta; }
+        else if (max_val == c.g) { hsv.x = 2.0 + (c.b - c.r) / delta; }
+        else { hsv.x = 4.0 + (c.r - c.g) / delta; }
+        hsv.x = fract(hsv.x / 6.0);
+        hsv.y = delta / max_val;
+    }
+    return hsv;
+}
+
+fn hsv_to_rgb(hsv: vec3<f32>) -> vec3<f32> {
+    let i = floor(hsv.x * 6.0);
+    let f = hsv.x * 6.0 - i;
+    let p = hsv.z * (1.0 - hsv.y);
+    let q = hsv.z * (1.0 - f * hsv.y);
+    let t = hsv.z * (1.0 - (1.0 - f) * hsv.y);
+    switch i32(i) {
+        case 0: { return vec3<f32>(hsv.z, t, p); }
+        case 1: { return vec3<f32>(q, hsv.z, p); }
+        case 2: { return vec3<f32>(p, hsv.z, t); }
+        case 3: { return vec3<f32>(p, q, hsv.z); }
+        case 4: { return vec3<f32>(t, p, hsv.z); }
+        default: { return vec3<f32>(hsv.z, p, q); }
+    }
+}
+
+fn apply_hsv_shift(base: vec3<f32>, shift: vec3<f32>, a: f32) -> vec3<f32> {
+    var hsv = rgb_to_hsv(base);
+    hsv.x = fract(hsv.x + shift.r * a);
+    hsv.y = clamp(hsv.y * (1.0 + shift.g * a), 0.0, 1.0);
+    hsv.z = clamp(hsv.z * (1.0 + shift.b * a), 0.0, 1.0);
+    return hsv_to_rgb(hsv);
+}
+
+fn apply_blend_mode(base: vec3<f32>, blend_color: vec3<f32>, a_clamped: f32, a_raw: f32, mode: u32) -> vec3<f32> {
+    switch mode {
+        case 0u: { return mix(base, blend_color, a_clamped); }
+        case 1u: { return base + blend_color * a_clamped; }
+        case 2u: { return mix(base, base * blend_color, a_clamped); }
+        case 3u: { 
+            let s = vec3<f32>(1.0) - (vec3<f32>(1.0) - base) * (vec3<f32>(1.0) - blend_color); 
+            return mix(base, s, a_clamped); 
+        }
+        case 4u: { return apply_hsv_shift(base, blend_color, a_raw); }
+        default: { return base; }
+    }
+}
+
+fn path_point(points: array<vec4<f32>, MAX_PATH_VEC4S>, idx: u32) -> vec2<f32> {
+    var pts = points;
+    let packed = pts[idx / 2u];
+    return select(packed.xy, packed.zw, (idx % 2u) == 1u);
+}
+
+// Position along a path at progress u, segments evenly spread over 0..1
+fn eval_path(points: array<vec4<f32>, MAX_PATH_VEC4S>, count: u32, curved: bool, u: f32) -> vec2<f32> {
+    if (count < 2u) { return vec2<f32>(0.0); }
+
+    let s = saturate(u) * f32(count - 1u);
+    let i = min(u32(s), count - 2u);
+    let t = s - f32(i);
+    let p1 = path_point(points, i);
+    let p2 = path_point(points, i + 1u);
+    if (!curved) { return mix(p1, p2, t); }
+
+    // Catmull-Rom, end points duplicated
+    let p0 = path_point(points, max(i, 1u) - 1u);
+    let p3 = path_point(points, min(i + 2u, count - 1u));
+    let t2 = t * t;
+    let t3 = t2 * t;
+    return 0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3);
+}
+
+// Spatial effects
+fn apply_spatial(t: f32, pos: vec3<f32>, effect_stack: EffectStack) -> vec3<f32> {
+    var p = pos.xy;
+
+    for (var i: u32 = 0u; i < FX_SLOTS; i = i + 1u) {
+        if (i >= lod.spatial_slots) { break; } // Zoomed out
+        let eff = effect_stack.effects[i];
+        if (eff.lifetime.enabled == 0u) { continue; }
+
+        let mt = master_lifetime(t, eff.lifetime);
+        if (mt == 0.0 && eff.lifetime.looping == 0u) { continue; }
+        if (t < eff.lifetime.start_time) { continue; } // Queued, e.g. a later sequence step
+
+        for (var j: u32 = 0u; j < SPATIAL_SLOTS; j = j + 1u) {
+            let s = eff.spatial_effects[j];
+            if (s.intensity == 0.0) { continue; }
+
+            let pt = phase_lifetime(mt, s.phase);
+            if (pt == 0.0) { continue; }
+
+            let wave = eval_wave(pt, s.wave);
+            let amount = s.intensity * effect_stack.intensity;
+            let val = wave.y * amount;
+            let offset = (s.anchor - vec2<f32>(0.5, 0.5)) * sprite_px;
+            p = p - offset;
+
+            switch s.manipulation {
+                case 0u: { p.x = p.x + val; }
+                case 1u: { p.y = p.y + val; }
+                case 2u: { p.x = p.x * (1.0 + val); }
+                case 3u: { p.y = p.y * (1.0 + val); }
+                case 4u: { 
+                    let c = cos(val); 
+                    let si = sin(val); 
+                    p = vec2<f32>(p.x * c - p.y * si, p.x * si + p.y * c);
+                }
+                case 5u: { p.x = p.x + p.y * val; }
+                case 6u: { p.y = p.y + p.x * val; }
+                case 7u: { p.x = p.x * (1.0 - 2.0 * saturate(val)); }
+                case 8u: { p.y = p.y * (1.0 - 2.0 * saturate(val)); }
+                case 9u: { p = p + offset + amount * vec2<f32>(cos(wave.y), sin(wave.y)); }
+                case 10u: {
+                    let u = wave.y;
+                    p = p + amount * vec2<f32>(s.params.x * u, s.params.y * 4.0 * u * (1.0 - u));
+                }
+                case 11u: {
+                    let h = p.y / sprite_px.y;
+                    p.x = p.x + val * h * h;
+                }
+                case 12u: {
+                    p = p + amount * eval_path(eff.path_points, u32(s.params.x), s.params.y > 0.5, wave.y);
+                }
+                default: { }
+            }
+            p = p + offset;
+        }
+    }
+    return vec3<f32>(p, pos.z);
+}
+
+// Sprite index after tile cycle effects, the most recently started one wins
+fn cycled_sprite_index(t: f32, sprite_index: u32, effect_stack: EffectStack) -> u32 {
+    var index = sprite_index;
+    var latest_start = -1e30;
+    for (var i: u32 = 0u; i < FX_SLOTS; i = i + 1u) {
+        let eff = effect_stack.effects[i];
+        let c = eff.tile_cycle;
+        if (eff.lifetime.enabled == 0u || c.count == 0u) { continue; }
+
+        let mt = master_lifetime(t, eff.lifetime);
+        if (mt == 0.0 && eff.lifetime.looping == 0u) { continue; }
+        if (t < eff.lifetime.start_time || eff.lifetime.start_time < latest_start) { continue; }
+
+        let step = u32(max(t - eff.lifetime.start_time, 0.0) * max(c.fps, 0.0));
+        var frame = step % c.count;
+        if (c.mode == 1u) {
+            frame = min(step, c.count - 1u);
+        } else if (c.mode == 2u) {
+            if (c.count < 2u) {
+                frame = 0u;
+            } else {
+                let period = 2u * c.count - 2u;
+                let k = step % period;
+                frame = select(period - k, k, k < c.count);
+            }
+        }
+        index = c.start + frame;
+        latest_start = eff.lifetime.start_time;
+    }
+    return index;
+}
+
+// Fragment effects
+
+fn hash12(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+// Alpha of the sprite at a sprite-local UV, transparent outside the sprite rect
+fn sprite_alpha(local_uv: vec2<f32>, uv_offset: vec2<f32>, uv_scale: vec2<f32>) -> f32 {
+    if (any(local_uv < vec2<f32>(0.0)) || any(local_uv > vec2<f32>(1.0))) { return 0.0; }
+    return textureSampleLevel(texture, texture_sampler, uv_offset + local_uv * uv_scale, 0.0).a;
+}
+
+fn apply_outline(
+    c: vec4<f32>,
+    base_a: f32,
+    local_uv: vec2<f32>,
+    uv_offset: vec2<f32>,
+    uv_scale: vec2<f32>,
+    color: vec4<f32>,
+    params: vec4<f32>,
+) -> vec4<f32> {
+    let strength = params.w;
+    if (base_a > 1e-4 || strength <= 0.0) { return c; }
+
+    let texel = 1.0 / sprite_px;
+    let radius = u32(clamp(ceil(params.x), 1.0, 8.0));
+    var coverage = 0.0;
+    for (var r: u32 = 1u; r <= radius; r = r + 1u) {
+        let d = texel * f32(r);
+        coverage = max(coverage, sprite_alpha(local_uv + vec2<f32>(d.x, 0.0), uv_offset, uv_scale));
+        coverage = max(coverage, sprite_alpha(local_uv - vec2<f32>(d.x, 0.0), uv_offset, uv_scale));
+        coverage = max(coverage, sprite_alpha(local_uv + vec2<f32>(0.0, d.y), uv_offset, uv_scale));
+        coverage = max(coverage, sprite_alpha(local_uv - vec2<f32>(0.0, d.y), uv_offset, uv_scale));
+        coverage = max(coverage, sprite_alpha(local_uv + d, uv_offset, uv_scale));
+        coverage = max(coverage, sprite_alpha(local_uv - d, uv_offset, uv_scale));
+        coverage = max(coverage, sprite_alpha(local_uv + vec2<f32>(d.x, -d.y), uv_offset, uv_scale));
+        coverage = max(coverage, sprite_alpha(local_uv + vec2<f32>(-d.x, d.y), uv_offset, uv_scale));
+    }
+    if (coverage <= 0.0) { return c; }
+
+    return vec4<f32>(color.rgb, saturate(color.a * strength * coverage));
+}
+
+fn apply_dissolve(c: vec4<f32>, local_uv: vec2<f32>, color: vec4<f32>, params: vec4<f32>) -> vec4<f32> {
+    let threshold = params.w;
+    if (threshold <= 0.0) { return c; }
+
+    let noise = textureSampleLevel(noise_texture, noise_sampler, local_uv * max(params.y, 1e-3), 0.0).r;
+    // Scale so that threshold 1.0 also removes the edge band
+    let edge_width = max(params.x, 0.0);
+    let t = threshold * (1.0 + edge_width);
+    if (noise < t - edge_width) { return vec4<f32>(c.rgb, 0.0); }
+    if (noise < t) {
+        return vec4<f32>(mix(c.rgb, color.rgb, color.a), c.a);
+    }
+    return c;
+}
+
+fn apply_pixelate(local_uv: vec2<f32>, params: vec4<f32>) -> vec2<f32> {
+    let block = mix(1.0, max(params.x, 1.0), params.w);
+    if (block <= 1.0) { return local_uv; }
+
+    let texels = local_uv * sprite_px;
+    let snapped = (floor(texels / block) + 0.5) * block;
+    return clamp(snapped / sprite_px, vec2<f32>(0.0), vec2<f32>(1.0));
+}
+
+fn apply_glitch_uv(local_uv: vec2<f32>, params: vec4<f32>) -> vec2<f32> {
+    let strength = params.w;
+    if (strength <= 0.0) { return local_uv; }
+
+    let frame = floor(vfx_time() * 20.0);
+    let row = floor(local_uv.y * max(params.x, 1.0));
+    // Roughly half the rows move on any given frame
+    if (hash12(vec2<f32>(row, frame)) < 0.5) { return local_uv; }
+
+    let shift = (hash12(vec2<f32>(frame, row + 17.0)) * 2.0 - 1.0) * params.y * strength;
+    return vec2<f32>(fract(local_uv.x + shift), local_uv.y);
+}
+
+fn apply_glitch(c: vec4<f32>, local_uv: vec2<f32>, uv_offset: vec2<f32>, uv_scale: vec2<f32>, params: vec4<f32>) -> vec4<f32> {
+    let split = params.z * params.w / sprite_px.x;
+    if (split <= 0.0) { return c; }
+
+    let r = textureSampleLevel(texture, texture_sampler, uv_offset + fract(local_uv + vec2<f32>(split, 0.0)) * uv_scale, 0.0);
+    let b = textureSampleLevel(texture, texture_sampler, uv_offset + fract(local_uv - vec2<f32>(split, 0.0)) * uv_scale, 0.0);
+    return vec4<f32>(r.r, c.g, b.b, c.a);
+}
+
+fn apply_overlay(c: vec4<f32>, base_a: f32, local_uv: vec2<f32>, color: vec4<f32>, params: vec4<f32>) -> vec4<f32> {
+    let opacity = params.w * color.a * base_a;
+    if (opacity <= 0.0) { return c; }
+
+    let scroll = params.yz * vfx_time();
+    let o = textureSampleLevel(overlay_texture, overlay_sampler, fract(local_uv + scroll), 0.0);
+    let tinted = o.rgb * color.rgb;
+    let a = opacity * o.a;
+
+    // Same modes as color effects, minus HSV/Gradient
+    switch u32(params.x) {
+        case 1u: { return vec4<f32>(c.rgb + tinted * a, c.a); }
+        case 2u: { return vec4<f32>(mix(c.rgb, c.rgb * tinted, a), c.a); }
+        case 3u: {
+            let s = vec3<f32>(1.0) - (vec3<f32>(1.0) - c.rgb) * (vec3<f32>(1.0) - tinted);
+            return vec4<f32>(mix(c.rgb, s, a), c.a);
+        }
+        default: { return vec4<f32>(mix(c.rgb, tinted, a), c.a); }
+    }
+}
+
+fn apply_hologram(c: vec4<f32>, local_uv: vec2<f32>, color: vec4<f32>, params: vec4<f32>) -> vec4<f32> {
+    let lines = max(params.x, 1.0);
+    let scan = 0.5 + 0.5 * sin((local_uv.y * lines - vfx_time() * params.z) * 6.2831853);
+    let band = 1.0 - saturate(params.y) * (1.0 - scan);
+
+    let lum = dot(c.rgb, vec3<f32>(0.299, 0.587, 0.114));
+    let holo = color.rgb * (0.35 + lum) * (0.85 + 0.3 * scan);
+    return vec4<f32>(mix(c.rgb, holo, color.a), c.a * band * saturate(params.w));
+}
+
+// Hard-edged alpha: keep the texel at its original alpha or drop it
+fn apply_alpha_clip(alpha: f32, base_a: f32, local_uv: vec2<f32>, clip: vec2<f32>) -> f32 {
+    let mode = u32(clip.x + 0.5);
+    if (mode == 0u || base_a <= 0.0) { return alpha; }
+
+    let remaining = alpha / base_a;
+    var threshold = clip.y;
+    if (mode == 2u) {
+        var bayer = array<f32, 16>(
+            0.0, 8.0, 2.0, 10.0,
+            12.0, 4.0, 14.0, 6.0,
+            3.0, 11.0, 1.0, 9.0,
+            15.0, 7.0, 13.0, 5.0,
+        );
+        let texel = vec2<u32>(floor(local_uv * sprite_px)) % vec2<u32>(4u);
+        threshold = (bayer[texel.y * 4u + texel.x] + 0.5) / 16.0;
+    }
+    return select(0.0, base_a, remaining >= threshold);
+}
+
+// UV-stage fragment effects, applied before the atlas is sampled
+fn apply_fragment_uv(local_uv: vec2<f32>, kind: u32, color: vec4<f32>, params: vec4<f32>) -> vec2<f32> {
+    switch kind {
+        case FRAGMENT_PIXELATE: { return apply_pixelate(local_uv, params); }
+        case FRAGMENT_GLITCH: { return apply_glitch_uv(local_uv, params); }
+        default: { return local_uv; }
+    }
+}
+
+// Color-stage fragment effects, applied to the final color
+fn apply_fragment_effect(
+    c: vec4<f32>,
+    base_a: f32,
+    local_uv: vec2<f32>,
+    uv_offset: vec2<f32>,
+    uv_scale: vec2<f32>,
+    kind: u32,
+    color: vec4<f32>,
+    params: vec4<f32>,
+) -> vec4<f32> {
+    switch kind {
+        case FRAGMENT_OUTLINE: { return apply_outline(c, base_a, local_uv, uv_offset, uv_scale, color, params); }
+        case FRAGMENT_DISSOLVE: { return apply_dissolve(c, local_uv, color, params); }
+        case FRAGMENT_GLITCH: { return apply_glitch(c, local_uv, uv_offset, uv_scale, params); }
+        case FRAGMENT_OVERLAY: { return apply_overlay(c, base_a, local_uv, color, params); }
+        case FRAGMENT_HOLOGRAM: { return apply_hologram(c, local_uv, color, params); }
+        default: { return c; }
+    }
+}
@@ -0,0 +1,154 @@
+// Full-screen pass for `ScreenVfx`: the effect stack is evaluated on the CPU (the
+// vertex stage's work) and handed over in one uniform, this applies it to the screen.
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+const MAX_FRAGMENT_FX: u32 = 2u;
+const FRAGMENT_PIXELATE: u32 = 3u;
+const FRAGMENT_GLITCH: u32 = 4u;
+
+// The color and alpha terms of `vfx.wgsl`'s `Varyings`
+struct TintTerms {
+    acc_mul: vec4<f32>,
+    acc_add: vec4<f32>,
+    seq_mul: vec4<f32>,
+    seq_add: vec4<f32>,
+    hsv: vec4<f32>,
+}
+
+struct FragmentTerm {
+    kind: u32,
+    color: vec4<f32>,
+    params: vec4<f32>,
+}
+
+struct ScreenVfx {
+    tint: TintTerms,
+    fragments: array<FragmentTerm, MAX_FRAGMENT_FX>,
+    // Inverse of the spatial effects, screen point -> source point: columns, then translation
+    source_axes: vec4<f32>,
+    source_offset: vec2<f32>,
+    // Logical pixels, the unit of spatial and fragment effects
+    size: vec2<f32>,
+    time: f32,
+    enabled: u32,
+}
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var screen_sampler: sampler;
+@group(0) @binding(2) var<uniform> screen: ScreenVfx;
+
+fn hash12(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+fn rgb_to_hsv(c: vec3<f32>) -> vec3<f32> {
+    let max_val = max(max(c.r, c.g), c.b);
+    let min_val = min(min(c.r, c.g), c.b);
+    let delta = max_val - min_val;
+    var hsv = vec3<f32>(0.0, 0.0, max_val);
+    if (delta > 0.0) {
+        if (max_val == c.r) { hsv.x = (c.g - c.b) / delta; }
+        else if (max_val == c.g) { hsv.x = 2.0 + (c.b - c.r) / delta; }
+        else { hsv.x = 4.0 + (c.r - c.g) / delta; }
+        hsv.x = fract(hsv.x / 6.0);
+        hsv.y = delta / max_val;
+    }
+    return hsv;
+}
+
+fn hsv_to_rgb(hsv: vec3<f32>) -> vec3<f32> {
+    let i = floor(hsv.x * 6.0);
+    let f = hsv.x * 6.0 - i;
+    let p = hsv.z * (1.0 - hsv.y);
+    let q = hsv.z * (1.0 - f * hsv.y);
+    let t = hsv.z * (1.0 - (1.0 - f) * hsv.y);
+    switch i32(i) {
+        case 0: { return vec3<f32>(hsv.z, t, p); }
+        case 1: { return vec3<f32>(q, hsv.z, p); }
+        case 2: { return vec3<f32>(p, hsv.z, t); }
+        case 3: { return vec3<f32>(p, q, hsv.z); }
+        case 4: { return vec3<f32>(t, p, hsv.z); }
+        default: { return vec3<f32>(hsv.z, p, q); }
+    }
+}
+
+// UV of the screen texel the spatial effects moved to `uv` (Y down, points Y up)
+fn source_uv(uv: vec2<f32>) -> vec2<f32> {
+    let flip = vec2<f32>(1.0, -1.0);
+    let p = (uv - 0.5) * screen.size * flip;
+    let source = mat2x2<f32>(screen.source_axes.xy, screen.source_axes.zw) * p + screen.source_offset;
+    return source / screen.size * flip + 0.5;
+}
+
+fn apply_pixelate(uv: vec2<f32>, params: vec4<f32>) -> vec2<f32> {
+    let block = mix(1.0, max(params.x, 1.0), params.w);
+    if (block <= 1.0) { return uv; }
+
+    let pixels = uv * screen.size;
+    return (floor(pixels / block) + 0.5) * block / screen.size;
+}
+
+fn apply_glitch_uv(uv: vec2<f32>, params: vec4<f32>) -> vec2<f32> {
+    let strength = params.w;
+    if (strength <= 0.0) { return uv; }
+
+    let frame = floor(screen.time * 20.0);
+    let row = floor(uv.y * max(params.x, 1.0));
+    // Roughly half the rows move on any given frame
+    if (hash12(vec2<f32>(row, frame)) < 0.5) { return uv; }
+
+    let shift = (hash12(vec2<f32>(frame, row + 17.0)) * 2.0 - 1.0) * params.y * strength;
+    return vec2<f32>(uv.x + shift, uv.y);
+}
+
+fn apply_fragment_uv(uv: vec2<f32>, fragment: FragmentTerm) -> vec2<f32> {
+    switch fragment.kind {
+        case FRAGMENT_PIXELATE: { return apply_pixelate(uv, fragment.params); }
+        case FRAGMENT_GLITCH: { return apply_glitch_uv(uv, fragment.params); }
+        default: { return uv; }
+    }
+}
+
+// Chromatic aberration: red and blue sampled `params.z` pixels apart
+fn rgb_split(c: vec4<f32>, uv: vec2<f32>, fragment: FragmentTerm) -> vec4<f32> {
+    let split = select(0.0, fragment.params.z * fragment.params.w, fragment.kind == FRAGMENT_GLITCH);
+    if (split <= 0.0) { return c; }
+
+    let offset = vec2<f32>(split / screen.size.x, 0.0);
+    let r = textureSampleLevel(screen_texture, screen_sampler, uv + offset, 0.0).r;
+    let b = textureSampleLevel(screen_texture, screen_sampler, uv - offset, 0.0).b;
+    return vec4<f32>(r, c.g, b, c.a);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    if (screen.enabled == 0u) {
+        return textureSampleLevel(screen_texture, screen_sampler, in.uv, 0.0);
+    }
+
+    var uv = source_uv(in.uv);
+    for (var i: u32 = 0u; i < MAX_FRAGMENT_FX; i = i + 1u) {
+        uv = apply_fragment_uv(uv, screen.fragments[i]);
+    }
+    var sampled = textureSampleLevel(screen_texture, screen_sampler, uv, 0.0);
+    for (var i: u32 = 0u; i < MAX_FRAGMENT_FX; i = i + 1u) {
+        sampled = rgb_split(sampled, uv, screen.fragments[i]);
+    }
+
+    // Same color stages as `vfx.wgsl`, the screen being one opaque texel
+    let tint = screen.tint;
+    var rgb = clamp(sampled.rgb * tint.acc_mul.rgb + tint.acc_add.rgb, vec3<f32>(0.0), vec3<f32>(1.0));
+    rgb = rgb * tint.seq_mul.rgb + tint.seq_add.rgb;
+    if (tint.seq_add.w > 0.5) {
+        var hsv = rgb_to_hsv(clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0)));
+        hsv.x = fract(hsv.x + tint.hsv.x);
+        hsv.y = clamp(hsv.y * tint.hsv.y, 0.0, 1.0);
+        hsv.z = clamp(hsv.z * tint.hsv.z, 0.0, 1.0);
+        rgb = hsv_to_rgb(hsv);
+    }
+    rgb *= 1.0 + max(tint.hsv.w, 0.0);
+
+    // Alpha effects fade the screen to black
+    let alpha = saturate(tint.acc_mul.w + tint.acc_add.w);
+    return vec4<f32>(rgb * alpha, sampled.a);
+}
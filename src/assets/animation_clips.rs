@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+
+use crate::internal_prelude::*;
+
+/// Named flipbook clips (frame range, fps, mode) for a character's animation states.
+///
+/// Files ending in `.clips.ron` load as a RON map from clip name to clip:
+/// ```ron
+/// {
+///     "idle": (start: 0, count: 2, fps: 2.0),
+///     "walk": (start: 4, count: 4, fps: 8.0),
+///     "die": (start: 8, count: 5, fps: 10.0, mode: Once),
+/// }
+/// ```
+/// An [`AnimationClipPlayer`] plays them by name on its entity.
+#[derive(Asset, TypePath, Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AnimationClips {
+    pub clips: BTreeMap<String, AnimationClip>,
+}
+
+/// `count` consecutive atlas indices from `start`, played at `fps`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnimationClip {
+    pub start: u32,
+    pub count: u32,
+    pub fps: f32,
+    #[serde(default)]
+    pub mode: AnimationMode,
+}
+
+impl AnimationClip {
+    pub fn new(start: u32, count: u32, fps: f32, mode: AnimationMode) -> Self {
+        Self {
+            start,
+            count,
+            fps,
+            mode,
+        }
+    }
+
+    /// A fresh [`SpriteAnimation`] playing this clip from its first frame
+    pub fn animation(&self) -> SpriteAnimation {
+        SpriteAnimation::range(self.start, self.count, self.fps).with_mode(self.mode)
+    }
+}
+
+impl AnimationClips {
+    /// Add clip `name`, replacing any clip already using it
+    pub fn insert(&mut self, name: impl Into<String>, clip: AnimationClip) -> &mut Self {
+        self.clips.insert(name.into(), clip);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.clips.contains_key(name)
+    }
+
+    /// Clip names, in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.clips.keys().map(String::as_str)
+    }
+}
+
+/// Why a `.clips.ron` file failed to load.
+#[derive(Debug)]
+pub enum AnimationClipsError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for AnimationClipsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read animation clips: {err}"),
+            Self::Ron(err) => write!(f, "could not parse animation clips: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for AnimationClipsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Ron(err) => Some(err),
+        }
+    }
+}
+
+impl From<std::io::Error> for AnimationClipsError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for AnimationClipsError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Ron(err)
+    }
+}
+
+/// Loads [`AnimationClips`] from `.clips.ron` files.
+#[derive(Default, TypePath)]
+pub struct AnimationClipsLoader;
+
+impl AssetLoader for AnimationClipsLoader {
+    type Asset = AnimationClips;
+    type Settings = ();
+    type Error = AnimationClipsError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<AnimationClips, AnimationClipsError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["clips.ron"]
+    }
+}
@@ -0,0 +1,438 @@
+use std::fmt;
+
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use serde::de::{MapAccess, SeqAccess, Visitor};
+
+use crate::internal_prelude::*;
+
+/// How far (in pixels) a frame may sit from its inferred grid cell.
+const GRID_TOLERANCE: f32 = 0.5;
+
+/// Atlas grid and sprite names derived from an exported atlas description.
+///
+/// When the frames all share one size and sit on evenly spaced rows and columns, the
+/// grid's cell size and padding are inferred from the frame positions, and each name maps
+/// to the index the shaders use (`row * columns + column`). Otherwise each frame gets its
+/// own rect in `rects` (see [`SpriteRects`]) and names map to frame order.
+///
+/// Usually loaded through [`HirundoPlugin::with_atlas_json`](crate::HirundoPlugin::with_atlas_json),
+/// which applies it to the materials and [`SpriteNames`] once loaded.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct VfxAtlasLayout {
+    pub dimensions: AtlasDimensions,
+    pub names: HashMap<String, u32>,
+    /// Per-sprite rects by index, empty when the frames are on a grid
+    pub rects: Vec<Rect>,
+    /// Animation tags from an Aseprite export, empty for other atlases
+    pub tags: HashMap<String, AtlasTag>,
+}
+
+/// A named animation from an Aseprite export: its frames as atlas indices, in order.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct AtlasTag {
+    pub frames: Vec<u32>,
+    /// How long each frame shows, in seconds
+    pub durations: Vec<f32>,
+    pub direction: TagDirection,
+}
+
+/// Playback direction of an [`AtlasTag`], as set in Aseprite.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Reflect)]
+#[reflect(Default)]
+pub enum TagDirection {
+    #[default]
+    #[serde(rename = "forward")]
+    Forward,
+    #[serde(rename = "reverse")]
+    Reverse,
+    #[serde(rename = "pingpong")]
+    PingPong,
+    #[serde(rename = "pingpong_reverse")]
+    PingPongReverse,
+}
+
+impl VfxAtlasLayout {
+    /// Infer the grid from named frame rects (in pixels, origin top-left) on a
+    /// `texture_size` texture.
+    pub fn from_frames(
+        texture_size: Vec2,
+        frames: impl IntoIterator<Item = (String, Rect)>,
+    ) -> Result<Self, AtlasLayoutError> {
+        let frames: Vec<(String, Rect)> = frames.into_iter().collect();
+        let Some((_, first)) = frames.first() else {
+            return Err(AtlasLayoutError::Empty);
+        };
+        let sprite_size = first.size();
+        if let Some((name, _)) = frames
+            .iter()
+            .find(|(_, rect)| rect.size().distance(sprite_size) > GRID_TOLERANCE)
+        {
+            return Err(AtlasLayoutError::NonUniformFrames(name.clone()));
+        }
+
+        let cell_size = Vec2::new(
+            grid_step(frames.iter().map(|(_, rect)| rect.min.x), sprite_size.x),
+            grid_step(frames.iter().map(|(_, rect)| rect.min.y), sprite_size.y),
+        );
+        let origin = frames
+            .iter()
+            .fold(Vec2::MAX, |origin, (_, rect)| origin.min(rect.min));
+        let dimensions = AtlasDimensions {
+            texture_size,
+            cell_size,
+            sprite_size,
+            padding: origin % cell_size,
+            uv_inset: Vec2::ZERO,
+        };
+
+        let mut names = HashMap::default();
+        for (name, rect) in &frames {
+            let Some(index) = grid_index(&dimensions, *rect) else {
+                return Err(AtlasLayoutError::NotAGrid(name.clone()));
+            };
+            insert_name(&mut names, name, index)?;
+        }
+
+        Ok(Self {
+            dimensions,
+            names,
+            rects: Vec::new(),
+            tags: HashMap::default(),
+        })
+    }
+
+    /// Give every named frame rect (in pixels, origin top-left) its own sprite index, in
+    /// order, for atlases whose frames don't share a size or grid.
+    pub fn from_rects(
+        texture_size: Vec2,
+        frames: impl IntoIterator<Item = (String, Rect)>,
+    ) -> Result<Self, AtlasLayoutError> {
+        let (names, rects): (Vec<String>, Vec<Rect>) = frames.into_iter().unzip();
+        let Some(first) = rects.first() else {
+            return Err(AtlasLayoutError::Empty);
+        };
+        let sprite_size = first.size();
+        let mut sprite_names = HashMap::default();
+        for (index, name) in names.iter().enumerate() {
+            insert_name(&mut sprite_names, name, index as u32)?;
+        }
+
+        Ok(Self {
+            // The grid only sizes the shared quad, every sprite has a rect
+            dimensions: AtlasDimensions {
+                texture_size,
+                cell_size: sprite_size,
+                sprite_size,
+                padding: Vec2::ZERO,
+                uv_inset: Vec2::ZERO,
+            },
+            names: sprite_names,
+            rects,
+            tags: HashMap::default(),
+        })
+    }
+
+    /// Read a TexturePacker or Aseprite JSON export, as [`AtlasJsonLoader`] does.
+    pub fn from_json(bytes: &[u8]) -> Result<Self, AtlasLayoutError> {
+        serde_json::from_slice::<AtlasJson>(bytes)?.into_layout()
+    }
+
+    /// Sprite index of the frame at `rect`, `order`th in the description it was built from
+    fn frame_index(&self, order: usize, rect: Rect) -> Option<u32> {
+        if self.rects.is_empty() {
+            grid_index(&self.dimensions, rect)
+        } else {
+            Some(order as u32)
+        }
+    }
+}
+
+/// Index of the grid cell `rect` sits in (`row * columns + column`), `None` when it's off
+/// the grid
+fn grid_index(dimensions: &AtlasDimensions, rect: Rect) -> Option<u32> {
+    let cell = (rect.min - dimensions.padding) / dimensions.cell_size;
+    if ((cell - cell.round()) * dimensions.cell_size)
+        .abs()
+        .max_element()
+        > GRID_TOLERANCE
+    {
+        return None;
+    }
+    let cell = cell.round();
+    let columns = (dimensions.texture_size.x / dimensions.cell_size.x).floor();
+    Some((cell.y * columns + cell.x) as u32)
+}
+
+/// Smallest gap between distinct frame positions along one axis, or the sprite
+/// extent when there is only one row/column.
+fn grid_step(positions: impl Iterator<Item = f32>, sprite_extent: f32) -> f32 {
+    let mut positions: Vec<f32> = positions.collect();
+    positions.sort_by(f32::total_cmp);
+    positions
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .filter(|gap| *gap > GRID_TOLERANCE)
+        .fold(None, |step: Option<f32>, gap| {
+            Some(step.map_or(gap, |s| s.min(gap)))
+        })
+        .unwrap_or(sprite_extent)
+}
+
+/// Frame names without their image extension: `goblin_idle_0.png` -> `goblin_idle_0`
+fn sprite_name(frame_name: &str) -> String {
+    std::path::Path::new(frame_name)
+        .with_extension("")
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Map `frame_name`'s sprite name to `index`, failing when another frame already has it
+fn insert_name(
+    names: &mut HashMap<String, u32>,
+    frame_name: &str,
+    index: u32,
+) -> Result<(), AtlasLayoutError> {
+    let name = sprite_name(frame_name);
+    if names.contains_key(&name) {
+        return Err(AtlasLayoutError::DuplicateName(name));
+    }
+    names.insert(name, index);
+    Ok(())
+}
+
+/// TexturePacker's JSON export, or Aseprite's (the same format plus `frameTags`)
+#[derive(Deserialize)]
+struct AtlasJson {
+    frames: AtlasJsonFrames,
+    meta: AtlasJsonMeta,
+}
+
+/// Frames in file order, from either the hash (`{"name": frame}`) or the array
+/// (`[{"filename": "name", ..frame}]`) flavour. Aseprite tags refer to this order.
+struct AtlasJsonFrames(Vec<(String, AtlasJsonFrame)>);
+
+#[derive(Deserialize)]
+struct AtlasJsonNamedFrame {
+    filename: String,
+    #[serde(flatten)]
+    frame: AtlasJsonFrame,
+}
+
+#[derive(Deserialize)]
+struct AtlasJsonFrame {
+    frame: AtlasJsonRect,
+    #[serde(default)]
+    rotated: bool,
+    /// Aseprite only, in milliseconds
+    #[serde(default = "default_frame_duration")]
+    duration: f32,
+}
+
+fn default_frame_duration() -> f32 {
+    100.0
+}
+
+#[derive(Deserialize)]
+struct AtlasJsonRect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct AtlasJsonMeta {
+    size: AtlasJsonSize,
+    #[serde(default, rename = "frameTags")]
+    frame_tags: Vec<AtlasJsonTag>,
+}
+
+#[derive(Deserialize)]
+struct AtlasJsonSize {
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct AtlasJsonTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: TagDirection,
+}
+
+impl<'de> Deserialize<'de> for AtlasJsonFrames {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FramesVisitor;
+
+        impl<'de> Visitor<'de> for FramesVisitor {
+            type Value = AtlasJsonFrames;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map or array of frames")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    frames.push(entry);
+                }
+                Ok(AtlasJsonFrames(frames))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::new();
+                while let Some(AtlasJsonNamedFrame { filename, frame }) = seq.next_element()? {
+                    frames.push((filename, frame));
+                }
+                Ok(AtlasJsonFrames(frames))
+            }
+        }
+
+        deserializer.deserialize_any(FramesVisitor)
+    }
+}
+
+impl AtlasJson {
+    fn into_layout(self) -> Result<VfxAtlasLayout, AtlasLayoutError> {
+        let frames = self.frames.0;
+        if let Some((name, _)) = frames.iter().find(|(_, frame)| frame.rotated) {
+            return Err(AtlasLayoutError::RotatedFrame(name.clone()));
+        }
+
+        let texture_size = Vec2::new(self.meta.size.w, self.meta.size.h);
+        let rects: Vec<(String, Rect)> = frames
+            .iter()
+            .map(|(name, frame)| {
+                let rect = &frame.frame;
+                (
+                    name.clone(),
+                    Rect::new(rect.x, rect.y, rect.x + rect.w, rect.y + rect.h),
+                )
+            })
+            .collect();
+        let mut layout = match VfxAtlasLayout::from_frames(texture_size, rects.clone()) {
+            Err(AtlasLayoutError::NonUniformFrames(_) | AtlasLayoutError::NotAGrid(_)) => {
+                VfxAtlasLayout::from_rects(texture_size, rects.clone())?
+            }
+            layout => layout?,
+        };
+
+        for tag in self.meta.frame_tags {
+            let Some(tag_frames) = frames.get(tag.from..=tag.to) else {
+                return Err(AtlasLayoutError::TagOutOfRange(tag.name));
+            };
+            // Each frame by its own rect, not its name
+            let indices = (tag.from..=tag.to)
+                .map(|order| {
+                    let (name, rect) = &rects[order];
+                    layout
+                        .frame_index(order, *rect)
+                        .ok_or_else(|| AtlasLayoutError::NotAGrid(name.clone()))
+                })
+                .collect::<Result<_, _>>()?;
+            let atlas_tag = AtlasTag {
+                frames: indices,
+                durations: tag_frames
+                    .iter()
+                    .map(|(_, frame)| frame.duration / 1000.0)
+                    .collect(),
+                direction: tag.direction,
+            };
+            layout.tags.insert(tag.name, atlas_tag);
+        }
+        Ok(layout)
+    }
+}
+
+/// Why an atlas description failed to load.
+#[derive(Debug)]
+pub enum AtlasLayoutError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// The atlas has no frames
+    Empty,
+    /// A frame is rotated in the texture, which the shaders can't sample
+    RotatedFrame(String),
+    /// A frame's size differs from the first frame's
+    NonUniformFrames(String),
+    /// A frame doesn't line up with the grid inferred from the others
+    NotAGrid(String),
+    /// Two frames have the same sprite name (file extensions aside)
+    DuplicateName(String),
+    /// An Aseprite tag refers to frames past the end of the atlas
+    TagOutOfRange(String),
+}
+
+impl std::fmt::Display for AtlasLayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read atlas: {err}"),
+            Self::Json(err) => write!(f, "could not parse atlas: {err}"),
+            Self::Empty => write!(f, "atlas has no frames"),
+            Self::RotatedFrame(name) => write!(f, "frame \"{name}\" is rotated"),
+            Self::NonUniformFrames(name) => {
+                write!(f, "frame \"{name}\" differs in size from the other frames")
+            }
+            Self::NotAGrid(name) => write!(f, "frame \"{name}\" is not on the atlas grid"),
+            Self::DuplicateName(name) => write!(f, "more than one frame is named \"{name}\""),
+            Self::TagOutOfRange(name) => write!(f, "tag \"{name}\" has frames out of range"),
+        }
+    }
+}
+
+impl std::error::Error for AtlasLayoutError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for AtlasLayoutError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for AtlasLayoutError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// Loads a [`VfxAtlasLayout`] from a TexturePacker or Aseprite JSON export (hash or
+/// array format). Aseprite's frame tags become [`AtlasTag`]s. Frames that aren't on a
+/// uniform grid get per-sprite rects.
+///
+/// Aseprite files have to be exported first (*File > Export Sprite Sheet* with JSON data
+/// and *Tags* enabled); `.aseprite` files themselves can't be read.
+///
+/// Registered for `.atlas.json` and `.aseprite.json`; load other names with an explicit
+/// type, e.g. `asset_server.load::<VfxAtlasLayout>("sprites.json")`.
+#[derive(Default, TypePath)]
+pub struct AtlasJsonLoader;
+
+impl AssetLoader for AtlasJsonLoader {
+    type Asset = VfxAtlasLayout;
+    type Settings = ();
+    type Error = AtlasLayoutError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<VfxAtlasLayout, AtlasLayoutError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        VfxAtlasLayout::from_json(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["atlas.json", "aseprite.json"]
+    }
+}
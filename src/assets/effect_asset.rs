@@ -0,0 +1,306 @@
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+
+use crate::internal_prelude::*;
+
+/// An effect authored as data, loaded from `.effect.ron` files.
+///
+/// Sub-effects use the same fields as their Rust types, and anything left out starts
+/// where the matching [`EffectBuilder`] method would (e.g. a `Wave::constant(1.0)`).
+/// Kinds and modes are written by name: [`SpatialKind`] for `manipulation`,
+/// [`FragmentKind`] for `kind`, [`WaveKind`] for a wave's `kind`, [`BlendMode`],
+/// [`GrowthMode`] for an envelope's `growth_mode` and `decay_mode`, an alpha's
+/// [`AlphaComposite`] and [`AlphaClip`] (`clip: Cutout(0.5)`), and the tile cycle's
+/// [`AnimationMode`]. Unknown names fail to load.
+/// ```ron
+/// (
+///     duration: 0.4,
+///     colors: [(color: (1.0, 1.0, 1.0, 0.0), blend_mode: Add)],
+///     spatial: [
+///         (manipulation: ScaleY, wave: (kind: Sine, freq: 2.0, amp: 0.3), anchor: (0.5, 0.0)),
+///     ],
+/// )
+/// ```
+/// A path spatial effect (`manipulation: Path`) follows the `path` points; its point
+/// count is filled in on load, `params.y` = 1.0 makes it curved.
+///
+/// Play it with [`Self::build`], or [`Self::builder`] to tweak it first:
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # fn play_hit(
+/// #     asset_server: Res<AssetServer>,
+/// #     effect_assets: Res<Assets<EffectAsset>>,
+/// #     time: Res<Time>,
+/// #     mut vfx: Single<&mut Vfx>,
+/// # ) {
+/// let hit: Handle<EffectAsset> = asset_server.load("effects/hit.effect.ron");
+/// // later
+/// if let Some(asset) = effect_assets.get(&hit) {
+///     vfx.push_effect(asset.build(time.elapsed_secs()));
+/// }
+/// # }
+/// ```
+#[derive(Asset, Reflect, Clone, Debug, Serialize, Deserialize)]
+#[reflect(Default)]
+#[serde(default)]
+pub struct EffectAsset {
+    /// One-shot duration, or period when `looping`
+    pub duration: f32,
+    pub looping: bool,
+    pub colors: Vec<ColorEffect>,
+    pub alphas: Vec<AlphaEffect>,
+    pub spatial: Vec<SpatialEffect>,
+    pub fragment: Vec<FragmentEffect>,
+    /// Control points for a path spatial effect
+    pub path: Vec<Vec2>,
+    /// Shader-side flipbook, e.g. `(start: 40, count: 4, fps: 8.0)`
+    pub tile_cycle: TileCycleEffect,
+}
+
+impl Default for EffectAsset {
+    fn default() -> Self {
+        Self {
+            duration: 1.0,
+            looping: false,
+            colors: Vec::new(),
+            alphas: Vec::new(),
+            spatial: Vec::new(),
+            fragment: Vec::new(),
+            path: Vec::new(),
+            tile_cycle: TileCycleEffect::default(),
+        }
+    }
+}
+
+impl EffectAsset {
+    /// Builder holding this asset's sub-effects, starting at `now`.
+    ///
+    /// Sub-effects are reachable through [`EffectBuilder::modify`]. Sub-effects beyond
+    /// the slot limits and unknown kinds are dropped; [`EffectAssetLoader`] rejects
+    /// assets with either, so this only happens to assets built in code.
+    pub fn builder(&self, now: f32) -> EffectBuilder {
+        let mut builder = if self.looping {
+            EffectBuilder::looping(now, self.duration)
+        } else {
+            EffectBuilder::one_shot(now, self.duration)
+        };
+
+        for (slot, color) in builder.colors.iter_mut().zip(&self.colors) {
+            *slot = Some(*color);
+        }
+        if self.colors.len() > MAX_COLOR_FX {
+            builder.dropped.push(EffectError::SlotOverflow {
+                effects: "color",
+                max: MAX_COLOR_FX,
+            });
+        }
+        for (slot, alpha) in builder.alphas.iter_mut().zip(&self.alphas) {
+            *slot = Some(*alpha);
+        }
+        if self.alphas.len() > MAX_ALPHA_FX {
+            builder.dropped.push(EffectError::SlotOverflow {
+                effects: "alpha",
+                max: MAX_ALPHA_FX,
+            });
+        }
+
+        for spatial in &self.spatial {
+            let Some(kind) = SpatialKind::from_raw(spatial.manipulation) else {
+                continue;
+            };
+            let mut spatial = *spatial;
+            if kind == SpatialKind::Path {
+                // Packs the points and reports them like a path built in code
+                builder = builder.add_path(&self.path, spatial.params.y > 0.5);
+                let Some(path) = builder.spatial[kind] else {
+                    continue;
+                };
+                spatial.params.x = path.params.x;
+            }
+            builder.spatial[kind] = Some(spatial);
+        }
+        for fragment in &self.fragment {
+            if let Some(kind) = FragmentKind::from_raw(fragment.kind) {
+                builder.fragment[kind] = Some(*fragment);
+            }
+        }
+        builder.tile_cycle = self.tile_cycle;
+        builder.last_effect = None;
+        builder
+    }
+
+    /// Asset holding `effect`'s sub-effects, e.g. to save an effect tuned at runtime
+    /// with [`Self::to_ron`]. The start time is dropped.
+    pub fn from_effect(effect: &Effect) -> Self {
+        let builder = EffectBuilder::from_effect(effect);
+        let point_count = builder.spatial[SpatialKind::Path]
+            .map_or(0, |path| path.params.x as usize)
+            .min(MAX_PATH_POINTS);
+        let path = (0..point_count)
+            .map(|i| {
+                let packed = builder.path_points[i / 2];
+                if i % 2 == 0 { packed.xy() } else { packed.zw() }
+            })
+            .collect();
+
+        Self {
+            duration: builder.lifetime.duration,
+            looping: builder.lifetime.looping == 1,
+            colors: builder.colors.iter().flatten().copied().collect(),
+            alphas: builder.alphas.iter().flatten().copied().collect(),
+            spatial: builder.spatial.values().flatten().copied().collect(),
+            fragment: builder.fragment.values().flatten().copied().collect(),
+            path,
+            tile_cycle: builder.tile_cycle,
+        }
+    }
+
+    /// This asset as the contents of an `.effect.ron` file, failing on an unknown kind
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// Build this asset into an effect starting at `now`
+    pub fn build(&self, now: f32) -> Effect {
+        self.builder(now).build()
+    }
+
+    /// Everything [`EffectBuilder::validate`] checks, plus unknown and repeated kinds.
+    pub fn validate(&self) -> Result<(), EffectAssetError> {
+        if let Some(spatial) = self
+            .spatial
+            .iter()
+            .find(|spatial| SpatialKind::from_raw(spatial.manipulation).is_none())
+        {
+            return Err(EffectAssetError::UnknownSpatialKind(spatial.manipulation));
+        }
+        if let Some(fragment) = self
+            .fragment
+            .iter()
+            .find(|fragment| FragmentKind::from_raw(fragment.kind).is_none())
+        {
+            return Err(EffectAssetError::UnknownFragmentKind(fragment.kind));
+        }
+        // Each kind has one slot, so a repeated kind would overwrite the earlier entry
+        for (i, spatial) in self.spatial.iter().enumerate() {
+            if self.spatial[..i]
+                .iter()
+                .any(|earlier| earlier.manipulation == spatial.manipulation)
+            {
+                return Err(EffectAssetError::DuplicateKind {
+                    effects: "spatial",
+                    kind: spatial.manipulation,
+                });
+            }
+        }
+        for (i, fragment) in self.fragment.iter().enumerate() {
+            if self.fragment[..i]
+                .iter()
+                .any(|earlier| earlier.kind == fragment.kind)
+            {
+                return Err(EffectAssetError::DuplicateKind {
+                    effects: "fragment",
+                    kind: fragment.kind,
+                });
+            }
+        }
+        self.builder(0.0).validate()?;
+        Ok(())
+    }
+}
+
+/// Why an `.effect.ron` or effect library file failed to load.
+#[derive(Debug)]
+pub enum EffectAssetError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    UnknownSpatialKind(u32),
+    UnknownFragmentKind(u32),
+    /// Two `effects` entries share `kind`, which has a single slot
+    DuplicateKind {
+        effects: &'static str,
+        kind: u32,
+    },
+    Invalid(EffectError),
+    /// An entry of an [`EffectLibrary`](super::EffectLibrary) failed
+    Entry {
+        name: String,
+        error: Box<EffectAssetError>,
+    },
+}
+
+impl std::fmt::Display for EffectAssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read effect asset: {err}"),
+            Self::Ron(err) => write!(f, "could not parse effect asset: {err}"),
+            Self::UnknownSpatialKind(kind) => write!(f, "unknown spatial manipulation {kind}"),
+            Self::UnknownFragmentKind(kind) => write!(f, "unknown fragment kind {kind}"),
+            Self::DuplicateKind { effects, kind } => {
+                write!(f, "{effects} kind {kind} is listed more than once")
+            }
+            Self::Invalid(err) => write!(f, "invalid effect asset: {err}"),
+            Self::Entry { name, error } => write!(f, "effect \"{name}\": {error}"),
+        }
+    }
+}
+
+impl std::error::Error for EffectAssetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Ron(err) => Some(err),
+            Self::Invalid(err) => Some(err),
+            Self::Entry { error, .. } => Some(error.as_ref()),
+            Self::UnknownSpatialKind(_)
+            | Self::UnknownFragmentKind(_)
+            | Self::DuplicateKind { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for EffectAssetError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for EffectAssetError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Ron(err)
+    }
+}
+
+impl From<EffectError> for EffectAssetError {
+    fn from(err: EffectError) -> Self {
+        Self::Invalid(err)
+    }
+}
+
+/// Loads [`EffectAsset`]s from `.effect.ron` files, rejecting any that fail
+/// [`EffectAsset::validate`].
+#[derive(Default, TypePath)]
+pub struct EffectAssetLoader;
+
+impl AssetLoader for EffectAssetLoader {
+    type Asset = EffectAsset;
+    type Settings = ();
+    type Error = EffectAssetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<EffectAsset, EffectAssetError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let asset: EffectAsset = ron::de::from_bytes(&bytes)?;
+        asset.validate()?;
+        Ok(asset)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["effect.ron"]
+    }
+}
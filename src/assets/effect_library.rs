@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+
+use crate::internal_prelude::*;
+
+/// Named [`EffectAsset`]s loaded from a single file, so a game can ship one library
+/// instead of a file per effect.
+///
+/// The file is a RON map from name to effect, in the `.effect.ron` format:
+/// ```ron
+/// {
+///     "hit": (duration: 0.3, colors: [(color: (1.0, 1.0, 1.0, 0.0), blend_mode: Add)]),
+///     "spin": (duration: 0.5, spatial: [(manipulation: Rotation, wave: (kind: Saw, amp: 3.14, bias: 3.14))]),
+/// }
+/// ```
+/// Files ending in `.vfxlib.ron` load as libraries; any other name works with a typed load:
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # fn play_hit(
+/// #     asset_server: Res<AssetServer>,
+/// #     libraries: Res<Assets<EffectLibrary>>,
+/// #     time: Res<Time>,
+/// #     mut vfx: Single<&mut Vfx>,
+/// # ) {
+/// # let now = time.elapsed_secs();
+/// let library: Handle<EffectLibrary> = asset_server.load("effects/vfx_library.vfxlib.ron");
+/// // later
+/// if let Some(effect) = libraries.get(&library).and_then(|lib| lib.build("hit", now)) {
+///     vfx.push_effect(effect);
+/// }
+/// # }
+/// ```
+#[derive(Asset, TypePath, Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EffectLibrary {
+    pub effects: BTreeMap<String, EffectAsset>,
+}
+
+impl EffectLibrary {
+    pub fn get(&self, name: &str) -> Option<&EffectAsset> {
+        self.effects.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.effects.contains_key(name)
+    }
+
+    /// Effect names, in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.effects.keys().map(String::as_str)
+    }
+
+    /// The builder of effect `name` starting at `now`, for further tweaks before `build()`
+    pub fn builder(&self, name: &str, now: f32) -> Option<EffectBuilder> {
+        self.get(name).map(|effect| effect.builder(now))
+    }
+
+    /// Build effect `name` starting at `now`
+    pub fn build(&self, name: &str, now: f32) -> Option<Effect> {
+        self.get(name).map(|effect| effect.build(now))
+    }
+
+    /// [`EffectAsset::validate`] for every entry.
+    pub fn validate(&self) -> Result<(), EffectAssetError> {
+        for (name, effect) in &self.effects {
+            effect.validate().map_err(|error| EffectAssetError::Entry {
+                name: name.clone(),
+                error: Box::new(error),
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Loads [`EffectLibrary`] files, rejecting any with an entry that fails
+/// [`EffectAsset::validate`].
+#[derive(Default, TypePath)]
+pub struct EffectLibraryLoader;
+
+impl AssetLoader for EffectLibraryLoader {
+    type Asset = EffectLibrary;
+    type Settings = ();
+    type Error = EffectAssetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<EffectLibrary, EffectAssetError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let library: EffectLibrary = ron::de::from_bytes(&bytes)?;
+        library.validate()?;
+        Ok(library)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["vfxlib.ron"]
+    }
+}
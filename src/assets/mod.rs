@@ -0,0 +1,11 @@
+mod animation_clips;
+mod atlas_layout;
+mod effect_asset;
+mod effect_library;
+mod vfx_level;
+
+pub use animation_clips::*;
+pub use atlas_layout::*;
+pub use effect_asset::*;
+pub use effect_library::*;
+pub use vfx_level::*;
@@ -0,0 +1,431 @@
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use serde_json::Value;
+
+use crate::internal_prelude::*;
+
+/// Tiled stores flips and rotation in the top bits of a tile gid
+const TILED_FLIP_BITS: u32 = 0xF000_0000;
+
+/// Decorations read from the entity layers of an LDtk project or Tiled map, spawned as
+/// `Vfx` or broadcast entities by a [`VfxLevelRoot`].
+///
+/// Every entity (LDtk) or object (Tiled) becomes a [`LevelEntity`]. Its sprite is the
+/// `sprite` field (an atlas index, or a [`SpriteNames`] entry) or else its tile's index in
+/// the tileset, so tilesets should use the Hirundo atlas image on the same grid. An
+/// `effect` field names the [`EffectTemplates`] entry to play and a `broadcast` field set
+/// to true draws it as a broadcast entity.
+///
+/// Loaded by [`HirundoPlugin::with_level_import`](crate::HirundoPlugin::with_level_import)
+/// from `.ldtk` projects (embedded levels only) and `.tmj` Tiled maps (JSON format).
+#[derive(Asset, TypePath, Clone, Debug, Default)]
+pub struct VfxLevel {
+    pub entities: Vec<LevelEntity>,
+}
+
+impl VfxLevel {
+    /// Read an `.ldtk` project, as [`LdtkLevelLoader`] does.
+    pub fn from_ldtk(bytes: &[u8]) -> Result<Self, VfxLevelError> {
+        serde_json::from_slice::<LdtkJson>(bytes)?.into_level()
+    }
+
+    /// Read a `.tmj` Tiled map, as [`TiledLevelLoader`] does.
+    pub fn from_tiled(bytes: &[u8]) -> Result<Self, VfxLevelError> {
+        Ok(serde_json::from_slice::<TiledJson>(bytes)?.into_level())
+    }
+}
+
+/// One decoration of a [`VfxLevel`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelEntity {
+    /// LDtk identifier, or the Tiled object's name (its class when unnamed)
+    pub name: String,
+    /// Center in world units (Y up), relative to the level's top-left corner
+    pub position: Vec2,
+    pub sprite: LevelSprite,
+    /// [`EffectTemplates`] entry played once spawned
+    pub effect: Option<String>,
+    /// Spawn a broadcast entity instead of a `Vfx`
+    pub broadcast: bool,
+}
+
+/// Where a [`LevelEntity`]'s sprite comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LevelSprite {
+    Index(u32),
+    /// A [`SpriteNames`] entry, looked up at spawn
+    Name(String),
+}
+
+impl Default for LevelSprite {
+    fn default() -> Self {
+        Self::Index(0)
+    }
+}
+
+/// The `sprite`, `effect` and `broadcast` fields of an entity or object
+#[derive(Default)]
+struct LevelFields {
+    sprite: Option<LevelSprite>,
+    effect: Option<String>,
+    broadcast: bool,
+}
+
+impl LevelFields {
+    fn read<'a>(fields: impl IntoIterator<Item = (&'a str, &'a Value)>) -> Self {
+        let mut level_fields = Self::default();
+        for (name, value) in fields {
+            match (name, value) {
+                ("sprite", Value::Number(index)) => {
+                    level_fields.sprite =
+                        index.as_u64().map(|index| LevelSprite::Index(index as u32));
+                }
+                ("sprite", Value::String(sprite)) => {
+                    level_fields.sprite = Some(LevelSprite::Name(sprite.clone()));
+                }
+                ("effect", Value::String(effect)) if !effect.is_empty() => {
+                    level_fields.effect = Some(effect.clone());
+                }
+                ("broadcast", Value::Bool(broadcast)) => level_fields.broadcast = *broadcast,
+                _ => {}
+            }
+        }
+        level_fields
+    }
+
+    fn into_entity(self, name: String, position: Vec2, tile: Option<u32>) -> LevelEntity {
+        LevelEntity {
+            name,
+            position,
+            sprite: self
+                .sprite
+                .or(tile.map(LevelSprite::Index))
+                .unwrap_or_default(),
+            effect: self.effect,
+            broadcast: self.broadcast,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct LdtkJson {
+    defs: LdtkDefs,
+    levels: Vec<LdtkLevel>,
+}
+
+#[derive(Deserialize)]
+struct LdtkDefs {
+    tilesets: Vec<LdtkTileset>,
+}
+
+#[derive(Deserialize)]
+struct LdtkTileset {
+    uid: i64,
+    #[serde(rename = "tileGridSize")]
+    grid_size: f32,
+    spacing: f32,
+    padding: f32,
+    #[serde(rename = "__cWid")]
+    columns: u32,
+}
+
+#[derive(Deserialize)]
+struct LdtkLevel {
+    #[serde(rename = "worldX")]
+    world_x: f32,
+    #[serde(rename = "worldY")]
+    world_y: f32,
+    /// `null` for levels saved in separate files
+    #[serde(rename = "layerInstances")]
+    layers: Option<Vec<LdtkLayer>>,
+}
+
+#[derive(Deserialize)]
+struct LdtkLayer {
+    #[serde(rename = "__type")]
+    kind: String,
+    #[serde(rename = "__pxTotalOffsetX")]
+    offset_x: f32,
+    #[serde(rename = "__pxTotalOffsetY")]
+    offset_y: f32,
+    #[serde(rename = "entityInstances")]
+    entities: Vec<LdtkEntity>,
+}
+
+#[derive(Deserialize)]
+struct LdtkEntity {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    px: [f32; 2],
+    width: f32,
+    height: f32,
+    #[serde(rename = "__pivot")]
+    pivot: [f32; 2],
+    #[serde(rename = "__tile")]
+    tile: Option<LdtkTileRect>,
+    #[serde(rename = "fieldInstances")]
+    fields: Vec<LdtkField>,
+}
+
+#[derive(Deserialize)]
+struct LdtkTileRect {
+    #[serde(rename = "tilesetUid")]
+    tileset: i64,
+    x: f32,
+    y: f32,
+}
+
+#[derive(Deserialize)]
+struct LdtkField {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__value")]
+    value: Value,
+}
+
+impl LdtkJson {
+    fn into_level(self) -> Result<VfxLevel, VfxLevelError> {
+        let mut entities = Vec::new();
+        for level in &self.levels {
+            let Some(layers) = &level.layers else {
+                return Err(VfxLevelError::ExternalLevels);
+            };
+            for layer in layers.iter().filter(|layer| layer.kind == "Entities") {
+                let origin = Vec2::new(
+                    level.world_x + layer.offset_x,
+                    level.world_y + layer.offset_y,
+                );
+                for entity in &layer.entities {
+                    let size = Vec2::new(entity.width, entity.height);
+                    let pivot = Vec2::from(entity.pivot);
+                    let center = origin + Vec2::from(entity.px) + (Vec2::splat(0.5) - pivot) * size;
+                    let fields = LevelFields::read(
+                        entity
+                            .fields
+                            .iter()
+                            .map(|field| (field.identifier.as_str(), &field.value)),
+                    );
+                    let tile = entity.tile.as_ref().and_then(|tile| self.tile_index(tile));
+                    entities.push(fields.into_entity(
+                        entity.identifier.clone(),
+                        Vec2::new(center.x, -center.y),
+                        tile,
+                    ));
+                }
+            }
+        }
+        Ok(VfxLevel { entities })
+    }
+
+    /// Index of the tile at `tile`'s rect in its tileset
+    fn tile_index(&self, tile: &LdtkTileRect) -> Option<u32> {
+        let tileset = self
+            .defs
+            .tilesets
+            .iter()
+            .find(|tileset| tileset.uid == tile.tileset)?;
+        let step = tileset.grid_size + tileset.spacing;
+        let column = ((tile.x - tileset.padding) / step).round() as u32;
+        let row = ((tile.y - tileset.padding) / step).round() as u32;
+        Some(row * tileset.columns + column)
+    }
+}
+
+#[derive(Deserialize)]
+struct TiledJson {
+    #[serde(default)]
+    tilesets: Vec<TiledTileset>,
+    layers: Vec<TiledLayer>,
+}
+
+#[derive(Deserialize)]
+struct TiledTileset {
+    firstgid: u32,
+}
+
+#[derive(Deserialize)]
+struct TiledLayer {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    offsetx: f32,
+    #[serde(default)]
+    offsety: f32,
+    #[serde(default)]
+    objects: Vec<TiledObject>,
+    /// Children of a group layer
+    #[serde(default)]
+    layers: Vec<TiledLayer>,
+}
+
+#[derive(Deserialize)]
+struct TiledObject {
+    #[serde(default)]
+    name: String,
+    /// `class` since Tiled 1.9
+    #[serde(default, alias = "class")]
+    r#type: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    gid: Option<u32>,
+    #[serde(default)]
+    properties: Vec<TiledProperty>,
+}
+
+#[derive(Deserialize)]
+struct TiledProperty {
+    name: String,
+    value: Value,
+}
+
+impl TiledJson {
+    fn into_level(self) -> VfxLevel {
+        let mut entities = Vec::new();
+        self.read_layers(&self.layers, Vec2::ZERO, &mut entities);
+        VfxLevel { entities }
+    }
+
+    fn read_layers(&self, layers: &[TiledLayer], offset: Vec2, entities: &mut Vec<LevelEntity>) {
+        for layer in layers {
+            let offset = offset + Vec2::new(layer.offsetx, layer.offsety);
+            if layer.kind == "group" {
+                self.read_layers(&layer.layers, offset, entities);
+            }
+            if layer.kind != "objectgroup" {
+                continue;
+            }
+            for object in &layer.objects {
+                let size = Vec2::new(object.width, object.height);
+                // Tile objects are placed by their bottom-left corner, others by their top-left
+                let corner = if object.gid.is_some() {
+                    Vec2::new(0.0, -size.y)
+                } else {
+                    Vec2::ZERO
+                };
+                let center = offset + Vec2::new(object.x, object.y) + corner + size / 2.0;
+                let fields = LevelFields::read(
+                    object
+                        .properties
+                        .iter()
+                        .map(|property| (property.name.as_str(), &property.value)),
+                );
+                let name = if object.name.is_empty() {
+                    object.r#type.clone()
+                } else {
+                    object.name.clone()
+                };
+                let tile = object.gid.and_then(|gid| self.tile_index(gid));
+                entities.push(fields.into_entity(name, Vec2::new(center.x, -center.y), tile));
+            }
+        }
+    }
+
+    /// Index of tile `gid` in its tileset
+    fn tile_index(&self, gid: u32) -> Option<u32> {
+        let gid = gid & !TILED_FLIP_BITS;
+        let firstgid = self
+            .tilesets
+            .iter()
+            .map(|tileset| tileset.firstgid)
+            .filter(|&firstgid| firstgid <= gid)
+            .max()?;
+        Some(gid - firstgid)
+    }
+}
+
+/// Why a level file failed to load.
+#[derive(Debug)]
+pub enum VfxLevelError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// The LDtk project saves its levels in separate files
+    ExternalLevels,
+}
+
+impl std::fmt::Display for VfxLevelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read level: {err}"),
+            Self::Json(err) => write!(f, "could not parse level: {err}"),
+            Self::ExternalLevels => {
+                write!(f, "LDtk levels saved in separate files aren't supported")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VfxLevelError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::ExternalLevels => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for VfxLevelError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for VfxLevelError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// Loads a [`VfxLevel`] from the entity layers of every level of an `.ldtk` project.
+#[derive(Default, TypePath)]
+pub struct LdtkLevelLoader;
+
+impl AssetLoader for LdtkLevelLoader {
+    type Asset = VfxLevel;
+    type Settings = ();
+    type Error = VfxLevelError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<VfxLevel, VfxLevelError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        VfxLevel::from_ldtk(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ldtk"]
+    }
+}
+
+/// Loads a [`VfxLevel`] from the object layers (group layers included) of a `.tmj` Tiled
+/// map.
+#[derive(Default, TypePath)]
+pub struct TiledLevelLoader;
+
+impl AssetLoader for TiledLevelLoader {
+    type Asset = VfxLevel;
+    type Settings = ();
+    type Error = VfxLevelError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<VfxLevel, VfxLevelError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        VfxLevel::from_tiled(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tmj"]
+    }
+}
@@ -0,0 +1,225 @@
+use crate::internal_prelude::*;
+use bevy::ecs::system::EntityCommand;
+
+/// Turns a broadcast entity into a full [`Vfx`] entity with its own stack, e.g. the one
+/// tile of a broadcast field that got hit and needs a flash of its own.
+///
+/// The entity keeps the sprite it was drawn with ([`BroadcastSprite`] or the stack's
+/// `tile_index`) and, unless [`Self::without_effects`], copies of the effects running on its
+/// material, so it carries on in step with the rest. A [`BroadcastTint`] becomes a
+/// [`VfxTint`] of the same color. Its `MeshTag` slot, material and ghost
+/// buffer veil are set up by `Vfx`'s hook as for any new entity; queued before
+/// [`HirundoSet::Upload`], it's drawn the same frame.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Event)]
+/// # struct TileHit {
+/// #     tile: Entity,
+/// # }
+/// fn on_tile_hit(mut commands: Commands, hit: On<TileHit>, time: Res<Time>) {
+///     let flash = EffectBuilder::flash(time.elapsed_secs(), LinearRgba::WHITE, 0.2).build();
+///     commands.entity(hit.tile).queue(PromoteToVfx::new().with_effect(flash));
+/// }
+/// ```
+/// [`DemoteToBroadcast`] goes back.
+#[derive(Clone, Debug)]
+pub struct PromoteToVfx {
+    keep_effects: bool,
+    effects: Vec<Effect>,
+}
+
+impl Default for PromoteToVfx {
+    fn default() -> Self {
+        Self {
+            keep_effects: true,
+            effects: Vec::new(),
+        }
+    }
+}
+
+impl PromoteToVfx {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with an empty stack instead of the material's effects
+    pub fn without_effects(mut self) -> Self {
+        self.keep_effects = false;
+        self
+    }
+
+    /// Also push `effect`, after the material's
+    pub fn with_effect(mut self, effect: Effect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+}
+
+impl EntityCommand for PromoteToVfx {
+    fn apply(self, mut entity: EntityWorldMut) {
+        if entity.contains::<Vfx>() {
+            return;
+        }
+        let sprite = entity.get::<BroadcastSprite>().map(|sprite| sprite.0);
+        let tint = entity.get::<BroadcastTint>().map(|tint| tint.0);
+        let group = entity
+            .get::<VfxBroadcastGroup>()
+            .map(|group| group.0.clone());
+        let material = entity
+            .get::<MeshMaterial2d<VfxBroadcastMaterial>>()
+            .map(|material| material.0.clone());
+
+        // A group's own stack, as its material's has accessibility applied already
+        let stack = entity.world_scope(|world| {
+            if let Some(stack) = group.and_then(|group| {
+                world
+                    .get_resource::<VfxBroadcasts>()
+                    .and_then(|broadcasts| broadcasts.effects(&group).cloned())
+            }) {
+                return Some(stack);
+            }
+            material.and_then(|material| {
+                world
+                    .resource::<Assets<VfxBroadcastMaterial>>()
+                    .get(&material)
+                    .map(|material| material.effect_stack.clone())
+            })
+        });
+
+        let mut vfx = Vfx::with_sprite(
+            sprite.unwrap_or_else(|| stack.as_ref().map_or(0, |stack| stack.tile_index)),
+        );
+        if self.keep_effects
+            && let Some(stack) = &stack
+        {
+            for effect in stack.iter_active() {
+                vfx.push_effect(*effect);
+            }
+        }
+        for effect in self.effects {
+            vfx.push_effect(effect);
+        }
+
+        entity.remove::<(
+            BroadcastSprite,
+            BroadcastTint,
+            VfxBroadcastGroup,
+            VfxBroadcast,
+            MeshMaterial2d<VfxBroadcastMaterial>,
+        )>();
+        entity.insert(vfx);
+        if let Some(tint) = tint {
+            entity.insert(VfxTint(tint));
+        }
+    }
+}
+
+/// Turns a [`Vfx`] entity back into a broadcast entity, drawn with the default broadcast
+/// material (or a [`VfxBroadcasts`] group's) and keeping its sprite as a [`BroadcastSprite`].
+///
+/// Its own effects are dropped and its `MeshTag` slot freed, as when removing [`VfxBundle`].
+/// The other components that only make sense on a `Vfx` go with it (e.g. [`SpriteRect`],
+/// [`SpriteAnimation`], [`VfxMesh`], [`DespawnWhenEffectsFinish`]), and a [`VfxTint`]
+/// becomes a [`BroadcastTint`] of the same color.
+///
+/// Pooled, [`Vfx3d`] and [`VfxInstanced`] entities are left as they are with a warning:
+/// the pool would hand the entity out again, and broadcasts draw neither in 3D nor
+/// through the instance batch.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let mut world = World::new();
+/// # let tile = world.spawn_empty().id();
+/// # let mut commands = world.commands();
+/// commands.entity(tile).queue(DemoteToBroadcast::new());
+/// ```
+#[derive(Clone, Debug)]
+pub struct DemoteToBroadcast {
+    group: Option<String>,
+    keep_sprite: bool,
+}
+
+impl Default for DemoteToBroadcast {
+    fn default() -> Self {
+        Self {
+            group: None,
+            keep_sprite: true,
+        }
+    }
+}
+
+impl DemoteToBroadcast {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Join [`VfxBroadcasts`] group `group` instead of the default material
+    pub fn in_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Draw the stack's `tile_index` like the rest of the material, not the entity's sprite
+    pub fn with_shared_sprite(mut self) -> Self {
+        self.keep_sprite = false;
+        self
+    }
+}
+
+impl EntityCommand for DemoteToBroadcast {
+    fn apply(self, mut entity: EntityWorldMut) {
+        let Some(sprite) = entity.get::<Vfx>().map(|vfx| vfx.sprite_index) else {
+            return;
+        };
+        let refusal = if entity.contains::<PooledVfx>() {
+            Some("it belongs to the VfxPool")
+        } else if entity.contains::<Vfx3d>() {
+            Some("broadcasts don't draw in 3D")
+        } else if entity.contains::<VfxInstanced>() {
+            Some("broadcasts don't draw through the instance batch")
+        } else {
+            None
+        };
+        if let Some(refusal) = refusal {
+            warn!("Can't demote {} to a broadcast entity, {refusal}", entity.id());
+            return;
+        }
+        let tint = entity.get::<VfxTint>().map(|tint| tint.0);
+        let (mesh, material) = entity.world_scope(|world| {
+            (
+                world.resource::<VfxMeshHandle>().0.clone(),
+                world.resource::<VfxBroadcastMaterialHandle>().0.clone(),
+            )
+        });
+        // Still veiled: nothing reveals it once the ghost buffer is gone
+        if entity.contains::<VfxGhostBuffer>()
+            && let Some(mut visibility) = entity.get_mut::<Visibility>()
+        {
+            *visibility = Visibility::Visible;
+        }
+
+        entity.remove::<(VfxBundle, VfxTint, VfxIntensity, SpriteRect, VfxGhostBuffer)>();
+        // Everything else requiring `Vfx`, which would add it back or act on the entity
+        entity.remove::<(
+            VfxMesh,
+            SpriteAnimation,
+            AnimationClipPlayer,
+            DirectionalSprite,
+            EffectAssetPlayer,
+            VfxOnSpawn,
+            DespawnWhenEffectsFinish,
+        )>();
+        entity.insert((Mesh2d(mesh), MeshMaterial2d(material), VfxBroadcast));
+        if self.keep_sprite {
+            entity.insert(BroadcastSprite(sprite));
+        }
+        // Added, so `sync_broadcast_tints` swaps in the mesh of its color
+        if let Some(tint) = tint {
+            entity.insert(BroadcastTint(tint));
+        }
+        if let Some(group) = self.group {
+            entity.insert(VfxBroadcastGroup(group));
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::internal_prelude::*;
+
+/// Spawns `value` as a row of digit sprites centered on `position`, each playing
+/// [`EffectBuilder::damage_popup`] and despawning once it's done.
+///
+/// Digits, spacing and timing come from [`DamageNumbers`]. Usually queued through
+/// [`DamageNumberCommands::spawn_damage_number`]; queue it directly for a tint:
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let (crit, position) = (128, Vec2::ZERO);
+/// # let mut world = World::new();
+/// # let mut commands = world.commands();
+/// commands.queue(SpawnDamageNumber::new(crit, position).with_color(Srgba::rgb(1.0, 0.8, 0.0)));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SpawnDamageNumber {
+    pub value: u32,
+    pub position: Vec2,
+    /// Tints every digit, e.g. yellow for critical hits
+    pub color: Option<Color>,
+}
+
+impl SpawnDamageNumber {
+    pub fn new(value: u32, position: Vec2) -> Self {
+        Self {
+            value,
+            position,
+            color: None,
+        }
+    }
+
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+}
+
+impl Command for SpawnDamageNumber {
+    fn apply(self, world: &mut World) {
+        let config = world
+            .get_resource::<DamageNumbers>()
+            .copied()
+            .unwrap_or_default();
+        let now = world.resource::<Time>().elapsed_secs();
+        let effect = EffectBuilder::damage_popup(now, config.rise, config.duration).build();
+
+        let digits: Vec<u32> = self
+            .value
+            .to_string()
+            .bytes()
+            .map(|digit| (digit - b'0') as u32)
+            .collect();
+        let left = -(digits.len() as f32 - 1.0) * 0.5 * config.spacing;
+        for (i, digit) in digits.into_iter().enumerate() {
+            let mut vfx = Vfx::with_sprite(config.first_digit + digit);
+            vfx.push_effect(effect);
+            let x = self.position.x + left + i as f32 * config.spacing;
+            let mut entity = world.spawn((
+                vfx,
+                DespawnWhenEffectsFinish::default(),
+                Transform::from_xyz(x, self.position.y, config.z),
+            ));
+            if let Some(color) = self.color {
+                entity.insert(VfxTint(color));
+            }
+        }
+    }
+}
+
+/// Damage number popups on [`Commands`], the canonical stack of one-shot effects.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Event)]
+/// # struct Hit {
+/// #     damage: u32,
+/// #     position: Vec2,
+/// # }
+/// fn on_hit(mut commands: Commands, hit: On<Hit>) {
+///     commands.spawn_damage_number(hit.damage, hit.position);
+/// }
+/// ```
+pub trait DamageNumberCommands {
+    /// Pop `value` up at `position`, see [`SpawnDamageNumber`]
+    fn spawn_damage_number(&mut self, value: u32, position: Vec2);
+}
+
+impl DamageNumberCommands for Commands<'_, '_> {
+    fn spawn_damage_number(&mut self, value: u32, position: Vec2) {
+        self.queue(SpawnDamageNumber::new(value, position));
+    }
+}
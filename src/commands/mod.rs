@@ -0,0 +1,7 @@
+mod swap_atlas;
+mod broadcast_conversion;
+mod damage_numbers;
+
+pub use swap_atlas::*;
+pub use broadcast_conversion::*;
+pub use damage_numbers::*;
@@ -0,0 +1,147 @@
+use crate::HirundoPlugin;
+use crate::internal_prelude::*;
+use bevy::ecs::system::SystemState;
+
+/// Swaps the atlas every VFX sprite samples at runtime, e.g. for seasonal reskins or a
+/// mod's atlas.
+///
+/// Updates every material (broadcast groups included), the sprite mesh and
+/// `HirundoPlugin::atlas_dimensions`, then re-uploads the whole effect storage so the next
+/// frame draws every sprite against the new grid. Sprite indices are kept as they are: swap between atlases with matching
+/// layouts, or swap with a [`VfxAtlasLayout`] and re-resolve indices through [`SpriteNames`].
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # const WINTER_ATLAS: AtlasDimensions = AtlasDimensions {
+/// #     texture_size: Vec2::new(512.0, 512.0),
+/// #     cell_size: Vec2::splat(32.0),
+/// #     sprite_size: Vec2::splat(32.0),
+/// #     padding: Vec2::ZERO,
+/// #     uv_inset: Vec2::ZERO,
+/// # };
+/// fn winter(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.queue(SwapVfxAtlas::new(asset_server.load("winter.png"), WINTER_ATLAS));
+/// }
+/// ```
+/// With [`HirundoPlugin::with_atlas_json`], a later hot reload of that file applies its
+/// layout over the swapped one.
+pub struct SwapVfxAtlas {
+    texture: Option<Handle<Image>>,
+    dimensions: AtlasDimensions,
+    names: Option<SpriteNames>,
+    rects: Option<SpriteRects>,
+    /// Layouts don't know the configured `uv_inset`, the current one is kept
+    keep_uv_inset: bool,
+}
+
+impl SwapVfxAtlas {
+    /// Swap to `texture`, laid out as `dimensions`. [`SpriteNames`] and [`SpriteRects`]
+    /// are left untouched.
+    pub fn new(texture: Handle<Image>, dimensions: AtlasDimensions) -> Self {
+        Self {
+            texture: Some(texture),
+            dimensions,
+            names: None,
+            rects: None,
+            keep_uv_inset: false,
+        }
+    }
+
+    /// Swap to `texture`, laid out as `layout`, replacing [`SpriteNames`] and
+    /// [`SpriteRects`] with its names and rects.
+    pub fn with_layout(texture: Handle<Image>, layout: &VfxAtlasLayout) -> Self {
+        Self {
+            texture: Some(texture),
+            ..Self::layout_only(layout)
+        }
+    }
+
+    /// Apply `layout` to the current texture
+    pub(crate) fn layout_only(layout: &VfxAtlasLayout) -> Self {
+        Self {
+            texture: None,
+            dimensions: layout.dimensions.clone(),
+            names: Some(SpriteNames {
+                names: layout.names.clone(),
+                tags: layout.tags.clone(),
+            }),
+            rects: Some(SpriteRects {
+                rects: layout.rects.iter().copied().map(Some).collect(),
+            }),
+            keep_uv_inset: true,
+        }
+    }
+}
+
+impl Command for SwapVfxAtlas {
+    fn apply(self, world: &mut World) {
+        let mut dimensions = self.dimensions;
+        if self.keep_uv_inset {
+            dimensions.uv_inset = world.resource::<HirundoPlugin>().atlas_dimensions.uv_inset;
+        }
+        if let Some(names) = self.names {
+            world.insert_resource(names);
+        }
+        if let Some(rects) = self.rects {
+            world.insert_resource(rects);
+        }
+
+        let mesh_handle = world.resource::<VfxMeshHandle>().0.clone();
+        if let Some(mesh) = world.resource_mut::<Assets<Mesh>>().get_mut(&mesh_handle) {
+            *mesh = sprite_mesh(dimensions.sprite_size);
+        }
+        let tint_meshes: Vec<_> = world
+            .get_resource::<BroadcastTintMeshes>()
+            .map(|tint_meshes| tint_meshes.iter().map(|(k, h)| (*k, h.clone())).collect())
+            .unwrap_or_default();
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        for (color, handle) in tint_meshes {
+            if let Some(mesh) = meshes.get_mut(&handle) {
+                let color = LinearRgba::from_f32_array(color.map(f32::from_bits));
+                *mesh = tinted_sprite_mesh(dimensions.sprite_size, color);
+            }
+        }
+
+        let material_handle = world.resource::<VfxMaterialHandle>().0.clone();
+        let mut storage_handle = None;
+        if let Some(material) = world
+            .resource_mut::<Assets<VfxMaterial>>()
+            .get_mut(&material_handle)
+        {
+            if let Some(texture) = &self.texture {
+                material.texture = texture.clone();
+            }
+            material.atlas_dimensions = dimensions.clone();
+            storage_handle = Some(material.effect_storage.clone());
+        }
+
+        let mut broadcast_handles: Vec<_> = world
+            .get_resource::<VfxBroadcastMaterialHandle>()
+            .map(|handle| handle.0.clone())
+            .into_iter()
+            .collect();
+        if let Some(broadcasts) = world.get_resource::<VfxBroadcasts>() {
+            broadcast_handles.extend(broadcasts.materials.values().cloned());
+        }
+        let mut broadcast_materials = world.resource_mut::<Assets<VfxBroadcastMaterial>>();
+        for broadcast_handle in &broadcast_handles {
+            if let Some(material) = broadcast_materials.get_mut(broadcast_handle) {
+                if let Some(texture) = &self.texture {
+                    material.texture = texture.clone();
+                }
+                material.atlas_dimensions = dimensions.clone();
+            }
+        }
+
+        // Re-upload every slot, not just dirty ones, so nothing stays on the old grid
+        if let Some(storage_handle) = storage_handle {
+            let effects = world.resource::<EffectStorageData>().effects.clone();
+            let mut storages = SystemState::<VfxStorages>::new(world);
+            storages
+                .get_mut(world)
+                .set_effects(&storage_handle, &effects);
+        }
+
+        world.resource_mut::<HirundoPlugin>().atlas_dimensions = dimensions;
+    }
+}
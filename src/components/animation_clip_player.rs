@@ -0,0 +1,73 @@
+use crate::internal_prelude::*;
+
+/// Plays [`AnimationClips`] by name on this entity's [`SpriteAnimation`], so a character
+/// state machine only has to say which state it is in.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Component)]
+/// # struct Velocity(Vec2);
+/// # fn spawn_goblin(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// commands.spawn((
+///     Vfx::default(),
+///     AnimationClipPlayer::new(asset_server.load("animations/goblin.clips.ron")).with_clip("idle"),
+/// ));
+/// # }
+///
+/// fn goblin_states(mut goblins: Query<(&Velocity, &mut AnimationClipPlayer)>) {
+///     for (velocity, mut player) in &mut goblins {
+///         let clip = if velocity.0.length() > 0.1 { "walk" } else { "idle" };
+///         player.play_clip_crossfade(clip, 0.1);
+///     }
+/// }
+/// ```
+/// Asking for the clip that is already playing does nothing, so this can run every frame.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(SpriteAnimation)]
+pub struct AnimationClipPlayer {
+    pub clips: Handle<AnimationClips>,
+    pub(crate) current: Option<String>,
+    /// Clip to switch to and its crossfade, applied once the clips have loaded
+    pub(crate) requested: Option<(String, f32)>,
+}
+
+impl AnimationClipPlayer {
+    pub fn new(clips: Handle<AnimationClips>) -> Self {
+        Self {
+            clips,
+            current: None,
+            requested: None,
+        }
+    }
+
+    /// Start with clip `name`
+    pub fn with_clip(mut self, name: &str) -> Self {
+        self.play_clip(name);
+        self
+    }
+
+    /// Switch to clip `name` from its first frame
+    pub fn play_clip(&mut self, name: &str) {
+        self.play_clip_crossfade(name, 0.0);
+    }
+
+    /// Switch to clip `name`, blending it in from half transparency over `crossfade`
+    /// seconds to soften the cut
+    pub fn play_clip_crossfade(&mut self, name: &str, crossfade: f32) {
+        // A pending request is what plays next
+        let next = match &self.requested {
+            Some((requested, _)) => Some(requested.as_str()),
+            None => self.current.as_deref(),
+        };
+        if next == Some(name) {
+            return;
+        }
+        self.requested = Some((name.to_string(), crossfade));
+    }
+
+    /// The clip playing now, `None` until the clips have loaded
+    pub fn current_clip(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+}
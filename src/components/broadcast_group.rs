@@ -0,0 +1,30 @@
+use crate::internal_prelude::*;
+
+/// Puts a broadcast entity in a [`VfxBroadcasts`] group, drawing it with that group's
+/// material and shared effect stack instead of the default broadcast material.
+///
+/// The material is assigned in `PostUpdate`, creating the group if nothing was pushed
+/// to it yet; changing the name moves the entity to another group.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # use bevy_hirundo::resources::VfxMeshHandle;
+/// # fn spawn_enemy(mut commands: Commands, mesh_handle: Res<VfxMeshHandle>) {
+/// # let (x, y) = (0.0, 0.0);
+/// commands.spawn((
+///     Mesh2d(mesh_handle.0.clone()),
+///     VfxBroadcastGroup::new("enemies"),
+///     Transform::from_xyz(x, y, 0.0),
+/// ));
+/// # }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+#[require(VfxBroadcast, MeshMaterial2d<VfxBroadcastMaterial>)]
+pub struct VfxBroadcastGroup(pub String);
+
+impl VfxBroadcastGroup {
+    pub fn new(group: impl Into<String>) -> Self {
+        Self(group.into())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::internal_prelude::*;
+
+/// Draws a broadcast entity with its own atlas sprite while it keeps sharing its material's
+/// effect stack, so a varied crowd still animates together in one uniform.
+///
+/// Stored in the entity's `MeshTag` (sprite + 1), which broadcast entities have no other
+/// use for. Entities without one draw the stack's `tile_index` (`BroadcastFx::set_tile`);
+/// a stack sprite rect or running tile cycle effect still overrides both.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # use bevy_hirundo::resources::VfxMeshHandle;
+/// # use rand::Rng;
+/// # fn spawn_tile(
+/// #     mut commands: Commands,
+/// #     mesh_handle: Res<VfxMeshHandle>,
+/// #     broadcast_mat_handle: Res<VfxBroadcastMaterialHandle>,
+/// # ) {
+/// # let mut rng = rand::rng();
+/// commands.spawn((
+///     Mesh2d(mesh_handle.0.clone()),
+///     MeshMaterial2d(broadcast_mat_handle.0.clone()),
+///     BroadcastSprite(rng.random_range(0..625)),
+/// ));
+/// # }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default)]
+#[component(on_remove = crate::hooks::clear_broadcast_sprite)]
+#[require(VfxBroadcast, MeshTag)]
+pub struct BroadcastSprite(pub u32);
@@ -0,0 +1,36 @@
+use crate::internal_prelude::*;
+
+/// Multiplies a broadcast entity's sprite by a color before the shared effects, like
+/// [`VfxTint`] does for `Vfx` entities: team colors in a crowd that still animates
+/// together in one uniform.
+///
+/// The color is written into the vertices of a copy of the sprite mesh, shared by every
+/// entity with the same color, so each color in use is one more mesh (and batch). Keep
+/// them to a palette. Replaces the entity's `Mesh2d`; removing it goes back to the shared quad.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # use bevy_hirundo::resources::VfxMeshHandle;
+/// # fn spawn_tile(
+/// #     mut commands: Commands,
+/// #     mesh_handle: Res<VfxMeshHandle>,
+/// #     broadcast_mat_handle: Res<VfxBroadcastMaterialHandle>,
+/// # ) {
+/// commands.spawn((
+///     Mesh2d(mesh_handle.0.clone()),
+///     MeshMaterial2d(broadcast_mat_handle.0.clone()),
+///     BroadcastTint(Color::srgb(1.0, 0.3, 0.3)),
+/// ));
+/// # }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+#[component(on_remove = crate::hooks::restore_broadcast_mesh)]
+#[require(VfxBroadcast)]
+pub struct BroadcastTint(pub Color);
+
+impl Default for BroadcastTint {
+    fn default() -> Self {
+        Self(Color::WHITE)
+    }
+}
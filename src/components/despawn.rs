@@ -0,0 +1,25 @@
+use crate::internal_prelude::*;
+
+/// Despawns this entity once its last [`Vfx`] effect expires, for fire-and-forget
+/// one-shot effects (hit sparks, pickups, impact puffs).
+///
+/// The entity is only considered done after it has had an effect, so effects may be
+/// pushed a frame or more after spawning. Looping effects never expire; an entity
+/// holding one stays until it is cleared.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let (spark, now, hit) = (7, 0.0, Vec3::ZERO);
+/// # let mut world = World::new();
+/// # let mut commands = world.commands();
+/// let mut vfx = Vfx::with_sprite(spark);
+/// vfx.push_effect(EffectBuilder::fade_out(now, 0.4).build());
+/// commands.spawn((vfx, DespawnWhenEffectsFinish::default(), Transform::from_translation(hit)));
+/// ```
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default)]
+#[require(Vfx)]
+pub struct DespawnWhenEffectsFinish {
+    /// Whether the entity has had an active effect yet
+    pub(crate) armed: bool,
+}
@@ -0,0 +1,99 @@
+use crate::internal_prelude::*;
+
+/// Picks this entity's sprite (or clip) from the direction it faces, for top-down and
+/// isometric characters with 4 or 8 drawn directions.
+///
+/// Directions run counter-clockwise from east (+X), with north as +Y. Set `facing`
+/// (e.g. from velocity) and the matching entry is applied to [`Vfx::sprite_index`], or
+/// played through the entity's [`AnimationClipPlayer`] for [`DirectionalTarget::Clip`]s.
+/// A zero `facing` keeps the current direction.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Component)]
+/// # struct Velocity(Vec2);
+/// # let mut world = World::new();
+/// # let mut commands = world.commands();
+/// commands.spawn((
+///     Vfx::default(),
+///     DirectionalSprite::four_way(12, 8, 4, 0),
+/// ));
+///
+/// fn face_movement(mut query: Query<(&Velocity, &mut DirectionalSprite)>) {
+///     for (velocity, mut directional) in &mut query {
+///         directional.set_facing(velocity.0);
+///     }
+/// }
+/// ```
+/// Fixed sprites are overwritten by a running [`SpriteAnimation`]; use clips for
+/// animated directions.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+#[require(Vfx)]
+pub struct DirectionalSprite {
+    /// One entry per direction, counter-clockwise from east
+    pub directions: Vec<DirectionalTarget>,
+    pub facing: Vec2,
+}
+
+/// What a [`DirectionalSprite`] shows for one direction.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub enum DirectionalTarget {
+    /// A fixed atlas index
+    Sprite(u32),
+    /// An [`AnimationClips`] entry, played with [`AnimationClipPlayer::play_clip`]
+    Clip(String),
+}
+
+impl DirectionalSprite {
+    /// Any number of directions, counter-clockwise from east, facing south
+    pub fn new(directions: impl IntoIterator<Item = DirectionalTarget>) -> Self {
+        Self {
+            directions: directions.into_iter().collect(),
+            facing: Vec2::NEG_Y,
+        }
+    }
+
+    /// Sprites for east, north, west and south
+    pub fn four_way(east: u32, north: u32, west: u32, south: u32) -> Self {
+        Self::new([east, north, west, south].map(DirectionalTarget::Sprite))
+    }
+
+    /// Sprites for east, north-east, north, ... south-east
+    pub fn eight_way(sprites: [u32; 8]) -> Self {
+        Self::new(sprites.map(DirectionalTarget::Sprite))
+    }
+
+    /// Clips for each direction, counter-clockwise from east, e.g.
+    /// `["walk_e", "walk_n", "walk_w", "walk_s"]`
+    pub fn clips<S: Into<String>>(clips: impl IntoIterator<Item = S>) -> Self {
+        Self::new(
+            clips
+                .into_iter()
+                .map(|clip| DirectionalTarget::Clip(clip.into())),
+        )
+    }
+
+    /// Face along `direction`; zero keeps the current direction
+    pub fn set_facing(&mut self, direction: Vec2) {
+        if direction != Vec2::ZERO && direction != self.facing {
+            self.facing = direction;
+        }
+    }
+
+    /// Index into `directions` closest to `facing`
+    pub fn direction_index(&self) -> Option<usize> {
+        let count = self.directions.len();
+        if count == 0 || self.facing == Vec2::ZERO {
+            return None;
+        }
+        let sector = f32::consts::TAU / count as f32;
+        let angle = self.facing.to_angle().rem_euclid(f32::consts::TAU);
+        Some((angle / sector).round() as usize % count)
+    }
+
+    /// Entry for the current facing
+    pub fn current(&self) -> Option<&DirectionalTarget> {
+        self.direction_index().map(|index| &self.directions[index])
+    }
+}
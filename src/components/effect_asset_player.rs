@@ -0,0 +1,35 @@
+use crate::internal_prelude::*;
+
+/// Plays an [`EffectAsset`] on this entity's [`Vfx`] as soon as it has loaded.
+///
+/// When the asset's file changes on disk, the effect is rebuilt: still running, it is
+/// replaced in place and keeps its timing; already finished, it plays again. Hot
+/// reloading needs Bevy's `file_watcher` feature.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # fn spawn_hit(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// commands.spawn((
+///     Vfx::with_sprite(12),
+///     EffectAssetPlayer::new(asset_server.load("effects/hit.effect.ron")),
+/// ));
+/// # }
+/// ```
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(Vfx)]
+pub struct EffectAssetPlayer {
+    pub handle: Handle<EffectAsset>,
+    /// The last push, to find it again on reload
+    #[reflect(ignore)]
+    pub(crate) pushed: Option<EffectHandle>,
+}
+
+impl EffectAssetPlayer {
+    pub fn new(handle: Handle<EffectAsset>) -> Self {
+        Self {
+            handle,
+            pushed: None,
+        }
+    }
+}
@@ -1,9 +1,17 @@
 use crate::internal_prelude::*;
 
 /// Marker component to delay visibility, veiling ghost sprites during initialization.
-#[derive(Component, Default)]
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
 pub struct VfxGhostBuffer;
 
+/// Marks a `Vfx` whose stack changed while it was culled, uploaded once it's back in view,
+/// see `HirundoPlugin::with_upload_culling`
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct VfxCulledUpload;
+
 /// Component marker for entities using broadcast material
-#[derive(Component)]
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
 pub struct VfxBroadcast;
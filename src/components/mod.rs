@@ -1,7 +1,47 @@
 mod vfx;
 mod sprite_index;
 mod markers;
+mod effect_asset_player;
+mod sprite_animation;
+mod animation_clip_player;
+mod directional_sprite;
+mod despawn;
+mod pooled;
+mod on_spawn;
+mod vfx_3d;
+mod vfx_instanced;
+mod ui_vfx;
+mod text_vfx;
+mod vfx_mesh;
+mod sprite_rect;
+mod vfx_tint;
+mod vfx_intensity;
+mod broadcast_group;
+mod broadcast_sprite;
+mod broadcast_tint;
+mod sprite_to_vfx;
+mod vfx_level_root;
 
 pub use vfx::*;
 pub use sprite_index::*;
 pub use markers::*;
+pub use effect_asset_player::*;
+pub use sprite_animation::*;
+pub use animation_clip_player::*;
+pub use directional_sprite::*;
+pub use despawn::*;
+pub use pooled::*;
+pub use on_spawn::*;
+pub use vfx_3d::*;
+pub use vfx_instanced::*;
+pub use ui_vfx::*;
+pub use text_vfx::*;
+pub use vfx_mesh::*;
+pub use sprite_rect::*;
+pub use vfx_tint::*;
+pub use vfx_intensity::*;
+pub use broadcast_group::*;
+pub use broadcast_sprite::*;
+pub use broadcast_tint::*;
+pub use sprite_to_vfx::*;
+pub use vfx_level_root::*;
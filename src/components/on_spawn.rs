@@ -0,0 +1,29 @@
+use crate::internal_prelude::*;
+
+/// Effects pushed to the entity's [`Vfx`] the moment it is spawned, for spawn-pop
+/// animations without a system of their own.
+///
+/// Build the effects with `now = 0.0`: start times are offsets from the spawn, so an
+/// [`EffectSequence`] keeps its steps in order. The component is consumed on spawn.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let (chest, pos) = (12, Vec3::ZERO);
+/// # let mut world = World::new();
+/// # let mut commands = world.commands();
+/// commands.spawn((
+///     Vfx::with_sprite(chest),
+///     VfxOnSpawn::from(EffectBuilder::fade_in(0.0, 0.3).build()),
+///     Transform::from_translation(pos),
+/// ));
+/// ```
+#[derive(Component, Default, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+#[require(Vfx)]
+pub struct VfxOnSpawn(pub Vec<Effect>);
+
+impl From<Effect> for VfxOnSpawn {
+    fn from(effect: Effect) -> Self {
+        Self(vec![effect])
+    }
+}
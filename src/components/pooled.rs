@@ -0,0 +1,15 @@
+use crate::internal_prelude::*;
+
+/// A [`Vfx`] entity owned by the [`VfxPool`](crate::resources::VfxPool).
+///
+/// Added by the pool itself; game code only sees it on entities handed out by
+/// [`VfxPool::spawn`](crate::resources::VfxPool::spawn).
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default)]
+#[require(Vfx)]
+pub struct PooledVfx {
+    /// Handed out and not yet reclaimed
+    pub(crate) in_use: bool,
+    /// Made visible since it was handed out
+    pub(crate) shown: bool,
+}
@@ -0,0 +1,137 @@
+use crate::internal_prelude::*;
+
+/// Flipbook playback of atlas frames on this entity's [`Vfx::sprite_index`].
+///
+/// Frames advance with virtual time, so pausing `Time<Virtual>` pauses the animation.
+/// A frame change goes through the same storage upload as any other [`Vfx`] change.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # fn spawn_runner(mut commands: Commands, names: Res<SpriteNames>) {
+/// commands.spawn((
+///     Vfx::with_sprite(40),
+///     SpriteAnimation::range(40, 4, 8.0),
+/// ));
+///
+/// // Or from an Aseprite tag
+/// let run = names.tag("run").unwrap();
+/// commands.spawn(SpriteAnimation::from_tag(run));
+/// # }
+/// ```
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+#[require(Vfx)]
+pub struct SpriteAnimation {
+    /// Atlas indices, in playback order
+    pub frames: Vec<u32>,
+    pub fps: f32,
+    pub mode: AnimationMode,
+    pub(crate) elapsed: f32,
+    /// Position in `frames` last shown, to emit one [`AnimationFrameEvent`] per frame
+    pub(crate) shown: Option<usize>,
+}
+
+/// How a [`SpriteAnimation`] continues after its last frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Reflect)]
+#[reflect(Default)]
+pub enum AnimationMode {
+    /// Start over from the first frame
+    #[default]
+    Loop,
+    /// Hold the last frame
+    Once,
+    /// Play backwards to the first frame, then forwards again
+    PingPong,
+}
+
+impl SpriteAnimation {
+    /// Loop `frames` at `fps`
+    pub fn new(frames: impl Into<Vec<u32>>, fps: f32) -> Self {
+        Self {
+            frames: frames.into(),
+            fps,
+            mode: AnimationMode::Loop,
+            elapsed: 0.0,
+            shown: None,
+        }
+    }
+
+    /// Loop the `count` consecutive atlas indices starting at `start`
+    pub fn range(start: u32, count: u32, fps: f32) -> Self {
+        Self::new((start..start + count).collect::<Vec<_>>(), fps)
+    }
+
+    /// Play an Aseprite tag, at the average rate of its frame durations
+    pub fn from_tag(tag: &AtlasTag) -> Self {
+        let total: f32 = tag.durations.iter().sum();
+        let fps = if total > 0.0 {
+            tag.durations.len() as f32 / total
+        } else {
+            10.0
+        };
+        let mut frames = tag.frames.clone();
+        let mode = match tag.direction {
+            TagDirection::Forward => AnimationMode::Loop,
+            TagDirection::Reverse => {
+                frames.reverse();
+                AnimationMode::Loop
+            }
+            TagDirection::PingPong => AnimationMode::PingPong,
+            TagDirection::PingPongReverse => {
+                frames.reverse();
+                AnimationMode::PingPong
+            }
+        };
+        Self::new(frames, fps).with_mode(mode)
+    }
+
+    pub fn with_mode(mut self, mode: AnimationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Play once and hold the last frame
+    pub fn once(self) -> Self {
+        self.with_mode(AnimationMode::Once)
+    }
+
+    pub fn ping_pong(self) -> Self {
+        self.with_mode(AnimationMode::PingPong)
+    }
+
+    /// Start over from the first frame
+    pub fn restart(&mut self) {
+        self.elapsed = 0.0;
+        self.shown = None;
+    }
+
+    /// Whether an [`AnimationMode::Once`] animation has shown its last frame in full
+    pub fn is_finished(&self) -> bool {
+        self.mode == AnimationMode::Once && self.elapsed * self.fps >= self.frames.len() as f32
+    }
+
+    /// Atlas index showing now, `None` without frames or with a non-positive `fps`
+    pub fn current_frame(&self) -> Option<u32> {
+        self.frame_position().map(|position| self.frames[position])
+    }
+
+    /// Position in `frames` showing now
+    pub fn frame_position(&self) -> Option<usize> {
+        let count = self.frames.len();
+        if count == 0 || self.fps <= 0.0 {
+            return None;
+        }
+        let step = (self.elapsed * self.fps) as usize;
+        let position = match self.mode {
+            AnimationMode::Loop => step % count,
+            AnimationMode::Once => step.min(count - 1),
+            AnimationMode::PingPong if count < 2 => 0,
+            AnimationMode::PingPong => {
+                let period = 2 * count - 2;
+                let i = step % period;
+                if i < count { i } else { period - i }
+            }
+        };
+        Some(position)
+    }
+}
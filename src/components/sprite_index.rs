@@ -1,4 +1,5 @@
 use crate::internal_prelude::*;
 
-#[derive(Component, Clone, Copy, Debug, Default)]
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
 pub struct SpriteIndex(pub u32);
@@ -0,0 +1,34 @@
+use crate::internal_prelude::*;
+
+/// Samples the entity's [`Vfx`] from this atlas rect (in pixels, origin top-left) and
+/// draws it at the rect's size, instead of from its sprite index. For sprites off the
+/// uniform grid that only one entity uses, or sub-regions like a health bar's fill:
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # const BAR_WIDTH: f32 = 32.0;
+/// # struct Health(f32);
+/// # impl Health {
+/// #     fn fraction(&self) -> f32 {
+/// #         self.0
+/// #     }
+/// # }
+/// # let health = Health(0.5);
+/// # let mut rect = SpriteRect(Rect::new(0.0, 0.0, BAR_WIDTH, 4.0));
+/// // Shrink the fill with the health left
+/// rect.0.max.x = rect.0.min.x + BAR_WIDTH * health.fraction();
+/// ```
+/// Tile cycle effects don't show while it's set. Sprites several entities share are
+/// better off in [`SpriteRects`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+#[component(on_remove = crate::hooks::refresh_vfx_stack)]
+#[require(Vfx)]
+pub struct SpriteRect(pub Rect);
+
+impl SpriteRect {
+    /// The rect as the shaders read it: `(x, y, width, height)`
+    pub(crate) fn gpu_rect(&self) -> Vec4 {
+        Vec4::new(self.0.min.x, self.0.min.y, self.0.width(), self.0.height())
+    }
+}
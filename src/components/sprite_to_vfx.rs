@@ -0,0 +1,31 @@
+use crate::internal_prelude::*;
+
+/// Converts this entity's Bevy `Sprite` into a [`Vfx`] drawn from the Hirundo atlas, so a
+/// project can move its sprites over one spawn site at a time.
+///
+/// The `TextureAtlas` index becomes [`Vfx::sprite_index`], `flip_x`/`flip_y` become looping
+/// [`EffectBuilder::flip_x`]/[`EffectBuilder::flip_y`] effects and a non-white `color`
+/// becomes a [`VfxTint`]. The `Sprite` and this marker are then removed. The sprite must
+/// already be laid out in the plugin's atlas; other images are drawn from the atlas anyway,
+/// with a warning.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # fn spawn_chest(
+/// #     mut commands: Commands,
+/// #     asset_server: Res<AssetServer>,
+/// #     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+/// # ) {
+/// # let atlas = asset_server.load("atlas.png");
+/// # let layout = layouts.add(TextureAtlasLayout::from_grid(UVec2::splat(32), 8, 8, None, None));
+/// commands.spawn((
+///     Sprite::from_atlas_image(atlas, TextureAtlas { layout, index: 12 }),
+///     SpriteToVfx,
+/// ));
+/// # }
+/// ```
+/// [`HirundoPlugin::with_sprite_bridge`](crate::HirundoPlugin::with_sprite_bridge) converts
+/// every atlas sprite without the marker.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct SpriteToVfx;
@@ -0,0 +1,99 @@
+use crate::internal_prelude::*;
+
+/// How a [`TextVfx`] applies spatial effects
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub enum TextVfxMode {
+    /// The text moves, scales and rotates as one quad the size of its layout, through its
+    /// `Transform`. Skew and sway bend it no further than a rotation and scale can.
+    #[default]
+    Whole,
+    /// Each glyph moves on its own, `stagger` seconds behind the one before it: a looping
+    /// bob becomes wavy text, a one-shot pop ripples through the word. Glyphs are moved,
+    /// not rotated or scaled, so only the offsets effects give their centers show.
+    Glyphs { stagger: f32 },
+}
+
+/// Effects on a `Text2d` (damage numbers, wavy dialogue, fading labels), built with the
+/// same [`EffectBuilder`] waves, envelopes and presets as `Vfx` sprites.
+///
+/// Text isn't drawn by the `Vfx` materials, so the stack is evaluated on the CPU with
+/// the shaders' math ([`EffectStack::spatial_point`], [`EffectStack::tint`]): spatial
+/// effects move the `Transform` or the glyphs (see [`TextVfxMode`]), color and alpha
+/// effects tint the whole text's `TextColor`. Fragment effects have no texels to work on
+/// and are ignored. Spatial effects are in logical pixels.
+///
+/// Effects are applied on top of the text's own transform and color, which can still be
+/// moved and recolored while they play, e.g. a damage number drifting up while it pops.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let now = 0.0;
+/// # let mut world = World::new();
+/// # let mut commands = world.commands();
+/// commands.spawn((
+///     Text2d::new("128"),
+///     TextVfx::default()
+///         .with_effect(EffectBuilder::pop(now, 0.5, 0.4).build())
+///         .with_effect(EffectBuilder::fade_out(now, 1.0).build()),
+/// ));
+/// ```
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+#[require(Text2d)]
+pub struct TextVfx {
+    pub mode: TextVfxMode,
+    pub(crate) effects: EffectStack,
+    /// What the last update changed, taken back before the next one
+    #[reflect(ignore)]
+    pub(crate) applied: AppliedTextVfx,
+}
+
+/// The parts of a text [`TextVfx`] changed, so they can be told apart from changes by
+/// the app and the text layout
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AppliedTextVfx {
+    /// Added to the `Transform`: translation in the parent's space, rotation and scale
+    /// multiplied in
+    pub transform: Option<Transform>,
+    /// The `TextColor` before and after tinting
+    pub color: Option<(Color, Color)>,
+    /// Each glyph's offset and the position it was left at, in layout pixels
+    pub glyphs: Vec<(Vec2, Vec2)>,
+}
+
+impl TextVfx {
+    /// Glyphs moving on their own, see [`TextVfxMode::Glyphs`]
+    pub fn per_glyph(stagger: f32) -> Self {
+        Self {
+            mode: TextVfxMode::Glyphs { stagger },
+            ..default()
+        }
+    }
+
+    pub fn with_effect(mut self, effect: Effect) -> Self {
+        self.push_effect(effect);
+        self
+    }
+
+    /// Whether there's nothing playing and nothing left to take back
+    pub(crate) fn is_idle(&self) -> bool {
+        self.effects.active_count() == 0
+            && self.applied.transform.is_none()
+            && self.applied.color.is_none()
+            && self
+                .applied
+                .glyphs
+                .iter()
+                .all(|(offset, _)| *offset == Vec2::ZERO)
+    }
+}
+
+impl EffectTarget for TextVfx {
+    fn stack(&self) -> &EffectStack {
+        &self.effects
+    }
+
+    fn stack_mut(&mut self) -> &mut EffectStack {
+        &mut self.effects
+    }
+}
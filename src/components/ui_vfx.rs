@@ -0,0 +1,61 @@
+use crate::internal_prelude::*;
+use bevy::ui_render::prelude::MaterialNode;
+
+/// Effects on a UI node (buttons, icons, HUD elements), built with the same
+/// [`EffectBuilder`] waves, envelopes and presets as world sprites. Needs
+/// `HirundoPlugin::with_ui`.
+///
+/// The node shows atlas sprite `sprite_index` stretched over it, through its own
+/// [`VfxUiMaterial`]; the stack is uploaded to it whenever this component changes.
+/// Spatial effects are in the node's pixels, and anchors relative to its size.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+#[component(on_add = crate::hooks::hydrate_ui_vfx)]
+#[require(MaterialNode<VfxUiMaterial>)]
+pub struct UiVfx {
+    pub sprite_index: u32,
+    pub(crate) effects: EffectStack,
+}
+
+impl UiVfx {
+    pub fn with_sprite(sprite_index: u32) -> Self {
+        UiVfx {
+            sprite_index,
+            effects: EffectStack::default(),
+        }
+    }
+
+    /// Sprite `name` from [`SpriteNames`], warning and falling back to sprite 0 if
+    /// it isn't in the atlas
+    pub fn with_sprite_name(name: &str, names: &SpriteNames) -> Self {
+        let sprite_index = names.get(name).unwrap_or_else(|| {
+            warn!("No sprite named \"{}\"", name);
+            0
+        });
+        Self::with_sprite(sprite_index)
+    }
+
+    /// The stack as the shader reads it, sprite included
+    pub(crate) fn gpu_stack(&self) -> EffectStack {
+        EffectStack {
+            tile_index: self.sprite_index,
+            ..self.effects.clone()
+        }
+    }
+}
+
+impl EffectTarget for UiVfx {
+    fn stack(&self) -> &EffectStack {
+        &self.effects
+    }
+
+    fn stack_mut(&mut self) -> &mut EffectStack {
+        &mut self.effects
+    }
+}
+
+impl Default for UiVfx {
+    fn default() -> Self {
+        Self::with_sprite(0)
+    }
+}
@@ -10,21 +10,62 @@ use crate::internal_prelude::*;
 ///
 /// **Preferred patterns**:
 ///
-/// - For temporary/one-shot effects: spawn a new entity, push effects, then despawn when done.
+/// - For temporary/one-shot effects: spawn a new entity, push effects, then despawn when done
+///   (add [`DespawnWhenEffectsFinish`] to have that done for you), or borrow an entity
+///   from the [`VfxPool`] when they are spawned many times a second.
 /// - For persistent effects on game objects: add `Vfx` once at spawn and keep it forever.
 ///   Toggle visibility by clearing effects and/or switching to a blank sprite.
-/// - To "hide" without despawning: use `vfx.hide()` (see below) or push a looping
+/// - To "hide" without despawning: use [`Vfx::hide`] or push a looping
 ///   effect that sets scale = 0.0 or alpha = 0.0.
 ///
 /// Removing the component is allowed but strongly discouraged for performance-critical use.
 /// If you must remove `Vfx`, remove `VfxBundle` to mitigate archetype thrashing.
 /// Removing `Vfx` alone will leave behind the other components added by `#[require]`.
-#[derive(Component)]
-#[component(on_add = crate::hooks::hydrate_vfx, on_remove = crate::hooks::dehydrate_vfx)]
+#[derive(Component, Reflect)]
+#[reflect(Component, Default)]
+#[component(
+    on_add = crate::hooks::hydrate_vfx,
+    on_insert = crate::hooks::limit_vfx_slots,
+    on_remove = crate::hooks::dehydrate_vfx
+)]
 #[require(MeshTag, Mesh2d, MeshMaterial2d<VfxMaterial>, SpriteIndex, Visibility, VfxGhostBuffer)]
 pub struct Vfx {
     pub sprite_index: u32,
     pub(crate) effects: EffectStack,
+    /// Bumped each time a stack slot is reused, so stale [`EffectHandle`]s miss
+    pub(crate) generations: [u32; MAX_FX],
+    /// Slots ramping in or out, see [`Vfx::crossfade_effect`]
+    #[reflect(ignore)]
+    pub(crate) fades: Vec<SlotFade>,
+}
+
+/// A slot whose effect is being ramped between two strengths
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SlotFade {
+    slot: usize,
+    generation: u32,
+    /// The effect as pushed, at full strength
+    effect: Effect,
+    start: f32,
+    duration: f32,
+    from: f32,
+    to: f32,
+}
+
+impl SlotFade {
+    fn weight(&self, now: f32) -> f32 {
+        let t = ((now - self.start) / self.duration).clamp(0.0, 1.0);
+        self.from + (self.to - self.from) * t
+    }
+}
+
+/// Refers to an effect pushed to a [`Vfx`], returned by [`Vfx::push_effect`].
+///
+/// Stops resolving once the effect expires or its slot is reused by a later push.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EffectHandle {
+    slot: usize,
+    generation: u32,
 }
 
 impl Vfx {
@@ -32,15 +73,231 @@ impl Vfx {
         Vfx {
             sprite_index,
             effects: EffectStack::default(),
+            generations: [0; MAX_FX],
+            fades: Vec::new(),
+        }
+    }
+
+    /// Sprite `name` from [`SpriteNames`], so game code survives the atlas being repacked.
+    ///
+    /// Warns and falls back to sprite 0 if `name` isn't in the atlas.
+    pub fn with_sprite_name(name: &str, names: &SpriteNames) -> Self {
+        let sprite_index = names.get(name).unwrap_or_else(|| {
+            warn!("No sprite named \"{}\"", name);
+            0
+        });
+        Self::with_sprite(sprite_index)
+    }
+
+    pub fn push_effect(&mut self, effect: Effect) -> EffectHandle {
+        let slot = self.effects.push(effect);
+        let generation = &mut self.generations[slot];
+        *generation = generation.wrapping_add(1);
+        EffectHandle {
+            slot,
+            generation: *generation,
+        }
+    }
+
+    /// Push a prebuilt effect so it starts at `now`, whatever start time it was built with.
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_hirundo::prelude::*;
+    /// # struct Hits {
+    /// #     light: Effect,
+    /// # }
+    /// # let hits = Hits { light: EffectBuilder::flash(0.0, LinearRgba::WHITE, 0.1).build() };
+    /// # let (time, mut vfx) = (Time::<()>::default(), Vfx::default());
+    /// // `hits.light` was built once at startup, with `now = 0.0`
+    /// vfx.push_effect_now(hits.light, time.elapsed_secs());
+    /// ```
+    pub fn push_effect_now(&mut self, effect: Effect, now: f32) -> EffectHandle {
+        self.push_effect(effect.retimed(now))
+    }
+
+    /// Swap the effect `handle` refers to for `effect`, in the same slot, e.g. a looping
+    /// idle for a looping run. Pushes `effect` if `handle` no longer resolves.
+    ///
+    /// The swap is instant; [`Self::crossfade_effect`] blends the two instead.
+    pub fn replace_effect(&mut self, handle: EffectHandle, effect: Effect) -> EffectHandle {
+        if self.effect(handle).is_none() {
+            return self.push_effect(effect);
+        }
+        self.fades.retain(|fade| fade.slot != handle.slot);
+        self.effects.effects[handle.slot] = effect;
+        let generation = &mut self.generations[handle.slot];
+        *generation = generation.wrapping_add(1);
+        EffectHandle {
+            slot: handle.slot,
+            generation: *generation,
+        }
+    }
+
+    /// [`Self::replace_effect`] with a crossfade: over `seconds` from `now`, the old
+    /// effect's amplitudes ramp down while `effect`'s ramp up, instead of a hard pop
+    /// between two looping effects.
+    ///
+    /// Both hold a slot until the crossfade ends, then the old one's is freed. With no free
+    /// slot, or `seconds` of 0.0, this is a plain [`Self::replace_effect`]. Replacing an
+    /// effect that is still fading in fades it out from where it got to.
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, time, mut vfx) = (0.0, Time::<()>::default(), Vfx::default());
+    /// # let run_bob = EffectBuilder::looping(0.0, 0.4).offset_y(2.0).build();
+    /// let idle = vfx.push_effect(EffectBuilder::looping(now, 2.0).offset_y(1.0).build());
+    /// // later, starting to run
+    /// let run = vfx.crossfade_effect(idle, run_bob, time.elapsed_secs(), 0.25);
+    /// ```
+    pub fn crossfade_effect(
+        &mut self,
+        handle: EffectHandle,
+        effect: Effect,
+        now: f32,
+        seconds: f32,
+    ) -> EffectHandle {
+        if seconds <= 0.0 || self.effects.is_full() || self.effect(handle).is_none() {
+            return self.replace_effect(handle, effect);
+        }
+        // An effect still fading in leaves from the strength it reached
+        let (old, weight) = match self.fades.iter().position(|fade| fade.slot == handle.slot) {
+            Some(i) => {
+                let fade = self.fades.swap_remove(i);
+                (fade.effect, fade.weight(now))
+            }
+            None => (self.effects.effects[handle.slot], 1.0),
+        };
+        let generation = &mut self.generations[handle.slot];
+        *generation = generation.wrapping_add(1);
+        self.fades.push(SlotFade {
+            slot: handle.slot,
+            generation: *generation,
+            effect: old,
+            start: now,
+            duration: seconds,
+            from: weight,
+            to: 0.0,
+        });
+
+        let new = self.push_effect(effect.weighted(0.0));
+        self.fades.push(SlotFade {
+            slot: new.slot,
+            generation: new.generation,
+            effect,
+            start: now,
+            duration: seconds,
+            from: 0.0,
+            to: 1.0,
+        });
+        new
+    }
+
+    /// Write the crossfading slots' strengths at `now`, dropping finished fades
+    pub(crate) fn advance_fades(&mut self, now: f32) {
+        let mut fades = std::mem::take(&mut self.fades);
+        fades.retain(|fade| {
+            // Cleared or reused since
+            if self.generations[fade.slot] != fade.generation
+                || !self.effects.effects[fade.slot].is_active()
+            {
+                return false;
+            }
+            let weight = fade.weight(now);
+            let done = now >= fade.start + fade.duration;
+            let slot = &mut self.effects.effects[fade.slot];
+            if done && fade.to == 0.0 {
+                slot.lifetime.enabled = 0;
+            } else {
+                *slot = fade.effect.weighted(weight);
+            }
+            !done
+        });
+        self.fades = fades;
+    }
+
+    /// Continue `previous`'s slot generations, so handles to its effects don't resolve
+    /// against this one's when it takes over the entity
+    pub(crate) fn follow_generations(&mut self, previous: &Vfx) {
+        for (generation, previous) in self.generations.iter_mut().zip(previous.generations) {
+            *generation = generation.wrapping_add(previous);
+        }
+        for fade in &mut self.fades {
+            fade.generation = fade
+                .generation
+                .wrapping_add(previous.generations[fade.slot]);
         }
     }
 
-    pub fn push_effect(&mut self, effect: Effect) {
-        self.effects.push(effect);
+    /// The effect `handle` refers to, while it is still active
+    pub fn effect(&self, handle: EffectHandle) -> Option<&Effect> {
+        let effect = &self.effects.effects[handle.slot];
+        (self.generations[handle.slot] == handle.generation && effect.is_active()).then_some(effect)
+    }
+
+    /// How far along the effect `handle` refers to is at `now`, from 0.0 to 1.0
+    /// (see [`Effect::progress`]), or `None` once it has expired.
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_hirundo::prelude::*;
+    /// # struct Collider;
+    /// # impl Collider {
+    /// #     fn disable(&mut self) {}
+    /// # }
+    /// # let (now, time, mut vfx, mut collider) = (0.0, Time::<()>::default(), Vfx::default(), Collider);
+    /// let fade = vfx.push_effect(EffectBuilder::fade_out(now, 0.5).build());
+    /// // later
+    /// if vfx.effect_progress(fade, time.elapsed_secs()).is_none_or(|t| t > 0.8) {
+    ///     collider.disable();
+    /// }
+    /// ```
+    pub fn effect_progress(&self, handle: EffectHandle, now: f32) -> Option<f32> {
+        self.effect(handle).map(|effect| effect.progress(now))
+    }
+
+    /// Stop the effect `handle` refers to, e.g. a looping status effect wearing off.
+    /// Returns it, or `None` if it had already expired or been replaced.
+    pub fn remove_effect(&mut self, handle: EffectHandle) -> Option<Effect> {
+        let effect = *self.effect(handle)?;
+        self.effects.effects[handle.slot].lifetime.enabled = 0;
+        Some(effect)
+    }
+
+    /// Stop drawing the sprite, leaving `Visibility` and its effects alone.
+    ///
+    /// Effects keep running while hidden, so a looping effect picks up where it would
+    /// have been on [`Self::show`].
+    pub fn hide(&mut self) {
+        self.effects.hidden = 1;
+    }
+
+    /// Draw the sprite again after [`Self::hide`]
+    pub fn show(&mut self) {
+        self.effects.hidden = 0;
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.effects.hidden != 0
+    }
+}
+
+/// Pushes go through [`Vfx::push_effect`], so handles taken before stay in step
+impl EffectTarget for Vfx {
+    fn stack(&self) -> &EffectStack {
+        &self.effects
+    }
+
+    fn stack_mut(&mut self) -> &mut EffectStack {
+        &mut self.effects
+    }
+
+    fn push_effect(&mut self, effect: Effect) {
+        Vfx::push_effect(self, effect);
     }
 
-    pub fn clear_effects(&mut self) {
+    fn clear_effects(&mut self) {
         self.effects.clear();
+        self.fades.clear();
     }
 }
 
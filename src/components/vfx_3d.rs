@@ -0,0 +1,14 @@
+use crate::internal_prelude::*;
+use bevy::camera::visibility::NoFrustumCulling;
+
+/// Draws a [`Vfx`] entity as a camera-facing quad in a 3D scene, needs
+/// `HirundoPlugin::with_3d`.
+///
+/// Effects are pushed to the `Vfx` as usual. The add hook swaps the 2D mesh and
+/// material for `Mesh3d` and the shared [`VfxMaterial3d`]; frustum culling is off since
+/// the mesh's bounds don't follow the billboard.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+#[component(on_add = crate::hooks::hydrate_vfx_3d)]
+#[require(Vfx, Mesh3d, MeshMaterial3d<VfxMaterial3d>, NoFrustumCulling)]
+pub struct Vfx3d;
@@ -0,0 +1,22 @@
+use crate::internal_prelude::*;
+
+/// Draws a [`Vfx`] entity as part of one shared batch mesh, needs
+/// `HirundoPlugin::with_instancing`.
+///
+/// Every `VfxInstanced` sprite goes into the [`VfxInstanceBatch`] mesh with its 2D
+/// transform and `MeshTag` in its vertices, so they all take one draw however many there
+/// are, and none is extracted, culled or sorted on its own. The add hook drops the
+/// entity's `Mesh2d` and material; removing `VfxInstanced` puts them back.
+///
+/// The batch is rebuilt on frames where an instanced entity moved, was shown or hidden,
+/// or came and went, ordered by Z within it. It sorts as a single item against other
+/// sprites, draws every sprite on the shared quad in the plugin's alpha mode ([`VfxMesh`]
+/// and [`VfxAlphaMode`] don't apply), and keeps rotation and scale in the XY plane only.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+#[component(
+    on_add = crate::hooks::hydrate_vfx_instanced,
+    on_remove = crate::hooks::restore_vfx_instanced
+)]
+#[require(Vfx)]
+pub struct VfxInstanced;
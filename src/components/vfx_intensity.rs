@@ -0,0 +1,34 @@
+use crate::internal_prelude::*;
+
+/// Scales every effect on the entity's [`Vfx`]: spatial amplitudes (shake distance,
+/// squash, rotation, paths) and color strengths (flashes, tints, HSV shifts, emissive).
+/// For accessibility settings ("reduce screen shake / flashes") or fading effects out
+/// with distance.
+///
+/// 1.0 plays effects as built and 0.0 leaves the sprite still and untinted. Above 1.0
+/// exaggerates movement, while colors stay at their full strength. Alpha and fragment
+/// effects aren't scaled, so fades and outlines still finish.
+///
+/// Stored with the entity's stack, so changing it re-uploads that one slot.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # struct Settings {
+/// #     effect_intensity: f32,
+/// # }
+/// # let (enemy, settings) = (5, Settings { effect_intensity: 0.5 });
+/// # let mut world = World::new();
+/// # let mut commands = world.commands();
+/// commands.spawn((Vfx::with_sprite(enemy), VfxIntensity(settings.effect_intensity)));
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+#[component(on_remove = crate::hooks::refresh_vfx_stack)]
+#[require(Vfx)]
+pub struct VfxIntensity(pub f32);
+
+impl Default for VfxIntensity {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
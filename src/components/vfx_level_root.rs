@@ -0,0 +1,37 @@
+use crate::internal_prelude::*;
+
+/// Spawns the decorations of a [`VfxLevel`] as children of this entity once it has loaded,
+/// so decorations placed in LDtk or Tiled animate without code per entity.
+///
+/// Each [`LevelEntity`] becomes a `Vfx` (or, marked `broadcast`, a [`BroadcastSprite`]) at
+/// its position, named after it, playing its `effect` template. Broadcast decorations with
+/// an effect join the [`VfxBroadcasts`] group of that name, which plays the template once.
+/// The level's top-left corner sits at this entity's origin. Needs
+/// [`HirundoPlugin::with_level_import`](crate::HirundoPlugin::with_level_import).
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # fn spawn_level(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// commands.spawn((
+///     VfxLevelRoot::new(asset_server.load("levels/forest.ldtk")),
+///     Transform::from_xyz(-320.0, 240.0, 0.0),
+/// ));
+/// # }
+/// ```
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(Transform, Visibility)]
+pub struct VfxLevelRoot {
+    pub handle: Handle<VfxLevel>,
+    /// Set once the children are spawned
+    pub(crate) spawned: bool,
+}
+
+impl VfxLevelRoot {
+    pub fn new(handle: Handle<VfxLevel>) -> Self {
+        Self {
+            handle,
+            spawned: false,
+        }
+    }
+}
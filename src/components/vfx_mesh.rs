@@ -0,0 +1,31 @@
+use crate::internal_prelude::*;
+
+/// Draws a [`Vfx`] entity with its own mesh (hexes, capsules, multi-quad meshes)
+/// instead of the shared sprite quad, still driven by its effect stack through its
+/// `MeshTag`.
+///
+/// The shaders read the mesh like the sprite quad: positions in pixels around the
+/// entity's origin, at the atlas' grid sprite size (sprites with their own
+/// [`SpriteRects`] rect are stretched to it), and UVs from 0.0 to 1.0 across the sprite.
+/// Bevy's 2D primitives already follow this, e.g. `RegularPolygon::new(16.0, 6)` for
+/// a hex over a 32px sprite. Sway bends the mesh at its vertices, so it only shows on
+/// meshes with vertices along their height.
+///
+/// Replaces the `Mesh2d` (or `Mesh3d` on a [`Vfx3d`]) whenever inserted; removing it
+/// goes back to the shared quad.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # fn spawn_tile(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+/// # let tile = 3;
+/// let hex = meshes.add(RegularPolygon::new(16.0, 6));
+/// commands.spawn((Vfx::with_sprite(tile), VfxMesh(hex)));
+/// # }
+/// ```
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+#[component(
+    on_insert = crate::hooks::apply_vfx_mesh,
+    on_remove = crate::hooks::restore_vfx_mesh
+)]
+pub struct VfxMesh(pub Handle<Mesh>);
@@ -0,0 +1,26 @@
+use crate::internal_prelude::*;
+
+/// Multiplies the entity's [`Vfx`] sprite by a color before any effect, like `Sprite::color`:
+/// permanent team colors or rarity tints that don't take up a [`ColorEffect`] slot.
+///
+/// Stored with the entity's stack, so changing it re-uploads that one slot. Color
+/// effects work on the tinted sprite, e.g. a white flash still reaches full white.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let knight = 9;
+/// # let mut world = World::new();
+/// # let mut commands = world.commands();
+/// commands.spawn((Vfx::with_sprite(knight), VfxTint(Color::srgb(0.4, 0.6, 1.0))));
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+#[component(on_remove = crate::hooks::refresh_vfx_stack)]
+#[require(Vfx)]
+pub struct VfxTint(pub Color);
+
+impl Default for VfxTint {
+    fn default() -> Self {
+        Self(Color::WHITE)
+    }
+}
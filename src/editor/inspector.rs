@@ -0,0 +1,102 @@
+use crate::internal_prelude::*;
+use bevy_inspector_egui::bevy_egui::{EguiContexts, egui};
+
+/// Amplitude range of the sliders for spatial waves, in pixels (radians, scale factors)
+const SPATIAL_AMP_RANGE: f32 = 64.0;
+/// Amplitude range of the sliders for color, alpha and fragment waves
+const UNIT_AMP_RANGE: f32 = 2.0;
+/// Frequency range of the sliders, in cycles per effect duration
+const FREQ_RANGE: f32 = 32.0;
+
+/// The window of [`HirundoPlugin::with_inspector`](crate::HirundoPlugin::with_inspector):
+/// every `Vfx` entity with its active effects, the seconds each one-shot has left, and
+/// amplitude/frequency sliders for the wave of every sub-effect in use.
+///
+/// Edits go to the `Vfx` component, so its storage slot is re-uploaded as for any other
+/// change. Add bevy-inspector-egui's `WorldInspectorPlugin` for every other field.
+pub fn draw_vfx_inspector(
+    mut contexts: EguiContexts,
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    mut query: Query<(Entity, Option<&Name>, &mut Vfx)>,
+) -> Result {
+    let now = timeline.now(time.elapsed_secs());
+    egui::Window::new("Vfx").show(contexts.ctx_mut()?, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (entity, name, mut vfx) in &mut query {
+                let title = match name {
+                    Some(name) => format!("{name} ({entity})"),
+                    None => entity.to_string(),
+                };
+                let active = vfx.effects().active_count();
+                let mut changed = false;
+                egui::CollapsingHeader::new(format!("{title}: {active} active"))
+                    .id_salt(entity)
+                    .show(ui, |ui| {
+                        // Only flag the `Vfx` changed when a slider moved
+                        let stack = &mut vfx.bypass_change_detection().effects;
+                        for (slot, effect) in stack.effects.iter_mut().enumerate() {
+                            if effect.is_active() {
+                                changed |= effect_ui(ui, slot, effect, now);
+                            }
+                        }
+                    });
+                if changed {
+                    vfx.set_changed();
+                }
+            }
+        });
+    });
+    Ok(())
+}
+
+/// One effect's timing and wave sliders, whether any of them changed
+fn effect_ui(ui: &mut egui::Ui, slot: usize, effect: &mut Effect, now: f32) -> bool {
+    let timing = match effect.remaining(now) {
+        Some(remaining) => format!("{remaining:.2}s left"),
+        None => format!("looping, {:.0}%", effect.progress(now) * 100.0),
+    };
+    ui.label(format!("Slot {slot}: {timing}"));
+
+    let mut changed = false;
+    ui.indent(slot, |ui| {
+        for (i, color) in effect.color_effects.iter_mut().enumerate() {
+            if *color != ColorEffect::default() {
+                let label = format!("Color {i}");
+                changed |= wave_sliders(ui, &label, &mut color.wave, UNIT_AMP_RANGE);
+            }
+        }
+        for (i, alpha) in effect.alpha_effects.iter_mut().enumerate() {
+            if *alpha != AlphaEffect::default() {
+                let label = format!("Alpha {i}");
+                changed |= wave_sliders(ui, &label, &mut alpha.wave, UNIT_AMP_RANGE);
+            }
+        }
+        // Empty spatial slots are all zeroes, which reads as an OffsetX
+        for spatial in &mut effect.spatial_effects {
+            if *spatial != SpatialEffect::default()
+                && let Some(kind) = SpatialKind::from_raw(spatial.manipulation)
+            {
+                let label = format!("{kind:?}");
+                changed |= wave_sliders(ui, &label, &mut spatial.wave, SPATIAL_AMP_RANGE);
+            }
+        }
+        for fragment in &mut effect.fragment_effects {
+            if let Some(kind) = FragmentKind::from_raw(fragment.kind) {
+                let label = format!("{kind:?}");
+                changed |= wave_sliders(ui, &label, &mut fragment.wave, UNIT_AMP_RANGE);
+            }
+        }
+    });
+    changed
+}
+
+fn wave_sliders(ui: &mut egui::Ui, label: &str, wave: &mut Wave, amp_range: f32) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let amp = ui.add(egui::Slider::new(&mut wave.amp, -amp_range..=amp_range).text("amp"));
+        let freq = ui.add(egui::Slider::new(&mut wave.freq, 0.0..=FREQ_RANGE).text("freq"));
+        amp.changed() || freq.changed()
+    })
+    .inner
+}
@@ -0,0 +1,7 @@
+#[cfg(feature = "inspector")]
+mod inspector;
+mod vfx_editor;
+
+#[cfg(feature = "inspector")]
+pub use inspector::*;
+pub use vfx_editor::*;
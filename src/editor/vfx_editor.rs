@@ -0,0 +1,143 @@
+use crate::internal_prelude::*;
+
+/// Live effect authoring: a preview entity replaying [`VfxEditor::effect`] whenever it
+/// changes or finishes, and a key that saves it as an `.effect.ron` file.
+///
+/// The crate ships no widgets; edit the [`VfxEditor`] resource from your own UI, or
+/// through bevy-inspector-egui's `ResourceInspectorPlugin::<VfxEditor>`, which gets a
+/// field for every wave, envelope and phase parameter from its reflection.
+/// ```text
+/// App::new()
+///     .add_plugins((DefaultPlugins, HirundoPlugin::default().with_camera()))
+///     .add_plugins(HirundoEditorPlugin::default().with_export_path("assets/effects/new.effect.ron"))
+///     .add_plugins(ResourceInspectorPlugin::<VfxEditor>::default())
+///     .run();
+/// ```
+pub struct HirundoEditorPlugin {
+    /// File written on export, relative to the working directory
+    pub export_path: String,
+    pub export_key: KeyCode,
+}
+
+impl Default for HirundoEditorPlugin {
+    fn default() -> Self {
+        Self {
+            export_path: "assets/effects/edited.effect.ron".to_string(),
+            export_key: KeyCode::F5,
+        }
+    }
+}
+
+impl HirundoEditorPlugin {
+    pub fn with_export_path(mut self, path: &str) -> Self {
+        self.export_path = path.to_string();
+        self
+    }
+
+    pub fn with_export_key(mut self, key: KeyCode) -> Self {
+        self.export_key = key;
+        self
+    }
+}
+
+impl Plugin for HirundoEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(VfxEditor {
+            export_path: self.export_path.clone(),
+            export_key: self.export_key,
+            ..default()
+        });
+        app.register_type::<VfxEditor>()
+            .register_type::<EffectAsset>();
+        app.add_systems(Startup, spawn_editor_preview);
+        app.add_systems(
+            Update,
+            (replay_editor_preview, export_edited_effect).before(HirundoSet::Sync),
+        );
+    }
+}
+
+/// The effect being authored by [`HirundoEditorPlugin`].
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource)]
+pub struct VfxEditor {
+    pub effect: EffectAsset,
+    /// Atlas index of the preview sprite
+    pub sprite_index: u32,
+    pub export_path: String,
+    pub export_key: KeyCode,
+    /// Last validation error, so it is only logged once
+    #[reflect(ignore)]
+    pub(crate) error: Option<String>,
+}
+
+impl Default for VfxEditor {
+    fn default() -> Self {
+        Self {
+            effect: EffectAsset::default(),
+            sprite_index: 0,
+            export_path: HirundoEditorPlugin::default().export_path,
+            export_key: KeyCode::F5,
+            error: None,
+        }
+    }
+}
+
+impl VfxEditor {
+    /// Write the effect to `export_path` as RON, kinds and modes by name like the
+    /// shipped `.effect.ron` files.
+    pub fn export(&self) -> std::io::Result<()> {
+        let ron = self.effect.to_ron().map_err(std::io::Error::other)?;
+        std::fs::write(&self.export_path, ron)
+    }
+}
+
+/// Marker for the entity previewing the [`VfxEditor`] effect
+#[derive(Component)]
+pub struct VfxEditorPreview;
+
+pub fn spawn_editor_preview(mut commands: Commands, editor: Res<VfxEditor>) {
+    commands.spawn((
+        Vfx::with_sprite(editor.sprite_index),
+        VfxEditorPreview,
+        Transform::default(),
+    ));
+}
+
+/// Restarts the preview when the edited effect changes or its last play has finished
+pub fn replay_editor_preview(
+    time: Res<Time>,
+    mut editor: ResMut<VfxEditor>,
+    mut query: Query<&mut Vfx, With<VfxEditorPreview>>,
+) {
+    for mut vfx in &mut query {
+        if !editor.is_changed() && vfx.effects().active_count() > 0 {
+            continue;
+        }
+
+        let error = editor.effect.validate().err().map(|err| err.to_string());
+        if error.is_some() && error != editor.error {
+            warn!(
+                "Edited effect is invalid: {}",
+                error.as_deref().unwrap_or_default()
+            );
+        }
+        // Leave change detection alone, or the preview would restart every frame
+        editor.bypass_change_detection().error = error;
+
+        vfx.sprite_index = editor.sprite_index;
+        vfx.clear_effects();
+        vfx.push_effect(editor.effect.build(time.elapsed_secs()));
+    }
+}
+
+/// Saves the edited effect when the export key is pressed
+pub fn export_edited_effect(input: Res<ButtonInput<KeyCode>>, editor: Res<VfxEditor>) {
+    if !input.just_pressed(editor.export_key) {
+        return;
+    }
+    match editor.export() {
+        Ok(()) => info!("Exported effect to {}", editor.export_path),
+        Err(err) => error!("Could not export effect to {}: {}", editor.export_path, err),
+    }
+}
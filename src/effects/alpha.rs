@@ -1,4 +1,5 @@
 use crate::internal_prelude::*;
+use super::builder::{EffectBuilder, EffectModifier, LastEffect};
 use super::phase::Phase;
 use super::wave::Wave;
 
@@ -10,22 +11,28 @@ use super::wave::Wave;
 ///
 /// **Fade out**
 /// ```rust
-/// AlphaEffect {
-///     phase: Phase::full(),
-///     wave: Wave::sine(0.5, -0.5)
-///         .with_bias(0.5)  // Start at 1.0 (opaque), end at 0.0 (transparent)
-///         .with_amp_envelope(0.0, 0.0, 1.0), // Linear fade
-/// }
+/// # use bevy_hirundo::prelude::*;
+/// AlphaEffect::new(
+///     0.0, // Toward transparent
+///     Wave::constant(1.0).with_amp_envelope(1.0, 0.0, 0.0), // Linear fade, 1.0 (opaque) to 0.0
+/// )
+/// .with_phase(Phase::full())
+/// # ;
 /// ```
+///
+/// An effect holds up to `MAX_ALPHA_FX` alpha effects, applied in order;
+/// see [`AlphaComposite`] for how each one combines with the previous result.
+/// [`AlphaClip`] turns the blended result into hard-edged transparency.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, ShaderType)]
+#[derive(Clone, Copy, Debug, ShaderType, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(from = "AlphaEffectAsset", into = "AlphaEffectAsset")]
 pub struct AlphaEffect {
     pub(crate) phase: Phase,
     pub(crate) wave: Wave,
-    target_alpha: f32,
-    _pad0: f32,
-    _pad1: f32,
-    _pad2: f32,
+    pub(crate) target_alpha: f32,
+    pub(crate) composite: u32,
+    clip_mode: u32,
+    clip_threshold: f32,
 }
 
 impl AlphaEffect {
@@ -33,11 +40,16 @@ impl AlphaEffect {
     pub fn new(target: f32, wave: Wave) -> Self {
         Self {
             target_alpha: target,
-            wave: wave,
+            wave,
             ..default()
         }
     }
 
+    /// Baseline for fields left out of an effect asset, like [`EffectBuilder::alpha`]
+    fn asset_default() -> Self {
+        Self::new(0.0, Wave::constant(1.0))
+    }
+
     pub fn with_phase(mut self, phase: Phase) -> Self {
         self.phase = phase;
         self
@@ -50,9 +62,138 @@ impl Default for AlphaEffect {
             phase: Phase::full(),
             wave: Wave::constant(0.0), // strength=0 => no-op
             target_alpha: 1.0,
-            _pad0: 0.0,
-            _pad1: 0.0,
-            _pad2: 0.0,
+            composite: AlphaComposite::default() as u32,
+            clip_mode: 0,
+            clip_threshold: 0.5,
+        }
+    }
+}
+
+/// An [`AlphaEffect`] as written in effect assets, with its clip mode and threshold
+/// as one [`AlphaClip`], e.g. `clip: Cutout(0.5)`.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct AlphaEffectAsset {
+    phase: Phase,
+    wave: Wave,
+    target_alpha: f32,
+    composite: AlphaComposite,
+    clip: AlphaClip,
+}
+
+impl Default for AlphaEffectAsset {
+    fn default() -> Self {
+        AlphaEffect::asset_default().into()
+    }
+}
+
+impl From<AlphaEffect> for AlphaEffectAsset {
+    fn from(alpha: AlphaEffect) -> Self {
+        Self {
+            phase: alpha.phase,
+            wave: alpha.wave,
+            target_alpha: alpha.target_alpha,
+            composite: AlphaComposite::from_raw(alpha.composite).unwrap_or_default(),
+            clip: AlphaClip::from_mode(alpha.clip_mode, alpha.clip_threshold),
+        }
+    }
+}
+
+impl From<AlphaEffectAsset> for AlphaEffect {
+    fn from(asset: AlphaEffectAsset) -> Self {
+        let (clip_mode, clip_threshold) = asset.clip.mode();
+        Self {
+            phase: asset.phase,
+            wave: asset.wave,
+            target_alpha: asset.target_alpha,
+            composite: asset.composite.into_raw(),
+            clip_mode,
+            clip_threshold,
+        }
+    }
+}
+
+/// How an alpha effect combines with the alpha produced before it.
+///
+/// Defaults to **Lerp**.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[repr(u32)]
+pub enum AlphaComposite {
+    /// **(Mode 0)** -> **Default**
+    ///
+    /// Moves the current alpha towards `target_alpha` by the wave's strength,
+    /// overriding whatever came before at full strength.
+    #[default]
+    Lerp,
+
+    /// **(Mode 1)**
+    ///
+    /// Scales the current alpha by a factor that goes from 1.0 towards `target_alpha`
+    /// with the wave's strength. Layer this on top of a fade for flicker that keeps
+    /// fading out with it.
+    Multiply,
+}
+
+impl EffectModifier for AlphaComposite {
+    fn apply(&self, builder: &mut EffectBuilder) {
+        match builder.last_effect {
+            Some(LastEffect::Alpha(idx)) => {
+                builder.alphas[idx].as_mut().unwrap().composite = *self as u32
+            }
+            _ => warn!("No previous alpha effect to modify."),
+        }
+    }
+}
+
+/// How the sprite's final alpha is rendered while an alpha effect is active.
+///
+/// Clipping is resolved per sprite: the clip of the last active alpha effect
+/// (in stack order) that sets one applies to the final, fully composited alpha.
+/// Each texel is either drawn at its original alpha or discarded, so pixel-art
+/// sprites can fade without soft, half-transparent edges.
+///
+/// Defaults to **Blend**.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum AlphaClip {
+    /// Regular alpha blending.
+    #[default]
+    Blend,
+
+    /// Texels are kept while the remaining alpha is at least the threshold (0.0 - 1.0).
+    Cutout(f32),
+
+    /// Ordered 4x4 dithering in texel space: the remaining alpha decides how many
+    /// texels of each 4x4 block stay visible.
+    Dither,
+}
+
+impl AlphaClip {
+    fn mode(&self) -> (u32, f32) {
+        match self {
+            AlphaClip::Blend => (0, 0.5),
+            AlphaClip::Cutout(threshold) => (1, *threshold),
+            AlphaClip::Dither => (2, 0.5),
+        }
+    }
+
+    /// The clip [`Self::mode`] returned `(mode, threshold)` for
+    fn from_mode(mode: u32, threshold: f32) -> Self {
+        match mode {
+            1 => AlphaClip::Cutout(threshold),
+            2 => AlphaClip::Dither,
+            _ => AlphaClip::Blend,
+        }
+    }
+}
+
+impl EffectModifier for AlphaClip {
+    fn apply(&self, builder: &mut EffectBuilder) {
+        match builder.last_effect {
+            Some(LastEffect::Alpha(idx)) => {
+                let alpha = builder.alphas[idx].as_mut().unwrap();
+                (alpha.clip_mode, alpha.clip_threshold) = self.mode();
+            }
+            _ => warn!("No previous alpha effect to modify."),
         }
     }
 }
@@ -1,39 +1,54 @@
 use crate::internal_prelude::*;
 use super::lifetime::Lifetime;
-use super::color::ColorEffect;
+use super::color::{BlendMode, ColorEffect, Emissive};
 use super::alpha::AlphaEffect;
 use super::spatial::{SpatialEffect, SpatialKind};
+use super::fragment::{FragmentEffect, FragmentKind};
+use super::tile_cycle::TileCycleEffect;
 use super::wave::Wave;
 use super::effect_stack::Effect;
+use super::validation::EffectError;
 
 /// Tracks which sub-effect was most recently added to the builder.
-/// ```rust
+/// ```text
 /// match builder.last_effect {
 ///     Some(LastEffect::Color(idx)) => builder.colors[idx],
-///     Some(LastEffect::Alpha) => builder.alpha,
+///     Some(LastEffect::Alpha(idx)) => builder.alphas[idx],
 ///     Some(LastEffect::Spatial(kind)) => builder.spatial[kind],
+///     Some(LastEffect::Fragment(kind)) => builder.fragment[kind],
 ///     None => warn!("No previous sub-effect to modify."),
+/// }
 /// ```
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum LastEffect {
     Color(usize),
-    Alpha,
+    Alpha(usize),
     Spatial(SpatialKind),
+    Fragment(FragmentKind),
 }
 
 /// Builder for creating effects with chainable modifications.
 ///
 /// All sub-effects intialize with ~
 /// ```rust
+/// # use bevy_hirundo::prelude::*;
 /// Wave::constant(1.0)
+/// # ;
 /// ```
 #[derive(Default)]
 pub struct EffectBuilder {
     pub(crate) lifetime: Lifetime,
     pub(crate) colors: [Option<ColorEffect>; MAX_COLOR_FX],
-    pub(crate) alpha: Option<AlphaEffect>,
+    pub(crate) alphas: [Option<AlphaEffect>; MAX_ALPHA_FX],
     pub(crate) spatial: EnumMap<SpatialKind, Option<SpatialEffect>>, // One SpatialEffect per SpatialKind
+    pub(crate) fragment: EnumMap<FragmentKind, Option<FragmentEffect>>, // One FragmentEffect per FragmentKind
+    pub(crate) path_points: [Vec4; MAX_PATH_POINTS / 2],
+    pub(crate) tile_cycle: TileCycleEffect,
     pub(crate) last_effect: Option<LastEffect>,
+    /// Second axis of an `_xy`/`_uniform` pair, modified alongside the first by `with()`
+    pub(crate) linked: Option<(SpatialKind, SpatialKind)>,
+    /// Why sub-effects or modifications were dropped, reported by `build_checked`
+    pub(crate) dropped: Vec<EffectError>,
 }
 
 impl EffectBuilder {
@@ -53,6 +68,70 @@ impl EffectBuilder {
         }
     }
 
+    /// Start building an effect looping every `beats` beats of `clock`, starting on the
+    /// first beat at or after `now` so it stays in phase with every other synced loop.
+    ///
+    /// A stopped clock (`bpm` of 0.0 or less, or not finite) has no beat to loop on: the
+    /// effect falls back to [`VfxClock::default`]'s 120 bpm from `now`, with a warning,
+    /// and [`Self::build_checked`] rejects it with [`EffectError::StoppedClock`].
+    pub fn looping_synced(now: f32, clock: &VfxClock, beats: f32) -> Self {
+        if clock.beat_length() > 0.0 {
+            return Self::looping(clock.next_beat(now), clock.beats(beats));
+        }
+        warn!(
+            "Synced loop on a stopped clock ({} bpm), looping at 120 bpm instead",
+            clock.bpm
+        );
+        let mut builder = Self::looping(now, VfxClock::default().beats(beats));
+        builder.dropped.push(EffectError::StoppedClock { bpm: clock.bpm });
+        builder
+    }
+
+    /// Start from an already built effect, e.g. a stored preset, to tweak it before pushing.
+    ///
+    /// Every sub-effect comes back in its slot, so [`Self::modify`] can reach it; there is
+    /// no most recent sub-effect, so `with()` needs a new sub-effect or a `modify` first.
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let stored_hit = EffectBuilder::one_shot(0.0, 0.3).offset_x(4.0).build();
+    /// let effect = EffectBuilder::from_effect(&stored_hit)
+    ///     .modify_spatial(SpatialKind::OffsetX, Wave::noise(30.0, 8.0, 0.0))
+    ///     .build();
+    /// ```
+    pub fn from_effect(effect: &Effect) -> Self {
+        let mut builder = Self {
+            lifetime: effect.lifetime,
+            path_points: effect.path_points,
+            tile_cycle: effect.tile_cycle,
+            ..default()
+        };
+
+        for (slot, color) in builder.colors.iter_mut().zip(&effect.color_effects) {
+            if *color != ColorEffect::default() {
+                *slot = Some(*color);
+            }
+        }
+        for (slot, alpha) in builder.alphas.iter_mut().zip(&effect.alpha_effects) {
+            if *alpha != AlphaEffect::default() {
+                *slot = Some(*alpha);
+            }
+        }
+        // Empty spatial slots are all zeroes, which reads as an OffsetX without intensity
+        for spatial in &effect.spatial_effects {
+            if *spatial != SpatialEffect::default()
+                && let Some(kind) = SpatialKind::from_raw(spatial.manipulation)
+            {
+                builder.spatial[kind] = Some(*spatial);
+            }
+        }
+        for fragment in &effect.fragment_effects {
+            if let Some(kind) = FragmentKind::from_raw(fragment.kind) {
+                builder.fragment[kind] = Some(*fragment);
+            }
+        }
+        builder
+    }
+
     /// Add an RGB effect using a color that implements ColorToComponents
     ///
     /// **Important** the 4th value, usually reserved for Alpha, is repurposed as the [CompositeMode]
@@ -71,23 +150,92 @@ impl EffectBuilder {
             "Maximum color effects ({}) reached, ignoring additional color",
             MAX_COLOR_FX
         );
+        self.dropped.push(EffectError::SlotOverflow {
+            effects: "color",
+            max: MAX_COLOR_FX,
+        });
         self
     }
 
+    /// Add a two-color effect that replaces the sprite's color with one interpolated
+    /// between `from` and `to`, initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::saw(1.0, 0.5, 0.5) // from -> to once over the phase
+    /// # ;
+    /// ```
+    /// Swap in a looping lifetime and a triangle wave for ping-pong pulses.
+    ///
+    /// **Important** `to`'s 4th value is the [CompositeMode], as with [`Self::color`].
+    pub fn color_lerp(
+        mut self,
+        from: impl ColorToComponents,
+        to: impl ColorToComponents,
+    ) -> Self {
+        for (i, slot) in self.colors.iter_mut().enumerate() {
+            if slot.is_none() {
+                *slot = Some(ColorEffect::gradient(
+                    from.to_vec4(),
+                    to.to_vec4(),
+                    Wave::saw(1.0, 0.5, 0.5),
+                ));
+                self.last_effect = Some(LastEffect::Color(i));
+                return self;
+            }
+        }
+        warn!(
+            "Maximum color effects ({}) reached, ignoring additional color",
+            MAX_COLOR_FX
+        );
+        self.dropped.push(EffectError::SlotOverflow {
+            effects: "color",
+            max: MAX_COLOR_FX,
+        });
+        self
+    }
+
+    /// Shortcut for
+    /// ```text
+    /// with(Emissive(strength))
+    /// ```
+    /// Boosts the most recent color effect into HDR range for bloom.
+    pub fn with_emissive(self, strength: f32) -> Self {
+        self.with(Emissive(strength))
+    }
+
     /// Add an alpha effect initialized with Wave::constant(1.0)
+    ///
+    /// Up to `MAX_ALPHA_FX` alpha effects can be layered, composited in order
+    /// (see [`AlphaComposite`]).
     pub fn alpha(mut self, alpha: f32) -> Self {
-        self.alpha = Some(AlphaEffect::new(alpha, Wave::constant(1.0)));
-        self.last_effect = Some(LastEffect::Alpha);
+        // Find first available slot
+        for (i, slot) in self.alphas.iter_mut().enumerate() {
+            if slot.is_none() {
+                *slot = Some(AlphaEffect::new(alpha, Wave::constant(1.0)));
+                self.last_effect = Some(LastEffect::Alpha(i));
+                return self;
+            }
+        }
+        warn!(
+            "Maximum alpha effects ({}) reached, ignoring additional alpha",
+            MAX_ALPHA_FX
+        );
+        self.dropped.push(EffectError::SlotOverflow {
+            effects: "alpha",
+            max: MAX_ALPHA_FX,
+        });
         self
     }
 
     /// Shortcut  for
-    /// ```
+    /// ```text
     /// alpha(0.0)
     /// ```
     /// Initialized with
     /// ```
+    /// # use bevy_hirundo::prelude::*;
     /// Wave::constant(1.0)
+    /// # ;
     /// ```
     /// Makes the sprite invisible.
     pub fn alpha_zero(self) -> Self {
@@ -95,12 +243,14 @@ impl EffectBuilder {
     }
 
     /// Shortcut  for
-    /// ```
+    /// ```text
     /// alpha(0.0)
     /// ```
     /// Initialized with
     /// ```
+    /// # use bevy_hirundo::prelude::*;
     /// Wave::constant(1.0)
+    /// # ;
     /// ```
     /// Makes the sprite invisible.
     pub fn invisibility(self) -> Self {
@@ -111,7 +261,10 @@ impl EffectBuilder {
 
     /// Add offset_x spatial effect, intialized with
     /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let pixels = 1.0;
     /// Wave::constant(pixels)
+    /// # ;
     /// ```
     /// **pixels** is amplitude.
     pub fn offset_x(self, pixels: f32) -> Self {
@@ -120,7 +273,10 @@ impl EffectBuilder {
 
     /// Add offset_y spatial effect, intialized with
     /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let pixels = 1.0;
     /// Wave::constant(pixels)
+    /// # ;
     /// ```
     /// **pixels** is amplitude.
     pub fn offset_y(self, pixels: f32) -> Self {
@@ -129,7 +285,10 @@ impl EffectBuilder {
 
     /// Add scale_x spatial effect, intialized with
     /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let factor = 1.0;
     /// Wave::constant(factor)
+    /// # ;
     /// ```
     /// **factor** is amplitude.
     pub fn scale_x(self, factor: f32) -> Self {
@@ -138,16 +297,46 @@ impl EffectBuilder {
 
     /// Add scale_y spatial effect, intialized with
     /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let factor = 1.0;
     /// Wave::constant(factor)
+    /// # ;
     /// ```
     /// **factor** is amplitude.
     pub fn scale_y(self, factor: f32) -> Self {
         self.add_spatial(SpatialKind::ScaleY, factor)
     }
 
+    /// Add offset_x and offset_y spatial effects in one go, intialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let x = 1.0;
+    /// Wave::constant(x) // and Wave::constant(y)
+    /// # ;
+    /// ```
+    /// Following `with()` calls modify both axes.
+    pub fn offset_xy(self, x: f32, y: f32) -> Self {
+        self.add_spatial_pair((SpatialKind::OffsetX, x), (SpatialKind::OffsetY, y))
+    }
+
+    /// Add scale_x and scale_y spatial effects with the same `factor`, intialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let factor = 1.0;
+    /// Wave::constant(factor)
+    /// # ;
+    /// ```
+    /// Following `with()` calls modify both axes.
+    pub fn scale_uniform(self, factor: f32) -> Self {
+        self.add_spatial_pair((SpatialKind::ScaleX, factor), (SpatialKind::ScaleY, factor))
+    }
+
     /// Add rotation spatial effect, intialized with
     /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let degrees = 1.0;
     /// Wave::constant(degrees)
+    /// # ;
     /// ```
     /// **degrees** is amplitude (converted to radians).
     ///
@@ -158,7 +347,10 @@ impl EffectBuilder {
 
     /// Add skew_x spatial effect, intialized with
     /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let factor = 1.0;
     /// Wave::constant(factor)
+    /// # ;
     /// ```
     /// **factor** is amplitude.
     pub fn skew_x(self, factor: f32) -> Self {
@@ -167,28 +359,310 @@ impl EffectBuilder {
 
     /// Add skew_y spatial effect, intialized with
     /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let factor = 1.0;
     /// Wave::constant(factor)
+    /// # ;
     /// ```
     /// **factor** is amplitude.
     pub fn skew_y(self, factor: f32) -> Self {
         self.add_spatial(SpatialKind::SkewY, factor)
     }
 
-    /// Modify the most recent sub-effect (Color, Alpha, or Spatial) with an [`EffectModifier`]
+    /// Add skew_x and skew_y spatial effects in one go, intialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let x = 1.0;
+    /// Wave::constant(x) // and Wave::constant(y)
+    /// # ;
+    /// ```
+    /// Following `with()` calls modify both axes.
+    pub fn skew_xy(self, x: f32, y: f32) -> Self {
+        self.add_spatial_pair((SpatialKind::SkewX, x), (SpatialKind::SkewY, y))
+    }
+
+    /// Add flip_x spatial effect, intialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::constant(1.0)
+    /// # ;
+    /// ```
+    /// Mirrors the sprite horizontally around the anchor. Animate the wave from 0.0 to 1.0
+    /// to turn the sprite over instead of snapping.
+    pub fn flip_x(self) -> Self {
+        self.add_spatial(SpatialKind::FlipX, 1.0)
+    }
+
+    /// Add flip_y spatial effect, intialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::constant(1.0)
+    /// # ;
+    /// ```
+    /// Mirrors the sprite vertically around the anchor.
+    pub fn flip_y(self) -> Self {
+        self.add_spatial(SpatialKind::FlipY, 1.0)
+    }
+
+    /// Add orbit spatial effect: the sprite circles `radius` pixels around its anchor,
+    /// `revolutions` times over the phase (negative = clockwise). Initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # use std::f32::consts::PI;
+    /// # let revolutions = 1.0;
+    /// Wave::saw(revolutions, PI, PI) // angle sweeps 0 -> 2PI per revolution
+    /// # ;
+    /// ```
+    /// **radius** is the intensity. Move the orbit's center with an [`Anchor`](super::Anchor).
+    pub fn orbit(self, radius: f32, revolutions: f32) -> Self {
+        let wave = Wave::saw(revolutions, f32::consts::PI, f32::consts::PI);
+        self.insert_spatial(SpatialKind::Orbit, SpatialEffect::orbit(radius, wave))
+    }
+
+    /// Add arc spatial effect: a parabola that travels `distance` pixels horizontally
+    /// and peaks `height` pixels up halfway through the phase. Initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::saw(1.0, 0.5, 0.5) // progress 0 -> 1 once over the phase
+    /// # ;
+    /// ```
+    /// Negative `distance` lobs to the left; a zero `distance` is an in-place hop.
+    pub fn arc(self, distance: f32, height: f32) -> Self {
+        let effect = SpatialEffect::arc(distance, height, Wave::saw(1.0, 0.5, 0.5));
+        self.insert_spatial(SpatialKind::Arc, effect)
+    }
+
+    /// Add sway spatial effect, initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let pixels = 1.0;
+    /// Wave::sine(1.0, pixels, 0.0)
+    /// # ;
+    /// ```
+    /// **pixels** is how far the top of the sprite swings either side; lower vertices
+    /// move less, down to none at the anchor (bottom center by default).
+    /// Use a looping lifetime for idle wind.
+    pub fn sway(self, pixels: f32) -> Self {
+        self.add_spatial(SpatialKind::Sway, pixels)
+    }
+
+    /// Add path spatial effect: the sprite follows a smooth curve through `points`
+    /// (pixel offsets from its rest position, usually starting at `Vec2::ZERO`).
+    /// Initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::saw(1.0, 0.5, 0.5) // start -> end once over the phase
+    /// # ;
+    /// ```
+    /// Takes 2 to `MAX_PATH_POINTS` points; extra points are ignored.
+    pub fn path(self, points: &[Vec2]) -> Self {
+        self.add_path(points, true)
+    }
+
+    /// Same as [`Self::path`], but with straight segments between the points.
+    pub fn polyline(self, points: &[Vec2]) -> Self {
+        self.add_path(points, false)
+    }
+
+    /// Cycle the sprite through `count` atlas indices from `start` at `fps`, in the
+    /// shader, for as long as the effect runs. See [`TileCycleEffect`].
+    ///
+    /// Not a sub-effect: `with()` still modifies the previous one.
+    pub fn tile_cycle(mut self, start: u32, count: u32, fps: f32, mode: AnimationMode) -> Self {
+        self.tile_cycle = TileCycleEffect::new(start, count, fps, mode);
+        self
+    }
+
+    // === Fragment Effect Constructors ===
+
+    /// Add an outline `thickness` texels wide, initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::constant(1.0)
+    /// # ;
+    /// ```
+    /// The wave drives the outline's alpha.
+    pub fn outline(self, color: impl ColorToComponents, thickness: f32) -> Self {
+        self.add_fragment(
+            FragmentKind::Outline,
+            FragmentEffect::outline(color.to_vec4(), thickness),
+        )
+    }
+
+    /// Add a dissolve that erodes the sprite through the material's noise texture,
+    /// initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::saw(1.0, 0.5, 0.5) // intact -> gone once over the phase
+    /// # ;
+    /// ```
+    /// The wave is the erosion threshold. `edge_width` (0.0 to 1.0, in noise units) sets the
+    /// band of `edge_color` at the boundary; a transparent `edge_color` disables the edge.
+    pub fn dissolve(self, edge_color: impl ColorToComponents, edge_width: f32) -> Self {
+        self.add_fragment(
+            FragmentKind::Dissolve,
+            FragmentEffect::dissolve(edge_color.to_vec4(), edge_width),
+        )
+    }
+
+    /// Add a pixelation of up to `block_size` texels per block, initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::constant(1.0)
+    /// # ;
+    /// ```
+    /// The wave scales the block size between 1 texel (0.0) and `block_size` (1.0).
+    pub fn pixelate(self, block_size: f32) -> Self {
+        self.add_fragment(FragmentKind::Pixelate, FragmentEffect::pixelate(block_size))
+    }
+
+    /// Add a glitch that shifts random horizontal slices and splits red/blue, initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::square(8.0, 0.5, 0.5) // strobes on and off 8 times per phase
+    /// # ;
+    /// ```
+    /// `slices` rows are displaced by up to `displacement` sprite widths;
+    /// `rgb_split` is in texels (0.0 = no channel split) and replaces the red/blue channels
+    /// with shifted samples of the unmodified sprite.
+    pub fn glitch(self, slices: f32, displacement: f32, rgb_split: f32) -> Self {
+        self.add_fragment(
+            FragmentKind::Glitch,
+            FragmentEffect::glitch(slices, displacement, rgb_split),
+        )
+    }
+
+    /// Composite the overlay texture (see `HirundoPlugin::with_overlay_texture`) over the
+    /// sprite, initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::constant(1.0)
+    /// # ;
+    /// ```
+    /// The wave drives opacity. `tint` multiplies the overlay and `blend_mode` picks how it
+    /// combines with the sprite; [`BlendMode`] also works as a modifier here.
+    pub fn overlay(self, tint: impl ColorToComponents, blend_mode: BlendMode) -> Self {
+        self.add_fragment(
+            FragmentKind::Overlay,
+            FragmentEffect::overlay(tint.to_vec4(), blend_mode),
+        )
+    }
+
+    /// Hologram projection: `tint`ed scanlines with transparency banding, initialized with
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// Wave::square(14.0, 0.12, 0.88)
+    /// # ;
+    /// ```
+    /// The wave is the hologram's opacity, flickering by default; use a `Wave::constant`
+    /// for a steady projection. `scanlines` is the number of lines per sprite and `banding`
+    /// how transparent the gaps between them get (0.0 - 1.0).
+    pub fn hologram(self, tint: impl ColorToComponents, scanlines: f32, banding: f32) -> Self {
+        self.add_fragment(
+            FragmentKind::Hologram,
+            FragmentEffect::hologram(tint.to_vec4(), scanlines, banding),
+        )
+    }
+
+    /// Scroll the overlay texture, in UVs per second. Only applies to an overlay effect.
+    pub fn with_overlay_scroll(mut self, scroll: Vec2) -> Self {
+        match self.last_effect {
+            Some(LastEffect::Fragment(FragmentKind::Overlay)) => {
+                let overlay = self.fragment[FragmentKind::Overlay].as_mut().unwrap();
+                overlay.params.y = scroll.x;
+                overlay.params.z = scroll.y;
+            }
+            _ => warn!("No previous overlay effect to modify."),
+        }
+        self
+    }
+
+    /// Modify the most recent sub-effect (Color, Alpha, Spatial or Fragment) with an [`EffectModifier`]
+    ///
+    /// After [`Self::offset_xy`], [`Self::scale_uniform`] or [`Self::skew_xy`], both axes are modified.
     /// # Modifiers
     /// * **[Wave]** - *-> modifies ->* Any *note*: All fields implement [`EffectModifier`] to modify the wave, rather than replace it.
-    /// *note*: `Phase` for Wave is called **[`WavePhase`]**
+    ///   *note*: `Phase` for Wave is called **[`WavePhase`]**
     /// * **[Phase]** - *-> modifies ->* Any
+    /// * **[Reverse]** - *-> modifies ->* Any
     /// * **[Envelope]** - *-> modifies ->* Any
     /// * **[Anchor]** - *-> modifies ->* Spatial
     /// * **[Intensity]** - *-> modifies ->* Spatial
-    /// * **[BlendMode]** *-> modifies ->* Color
+    /// * **[BlendMode]** *-> modifies ->* Color, Overlay
     /// * **[CompositeMode]** - *-> modifies ->* Color
+    /// * **[AlphaComposite]** - *-> modifies ->* Alpha
+    /// * **[AlphaClip]** - *-> modifies ->* Alpha
+    /// * **[Emissive]** - *-> modifies ->* Color
     pub fn with(mut self, modifier: impl EffectModifier) -> Self {
         modifier.apply(&mut self);
+        // Axis pairs from `offset_xy` and friends get the same modification
+        if let Some((first, second)) = self.linked
+            && self.last_effect == Some(LastEffect::Spatial(first))
+        {
+            self.last_effect = Some(LastEffect::Spatial(second));
+            modifier.apply(&mut self);
+            self.last_effect = Some(LastEffect::Spatial(first));
+        }
+        self
+    }
+
+    /// Like [`Self::with`], but modifies `target` instead of the most recent sub-effect.
+    ///
+    /// The most recent sub-effect is left unchanged, so `with()` calls after this keep
+    /// modifying it.
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_hirundo::prelude::*;
+    /// # use bevy_hirundo::effects::Anchor;
+    /// # let now = 0.0;
+    /// EffectBuilder::one_shot(now, 1.0)
+    ///     .color(LinearRgba::RED)
+    ///     .scale_x(0.2)
+    ///     .modify(LastEffect::Color(0), Wave::sine(2.0, 0.5, 0.5))
+    ///     .with(Anchor::BottomCenter) // still modifies scale_x
+    /// # ;
+    /// ```
+    pub fn modify(mut self, target: LastEffect, modifier: impl EffectModifier) -> Self {
+        let exists = match target {
+            LastEffect::Color(idx) => self.colors.get(idx).is_some_and(Option::is_some),
+            LastEffect::Alpha(idx) => self.alphas.get(idx).is_some_and(Option::is_some),
+            LastEffect::Spatial(kind) => self.spatial[kind].is_some(),
+            LastEffect::Fragment(kind) => self.fragment[kind].is_some(),
+        };
+        if !exists {
+            warn!("No {:?} sub-effect to modify.", target);
+            self.dropped.push(EffectError::MissingTarget { target });
+            return self;
+        }
+
+        let last = self.last_effect.replace(target);
+        modifier.apply(&mut self);
+        self.last_effect = last;
         self
     }
 
+    /// Shortcut for `modify(LastEffect::Color(idx), modifier)`, `idx` counting color
+    /// effects in the order they were added.
+    pub fn modify_color(self, idx: usize, modifier: impl EffectModifier) -> Self {
+        self.modify(LastEffect::Color(idx), modifier)
+    }
+
+    /// Shortcut for `modify(LastEffect::Alpha(idx), modifier)`, `idx` counting alpha
+    /// effects in the order they were added.
+    pub fn modify_alpha(self, idx: usize, modifier: impl EffectModifier) -> Self {
+        self.modify(LastEffect::Alpha(idx), modifier)
+    }
+
+    /// Shortcut for `modify(LastEffect::Spatial(kind), modifier)`.
+    pub fn modify_spatial(self, kind: SpatialKind, modifier: impl EffectModifier) -> Self {
+        self.modify(LastEffect::Spatial(kind), modifier)
+    }
+
+    /// Shortcut for `modify(LastEffect::Fragment(kind), modifier)`.
+    pub fn modify_fragment(self, kind: FragmentKind, modifier: impl EffectModifier) -> Self {
+        self.modify(LastEffect::Fragment(kind), modifier)
+    }
+
     /// Consume the builder and return the constructed effect
     pub fn build(self) -> Effect {
         // 1. Create the target array filled with defaults (disabled effects)
@@ -215,32 +689,137 @@ impl EffectBuilder {
             }
         }
 
+        // 4. Alpha effects keep their slot order, it decides how they composite
+        let mut alpha_effects = [AlphaEffect::default(); MAX_ALPHA_FX];
+        for (i, alpha_opt) in self.alphas.iter().enumerate() {
+            if let Some(alpha) = alpha_opt {
+                alpha_effects[i] = *alpha;
+            }
+        }
+
+        // 5. Same for fragment effects
+        let mut fragment_effects = [FragmentEffect::default(); MAX_FRAGMENT_FX];
+        for (i, effect) in self
+            .fragment
+            .values()
+            .flatten()
+            .take(MAX_FRAGMENT_FX)
+            .enumerate()
+        {
+            fragment_effects[i] = *effect;
+        }
+
         Effect {
             lifetime: self.lifetime,
             color_effects,
-            alpha_effect: self.alpha.unwrap_or_default(),
+            alpha_effects,
             spatial_effects,
+            fragment_effects,
+            path_points: self.path_points,
+            tile_cycle: self.tile_cycle,
         }
     }
 
     // === Internal Helpers ===
 
-    fn add_spatial(mut self, kind: SpatialKind, unit_value: f32) -> Self {
-        self.spatial[kind] = Some(SpatialEffect::from(kind, unit_value));
+    pub(crate) fn add_path(mut self, points: &[Vec2], curved: bool) -> Self {
+        if points.len() < 2 {
+            warn!("A path needs at least 2 points, ignoring it.");
+            self.dropped.push(EffectError::PathTooShort {
+                points: points.len(),
+            });
+            return self;
+        }
+        if points.len() > MAX_PATH_POINTS {
+            warn!(
+                "Maximum path points ({}) reached, ignoring the rest",
+                MAX_PATH_POINTS
+            );
+            self.dropped.push(EffectError::SlotOverflow {
+                effects: "path point",
+                max: MAX_PATH_POINTS,
+            });
+        }
+
+        let count = points.len().min(MAX_PATH_POINTS);
+        self.path_points = [Vec4::ZERO; MAX_PATH_POINTS / 2];
+        for (i, point) in points.iter().take(count).enumerate() {
+            let packed = &mut self.path_points[i / 2];
+            if i % 2 == 0 {
+                (packed.x, packed.y) = (point.x, point.y);
+            } else {
+                (packed.z, packed.w) = (point.x, point.y);
+            }
+        }
+
+        let effect = SpatialEffect::path(count, curved, Wave::saw(1.0, 0.5, 0.5));
+        self.insert_spatial(SpatialKind::Path, effect)
+    }
+
+    fn add_fragment(mut self, kind: FragmentKind, effect: FragmentEffect) -> Self {
+        // A kind already in the builder is replaced, a new one needs a free slot
+        if self.fragment[kind].is_none()
+            && self.fragment.values().flatten().count() >= MAX_FRAGMENT_FX
+        {
+            warn!(
+                "Maximum fragment effects ({}) reached, ignoring {:?}",
+                MAX_FRAGMENT_FX, kind
+            );
+            self.dropped.push(EffectError::SlotOverflow {
+                effects: "fragment",
+                max: MAX_FRAGMENT_FX,
+            });
+            return self;
+        }
+        self.fragment[kind] = Some(effect);
+        self.last_effect = Some(LastEffect::Fragment(kind));
+        self
+    }
+
+    fn add_spatial(self, kind: SpatialKind, unit_value: f32) -> Self {
+        self.insert_spatial(kind, SpatialEffect::from(kind, unit_value))
+    }
+
+    fn insert_spatial(mut self, kind: SpatialKind, effect: SpatialEffect) -> Self {
+        // A kind already in the builder is replaced, a new one needs a free slot
+        if self.spatial[kind].is_none()
+            && self.spatial.values().flatten().count() >= MAX_SPATIAL_FX
+        {
+            warn!(
+                "Maximum spatial effects ({}) reached, ignoring {:?}",
+                MAX_SPATIAL_FX, kind
+            );
+            self.dropped.push(EffectError::SlotOverflow {
+                effects: "spatial",
+                max: MAX_SPATIAL_FX,
+            });
+            return self;
+        }
+        self.spatial[kind] = Some(effect);
         self.last_effect = Some(LastEffect::Spatial(kind));
+        self.linked = None;
         self
     }
+
+    fn add_spatial_pair(self, first: (SpatialKind, f32), second: (SpatialKind, f32)) -> Self {
+        let mut builder = self
+            .add_spatial(second.0, second.1)
+            .add_spatial(first.0, first.1);
+        builder.linked = Some((first.0, second.0));
+        builder
+    }
 }
 
 /// Trait that enables use of [`EffectBuilder::with()`] for modifying the most recent effect
 /// in the builder chain.
 ///
 /// You probably want this match block in `fn apply`
-/// ``` rust
+/// ```text
 /// match builder.last_effect {
 ///     Some(LastEffect::Color(idx)) => builder.colors[idx],
-///     Some(LastEffect::Alpha) => builder.alpha,
+///     Some(LastEffect::Alpha(idx)) => builder.alphas[idx],
 ///     Some(LastEffect::Spatial(kind)) => builder.spatial[kind],
+///     Some(LastEffect::Fragment(kind)) => builder.fragment[kind],
 ///     None => warn!("No previous sub-effect to modify."),
 /// }
 /// ```
@@ -2,6 +2,7 @@ use crate::internal_prelude::*;
 use super::phase::Phase;
 use super::wave::Wave;
 use super::builder::{EffectBuilder, EffectModifier, LastEffect};
+use super::fragment::FragmentKind;
 
 /// RGB color effect with wave-driven parameters.
 ///
@@ -13,34 +14,45 @@ use super::builder::{EffectBuilder, EffectModifier, LastEffect};
 /// - **2: Multiply** - Multiplicative blending (darkens, good for shadows)
 /// - **3: Screen** - Inverse multiply (brightens without overexposure)
 /// - **4: HSV Shift** - Hue/Saturation/Value manipulation
+/// - **5: Gradient** - Interpolates `from_color` -> `color`, ignoring the base color
 ///
 /// # Examples
 ///
 /// **Color flash (additive)**
 /// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
 /// ColorEffect {
 ///     phase: Phase::full(),
-///     wave: Wave::sine(1.0, 0.5).with_bias(0.5),
-///     color: LinearRgba::from(RED).to_vec3(),
+///     wave: Wave::sine(1.0, 0.5, 0.5),
+///     color: LinearRgba::RED.to_vec4(),
 ///     blend_mode: 1, // Additive blend
+///     ..default()
 /// }
+/// # ;
 /// ```
 ///
 /// **HSV hue rotation**
 /// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
 /// ColorEffect {
 ///     phase: Phase::full(),
-///     wave: Wave::sine(1.0, 0.5).with_bias(0.5),
-///     color: Vec3::new(
+///     wave: Wave::sine(1.0, 0.5, 0.5),
+///     color: Vec4::new(
 ///         1.0,  // H: Full hue rotation (360 degrees)
 ///         0.0,  // S: No saturation change
 ///         0.0,  // V: No brightness change
+///         1.0,  // CompositeMode::Contributive
 ///     ),
 ///     blend_mode: 4, // HSV shift
+///     ..default()
 /// }
+/// # ;
 /// ```
 #[repr(C)]
-#[derive(Clone, Copy, Debug, ShaderType, Default)]
+#[derive(Clone, Copy, Debug, ShaderType, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default = "ColorEffect::asset_default")]
 pub struct ColorEffect {
     pub phase: Phase,
     pub wave: Wave,
@@ -49,8 +61,17 @@ pub struct ColorEffect {
     /// **Important** the 4th value, usually reserved for Alpha, is repurposed as a ... flag.
     /// Alpha is controlled separately.
     pub color: Vec4,
-    /// Blend mode: 0=Lerp, 1=Add, 2=Multiply, 3=Screen, 4=HSV
+    /// Start color for [`BlendMode::Gradient`], unused by the other modes.
+    pub from_color: Vec4,
+    /// Blend mode: 0=Lerp, 1=Add, 2=Multiply, 3=Screen, 4=HSV, 5=Gradient
+    #[serde(
+        serialize_with = "serialize_kind::<BlendMode, _>",
+        deserialize_with = "deserialize_kind::<BlendMode, _>"
+    )]
     pub blend_mode: u32,
+    /// HDR boost, scaled by the wave. Final RGB is multiplied by `1.0 + emissive`,
+    /// pushing it past 1.0 so it can feed bloom. Needs an HDR camera.
+    pub emissive: f32,
 }
 
 impl ColorEffect {
@@ -64,6 +85,11 @@ impl ColorEffect {
         }
     }
 
+    /// Baseline for fields left out of an effect asset, like [`EffectBuilder::color`]
+    fn asset_default() -> Self {
+        Self::new(Vec4::ZERO, Wave::constant(1.0))
+    }
+
     pub fn with_phase(mut self, phase: Phase) -> Self {
         self.phase = phase;
         self
@@ -74,6 +100,23 @@ impl ColorEffect {
         self
     }
 
+    /// HDR boost for bloom: the final RGB is scaled by `1.0 + strength`, weighted by the wave.
+    pub fn with_emissive(mut self, strength: f32) -> Self {
+        self.emissive = strength;
+        self
+    }
+
+    /// New two-color effect: the wave sweeps from `from` (0.0) to `to` (1.0).
+    pub fn gradient(from: Vec4, to: Vec4, wave: Wave) -> Self {
+        Self {
+            wave,
+            color: to,
+            from_color: from,
+            blend_mode: BlendMode::Gradient as u32,
+            ..default()
+        }
+    }
+
     // TODO
     // HSV helper
 }
@@ -84,8 +127,9 @@ impl ColorEffect {
 /// - **2: Multiply** - Multiplicative blending (darkens, good for negative statuses)
 /// - **3: Screen** - Inverse multiply (brightens without overexposure)
 /// - **4: HSV Shift** - Hue/Saturation/Value manipulation
+/// - **5: Gradient** - Two explicit colors, independent of the sprite's base color
 #[repr(u32)]
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub enum BlendMode {
     /// - **0: Lerp** - Smooth interpolation between base and target color
     #[default]
@@ -98,6 +142,8 @@ pub enum BlendMode {
     Screen = 3,
     /// - **4: HSV Shift** - Hue/Saturation/Value manipulation
     Hsv = 4,
+    /// - **5: Gradient** - Two explicit colors, independent of the sprite's base color
+    Gradient = 5,
 }
 
 impl EffectModifier for BlendMode {
@@ -106,7 +152,14 @@ impl EffectModifier for BlendMode {
             Some(LastEffect::Color(idx)) => {
                 builder.colors[idx].as_mut().unwrap().blend_mode = *self as u32
             }
-            _ => warn!("No previous RGB effect to modify."),
+            Some(LastEffect::Fragment(FragmentKind::Overlay)) => {
+                builder.fragment[FragmentKind::Overlay]
+                    .as_mut()
+                    .unwrap()
+                    .params
+                    .x = *self as u32 as f32
+            }
+            _ => warn!("No previous RGB or overlay effect to modify."),
         }
     }
 }
@@ -159,3 +212,19 @@ impl EffectModifier for CompositeMode {
         }
     }
 }
+
+/// Make the most recent color effect glow: its output is boosted past 1.0
+/// by up to `strength`, following the effect's wave.
+///
+/// Only visible with an HDR camera and `Bloom` (see `HirundoPlugin::with_bloom`).
+#[derive(Clone, Copy, Debug, From)]
+pub struct Emissive(pub f32);
+
+impl EffectModifier for Emissive {
+    fn apply(&self, builder: &mut EffectBuilder) {
+        match builder.last_effect {
+            Some(LastEffect::Color(idx)) => builder.colors[idx].as_mut().unwrap().emissive = self.0,
+            _ => warn!("No previous RGB effect to modify."),
+        }
+    }
+}
@@ -3,16 +3,24 @@ use super::lifetime::Lifetime;
 use super::color::ColorEffect;
 use super::alpha::AlphaEffect;
 use super::spatial::SpatialEffect;
+use super::fragment::FragmentEffect;
+use super::tile_cycle::TileCycleEffect;
+use bevy::math::Affine2;
 
 /// Complete effect containing master timing and sub-effects.
 /// RGB and Alpha are now separate for independent control.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, ShaderType, Default)]
+#[derive(Clone, Copy, Debug, ShaderType, Default, PartialEq, Serialize, Deserialize, Reflect)]
 pub struct Effect {
     pub(crate) lifetime: Lifetime,
     pub(crate) color_effects: [ColorEffect; MAX_COLOR_FX],
-    pub(crate) alpha_effect: AlphaEffect,
+    pub(crate) alpha_effects: [AlphaEffect; MAX_ALPHA_FX],
     pub(crate) spatial_effects: [SpatialEffect; MAX_SPATIAL_FX],
+    pub(crate) fragment_effects: [FragmentEffect; MAX_FRAGMENT_FX],
+    /// Control points of the path spatial effect, two per Vec4 (xy, zw)
+    pub(crate) path_points: [Vec4; MAX_PATH_POINTS / 2],
+    /// Shader-side flipbook, disabled when `count` is 0
+    pub(crate) tile_cycle: TileCycleEffect,
 }
 
 impl Effect {
@@ -31,19 +39,348 @@ impl Effect {
             ..default()
         }
     }
+
+    /// Copy of this effect starting at `now`, for prebuilt effects whose baked
+    /// start time has gone stale.
+    ///
+    /// Only the start moves: duration, looping and every sub-effect stay as built.
+    pub fn retimed(mut self, now: f32) -> Self {
+        self.lifetime.start_time = now;
+        self
+    }
+
+    /// Copy of this effect playing backwards, from its last frame to its first: every
+    /// sub-effect's window is mirrored and run the other way ([`Phase::mirrored`]).
+    /// Reversing twice plays forwards again; flipbooks play as built.
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, time) = (0.0, Time::<()>::default());
+    /// # let mut world = World::new();
+    /// # let entity = world.spawn_empty().id();
+    /// # let mut commands = world.commands();
+    /// # let mut vfx = Vfx::default();
+    /// // Grows in from nothing when spawned...
+    /// let grow = EffectBuilder::one_shot(now, 0.3)
+    ///     .scale_uniform(-1.0)
+    ///     .with(Envelope::amplitude(0.0, 0.0, 1.0).with_ease_out(3.0))
+    ///     .build();
+    /// // ...and shrinks away the same way before despawning
+    /// vfx.push_effect_now(grow.reversed(), time.elapsed_secs());
+    /// commands.entity(entity).insert(DespawnWhenEffectsFinish::default());
+    /// ```
+    pub fn reversed(mut self) -> Self {
+        for color in &mut self.color_effects {
+            color.phase = color.phase.mirrored();
+        }
+        for alpha in &mut self.alpha_effects {
+            alpha.phase = alpha.phase.mirrored();
+        }
+        for spatial in &mut self.spatial_effects {
+            spatial.phase = spatial.phase.mirrored();
+        }
+        for frag in &mut self.fragment_effects {
+            frag.phase = frag.phase.mirrored();
+        }
+        self
+    }
+
+    /// Copy of this effect played at `weight` of its strength: 1.0 as built, 0.0 leaving
+    /// the sprite as it is. Scales spatial intensities and the color, alpha and fragment
+    /// waves; gradients keep their colors and flipbooks play as built.
+    ///
+    /// What [`Vfx::crossfade_effect`] ramps both effects with.
+    pub fn weighted(mut self, weight: f32) -> Self {
+        for spatial in &mut self.spatial_effects {
+            spatial.intensity *= weight;
+        }
+        for color in &mut self.color_effects {
+            if color.blend_mode != BlendMode::Gradient as u32 {
+                color.wave.amp *= weight;
+                color.wave.bias *= weight;
+            }
+            color.emissive *= weight;
+        }
+        for alpha in &mut self.alpha_effects {
+            alpha.wave.amp *= weight;
+            alpha.wave.bias *= weight;
+        }
+        for frag in &mut self.fragment_effects {
+            frag.wave.amp *= weight;
+            frag.wave.bias *= weight;
+        }
+        self
+    }
+
+    /// Fraction of the lifetime elapsed at `now`, from 0.0 to 1.0; the position within
+    /// the current period for looping effects. Matches the shaders' master lifetime.
+    ///
+    /// Effects queued to start later (e.g. sequence steps) are at 0.0.
+    pub fn progress(&self, now: f32) -> f32 {
+        let Lifetime {
+            looping,
+            start_time,
+            duration,
+            ..
+        } = self.lifetime;
+        let elapsed = now - start_time;
+        if duration <= 0.0 || elapsed < 0.0 {
+            return 0.0;
+        }
+        if looping == 1 {
+            (elapsed / duration).fract()
+        } else {
+            (elapsed / duration).min(1.0)
+        }
+    }
+
+    /// Value the shaders apply for spatial kind `kind` at `now`: the wave times intensity,
+    /// e.g. pixels for offsets, radians for rotation, or the amount added to a 1.0 scale.
+    ///
+    /// 0.0 while the effect or the sub-effect's phase isn't playing, `None` if the effect
+    /// has no sub-effect of that kind.
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_hirundo::prelude::*;
+    /// # struct Hitbox { center: Vec2 }
+    /// # let (now, base_x, vfx) = (0.0, 0.0, Vfx::default());
+    /// # let mut hitbox = Hitbox { center: Vec2::ZERO };
+    /// # let audio = VfxAudioDriver::default();
+    /// let lunge = vfx
+    ///     .effects()
+    ///     .iter_active()
+    ///     .filter_map(|eff| eff.spatial_value(SpatialKind::OffsetX, now, &audio.levels()));
+    /// hitbox.center.x = base_x + lunge.sum::<f32>();
+    /// ```
+    pub fn spatial_value(
+        &self,
+        kind: SpatialKind,
+        now: f32,
+        audio: &VfxAudioUniform,
+    ) -> Option<f32> {
+        self.spatial_values(now, audio)
+            .find(|(spatial, _)| spatial.manipulation == kind as u32)
+            .map(|(_, value)| value)
+    }
+
+    /// Every enabled spatial sub-effect with its [`Self::spatial_value`] at `now`
+    pub(crate) fn spatial_values(
+        &self,
+        now: f32,
+        audio: &VfxAudioUniform,
+    ) -> impl Iterator<Item = (&SpatialEffect, f32)> {
+        let t = self.drawn_progress(now);
+        self.spatial_effects
+            .iter()
+            .filter(|spatial| spatial.intensity != 0.0)
+            .map(move |spatial| {
+                let phase_t = t.map_or(0.0, |t| spatial.phase.evaluate(t));
+                if phase_t == 0.0 {
+                    return (spatial, 0.0);
+                }
+                (
+                    spatial,
+                    spatial.wave.evaluate(phase_t, audio) * spatial.intensity,
+                )
+            })
+    }
+
+    /// Where the shaders draw `point` (pixels from the center of a sprite `size` pixels
+    /// big) at `now`, after this effect's spatial sub-effects. Same math as `apply_spatial`.
+    pub fn spatial_point(
+        &self,
+        now: f32,
+        point: Vec2,
+        size: Vec2,
+        audio: &VfxAudioUniform,
+    ) -> Vec2 {
+        self.scaled_spatial_point(now, point, size, 1.0, audio)
+    }
+
+    /// [`Self::spatial_point`] with every amplitude scaled by `scale`, like the shaders
+    /// do with [`EffectStack::intensity`]
+    fn scaled_spatial_point(
+        &self,
+        now: f32,
+        point: Vec2,
+        size: Vec2,
+        scale: f32,
+        audio: &VfxAudioUniform,
+    ) -> Vec2 {
+        let Some(t) = self.drawn_progress(now) else {
+            return point;
+        };
+        let mut p = point;
+        for spatial in self
+            .spatial_effects
+            .iter()
+            .filter(|spatial| spatial.intensity != 0.0)
+        {
+            let phase_t = spatial.phase.evaluate(t);
+            if phase_t == 0.0 {
+                continue;
+            }
+            let wave = spatial.wave.evaluate(phase_t, audio);
+            let amount = spatial.intensity * scale;
+            let value = wave * amount;
+            let offset = (spatial.anchor - Vec2::splat(0.5)) * size;
+            p -= offset;
+            match spatial.manipulation {
+                0 => p.x += value,
+                1 => p.y += value,
+                2 => p.x *= 1.0 + value,
+                3 => p.y *= 1.0 + value,
+                4 => p = Vec2::from_angle(value).rotate(p),
+                5 => p.x += p.y * value,
+                6 => p.y += p.x * value,
+                7 => p.x *= 1.0 - 2.0 * value.clamp(0.0, 1.0),
+                8 => p.y *= 1.0 - 2.0 * value.clamp(0.0, 1.0),
+                9 => p += offset + amount * Vec2::from_angle(wave),
+                10 => {
+                    let arc = Vec2::new(
+                        spatial.params.x * wave,
+                        spatial.params.y * 4.0 * wave * (1.0 - wave),
+                    );
+                    p += amount * arc;
+                }
+                11 => {
+                    // Zero-height glyphs (spaces) don't bend
+                    let h = if size.y != 0.0 { p.y / size.y } else { 0.0 };
+                    p.x += value * h * h;
+                }
+                12 => {
+                    let count = spatial.params.x as usize;
+                    p += amount * self.path_point(count, spatial.params.y > 0.5, wave);
+                }
+                _ => {}
+            }
+            p += offset;
+        }
+        p
+    }
+
+    /// The shaders' `eval_path`: position along the control points at progress `u`
+    fn path_point(&self, count: usize, curved: bool, u: f32) -> Vec2 {
+        if count < 2 {
+            return Vec2::ZERO;
+        }
+        let point = |idx: usize| {
+            let packed = self.path_points[idx / 2];
+            if idx % 2 == 1 {
+                packed.zw()
+            } else {
+                packed.xy()
+            }
+        };
+        let s = u.clamp(0.0, 1.0) * (count - 1) as f32;
+        let i = (s as usize).min(count - 2);
+        let t = s - i as f32;
+        let (p1, p2) = (point(i), point(i + 1));
+        if !curved {
+            return p1.lerp(p2, t);
+        }
+        // Catmull-Rom, end points duplicated
+        let p0 = point(i.max(1) - 1);
+        let p3 = point((i + 2).min(count - 1));
+        let (t2, t3) = (t * t, t * t * t);
+        0.5 * (2.0 * p1
+            + (p2 - p0) * t
+            + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+            + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+    }
+
+    /// The shaders' master lifetime, `None` when they skip the effect altogether
+    pub(crate) fn drawn_progress(&self, now: f32) -> Option<f32> {
+        let Lifetime {
+            enabled,
+            looping,
+            start_time,
+            duration,
+        } = self.lifetime;
+        let elapsed = now - start_time;
+        if enabled == 0 || elapsed < 0.0 {
+            return None;
+        }
+        let t = if duration <= 0.0 {
+            0.0
+        } else if looping == 1 {
+            (elapsed / duration).fract()
+        } else if elapsed >= duration {
+            0.0
+        } else {
+            elapsed / duration
+        };
+        (t > 0.0 || looping == 1).then_some(t)
+    }
+
+    /// Seconds left at `now` before a one-shot effect expires, `None` for looping effects
+    pub fn remaining(&self, now: f32) -> Option<f32> {
+        let Lifetime {
+            start_time,
+            duration,
+            ..
+        } = self.lifetime;
+        (!self.is_looping()).then(|| (start_time + duration - now).clamp(0.0, duration))
+    }
+
+    /// Start time, duration and looping, as built
+    pub fn lifetime(&self) -> Lifetime {
+        self.lifetime
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.lifetime.looping == 1
+    }
+
+    /// Whether this slot holds an effect that hasn't expired or been cleared.
+    pub fn is_active(&self) -> bool {
+        self.lifetime.enabled == 1
+    }
+
+    /// Whether this is an active one-shot that has played to its end by `now`
+    pub fn has_expired(&self, now: f32) -> bool {
+        let t = self.lifetime;
+        t.enabled == 1 && t.looping == 0 && now >= t.start_time + t.duration
+    }
 }
 
-/// Stack of up to MAX_FX simultaneous effects.
+/// Stack of up to MAX_FX simultaneous effects (`fx_slots` of them in use).
 #[repr(C)]
-#[derive(Component, Clone, ShaderType, Debug, Default)]
+#[derive(Component, Clone, ShaderType, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
 pub struct EffectStack {
     pub tile_index: u32,
-    pub _pad0: u32,
-    pub _pad1: u32,
-    pub _pad2: u32,
+    /// Non-zero skips drawing the sprite, see [`Vfx::hide`](crate::components::Vfx::hide)
+    pub hidden: u32,
+    /// Slots [`Self::push`] fills, 0 for all `MAX_FX`. Set from
+    /// [`HirundoPlugin::with_max_fx`](crate::HirundoPlugin::with_max_fx)
+    pub fx_slots: u32,
+    /// Scale of every spatial amplitude and color strength, 1.0 for as built. Set from
+    /// [`VfxIntensity`](crate::components::VfxIntensity)
+    pub intensity: f32,
+    /// Atlas pixel rect (xy = top-left, zw = size) drawn instead of the sprite's, unused
+    /// while its size is 0. Set from [`SpriteRect`](crate::components::SpriteRect)
+    pub sprite_rect: Vec4,
+    /// Linear RGBA the texel is multiplied by before any effect, white for none. Set from
+    /// [`VfxTint`](crate::components::VfxTint)
+    pub base_tint: Vec4,
     pub effects: [Effect; MAX_FX],
 }
 
+impl Default for EffectStack {
+    fn default() -> Self {
+        Self {
+            tile_index: 0,
+            hidden: 0,
+            fx_slots: 0,
+            intensity: 1.0,
+            sprite_rect: Vec4::ZERO,
+            base_tint: Vec4::ONE,
+            effects: default(),
+        }
+    }
+}
+
 impl EffectStack {
     pub fn clear(&mut self) {
         for eff in &mut self.effects {
@@ -51,24 +388,112 @@ impl EffectStack {
         }
     }
 
-    /// Use a disabled slot or overwrite the oldest
-    pub fn push(&mut self, effect: Effect) {
-        for slot in &mut self.effects {
+    /// Effects that haven't expired or been cleared, in slot order
+    pub fn iter_active(&self) -> impl Iterator<Item = &Effect> {
+        self.effects.iter().filter(|eff| eff.is_active())
+    }
+
+    /// [`Effect::spatial_point`] through every effect in slot order, like the shaders
+    pub fn spatial_point(
+        &self,
+        now: f32,
+        point: Vec2,
+        size: Vec2,
+        audio: &VfxAudioUniform,
+    ) -> Vec2 {
+        self.effects.iter().fold(point, |p, eff| {
+            eff.scaled_spatial_point(now, p, size, self.intensity, audio)
+        })
+    }
+
+    /// [`Self::spatial_point`] as an affine map of points around the sprite's center,
+    /// pinned down by three points: exact for every kind but sway, which bends.
+    pub(crate) fn spatial_affine(&self, now: f32, size: Vec2, audio: &VfxAudioUniform) -> Affine2 {
+        let origin = self.spatial_point(now, Vec2::ZERO, size, audio);
+        Affine2::from_cols(
+            self.spatial_point(now, Vec2::X, size, audio) - origin,
+            self.spatial_point(now, Vec2::Y, size, audio) - origin,
+            origin,
+        )
+    }
+
+    /// Whether every slot in use holds an active effect, so the next push overwrites one
+    pub(crate) fn is_full(&self) -> bool {
+        self.effects[..self.slot_count()]
+            .iter()
+            .all(|eff| eff.is_active())
+    }
+
+    /// Number of slots currently holding an active effect
+    pub fn active_count(&self) -> usize {
+        self.iter_active().count()
+    }
+
+    /// Active effects still playing at `now`: [`Self::active_count`] without the one-shots
+    /// that ended but haven't been pruned yet
+    pub fn playing_count(&self, now: f32) -> usize {
+        self.iter_active()
+            .filter(|eff| !eff.has_expired(now))
+            .count()
+    }
+
+    /// Use a disabled slot or overwrite the oldest, returning the slot used.
+    /// Only the first `fx_slots` slots are used.
+    pub fn push(&mut self, effect: Effect) -> usize {
+        let slots = self.slot_count();
+        for (i, slot) in self.effects[..slots].iter_mut().enumerate() {
             if slot.lifetime.enabled == 0 {
                 *slot = effect;
-                return;
+                return i;
             }
         }
         self.effects[0] = effect;
+        0
+    }
+
+    /// Slots in use: `fx_slots`, or all `MAX_FX` when it's 0
+    fn slot_count(&self) -> usize {
+        match self.fx_slots as usize {
+            0 => MAX_FX,
+            slots => slots.min(MAX_FX),
+        }
+    }
+
+    /// Limit the stack to its first `slots` slots, re-pushing effects held beyond them
+    pub(crate) fn limit_slots(&mut self, slots: usize) {
+        self.fx_slots = slots as u32;
+        for i in self.slot_count()..MAX_FX {
+            if self.effects[i].is_active() {
+                let effect = self.effects[i];
+                self.effects[i].lifetime.enabled = 0;
+                self.push(effect);
+            }
+        }
     }
 
     /// Disable expired one-shot effects
     pub fn expire(&mut self, now: f32) {
         for eff in &mut self.effects {
-            let t = eff.lifetime;
-            if t.enabled == 1 && t.looping == 0 && now >= t.start_time + t.duration {
+            if eff.has_expired(now) {
                 eff.lifetime.enabled = 0;
             }
         }
     }
+
+    /// [`Self::expire`], yielding the slot and effect of each one disabled
+    pub fn take_expired(&mut self, now: f32) -> impl Iterator<Item = (usize, Effect)> + '_ {
+        self.effects
+            .iter_mut()
+            .enumerate()
+            .filter(move |(_, eff)| eff.has_expired(now))
+            .map(|(slot, eff)| {
+                eff.lifetime.enabled = 0;
+                (slot, *eff)
+            })
+    }
+
+    /// Whether [`Self::expire`] would disable anything at `now`
+    pub fn has_expired(&self, now: f32) -> bool {
+        self.effects.iter().any(|eff| eff.has_expired(now))
+    }
 }
@@ -0,0 +1,66 @@
+use crate::internal_prelude::*;
+
+/// Anything holding an [`EffectStack`] effects can be pushed onto: [`Vfx`], [`UiVfx`],
+/// [`TextVfx`], [`ScreenVfx`], [`BroadcastFx`] and the groups of [`VfxBroadcasts`].
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// fn flash<T: EffectTarget>(target: &mut T, now: f32) {
+///     target.push_effect(EffectBuilder::flash(now, LinearRgba::WHITE, 0.15).build());
+/// }
+/// # flash(&mut ScreenVfx::default(), 0.0);
+/// ```
+pub trait EffectTarget {
+    /// The stack pushed effects go to.
+    #[doc(hidden)]
+    fn stack(&self) -> &EffectStack;
+
+    #[doc(hidden)]
+    fn stack_mut(&mut self) -> &mut EffectStack;
+
+    fn push_effect(&mut self, effect: Effect) {
+        self.stack_mut().push(effect);
+    }
+
+    /// Push a prebuilt effect so it starts at `now`, whatever start time it was built with
+    fn push_effect_now(&mut self, effect: Effect, now: f32) {
+        self.push_effect(effect.retimed(now));
+    }
+
+    /// Push several effects at once, e.g. the steps of an [`EffectSequence`]
+    fn push_effects(&mut self, effects: impl IntoIterator<Item = Effect>) {
+        for effect in effects {
+            self.push_effect(effect);
+        }
+    }
+
+    /// Build and push the [`EffectTemplates`] entry `name`, starting at `now`.
+    ///
+    /// Warns and pushes nothing if no template is registered under `name`.
+    fn play(&mut self, templates: &EffectTemplates, name: &str, now: f32) {
+        match templates.build(name, now) {
+            Some(effect) => self.push_effect(effect),
+            None => warn!("No effect template named \"{}\"", name),
+        }
+    }
+
+    /// The effect stack, e.g. to look through [`EffectStack::iter_active`]
+    fn effects(&self) -> &EffectStack {
+        self.stack()
+    }
+
+    fn clear_effects(&mut self) {
+        self.stack_mut().clear();
+    }
+}
+
+/// A bare stack, e.g. a [`VfxBroadcasts`] group
+impl EffectTarget for EffectStack {
+    fn stack(&self) -> &EffectStack {
+        self
+    }
+
+    fn stack_mut(&mut self) -> &mut EffectStack {
+        self
+    }
+}
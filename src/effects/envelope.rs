@@ -3,7 +3,7 @@ use super::builder::{EffectBuilder, EffectModifier, LastEffect};
 
 /// Growth mode for envelope amplitude modulation
 #[repr(u32)]
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub enum GrowthMode {
     #[default]
     None = 0, // No growth applied (passthrough)
@@ -19,12 +19,17 @@ pub enum GrowthMode {
 /// attack + hold + release must sum to 1.0 and are fractions of [`Phase`]
 ///
 /// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let time = Time::<()>::default();
 /// EffectBuilder::one_shot(time.elapsed_secs(), 1.0)
 /// .skew_x(0.4) // 0.4 is target amplitude
 /// .with(Envelope::amplitude(0.2, 0.0, 0.8)) // 0 to target in 0.2 seconds, target to 0 in 0.8
+/// # ;
 /// ```
 #[repr(C)]
-#[derive(Clone, Copy, Debug, ShaderType, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, ShaderType, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default = "Envelope::asset_default")]
 pub struct Envelope {
     /// Rise time as fraction of phase (0.0 to 1.0)
     attack: f32,
@@ -33,12 +38,20 @@ pub struct Envelope {
     /// Fall time as fraction of phase (0.0 to 1.0)
     release: f32,
     /// Growth mode for attack/attack (0=none, 1=exponential)
+    #[serde(
+        serialize_with = "serialize_kind::<GrowthMode, _>",
+        deserialize_with = "deserialize_kind::<GrowthMode, _>"
+    )]
     growth_mode: u32,
     /// Growth factor/strength for attack/attack
     growth: f32,
     /// Enable flag: 0=disabled (passthrough), 1=enabled
     enabled: u32,
     /// Decay mode for release/release (0=none, 1=exponential)
+    #[serde(
+        serialize_with = "serialize_kind::<GrowthMode, _>",
+        deserialize_with = "deserialize_kind::<GrowthMode, _>"
+    )]
     decay_mode: u32,
     /// Decay factor/strength for release/release
     decay: f32,
@@ -72,6 +85,12 @@ impl Envelope {
         }
     }
 
+    /// Baseline for fields left out of an effect asset: an envelope written in an
+    /// asset is enabled, with its timings starting from zero.
+    fn asset_default() -> Self {
+        Self::new(0.0, 0.0, 0.0)
+    }
+
     /// Disabled envelope - passthrough (no envelope applied)
     pub(crate) fn disabled() -> Self {
         Self {
@@ -86,6 +105,106 @@ impl Envelope {
         }
     }
 
+    /// Attack, hold and release, or `None` when the envelope is disabled
+    pub(crate) fn timings(&self) -> Option<[f32; 3]> {
+        (self.enabled != 0).then_some([self.attack, self.hold, self.release])
+    }
+
+    /// Envelope level (0.0 - 1.0) at phase progress `t`, 1.0 when disabled.
+    ///
+    /// Same as the shaders' `eval_envelope`.
+    pub fn evaluate(&self, t: f32) -> f32 {
+        self.evaluate_integral(t).x
+    }
+
+    /// Level (x) and normalized area under the envelope so far (y) at phase progress `t`,
+    /// mirroring the shaders' `eval_envelope_integral`. The area drives frequency envelopes.
+    pub(crate) fn evaluate_integral(&self, t: f32) -> Vec2 {
+        const EPSILON: f32 = 1e-5;
+        if self.enabled == 0 {
+            return Vec2::new(1.0, t);
+        }
+
+        let (attack, hold, release) = (self.attack, self.hold, self.release);
+        let total = attack + hold + release;
+        if total <= 0.0 {
+            return Vec2::new(1.0, t);
+        }
+
+        let nt = t.clamp(0.0, 1.0) * total;
+        let growth =
+            self.growth_mode == GrowthMode::Exponential as u32 && self.growth.abs() > EPSILON;
+        let decay = self.decay_mode == GrowthMode::Exponential as u32 && self.decay.abs() > EPSILON;
+
+        let level = if nt <= attack {
+            let phase_t = if attack > 0.0 { nt / attack } else { 0.0 };
+            if growth {
+                let s = self.growth;
+                ((phase_t * s).exp() - 1.0) / (s.exp() - 1.0)
+            } else {
+                phase_t
+            }
+        } else if nt <= attack + hold {
+            1.0
+        } else {
+            let s = nt - attack - hold;
+            let phase_t = if release > 0.0 { s / release } else { 0.0 };
+            if decay {
+                let d = self.decay;
+                1.0 - ((phase_t * d).exp() - 1.0) / (d.exp() - 1.0)
+            } else {
+                1.0 - phase_t
+            }
+        };
+
+        // Area of the attack ramp, curved by exponential growth
+        let attack_area = if growth {
+            let s = self.growth;
+            attack * ((s.exp() - 1.0) - s) / (s * (s.exp() - 1.0))
+        } else {
+            attack * 0.5
+        };
+
+        let integral = if nt <= attack {
+            if growth && attack > 0.0 {
+                let s = self.growth;
+                let x = s * nt / attack;
+                let area = attack * ((x.exp() - 1.0) - x) / (s * (s.exp() - 1.0));
+                area / (attack_area + hold + release * 0.5).max(EPSILON)
+            } else {
+                let area = if attack > 0.0 {
+                    nt * nt / (2.0 * attack)
+                } else {
+                    0.0
+                };
+                area / (attack * 0.5 + hold + release * 0.5).max(EPSILON)
+            }
+        } else if nt <= attack + hold {
+            (attack_area + nt - attack) / (attack_area + hold + release * 0.5).max(EPSILON)
+        } else if release > 0.0 {
+            let release_t = nt - attack - hold;
+            let (release_area, full_release) = if decay {
+                let d = self.decay;
+                let x = d * release_t / release;
+                let denom = d * (d.exp() - 1.0);
+                (
+                    release * (release_t / release - ((x.exp() - 1.0) - x) / denom),
+                    release * (1.0 - ((d.exp() - 1.0) - d) / denom),
+                )
+            } else {
+                (
+                    release_t - release_t * release_t / (2.0 * release),
+                    release * 0.5,
+                )
+            };
+            (attack_area + hold + release_area) / (attack_area + hold + full_release).max(EPSILON)
+        } else {
+            1.0
+        };
+
+        Vec2::new(level.clamp(0.0, 1.0), integral.clamp(0.0, 1.0))
+    }
+
     // === self Modifiers ===
 
     /// Exponentially curve the attack. (The attack starts slower but quickly accelerates)
@@ -103,15 +222,6 @@ impl Envelope {
     }
 }
 
-/// For future [`EffectBuilder`]/[`EffectModifier`] helpers i.e. *FadeIn*
-#[derive(Clone, Copy, Debug, Default)]
-struct EnvelopeIntent {
-    attack: Option<f32>,
-    hold: Option<f32>,
-    release: Option<f32>,
-    explicit: Option<Envelope>,
-}
-
 /// Newtype wrapper for [`Envelope`], explicitly targets the Wave's amplitude envelope.
 #[repr(transparent)]
 #[derive(Clone, Copy, Debug, From)]
@@ -140,12 +250,15 @@ impl EffectModifier for AmplitudeEnvelope {
             Some(LastEffect::Color(idx)) => {
                 builder.colors[idx].as_mut().unwrap().wave.amp_envelope = self.0;
             }
-            Some(LastEffect::Alpha) => {
-                builder.alpha.as_mut().unwrap().wave.amp_envelope = self.0;
+            Some(LastEffect::Alpha(idx)) => {
+                builder.alphas[idx].as_mut().unwrap().wave.amp_envelope = self.0;
             }
             Some(LastEffect::Spatial(kind)) => {
                 builder.spatial[kind].as_mut().unwrap().wave.amp_envelope = self.0;
             }
+            Some(LastEffect::Fragment(kind)) => {
+                builder.fragment[kind].as_mut().unwrap().wave.amp_envelope = self.0;
+            }
             None => {
                 warn!(
                     "Cannot apply AmplitudeEnvelope: No previous color or spatial effect to modify."
@@ -183,12 +296,15 @@ impl EffectModifier for FrequencyEnvelope {
             Some(LastEffect::Color(idx)) => {
                 builder.colors[idx].as_mut().unwrap().wave.freq_envelope = self.0;
             }
-            Some(LastEffect::Alpha) => {
-                builder.alpha.as_mut().unwrap().wave.freq_envelope = self.0;
+            Some(LastEffect::Alpha(idx)) => {
+                builder.alphas[idx].as_mut().unwrap().wave.freq_envelope = self.0;
             }
             Some(LastEffect::Spatial(kind)) => {
                 builder.spatial[kind].as_mut().unwrap().wave.freq_envelope = self.0;
             }
+            Some(LastEffect::Fragment(kind)) => {
+                builder.fragment[kind].as_mut().unwrap().wave.freq_envelope = self.0;
+            }
             None => {
                 warn!("Cannot apply FreqEnvelope: No previous color or spatial effect to modify.")
             }
@@ -0,0 +1,164 @@
+use crate::internal_prelude::*;
+use super::color::BlendMode;
+use super::phase::Phase;
+use super::wave::Wave;
+
+/// Fragment (per-pixel) effect types.
+///
+/// `0` is reserved for an empty slot, so kinds start at `1`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Enum, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FragmentKind {
+    Outline = 1,  // Colored border around opaque texels
+    Dissolve = 2, // Noise-driven erosion with a glowing edge
+    Pixelate = 3, // Snap UVs to a coarser texel grid
+    Glitch = 4,   // Random row displacement + RGB split
+    Overlay = 5,  // Composite the material's overlay texture
+    Hologram = 6, // Tinted scanlines with banded, flickering alpha
+}
+
+/// Per-pixel effect with wave-driven strength.
+///
+/// The wave is evaluated once per sprite in the vertex stage (like every other
+/// sub-effect) and its clamped value is handed to the fragment stage as the effect's
+/// strength. What `color` and `params` mean depends on the kind.
+///
+/// # Kinds
+/// - **Outline**: `color` = outline color, `params.x` = thickness in texels.
+///   The wave drives the outline's alpha.
+/// - **Dissolve**: `color` = edge color (alpha 0 = no edge), `params.x` = edge width,
+///   `params.y` = noise tiling per sprite. The wave is the erosion threshold
+///   (0.0 = intact, 1.0 = gone), compared against the material's noise texture.
+/// - **Pixelate**: `params.x` = largest block size in texels. The wave scales the block
+///   size from 1 texel (0.0) up to `params.x` (1.0).
+/// - **Glitch**: `params.x` = number of horizontal slices, `params.y` = max slice
+///   displacement as a fraction of sprite width, `params.z` = RGB split in texels
+///   (0.0 = none). Slices are reshuffled ~20 times a second; the wave gates the strength.
+/// - **Overlay**: `color` = tint multiplied with the overlay texture, `params.x` =
+///   [`BlendMode`] (Lerp, Add, Multiply or Screen), `params.yz` = UV scroll per second.
+///   The wave drives the overlay's opacity; it only covers the sprite's opaque texels.
+/// - **Hologram**: `color` = tint (alpha = how much of the original color is replaced),
+///   `params.x` = scanlines per sprite, `params.y` = banding depth (0.0 = no transparency
+///   banding, 1.0 = fully transparent gaps), `params.z` = scanline scroll in lines per
+///   second. The wave is the hologram's opacity, so a fast square wave makes it flicker.
+///
+/// Fragment effects can only draw inside the sprite's quad, so an outline needs
+/// transparent texels around the sprite's silhouette to show up.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, ShaderType, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default = "FragmentEffect::asset_default")]
+pub struct FragmentEffect {
+    pub phase: Phase,
+    pub wave: Wave,
+    /// Fragment kind (see FragmentKind enum), 0 = disabled
+    #[serde(
+        serialize_with = "serialize_kind::<FragmentKind, _>",
+        deserialize_with = "deserialize_kind::<FragmentKind, _>"
+    )]
+    pub kind: u32,
+    #[serde(skip)]
+    pub _pad0: u32,
+    #[serde(skip)]
+    pub _pad1: u32,
+    #[serde(skip)]
+    pub _pad2: u32,
+    /// Kind-specific color
+    pub color: Vec4,
+    /// Kind-specific parameters, `w` is reserved for the evaluated wave
+    pub params: Vec4,
+}
+
+impl FragmentEffect {
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Baseline for fields left out of an effect asset: fully applied
+    fn asset_default() -> Self {
+        Self {
+            wave: Wave::constant(1.0),
+            ..default()
+        }
+    }
+
+    /// Outline of `thickness` texels, fully shown by default.
+    pub fn outline(color: Vec4, thickness: f32) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave: Wave::constant(1.0),
+            kind: FragmentKind::Outline as u32,
+            color,
+            params: Vec4::new(thickness, 0.0, 0.0, 0.0),
+            ..default()
+        }
+    }
+
+    /// Dissolve that erodes the sprite once over the phase, leaving an
+    /// `edge_width` band of `edge_color` along the boundary.
+    pub fn dissolve(edge_color: Vec4, edge_width: f32) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave: Wave::saw(1.0, 0.5, 0.5),
+            kind: FragmentKind::Dissolve as u32,
+            color: edge_color,
+            params: Vec4::new(edge_width, 1.0, 0.0, 0.0),
+            ..default()
+        }
+    }
+
+    /// Pixelate up to `block_size` texels per block, fully applied by default.
+    pub fn pixelate(block_size: f32) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave: Wave::constant(1.0),
+            kind: FragmentKind::Pixelate as u32,
+            params: Vec4::new(block_size, 0.0, 0.0, 0.0),
+            ..default()
+        }
+    }
+
+    /// Glitch strobing with a square wave: `slices` rows displaced by up to
+    /// `displacement` sprite widths, red/blue split by `rgb_split` texels.
+    pub fn glitch(slices: f32, displacement: f32, rgb_split: f32) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave: Wave::square(8.0, 0.5, 0.5),
+            kind: FragmentKind::Glitch as u32,
+            params: Vec4::new(slices, displacement, rgb_split, 0.0),
+            ..default()
+        }
+    }
+
+    /// Overlay of the material's overlay texture, tinted and blended with `blend_mode`.
+    pub fn overlay(tint: Vec4, blend_mode: BlendMode) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave: Wave::constant(1.0),
+            kind: FragmentKind::Overlay as u32,
+            color: tint,
+            params: Vec4::new(blend_mode as u32 as f32, 0.0, 0.0, 0.0),
+            ..default()
+        }
+    }
+
+    /// Hologram tinted `tint` with `scanlines` rows of `banding` depth, flickering
+    /// between 0.76 and 1.0 opacity.
+    pub fn hologram(tint: Vec4, scanlines: f32, banding: f32) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave: Wave::square(14.0, 0.12, 0.88),
+            kind: FragmentKind::Hologram as u32,
+            color: tint,
+            params: Vec4::new(scanlines, banding, 4.0, 0.0),
+            ..default()
+        }
+    }
+
+    /// Control when this effect occurs, relative to *Lifetime*
+    ///
+    /// start and end are fractions of *Lifetime*)
+    pub fn with_phase(&mut self, start: f32, end: f32) -> Self {
+        self.phase = Phase::new(start, end);
+        *self
+    }
+}
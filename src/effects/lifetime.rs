@@ -2,7 +2,7 @@ use crate::internal_prelude::*;
 
 /// Controls when the effect is active and its overall duration.
 #[repr(C)]
-#[derive(Clone, Copy, Default, ShaderType, Debug)]
+#[derive(Clone, Copy, Default, ShaderType, Debug, PartialEq, Serialize, Deserialize, Reflect)]
 pub struct Lifetime {
     /// 0 = disabled, 1 = enabled
     pub enabled: u32,
@@ -4,10 +4,18 @@ mod phase;
 mod color;
 mod alpha;
 mod spatial;
+mod fragment;
 mod wave;
 mod envelope;
+mod tile_cycle;
+mod tint;
+mod raw_kind;
 mod effect_stack;
+mod effect_target;
 mod builder;
+mod sequence;
+mod validation;
+pub mod presets;
 
 // Re-export all public types
 pub use lifetime::*;
@@ -15,7 +23,14 @@ pub use phase::*;
 pub use color::*;
 pub use alpha::*;
 pub use spatial::*;
+pub use fragment::*;
 pub use wave::*;
 pub use envelope::*;
+pub use tile_cycle::*;
+pub(crate) use tint::*;
+pub(crate) use raw_kind::*;
 pub use effect_stack::*;
+pub use effect_target::*;
 pub use builder::*;
+pub use sequence::*;
+pub use validation::*;
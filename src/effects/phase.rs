@@ -7,19 +7,26 @@ use super::builder::{EffectBuilder, EffectModifier, LastEffect};
 ///
 /// # Example
 /// ```
-/// EffectBuilder::one_shot(now, 1.0) // effect lifetime is 2 seconds
-/// .offset_x(10)
+/// # use bevy_hirundo::prelude::*;
+/// # let now = 0.0;
+/// EffectBuilder::one_shot(now, 2.0) // effect lifetime is 2 seconds
+/// .offset_x(10.0)
 /// .with(Phase::new(0.2, 0.8)) // offset_x starts at 0.4 secs (20% of 2 seconds), ends at 1.6 secs.
+/// # ;
 /// ```
 #[repr(C)]
-#[derive(Clone, Copy, ShaderType, Debug, PartialEq)]
+#[derive(Clone, Copy, ShaderType, Debug, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default)]
 pub struct Phase {
     /// Start time as fraction of master duration (0.0 to 1.0)
     pub start: f32,
     /// End time as fraction of master duration (0.0 to 1.0)
     pub end: f32,
+    /// 1 = played backwards through the window, see [`Reverse`]
+    pub reversed: u32,
     /// Padding to ensure 16-byte alignment
-    _padding: Vec2,
+    #[serde(skip)]
+    _padding: f32,
 }
 
 impl Default for Phase {
@@ -33,28 +40,32 @@ impl Phase {
         Self {
             start,
             end,
-            _padding: Vec2::ZERO,
+            reversed: 0,
+            _padding: 0.0,
         }
     }
     pub fn full() -> Self {
         Self {
             start: 0.0,
             end: 1.0,
-            _padding: Vec2::ZERO,
+            reversed: 0,
+            _padding: 0.0,
         }
     }
     pub fn first_half() -> Self {
         Self {
             start: 0.0,
             end: 0.5,
-            _padding: Vec2::ZERO,
+            reversed: 0,
+            _padding: 0.0,
         }
     }
     pub fn second_half() -> Self {
         Self {
             start: 0.5,
             end: 1.0,
-            _padding: Vec2::ZERO,
+            reversed: 0,
+            _padding: 0.0,
         }
     }
 
@@ -62,7 +73,8 @@ impl Phase {
         Self {
             start: time,
             end: 1.0,
-            _padding: Vec2::ZERO,
+            reversed: 0,
+            _padding: 0.0,
         }
     }
 
@@ -70,20 +82,91 @@ impl Phase {
         Self {
             start: 0.0,
             end: time,
-            _padding: Vec2::ZERO,
+            reversed: 0,
+            _padding: 0.0,
         }
     }
+
+    /// Progress through this phase (0.0 - 1.0) at effect progress `t`, 0.0 outside it.
+    /// Runs from 1.0 down when reversed (see [`Reverse`]).
+    ///
+    /// Same as the shaders' `phase_lifetime`.
+    pub fn evaluate(&self, t: f32) -> f32 {
+        let start = self.start.clamp(0.0, 1.0);
+        let end = self.end.clamp(0.0, 1.0);
+        if start >= end || t < start || t > end {
+            return 0.0;
+        }
+        let progress = (t - start) / (end - start);
+        if self.is_reversed() {
+            1.0 - progress
+        } else {
+            progress
+        }
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        self.reversed != 0
+    }
+
+    /// Where this phase sits once the whole effect plays backwards: the window seen
+    /// from the end of the effect, run the other way
+    pub fn mirrored(self) -> Self {
+        Self {
+            start: 1.0 - self.end,
+            end: 1.0 - self.start,
+            reversed: 1 - self.reversed.min(1),
+            _padding: 0.0,
+        }
+    }
+}
+
+/// The phase of the builder's most recent sub-effect
+fn last_phase(builder: &mut EffectBuilder) -> Option<&mut Phase> {
+    match builder.last_effect? {
+        LastEffect::Color(idx) => builder.colors[idx].as_mut().map(|color| &mut color.phase),
+        LastEffect::Alpha(idx) => builder.alphas[idx].as_mut().map(|alpha| &mut alpha.phase),
+        LastEffect::Spatial(kind) => builder.spatial[kind]
+            .as_mut()
+            .map(|spatial| &mut spatial.phase),
+        LastEffect::Fragment(kind) => builder.fragment[kind].as_mut().map(|frag| &mut frag.phase),
+    }
 }
 
 impl EffectModifier for Phase {
     fn apply(&self, builder: &mut EffectBuilder) {
-        match builder.last_effect {
-            Some(LastEffect::Color(idx)) => builder.colors[idx].as_mut().unwrap().phase = *self,
-            Some(LastEffect::Alpha) => builder.alpha.as_mut().unwrap().phase = *self,
-            Some(LastEffect::Spatial(kind)) => {
-                builder.spatial[kind].as_mut().unwrap().phase = *self
+        match last_phase(builder) {
+            // Keeps a `Reverse` applied before the window
+            Some(phase) => {
+                *phase = Phase {
+                    reversed: phase.reversed,
+                    ..*self
+                }
             }
             None => warn!("No previous sub-effect to modify."),
         }
     }
 }
+
+/// Plays the most recent sub-effect backwards through its [`Phase`]: waves, envelopes,
+/// arcs and paths run from their end to their start. Applying it twice plays forwards.
+///
+/// [`Effect::reversed`](super::Effect::reversed) reverses a whole effect instead, e.g. a
+/// spawn pop as a despawn.
+/// ```rust
+/// # use bevy_hirundo::prelude::*;
+/// # let now = 0.0;
+/// // Swells up slowly and snaps back to size: `pop`'s scale, backwards
+/// EffectBuilder::pop(now, 0.5, 0.4).with(Reverse).build();
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Reverse;
+
+impl EffectModifier for Reverse {
+    fn apply(&self, builder: &mut EffectBuilder) {
+        match last_phase(builder) {
+            Some(phase) => phase.reversed = 1 - phase.reversed.min(1),
+            None => warn!("No previous sub-effect to modify."),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Ready-made [`EffectBuilder`] starting points for common effects.
+//!
+//! Presets return a builder rather than a finished [`Effect`](super::Effect), so the
+//! result can still be chained and tweaked before calling `build()`.
+//!
+//! [`status`] holds looping stacks for status effects (poison, burn, freeze, stun).
+use super::builder::EffectBuilder;
+use super::color::BlendMode;
+use super::envelope::Envelope;
+use super::phase::Phase;
+use super::spatial::Anchor;
+use super::wave::Wave;
+use crate::internal_prelude::*;
+
+pub mod status;
+
+impl EffectBuilder {
+    /// The canonical hit flash: an additive `color` that snaps to full strength
+    /// and eases back out over `duration` seconds.
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, mut vfx) = (0.0, Vfx::default());
+    /// vfx.push_effect(EffectBuilder::flash(now, LinearRgba::WHITE, 0.15).build());
+    /// ```
+    pub fn flash(now: f32, color: impl ColorToComponents, duration: f32) -> Self {
+        Self::one_shot(now, duration)
+            .color(color)
+            .with(BlendMode::Add)
+            .with(Envelope::amplitude(0.05, 0.0, 0.95).with_ease_out(4.0))
+    }
+
+    /// Camera-style shake of up to `magnitude` pixels that decays exponentially over
+    /// `duration` seconds.
+    ///
+    /// Offsets X and Y follow uncorrelated noise waves, so this uses two spatial slots.
+    ///
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, mut vfx) = (0.0, Vfx::default());
+    /// vfx.push_effect(EffectBuilder::shake(now, 4.0, 0.3).build());
+    /// ```
+    pub fn shake(now: f32, magnitude: f32, duration: f32) -> Self {
+        Self::one_shot(now, duration).with_shake(magnitude)
+    }
+
+    /// Impact: a [`flash`](Self::flash) of `color` and a [`shake`](Self::shake) of up to
+    /// `magnitude` pixels in one effect, both fading over `duration` seconds. Hits, landings,
+    /// collisions (see `physics::CollisionVfx`).
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, mut vfx) = (0.0, Vfx::default());
+    /// vfx.push_effect(EffectBuilder::impact(now, LinearRgba::WHITE, 3.0, 0.2).build());
+    /// ```
+    pub fn impact(now: f32, color: impl ColorToComponents, magnitude: f32, duration: f32) -> Self {
+        Self::flash(now, color, duration).with_shake(magnitude)
+    }
+
+    /// Noise offsets of up to `magnitude` pixels on both axes, decaying over the effect
+    fn with_shake(self, magnitude: f32) -> Self {
+        let decay = Envelope::amplitude(0.0, 0.0, 1.0).with_ease_out(4.0);
+        self.offset_x(magnitude)
+            .with(Wave::noise(30.0, magnitude, 0.0))
+            .with(decay)
+            .offset_y(magnitude)
+            .with(Wave::noise(30.0, magnitude, 0.0).with_phase(0.5))
+            .with(decay)
+    }
+
+    /// Squash-and-stretch: the sprite flattens by `amount` (0.3 = 70% height) while
+    /// widening by the same amount, then springs back over `duration` seconds.
+    /// Negative `amount` stretches instead. Both axes scale around `anchor`,
+    /// usually [`Anchor::BottomCenter`] so the sprite stays on the ground.
+    ///
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, mut vfx) = (0.0, Vfx::default());
+    /// vfx.push_effect(EffectBuilder::squash(now, 0.3, 0.5, Anchor::BottomCenter).build());
+    /// ```
+    pub fn squash(now: f32, amount: f32, duration: f32, anchor: Anchor) -> Self {
+        let half = amount * 0.5;
+        Self::one_shot(now, duration)
+            .scale_y(-amount)
+            .with(Wave::sine(1.0, half, -half))
+            .with(anchor)
+            .scale_x(amount)
+            .with(Wave::sine(1.0, -half, half))
+            .with(anchor)
+    }
+
+    /// Pop: grows by `amount` (0.5 = 150%) almost at once, then eases back to its own
+    /// size over `duration` seconds. Damage numbers, pickups, counters ticking up.
+    ///
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, mut text_vfx) = (0.0, TextVfx::default());
+    /// text_vfx.push_effect(EffectBuilder::pop(now, 0.5, 0.4).build());
+    /// ```
+    pub fn pop(now: f32, amount: f32, duration: f32) -> Self {
+        Self::one_shot(now, duration)
+            .scale_uniform(amount)
+            .with(Envelope::amplitude(0.1, 0.0, 0.9).with_ease_out(3.0))
+    }
+
+    /// Damage number popup: rises `rise` pixels, slowing as it goes, pops up a little at
+    /// the start and fades out over the last 40% of `duration` seconds.
+    /// What [`DamageNumberCommands::spawn_damage_number`](crate::commands::DamageNumberCommands::spawn_damage_number)
+    /// plays on every digit.
+    ///
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, mut vfx) = (0.0, Vfx::default());
+    /// vfx.push_effect(EffectBuilder::damage_popup(now, 24.0, 0.8).build());
+    /// ```
+    pub fn damage_popup(now: f32, rise: f32, duration: f32) -> Self {
+        Self::one_shot(now, duration)
+            .offset_y(rise)
+            .with(Envelope::amplitude(1.0, 0.0, 0.0).with_ease_in(-4.0))
+            .scale_uniform(0.3)
+            .with(Phase::end(0.3))
+            .with(Envelope::amplitude(0.1, 0.0, 0.9).with_ease_out(3.0))
+            .alpha_zero()
+            .with(Phase::start(0.6))
+            .with(Envelope::amplitude(1.0, 0.0, 0.0))
+    }
+
+    /// Red and blue split `pixels` apart, easing back together over `duration` seconds.
+    /// Mostly for [`ScreenVfx`](crate::resources::ScreenVfx) hits and explosions.
+    ///
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, mut screen) = (0.0, ScreenVfx::default());
+    /// screen.push_effect(EffectBuilder::chromatic_aberration(now, 8.0, 0.3).build());
+    /// ```
+    pub fn chromatic_aberration(now: f32, pixels: f32, duration: f32) -> Self {
+        Self::one_shot(now, duration)
+            .glitch(1.0, 0.0, pixels)
+            .with(Wave::constant(1.0))
+            .with(Envelope::amplitude(0.0, 0.0, 1.0))
+    }
+
+    /// Linear fade from invisible to the sprite's own alpha over `duration` seconds.
+    ///
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, mut vfx) = (0.0, Vfx::default());
+    /// vfx.push_effect(EffectBuilder::fade_in(now, 0.5).build());
+    /// ```
+    pub fn fade_in(now: f32, duration: f32) -> Self {
+        Self::one_shot(now, duration)
+            .alpha_zero()
+            .with(Envelope::amplitude(0.0, 0.0, 1.0))
+    }
+
+    /// Linear fade from the sprite's own alpha to invisible over `duration` seconds.
+    ///
+    /// The effect expires once the fade completes and the sprite shows again, so hide
+    /// or despawn the entity at that point if it should stay gone.
+    ///
+    /// ```rust
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, mut vfx) = (0.0, Vfx::default());
+    /// vfx.push_effect(EffectBuilder::fade_out(now, 0.5).build());
+    /// ```
+    pub fn fade_out(now: f32, duration: f32) -> Self {
+        Self::one_shot(now, duration)
+            .alpha_zero()
+            .with(Envelope::amplitude(1.0, 0.0, 0.0))
+    }
+}
@@ -0,0 +1,52 @@
+//! Looping status-effect stacks: push one when the status is applied and stop it with
+//! [`Vfx::remove_effect`](crate::components::Vfx::remove_effect) when it wears off.
+//!
+//! Each loop repeats every second, so wave frequencies read as Hz. Like the other presets
+//! they return a builder, to recolor or retime before calling `build()`.
+//! ```rust
+//! # use bevy_hirundo::prelude::*;
+//! use bevy_hirundo::effects::presets::status;
+//! # let (now, mut vfx) = (0.0, Vfx::default());
+//!
+//! let poisoned = vfx.push_effect(status::poison(now).build());
+//! // cured
+//! vfx.remove_effect(poisoned);
+//! ```
+use crate::effects::{Anchor, BlendMode, CompositeMode, EffectBuilder, Wave};
+use crate::internal_prelude::*;
+
+/// Poison: a green tint pulsing in and out once a second.
+pub fn poison(now: f32) -> EffectBuilder {
+    EffectBuilder::looping(now, 1.0)
+        .color(LinearRgba::rgb(0.25, 0.9, 0.1))
+        .with(Wave::sine(1.0, 0.2, 0.25))
+}
+
+/// Burn: a flickering orange glow, with the sprite wobbling from its base like
+/// air over a fire.
+pub fn burn(now: f32) -> EffectBuilder {
+    EffectBuilder::looping(now, 1.0)
+        .color(LinearRgba::rgb(1.0, 0.45, 0.05))
+        .with(BlendMode::Add)
+        .with(Wave::noise(10.0, 0.15, 0.3))
+        .skew_x(0.04)
+        .with(Wave::sine(3.0, 0.04, 0.0))
+        .with(Anchor::BottomCenter)
+}
+
+/// Freeze: the sprite's colors drained, then tinted icy blue.
+pub fn freeze(now: f32) -> EffectBuilder {
+    EffectBuilder::looping(now, 1.0)
+        .color(LinearRgba::new(0.0, -0.8, 0.0, 1.0))
+        .with(BlendMode::Hsv)
+        .with(CompositeMode::Multiplicative)
+        .color(LinearRgba::rgb(0.6, 0.8, 1.0))
+        .with(BlendMode::Multiply)
+        .with(CompositeMode::Multiplicative)
+}
+
+/// Stun: the sprite circles `radius` pixels around its spot once a second, dazed.
+/// On a star sprite, an [`Anchor`] below it moves the circle's center onto the head.
+pub fn stun(now: f32, radius: f32) -> EffectBuilder {
+    EffectBuilder::looping(now, 1.0).orbit(radius, 1.0)
+}
@@ -0,0 +1,144 @@
+use serde::de::{DeserializeOwned, Deserializer};
+use serde::ser::{Error as _, Serializer};
+
+use crate::internal_prelude::*;
+
+/// A kind the GPU structs hold as a `u32`, written by name in effect assets
+/// (`blend_mode: Add` rather than `blend_mode: 1`).
+pub(crate) trait RawKind: Copy + Serialize + DeserializeOwned {
+    /// The kind `raw` holds, `None` when it holds none
+    fn from_raw(raw: u32) -> Option<Self>;
+    fn into_raw(self) -> u32;
+}
+
+/// `serialize_with` for a raw `K` field, failing on values that aren't a `K`
+pub(crate) fn serialize_kind<K: RawKind, S: Serializer>(
+    raw: &u32,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let Some(kind) = K::from_raw(*raw) else {
+        let name = std::any::type_name::<K>()
+            .rsplit("::")
+            .next()
+            .unwrap_or_default();
+        return Err(S::Error::custom(format!("{raw} is not a {name}")));
+    };
+    kind.serialize(serializer)
+}
+
+/// `deserialize_with` for a raw `K` field, rejecting unknown names
+pub(crate) fn deserialize_kind<'de, K: RawKind, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u32, D::Error> {
+    K::deserialize(deserializer).map(K::into_raw)
+}
+
+impl RawKind for BlendMode {
+    fn from_raw(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(Self::Lerp),
+            1 => Some(Self::Add),
+            2 => Some(Self::Multiply),
+            3 => Some(Self::Screen),
+            4 => Some(Self::Hsv),
+            5 => Some(Self::Gradient),
+            _ => None,
+        }
+    }
+
+    fn into_raw(self) -> u32 {
+        self as u32
+    }
+}
+
+impl RawKind for AlphaComposite {
+    fn from_raw(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(Self::Lerp),
+            1 => Some(Self::Multiply),
+            _ => None,
+        }
+    }
+
+    fn into_raw(self) -> u32 {
+        self as u32
+    }
+}
+
+impl RawKind for GrowthMode {
+    fn from_raw(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(Self::None),
+            1 => Some(Self::Exponential),
+            _ => None,
+        }
+    }
+
+    fn into_raw(self) -> u32 {
+        self as u32
+    }
+}
+
+impl RawKind for AnimationMode {
+    fn from_raw(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(Self::Loop),
+            1 => Some(Self::Once),
+            2 => Some(Self::PingPong),
+            _ => None,
+        }
+    }
+
+    fn into_raw(self) -> u32 {
+        match self {
+            Self::Loop => 0,
+            Self::Once => 1,
+            Self::PingPong => 2,
+        }
+    }
+}
+
+impl RawKind for WaveKind {
+    fn from_raw(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(Self::Sine),
+            1 => Some(Self::Square),
+            2 => Some(Self::Triangle),
+            3 => Some(Self::Saw),
+            4 => Some(Self::Constant),
+            5 => Some(Self::Noise),
+            6 => Some(Self::Audio),
+            7 => Some(Self::Beat),
+            _ => None,
+        }
+    }
+
+    fn into_raw(self) -> u32 {
+        self as u32
+    }
+}
+
+impl RawKind for SpatialKind {
+    fn from_raw(raw: u32) -> Option<Self> {
+        let index = raw as usize;
+        (index < Self::LENGTH).then(|| Self::from_usize(index))
+    }
+
+    fn into_raw(self) -> u32 {
+        self as u32
+    }
+}
+
+/// `0` is an empty slot, so it holds no kind
+impl RawKind for FragmentKind {
+    fn from_raw(raw: u32) -> Option<Self> {
+        let index = raw as usize;
+        (1..=Self::LENGTH)
+            .contains(&index)
+            .then(|| Self::from_usize(index - 1))
+    }
+
+    fn into_raw(self) -> u32 {
+        self as u32
+    }
+}
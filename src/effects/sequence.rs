@@ -0,0 +1,65 @@
+use crate::internal_prelude::*;
+use super::builder::EffectBuilder;
+use super::effect_stack::Effect;
+
+/// Effects played back-to-back, started with [`EffectBuilder::then`].
+///
+/// Each step starts when the previous one ends, so only the first step's start time
+/// matters; the others are overwritten. Every step takes its own stack slot, so a
+/// sequence longer than `MAX_FX` overwrites older effects when pushed.
+///
+/// A looping step never ends: the next step starts after one period, on top of it.
+///
+/// ```rust
+/// # use bevy_hirundo::prelude::*;
+/// # let (now, mut vfx) = (0.0, Vfx::default());
+/// let steps = EffectBuilder::one_shot(now, 0.2)
+///     .scale_y(0.3)
+///     .then(EffectBuilder::one_shot(0.0, 0.5).rotate(360.0))
+///     .then(EffectBuilder::fade_out(0.0, 0.3))
+///     .build();
+/// vfx.push_effects(steps);
+/// ```
+pub struct EffectSequence {
+    steps: Vec<EffectBuilder>,
+}
+
+impl EffectSequence {
+    /// Append `next`, starting when the current last step ends.
+    pub fn then(mut self, next: EffectBuilder) -> Self {
+        self.steps.push(next);
+        self
+    }
+
+    /// Total time from the first step's start to the end of the last step
+    pub fn duration(&self) -> f32 {
+        self.steps.iter().map(|step| step.lifetime.duration).sum()
+    }
+
+    /// Build every step, offsetting start times so they play back-to-back
+    pub fn build(self) -> Vec<Effect> {
+        let mut start = self
+            .steps
+            .first()
+            .map_or(0.0, |step| step.lifetime.start_time);
+        self.steps
+            .into_iter()
+            .map(|mut step| {
+                step.lifetime.start_time = start;
+                start += step.lifetime.duration;
+                step.build()
+            })
+            .collect()
+    }
+}
+
+impl EffectBuilder {
+    /// Start a sequence: `next` plays once this effect ends.
+    ///
+    /// `next`'s start time is ignored, build it with any `now` (e.g. `0.0`).
+    pub fn then(self, next: EffectBuilder) -> EffectSequence {
+        EffectSequence {
+            steps: vec![self, next],
+        }
+    }
+}
@@ -5,7 +5,7 @@ use super::builder::{EffectBuilder, EffectModifier, LastEffect};
 
 /// Spatial (vertex) manipulation types
 #[repr(u32)]
-#[derive(Clone, Copy, Debug, Enum)]
+#[derive(Clone, Copy, Debug, Enum, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SpatialKind {
     OffsetX = 0,  // Horizontal translation (full sprite movement)
     OffsetY = 1,  // Vertical translation (full sprite movement)
@@ -14,6 +14,12 @@ pub enum SpatialKind {
     Rotation = 4, // Rotation in radians
     SkewX = 5,    // Shear on the x axis
     SkewY = 6,    // Shear on the y axis
+    FlipX = 7,    // Mirror horizontally around the anchor (1.0 = flipped)
+    FlipY = 8,    // Mirror vertically around the anchor (1.0 = flipped)
+    Orbit = 9,    // Circular motion around the anchor, angle in radians
+    Arc = 10,     // Parabolic jump/lob offset, wave = progress along the arc
+    Sway = 11,    // Horizontal bend, growing with height above the anchor
+    Path = 12,    // Follow the effect's control points, wave = progress along the path
 }
 
 /// Anchor presets for common pivot points.
@@ -22,6 +28,16 @@ pub enum SpatialKind {
 ///
 /// An important control parameter, **Anchor** defines the fulcrum point
 /// of rotation and how scaling and skewing look.
+///
+/// Use [`Anchor::Custom`] for any other pivot, e.g. a sword swinging around the hand:
+/// ```rust
+/// # use bevy::prelude::Vec2;
+/// # use bevy_hirundo::prelude::*;
+/// # EffectBuilder::one_shot(0.0, 1.0)
+/// .rotate(90.0)
+/// .with(Anchor::Custom(Vec2::new(0.2, 0.1)))
+/// # ;
+/// ```
 #[derive(Clone, Copy, Debug)]
 pub enum Anchor {
     Center,       // (0.5, 0.5)
@@ -33,6 +49,9 @@ pub enum Anchor {
     BottomLeft,   // (0.0, 0.0)
     BottomCenter, // (0.5, 0.0)
     BottomRight,  // (1.0, 0.0)
+    /// Fraction of the sprite, (0.0, 0.0) = bottom left and (1.0, 1.0) = top right.
+    /// Values outside 0.0 - 1.0 put the pivot outside the sprite.
+    Custom(Vec2),
 }
 
 impl Anchor {
@@ -47,6 +66,7 @@ impl Anchor {
             Anchor::BottomLeft => Vec2::new(0.0, 0.0),
             Anchor::BottomCenter => Vec2::new(0.5, 0.0),
             Anchor::BottomRight => Vec2::new(1.0, 0.0),
+            Anchor::Custom(anchor) => anchor,
         }
     }
 }
@@ -54,8 +74,10 @@ impl Anchor {
 impl EffectModifier for Anchor {
     fn apply(&self, builder: &mut EffectBuilder) {
         match builder.last_effect {
-            Some(LastEffect::Color(_)) | Some(LastEffect::Alpha) => {
-                warn!("Cannot apply anchorage to color or alpha effects.")
+            Some(LastEffect::Color(_))
+            | Some(LastEffect::Alpha(_))
+            | Some(LastEffect::Fragment(_)) => {
+                warn!("Cannot apply anchorage to color, alpha or fragment effects.")
             }
             Some(LastEffect::Spatial(kind)) => {
                 builder.spatial[kind]
@@ -75,53 +97,89 @@ impl EffectModifier for Anchor {
 /// - **ScaleX/Y**: Stretch/squash
 /// - **Rotation**: Rotate sprite (in radians)
 /// - **Skew**: Shear sprite
+/// - **FlipX/Y**: Mirror sprite around the anchor; values between 0.0 and 1.0 squeeze
+///   through the flip like a turning card
+/// - **Orbit**: Move the sprite's center on a circle of `intensity` pixels around the
+///   anchor; the wave is the angle in radians
+/// - **Arc**: Parabolic offset covering `params.x` pixels horizontally and peaking at
+///   `params.y` pixels halfway; the wave is the progress along the arc (0.0 - 1.0) and
+///   `intensity` scales the whole path
+/// - **Sway**: Horizontal displacement weighted by each vertex's squared height above the
+///   anchor (in sprite heights), so the base stays put and the top moves the full value.
+///   Anchored at the bottom by default, for grass, trees and flags
+/// - **Path**: Offset following up to `MAX_PATH_POINTS` control points stored on the
+///   [`Effect`](super::Effect) (pixels, relative to the rest position). `params.x` = point
+///   count, `params.y` = 1.0 for a Catmull-Rom curve through the points, 0.0 for straight
+///   segments. The wave is the progress along the path (0.0 - 1.0), each segment taking an
+///   equal share of it
 ///
 /// # Examples
 ///
 /// **Horizontal shake**
 /// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # use bevy_hirundo::effects::SpatialKind;
 /// SpatialEffect {
 ///     phase: Phase::full(),
-///     wave: Wave::square(10.0, 2.0),  // Fast square wave
+///     wave: Wave::square(10.0, 2.0, 0.0),  // Fast square wave
 ///     manipulation: SpatialKind::OffsetX as u32,
 ///     intensity: 1.0,  // 2 pixel shake range
+///     ..default()
 /// }
+/// # ;
 /// ```
 ///
 /// **Squash and stretch (hit feedback)**
 /// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # use bevy_hirundo::effects::SpatialKind;
 /// // Squash Y
-/// SpatialEffect {
+/// let squash = SpatialEffect {
 ///     phase: Phase::new(0.0, 0.3),
-///     wave: Wave::sine(1.0, -0.3),  // Compress to 70% height
+///     wave: Wave::sine(1.0, -0.3, 0.0),  // Compress to 70% height
 ///     manipulation: SpatialKind::ScaleY as u32,
 ///     intensity: 1.0,
-/// }
+///     ..default()
+/// };
 /// // Stretch X (pairs with squash for skew effect)
-/// SpatialEffect {
+/// let stretch = SpatialEffect {
 ///     phase: Phase::new(0.0, 0.3),
-///     wave: Wave::sine(1.0, 0.3),  // Expand to 130% width
+///     wave: Wave::sine(1.0, 0.3, 0.0),  // Expand to 130% width
 ///     manipulation: SpatialKind::ScaleX as u32,
 ///     intensity: 1.0,
-/// }
+///     ..default()
+/// };
 /// ```
 #[repr(C)]
-#[derive(Clone, Copy, Debug, ShaderType, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, ShaderType, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default = "SpatialEffect::asset_default")]
 pub struct SpatialEffect {
     pub phase: Phase,
     pub wave: Wave,
     /// Manipulation type (see SpatialKind enum)
+    #[serde(
+        serialize_with = "serialize_kind::<SpatialKind, _>",
+        deserialize_with = "deserialize_kind::<SpatialKind, _>"
+    )]
     pub manipulation: u32,
     /// Multiplier for effect strength
     pub intensity: f32,
     /// Pivot/Origin
     pub anchor: Vec2,
+    /// Kind-specific parameters (see manipulation types)
+    pub params: Vec4,
 }
 
 impl SpatialEffect {
     pub fn disabled() -> Self {
         Self::default()
     }
+    /// Baseline for fields left out of an effect asset: full intensity around the center
+    fn asset_default() -> Self {
+        Self::offset_x(Wave::constant(1.0))
+    }
     pub fn offset_x(wave: Wave) -> Self {
         Self {
             phase: Phase::default(),
@@ -129,6 +187,7 @@ impl SpatialEffect {
             manipulation: SpatialKind::OffsetX as u32,
             intensity: 1.0,
             anchor: Anchor::Center.to_vec2(),
+            params: Vec4::ZERO,
         }
     }
     pub fn offset_y(wave: Wave) -> Self {
@@ -138,6 +197,7 @@ impl SpatialEffect {
             manipulation: SpatialKind::OffsetY as u32,
             intensity: 1.0,
             anchor: Anchor::Center.to_vec2(),
+            params: Vec4::ZERO,
         }
     }
     pub fn scale_x(wave: Wave) -> Self {
@@ -147,6 +207,7 @@ impl SpatialEffect {
             manipulation: SpatialKind::ScaleX as u32,
             intensity: 1.0,
             anchor: Anchor::Center.to_vec2(),
+            params: Vec4::ZERO,
         }
     }
     pub fn scale_y(wave: Wave) -> Self {
@@ -156,6 +217,7 @@ impl SpatialEffect {
             manipulation: SpatialKind::ScaleY as u32,
             intensity: 1.0,
             anchor: Anchor::Center.to_vec2(),
+            params: Vec4::ZERO,
         }
     }
     pub fn rotation(wave: Wave) -> Self {
@@ -165,6 +227,7 @@ impl SpatialEffect {
             manipulation: SpatialKind::Rotation as u32,
             intensity: 1.0,
             anchor: Anchor::Center.to_vec2(),
+            params: Vec4::ZERO,
         }
     }
     pub fn skew_x(wave: Wave) -> Self {
@@ -174,6 +237,7 @@ impl SpatialEffect {
             manipulation: SpatialKind::SkewX as u32,
             intensity: 1.0,
             anchor: Anchor::Center.to_vec2(),
+            params: Vec4::ZERO,
         }
     }
     pub fn skew_y(wave: Wave) -> Self {
@@ -183,6 +247,72 @@ impl SpatialEffect {
             manipulation: SpatialKind::SkewY as u32,
             intensity: 1.0,
             anchor: Anchor::Center.to_vec2(),
+            params: Vec4::ZERO,
+        }
+    }
+    pub fn flip_x(wave: Wave) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave,
+            manipulation: SpatialKind::FlipX as u32,
+            intensity: 1.0,
+            anchor: Anchor::Center.to_vec2(),
+            params: Vec4::ZERO,
+        }
+    }
+    pub fn flip_y(wave: Wave) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave,
+            manipulation: SpatialKind::FlipY as u32,
+            intensity: 1.0,
+            anchor: Anchor::Center.to_vec2(),
+            params: Vec4::ZERO,
+        }
+    }
+    /// Orbit of `radius` pixels, the wave being the angle in radians.
+    pub fn orbit(radius: f32, wave: Wave) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave,
+            manipulation: SpatialKind::Orbit as u32,
+            intensity: radius,
+            anchor: Anchor::Center.to_vec2(),
+            params: Vec4::ZERO,
+        }
+    }
+    /// Arc over `distance` pixels horizontally, peaking at `height` pixels.
+    pub fn arc(distance: f32, height: f32, wave: Wave) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave,
+            manipulation: SpatialKind::Arc as u32,
+            intensity: 1.0,
+            anchor: Anchor::Center.to_vec2(),
+            params: Vec4::new(distance, height, 0.0, 0.0),
+        }
+    }
+    pub fn sway(wave: Wave) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave,
+            manipulation: SpatialKind::Sway as u32,
+            intensity: 1.0,
+            anchor: Anchor::BottomCenter.to_vec2(),
+            params: Vec4::ZERO,
+        }
+    }
+    /// Path through `point_count` control points, curved or straight.
+    ///
+    /// The points themselves live on the effect, see [`EffectBuilder::path`].
+    pub fn path(point_count: usize, curved: bool, wave: Wave) -> Self {
+        Self {
+            phase: Phase::default(),
+            wave,
+            manipulation: SpatialKind::Path as u32,
+            intensity: 1.0,
+            anchor: Anchor::Center.to_vec2(),
+            params: Vec4::new(point_count as f32, curved as u32 as f32, 0.0, 0.0),
         }
     }
     pub fn with_intensity(&mut self, intensity: f32) -> Self {
@@ -204,6 +334,14 @@ impl SpatialEffect {
             SpatialKind::SkewX => Self::skew_x(Wave::constant(unit)),
             SpatialKind::SkewY => Self::skew_y(Wave::constant(unit)),
             SpatialKind::Rotation => Self::rotation(Wave::constant(unit.to_radians())),
+            SpatialKind::FlipX => Self::flip_x(Wave::constant(unit)),
+            SpatialKind::FlipY => Self::flip_y(Wave::constant(unit)),
+            SpatialKind::Orbit => {
+                Self::orbit(unit, Wave::saw(1.0, f32::consts::PI, f32::consts::PI))
+            }
+            SpatialKind::Arc => Self::arc(0.0, unit, Wave::saw(1.0, 0.5, 0.5)),
+            SpatialKind::Sway => Self::sway(Wave::sine(1.0, unit, 0.0)),
+            SpatialKind::Path => Self::path(0, true, Wave::saw(1.0, 0.5, 0.5)).with_intensity(unit),
         }
     }
 
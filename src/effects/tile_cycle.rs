@@ -0,0 +1,48 @@
+use crate::internal_prelude::*;
+
+/// Flipbook playback evaluated in the shaders, for sprites that animate without any
+/// CPU work per frame (e.g. thousands of broadcast entities).
+///
+/// While its effect is active, the sprite shows `count` consecutive atlas indices from
+/// `start`, at `fps`, counted from the effect's start time. The effect's own lifetime
+/// bounds the playback: use a looping effect for an endless cycle. When several active
+/// effects cycle tiles, the most recently started one wins.
+/// ```rust
+/// # use bevy_hirundo::prelude::*;
+/// # let now = 0.0;
+/// // Torch flame: atlas indices 40..44 at 8 fps, forever
+/// EffectBuilder::looping(now, 1.0).tile_cycle(40, 4, 8.0, AnimationMode::Loop).build()
+/// # ;
+/// ```
+/// See [`SpriteAnimation`] for CPU-side playback of arbitrary frame lists.
+#[repr(C)]
+#[derive(Clone, Copy, ShaderType, Debug, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct TileCycleEffect {
+    /// First atlas index
+    pub start: u32,
+    /// Number of frames, 0 = disabled
+    pub count: u32,
+    pub fps: f32,
+    /// 0 = loop, 1 = once (hold the last frame), 2 = ping-pong
+    #[serde(
+        serialize_with = "serialize_kind::<AnimationMode, _>",
+        deserialize_with = "deserialize_kind::<AnimationMode, _>"
+    )]
+    pub mode: u32,
+}
+
+impl TileCycleEffect {
+    pub fn new(start: u32, count: u32, fps: f32, mode: AnimationMode) -> Self {
+        Self {
+            start,
+            count,
+            fps,
+            mode: mode.into_raw(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.count > 0
+    }
+}
@@ -0,0 +1,325 @@
+use super::effect_stack::EffectStack;
+use crate::internal_prelude::*;
+
+/// Running weights of one accumulated blend mode, see [`CompositeMode`]
+#[derive(Default)]
+struct Accumulated {
+    color: Vec3,
+    total_weight: f32,
+    max_weight: f32,
+    summed: bool,
+}
+
+impl Accumulated {
+    fn add(&mut self, color: Vec3, weight: f32, summed: bool) {
+        self.color += color * weight;
+        self.total_weight += weight;
+        self.max_weight = self.max_weight.max(weight);
+        self.summed |= summed;
+    }
+
+    /// Average color and strength, `None` below the shaders' weight threshold
+    fn resolve(&self) -> Option<(Vec3, f32)> {
+        (self.total_weight > 0.001).then(|| {
+            let strength = if self.summed {
+                self.total_weight
+            } else {
+                self.max_weight
+            };
+            (self.color / self.total_weight, strength)
+        })
+    }
+}
+
+impl EffectStack {
+    /// Color the shaders draw a texel of `base` with at `now`, after the color and alpha
+    /// effects. Same math as the vertex stage and `fragment`, for things that aren't drawn
+    /// by the `Vfx` materials (e.g. [`TextVfx`](crate::components::TextVfx)).
+    ///
+    /// Fragment effects, alpha clipping and the custom fragment hook need texels and
+    /// aren't included.
+    pub fn tint(&self, now: f32, base: LinearRgba, audio: &VfxAudioUniform) -> LinearRgba {
+        self.tint_terms(now, audio).apply(base)
+    }
+
+    /// The color and alpha terms the vertex stage computes at `now`
+    pub(crate) fn tint_terms(&self, now: f32, audio: &VfxAudioUniform) -> TintTerms {
+        let playing = || {
+            self.effects
+                .iter()
+                .filter_map(move |eff| eff.drawn_progress(now).map(|t| (eff, t)))
+        };
+        let mut emissive = 0.0;
+
+        // Accumulated color effects first, whatever their slot
+        let (mut tint, mut add, mut mult) = default::<(Accumulated, Accumulated, Accumulated)>();
+        for (eff, t) in playing() {
+            for color in &eff.color_effects {
+                let composite = color.color.w;
+                if composite < 0.9 {
+                    continue;
+                }
+                let phase_t = color.phase.evaluate(t);
+                if phase_t <= 0.0 {
+                    continue;
+                }
+                let weight = color.wave.evaluate(phase_t, audio).clamp(0.0, 1.0);
+                emissive += color.emissive * weight;
+
+                // Gradient: full-strength tint towards a color picked between from/to
+                if color.blend_mode == BlendMode::Gradient as u32 {
+                    tint.color += color.from_color.xyz().lerp(color.color.xyz(), weight);
+                    tint.total_weight += 1.0;
+                    tint.max_weight = 1.0;
+                    continue;
+                }
+                if weight == 0.0 {
+                    continue;
+                }
+                let summed = composite >= 1.9;
+                match color.blend_mode {
+                    0 => tint.add(color.color.xyz(), weight, summed),
+                    1 => add.add(color.color.xyz(), weight, summed),
+                    2 => mult.add(color.color.xyz(), weight, summed),
+                    _ => {}
+                }
+            }
+        }
+
+        let (mut acc_mul, mut acc_add) = (Vec3::ONE, Vec3::ZERO);
+        if let Some((avg, strength)) = tint.resolve() {
+            let strength = strength.clamp(0.0, 1.0);
+            acc_mul *= 1.0 - strength;
+            acc_add = acc_add * (1.0 - strength) + avg * strength;
+        }
+        if let Some((avg, strength)) = mult.resolve() {
+            let strength = strength.clamp(0.0, 1.0);
+            let k = Vec3::splat(1.0 - strength) + avg * strength;
+            acc_mul *= k;
+            acc_add *= k;
+        }
+        if let Some((avg, strength)) = add.resolve() {
+            acc_add += avg * strength;
+        }
+
+        // Sequential color and alpha effects, in slot order
+        let (mut seq_mul, mut seq_add) = (Vec3::ONE, Vec3::ZERO);
+        let mut hsv_shift: Option<Vec3> = None;
+        let (mut alpha_mul, mut alpha_add) = (1.0, 0.0);
+        for (eff, t) in playing() {
+            for color in &eff.color_effects {
+                if color.color.w >= 0.9 {
+                    continue;
+                }
+                let phase_t = color.phase.evaluate(t);
+                if phase_t <= 0.0 {
+                    continue;
+                }
+                let a_raw = color.wave.evaluate(phase_t, audio);
+                let a = a_raw.clamp(0.0, 1.0);
+                emissive += color.emissive * a;
+
+                if color.blend_mode == BlendMode::Gradient as u32 {
+                    seq_mul = Vec3::ZERO;
+                    seq_add = color.from_color.xyz().lerp(color.color.xyz(), a);
+                    continue;
+                }
+                if a == 0.0 && a_raw.abs() < 1e-6 {
+                    continue;
+                }
+                let target = color.color.xyz();
+                match color.blend_mode {
+                    0 => {
+                        seq_mul *= 1.0 - a;
+                        seq_add = seq_add * (1.0 - a) + target * a;
+                    }
+                    1 => seq_add += target * a,
+                    2 => {
+                        let k = Vec3::splat(1.0 - a) + target * a;
+                        seq_mul *= k;
+                        seq_add *= k;
+                    }
+                    3 => {
+                        let b = target * a;
+                        let k = Vec3::ONE - b;
+                        seq_mul *= k;
+                        seq_add = seq_add * k + b;
+                    }
+                    4 => {
+                        let shift = hsv_shift.get_or_insert(Vec3::new(0.0, 1.0, 1.0));
+                        shift.x += target.x * a_raw;
+                        shift.y *= 1.0 + target.y * a_raw;
+                        shift.z *= 1.0 + target.z * a_raw;
+                    }
+                    _ => {}
+                }
+            }
+
+            for alpha in &eff.alpha_effects {
+                let phase_t = alpha.phase.evaluate(t);
+                if phase_t <= 0.0 {
+                    continue;
+                }
+                let a = alpha.wave.evaluate(phase_t, audio).clamp(0.0, 1.0);
+                if a <= 0.0 {
+                    continue;
+                }
+                let to = alpha.target_alpha.clamp(0.0, 1.0);
+                if alpha.composite == AlphaComposite::Multiply as u32 {
+                    let k = 1.0 + (to - 1.0) * a;
+                    alpha_mul *= k;
+                    alpha_add *= k;
+                } else {
+                    alpha_mul *= 1.0 - a;
+                    alpha_add = alpha_add * (1.0 - a) + to * a;
+                }
+            }
+        }
+
+        // VfxIntensity pulls the color terms back towards leaving the texel as it is
+        let k = self.intensity.clamp(0.0, 1.0);
+        acc_mul = Vec3::ONE.lerp(acc_mul, k);
+        acc_add *= k;
+        seq_mul = Vec3::ONE.lerp(seq_mul, k);
+        seq_add *= k;
+        let hsv_shift = hsv_shift.map(|shift| Vec3::new(0.0, 1.0, 1.0).lerp(shift, k));
+        emissive *= k;
+
+        TintTerms {
+            acc_mul: acc_mul.extend(alpha_mul) * self.base_tint,
+            acc_add: acc_add.extend(alpha_add),
+            seq_mul: seq_mul.extend(0.0),
+            seq_add: seq_add.extend(if hsv_shift.is_some() { 1.0 } else { 0.0 }),
+            hsv: hsv_shift
+                .unwrap_or(Vec3::new(0.0, 1.0, 1.0))
+                .extend(emissive),
+        }
+    }
+
+    /// The first `MAX_FRAGMENT_FX` fragment effects playing at `now`, with their wave
+    /// in `params.w`, like the vertex stage hands them on
+    pub(crate) fn fragment_terms(
+        &self,
+        now: f32,
+        audio: &VfxAudioUniform,
+    ) -> [FragmentTerm; MAX_FRAGMENT_FX] {
+        let mut terms = [FragmentTerm::default(); MAX_FRAGMENT_FX];
+        let playing = self
+            .effects
+            .iter()
+            .filter_map(|eff| eff.drawn_progress(now).map(|t| (eff, t)))
+            .flat_map(|(eff, t)| eff.fragment_effects.iter().map(move |frag| (frag, t)))
+            .filter(|(frag, _)| frag.kind != 0)
+            .filter_map(move |(frag, t)| {
+                let phase_t = frag.phase.evaluate(t);
+                (phase_t > 0.0).then(|| FragmentTerm {
+                    kind: frag.kind,
+                    color: frag.color,
+                    params: frag
+                        .params
+                        .xyz()
+                        .extend(frag.wave.evaluate(phase_t, audio).clamp(0.0, 1.0)),
+                })
+            });
+        for (term, playing) in terms.iter_mut().zip(playing) {
+            *term = playing;
+        }
+        terms
+    }
+}
+
+/// The color and alpha terms of the shaders' `Varyings`, for an effect stack at one time
+#[derive(Clone, Copy, Debug, ShaderType, PartialEq)]
+pub(crate) struct TintTerms {
+    /// Accumulated pass, RGB then alpha
+    pub acc_mul: Vec4,
+    pub acc_add: Vec4,
+    /// Sequential pass
+    pub seq_mul: Vec4,
+    /// Sequential pass, HSV correction enabled when w > 0.5
+    pub seq_add: Vec4,
+    /// Hue shift, saturation and value multipliers, emissive
+    pub hsv: Vec4,
+}
+
+impl Default for TintTerms {
+    fn default() -> Self {
+        Self {
+            acc_mul: Vec4::ONE,
+            acc_add: Vec4::ZERO,
+            seq_mul: Vec4::ONE,
+            seq_add: Vec4::ZERO,
+            hsv: Vec4::new(0.0, 1.0, 1.0, 0.0),
+        }
+    }
+}
+
+impl TintTerms {
+    /// The fragment stage's color and alpha math for a texel of `base`
+    pub fn apply(&self, base: LinearRgba) -> LinearRgba {
+        let mut rgb = (Vec3::new(base.red, base.green, base.blue) * self.acc_mul.xyz()
+            + self.acc_add.xyz())
+        .clamp(Vec3::ZERO, Vec3::ONE);
+        rgb = rgb * self.seq_mul.xyz() + self.seq_add.xyz();
+        if self.seq_add.w > 0.5 {
+            let mut hsv = rgb_to_hsv(rgb.clamp(Vec3::ZERO, Vec3::ONE));
+            hsv.x = (hsv.x + self.hsv.x).rem_euclid(1.0);
+            hsv.y = (hsv.y * self.hsv.y).clamp(0.0, 1.0);
+            hsv.z = (hsv.z * self.hsv.z).clamp(0.0, 1.0);
+            rgb = hsv_to_rgb(hsv);
+        }
+        rgb *= 1.0 + self.hsv.w.max(0.0);
+
+        // Coverage-gated, fully transparent stays transparent
+        let base_a = base.alpha.clamp(0.0, 1.0);
+        let alpha = if base_a >= 1e-4 {
+            (base_a * self.acc_mul.w + self.acc_add.w).clamp(0.0, 1.0)
+        } else {
+            base_a
+        };
+        LinearRgba::new(rgb.x, rgb.y, rgb.z, alpha)
+    }
+}
+
+/// One fragment effect as the vertex stage hands it on, kind 0 for none
+#[derive(Clone, Copy, Debug, Default, ShaderType, PartialEq)]
+pub(crate) struct FragmentTerm {
+    pub kind: u32,
+    pub color: Vec4,
+    pub params: Vec4,
+}
+
+// The shaders' `rgb_to_hsv` and `hsv_to_rgb`, on linear values like the GPU
+fn rgb_to_hsv(c: Vec3) -> Vec3 {
+    let max = c.max_element();
+    let delta = max - c.min_element();
+    let mut hsv = Vec3::new(0.0, 0.0, max);
+    if delta > 0.0 {
+        let h = if max == c.x {
+            (c.y - c.z) / delta
+        } else if max == c.y {
+            2.0 + (c.z - c.x) / delta
+        } else {
+            4.0 + (c.x - c.y) / delta
+        };
+        hsv.x = (h / 6.0).rem_euclid(1.0);
+        hsv.y = delta / max;
+    }
+    hsv
+}
+
+fn hsv_to_rgb(hsv: Vec3) -> Vec3 {
+    let i = (hsv.x * 6.0).floor();
+    let f = hsv.x * 6.0 - i;
+    let p = hsv.z * (1.0 - hsv.y);
+    let q = hsv.z * (1.0 - f * hsv.y);
+    let t = hsv.z * (1.0 - (1.0 - f) * hsv.y);
+    match i as i32 {
+        0 => Vec3::new(hsv.z, t, p),
+        1 => Vec3::new(q, hsv.z, p),
+        2 => Vec3::new(p, hsv.z, t),
+        3 => Vec3::new(p, q, hsv.z),
+        4 => Vec3::new(t, p, hsv.z),
+        _ => Vec3::new(hsv.z, p, q),
+    }
+}
@@ -0,0 +1,209 @@
+use super::builder::{EffectBuilder, LastEffect};
+use super::effect_stack::Effect;
+use super::phase::Phase;
+use super::wave::Wave;
+use crate::internal_prelude::*;
+
+/// How far an envelope's attack + hold + release may stray from 1.0.
+const ENVELOPE_SUM_TOLERANCE: f32 = 1e-3;
+
+/// Why [`EffectBuilder::build_checked`] rejected a builder.
+///
+/// `target` names the offending sub-effect, using the same indices and kinds
+/// as [`EffectBuilder::modify`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EffectError {
+    /// More sub-effects of one type were added than the GPU stack can hold
+    /// (`effects` is "color", "alpha", "spatial", "fragment" or "path point").
+    SlotOverflow { effects: &'static str, max: usize },
+    /// Phase bounds outside 0.0 - 1.0, or `start` not before `end`.
+    InvalidPhase {
+        target: LastEffect,
+        start: f32,
+        end: f32,
+    },
+    /// Envelope timings that are negative or don't sum to 1.0.
+    InvalidEnvelope {
+        target: LastEffect,
+        attack: f32,
+        hold: f32,
+        release: f32,
+    },
+    /// A wave field that is NaN or infinite, or a negative frequency.
+    InvalidWave {
+        target: LastEffect,
+        field: &'static str,
+        value: f32,
+    },
+    /// A tile cycle whose fps is NaN, infinite or negative.
+    InvalidTileCycle { fps: f32 },
+    /// A path or polyline given fewer than 2 points.
+    PathTooShort { points: usize },
+    /// [`EffectBuilder::modify`] called on a sub-effect that was never added.
+    MissingTarget { target: LastEffect },
+    /// [`EffectBuilder::looping_synced`] given a [`VfxClock`] without a positive, finite bpm.
+    StoppedClock { bpm: f32 },
+}
+
+impl std::fmt::Display for EffectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SlotOverflow { effects, max } => {
+                write!(f, "too many {effects} effects (max {max})")
+            }
+            Self::InvalidPhase { target, start, end } => write!(
+                f,
+                "{target:?} phase {start}..{end} must satisfy 0.0 <= start < end <= 1.0"
+            ),
+            Self::InvalidEnvelope {
+                target,
+                attack,
+                hold,
+                release,
+            } => write!(
+                f,
+                "{target:?} envelope ({attack}, {hold}, {release}) must be non-negative and sum to 1.0"
+            ),
+            Self::InvalidWave {
+                target,
+                field,
+                value,
+            } => write!(f, "{target:?} wave has an invalid {field} of {value}"),
+            Self::InvalidTileCycle { fps } => write!(f, "tile cycle has an invalid fps of {fps}"),
+            Self::PathTooShort { points } => {
+                write!(f, "a path needs at least 2 points, got {points}")
+            }
+            Self::MissingTarget { target } => write!(f, "no {target:?} sub-effect to modify"),
+            Self::StoppedClock { bpm } => {
+                write!(f, "a synced loop needs a clock with a positive bpm, got {bpm}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EffectError {}
+
+impl EffectBuilder {
+    /// Like [`Self::build`], but returns an [`EffectError`] instead of building an effect
+    /// that silently differs from what was asked for.
+    ///
+    /// Catches sub-effects dropped for lack of slots, paths with too few points and
+    /// `modify` calls without a target (which `build` only warns about), phases outside
+    /// the effect's lifetime, envelopes that don't sum to 1.0 and NaN/infinite waves.
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_hirundo::prelude::*;
+    /// # let (now, mut vfx) = (0.0, Vfx::default());
+    /// match EffectBuilder::one_shot(now, 1.0).color(LinearRgba::RED).with(Phase::new(0.8, 0.2)).build_checked() {
+    ///     Ok(effect) => {
+    ///         vfx.push_effect(effect);
+    ///     }
+    ///     Err(err) => error!("Bad hit effect: {err}"),
+    /// }
+    /// ```
+    pub fn build_checked(self) -> Result<Effect, EffectError> {
+        self.validate()?;
+        Ok(self.build())
+    }
+
+    /// Run the checks of [`Self::build_checked`] without consuming the builder.
+    pub fn validate(&self) -> Result<(), EffectError> {
+        if let Some(&err) = self.dropped.first() {
+            return Err(err);
+        }
+        let spatial_count = self.spatial.values().flatten().count();
+        if spatial_count > MAX_SPATIAL_FX {
+            return Err(EffectError::SlotOverflow {
+                effects: "spatial",
+                max: MAX_SPATIAL_FX,
+            });
+        }
+        let fragment_count = self.fragment.values().flatten().count();
+        if fragment_count > MAX_FRAGMENT_FX {
+            return Err(EffectError::SlotOverflow {
+                effects: "fragment",
+                max: MAX_FRAGMENT_FX,
+            });
+        }
+
+        let colors = self.colors.iter().enumerate().filter_map(|(i, effect)| {
+            effect.map(|effect| (LastEffect::Color(i), effect.phase, effect.wave))
+        });
+        let alphas = self.alphas.iter().enumerate().filter_map(|(i, effect)| {
+            effect.map(|effect| (LastEffect::Alpha(i), effect.phase, effect.wave))
+        });
+        let spatial = self.spatial.iter().filter_map(|(kind, effect)| {
+            effect.map(|effect| (LastEffect::Spatial(kind), effect.phase, effect.wave))
+        });
+        let fragment = self.fragment.iter().filter_map(|(kind, effect)| {
+            effect.map(|effect| (LastEffect::Fragment(kind), effect.phase, effect.wave))
+        });
+
+        for (target, phase, wave) in colors.chain(alphas).chain(spatial).chain(fragment) {
+            validate_phase(target, &phase)?;
+            validate_wave(target, &wave)?;
+        }
+
+        let fps = self.tile_cycle.fps;
+        if self.tile_cycle.is_enabled() && !(fps.is_finite() && fps >= 0.0) {
+            return Err(EffectError::InvalidTileCycle { fps });
+        }
+        Ok(())
+    }
+}
+
+fn validate_phase(target: LastEffect, phase: &Phase) -> Result<(), EffectError> {
+    let in_range = |t: f32| (0.0..=1.0).contains(&t);
+    if in_range(phase.start) && in_range(phase.end) && phase.start < phase.end {
+        return Ok(());
+    }
+    Err(EffectError::InvalidPhase {
+        target,
+        start: phase.start,
+        end: phase.end,
+    })
+}
+
+fn validate_wave(target: LastEffect, wave: &Wave) -> Result<(), EffectError> {
+    let fields = [
+        ("frequency", wave.freq),
+        ("amplitude", wave.amp),
+        ("bias", wave.bias),
+        ("phase", wave.phase),
+    ];
+    for (field, value) in fields {
+        if !value.is_finite() {
+            return Err(EffectError::InvalidWave {
+                target,
+                field,
+                value,
+            });
+        }
+    }
+    if wave.freq < 0.0 {
+        return Err(EffectError::InvalidWave {
+            target,
+            field: "frequency",
+            value: wave.freq,
+        });
+    }
+
+    for envelope in [wave.amp_envelope, wave.freq_envelope] {
+        let Some([attack, hold, release]) = envelope.timings() else {
+            continue;
+        };
+        let valid = [attack, hold, release]
+            .iter()
+            .all(|t| t.is_finite() && *t >= 0.0);
+        let sum = attack + hold + release;
+        if !valid || (sum - 1.0).abs() > ENVELOPE_SUM_TOLERANCE {
+            return Err(EffectError::InvalidEnvelope {
+                target,
+                attack,
+                hold,
+                release,
+            });
+        }
+    }
+    Ok(())
+}
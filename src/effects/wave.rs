@@ -6,7 +6,7 @@ use crate::internal_prelude::*;
 ///
 /// This is an [`EffectModifier`].
 #[repr(u32)]
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub enum WaveKind {
     /// S
     Sine = 0,
@@ -19,6 +19,25 @@ pub enum WaveKind {
     /// ————————
     #[default]
     Constant = 4,
+    /// ~^~-^~
+    Noise = 5,
+    /// Follows the [`VfxAudioDriver`] amplitude (0.0 - 1.0)
+    Audio = 6,
+    /// Follows the [`VfxAudioDriver`] beat: 1.0 on a beat, fading to 0.0
+    Beat = 7,
+}
+
+impl WaveKind {
+    /// Whether the wave swings around its bias, rather than ramping somewhere (saw) or
+    /// holding still (constant)
+    pub(crate) fn oscillates(self) -> bool {
+        match self {
+            Self::Sine | Self::Square | Self::Triangle | Self::Noise | Self::Audio | Self::Beat => {
+                true
+            }
+            Self::Saw | Self::Constant => false,
+        }
+    }
 }
 
 impl EffectModifier for WaveKind {
@@ -28,10 +47,15 @@ impl EffectModifier for WaveKind {
             Some(LastEffect::Color(idx)) => {
                 builder.colors[idx].as_mut().unwrap().wave.kind = *self as u32
             }
-            Some(LastEffect::Alpha) => builder.alpha.as_mut().unwrap().wave.kind = *self as u32,
+            Some(LastEffect::Alpha(idx)) => {
+                builder.alphas[idx].as_mut().unwrap().wave.kind = *self as u32
+            }
             Some(LastEffect::Spatial(kind)) => {
                 builder.spatial[kind].as_mut().unwrap().wave.kind = *self as u32
             }
+            Some(LastEffect::Fragment(kind)) => {
+                builder.fragment[kind].as_mut().unwrap().wave.kind = *self as u32
+            }
             None => warn!("No previous sub-effect to modify."),
         }
     }
@@ -44,7 +68,7 @@ impl EffectModifier for WaveKind {
 /// The inverse is true for ramping waves.
 ///
 /// # Fields
-/// - `kind`: 0=sin, 1=square, 2=triangle, 3=saw
+/// - `kind`: 0=sin, 1=square, 2=triangle, 3=saw, 4=constant, 5=noise, 6=audio, 7=beat
 /// - `freq`: Cycles per effect duration (0.5 = half cycle, 1.0 = full cycle)
 /// - `amp`: Wave amplitude (peak-to-trough distance) (sign determines starting direction)
 /// - `bias`: Center point offset
@@ -52,16 +76,24 @@ impl EffectModifier for WaveKind {
 /// - `amp_envelope`: Envelope controlling amplitude modulation over time
 /// - `freq_envelope`: Envelope controlling frequency modulation over time
 #[repr(C)]
-#[derive(Clone, Copy, Debug, ShaderType, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, ShaderType, PartialEq, Default, Serialize, Deserialize, Reflect)]
+#[serde(default = "Wave::asset_default")]
 pub struct Wave {
-    /// 0=sin, 1=square, 2=triangle, 3=saw, 4=constant
+    /// 0=sin, 1=square, 2=triangle, 3=saw, 4=constant, 5=noise, 6=audio, 7=beat
+    #[serde(
+        serialize_with = "serialize_kind::<WaveKind, _>",
+        deserialize_with = "deserialize_kind::<WaveKind, _>"
+    )]
     pub(crate) kind: u32,
     pub(crate) freq: f32,
     pub(crate) amp: f32,
     pub(crate) bias: f32,
     pub(crate) phase: f32,
+    #[serde(skip)]
     _pad0: f32,
+    #[serde(skip)]
     _pad1: f32,
+    #[serde(skip)]
     _pad2: f32,
     pub(crate) amp_envelope: Envelope,  // 32 bytes
     pub(crate) freq_envelope: Envelope, // 32 bytes
@@ -139,6 +171,44 @@ impl Wave {
         }
     }
 
+    /// Creates a smooth value-noise wave that picks `freq` new random targets
+    /// (between -1.0 and 1.0) per effect duration.
+    ///
+    /// `phase` seeds the sequence rather than shifting it, so paired noise waves
+    /// (e.g. offset X and Y of a shake) only need different phases to be uncorrelated.
+    pub fn noise(freq: f32, amp: f32, bias: f32) -> Self {
+        Self {
+            kind: WaveKind::Noise as u32,
+            freq,
+            amp,
+            bias,
+            phase: 0.0,
+            _pad0: 0.0,
+            _pad1: 0.0,
+            _pad2: 0.0,
+            amp_envelope: Envelope::disabled(),
+            freq_envelope: Envelope::disabled(),
+        }
+    }
+
+    /// Creates a wave following the [`VfxAudioDriver`] amplitude: `bias` in silence,
+    /// `bias + amp` at full volume. `freq` and `phase` don't apply.
+    pub fn audio(amp: f32, bias: f32) -> Self {
+        Self {
+            kind: WaveKind::Audio as u32,
+            ..Self::constant(amp).with_bias(bias)
+        }
+    }
+
+    /// Creates a wave following the [`VfxAudioDriver`] beat: `bias + amp` on a beat,
+    /// falling back to `bias` before the next. `freq` and `phase` don't apply.
+    pub fn beat(amp: f32, bias: f32) -> Self {
+        Self {
+            kind: WaveKind::Beat as u32,
+            ..Self::constant(amp).with_bias(bias)
+        }
+    }
+
     /// Creates a constant value wave (no oscillation).
     ///
     /// Useful for static offsets, scales, or colors that should remain constant
@@ -160,6 +230,15 @@ impl Wave {
         }
     }
 
+    /// Baseline for fields left out of an effect asset: constant 1.0, one cycle
+    /// per phase once `kind` is changed.
+    fn asset_default() -> Self {
+        Self {
+            freq: 1.0,
+            ..Self::constant(1.0)
+        }
+    }
+
     /// This **must be called first** before any other `with_amp_envelope_...` methods.
     pub fn with_amp_envelope(mut self, attack: f32, hold: f32, release: f32) -> Self {
         self.amp_envelope = Envelope::new(attack, hold, release);
@@ -258,6 +337,44 @@ impl Wave {
         self.phase = center;
     }
 
+    /// Value of this wave at phase progress `t` (0.0 - 1.0), envelopes included.
+    ///
+    /// Same math as the shaders' `eval_wave`, so game logic can follow what is drawn,
+    /// e.g. moving a hitbox with a wave-driven lunge. Not clamped: color and alpha
+    /// effects saturate it to 0.0 - 1.0 themselves. Audio and beat waves follow `audio`,
+    /// [`VfxAudioDriver::levels`] for what the shaders draw this frame.
+    pub fn evaluate(&self, t: f32, audio: &VfxAudioUniform) -> f32 {
+        let cycles = self.freq * self.freq_envelope.evaluate_integral(t).y;
+        let phase = fract(self.phase + cycles);
+
+        let v = match self.kind {
+            0 => (phase * f32::consts::TAU).cos(),
+            1 => {
+                if phase > 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            2 => 2.0 * (2.0 * phase - 1.0).abs() - 1.0,
+            3 => phase * 2.0 - 1.0,
+            4 => 1.0,
+            5 => {
+                // Smooth value noise; phase seeds the sequence instead of shifting it
+                let x = cycles + self.phase * 101.0;
+                let i = x.floor();
+                let f = x - i;
+                let (a, b) = (hash12(Vec2::new(i, 0.0)), hash12(Vec2::new(i + 1.0, 0.0)));
+                (a + (b - a) * (f * f * (3.0 - 2.0 * f))) * 2.0 - 1.0
+            }
+            6 => audio.amplitude,
+            7 => audio.beat,
+            _ => 0.0,
+        };
+
+        v * self.amp * self.amp_envelope.evaluate(t) + self.bias
+    }
+
     pub fn rotate_oscillating(freq: f32, degrees: f32) -> Self {
         let rad = degrees.to_radians() / 2.0;
         Self {
@@ -286,13 +403,26 @@ impl Wave {
     }
 }
 
+// WGSL's `fract`, which unlike `f32::fract` is never negative
+fn fract(x: f32) -> f32 {
+    x - x.floor()
+}
+
+// The shaders' `hash12`. GPU sin precision differs, so noise only matches approximately.
+fn hash12(p: Vec2) -> f32 {
+    fract(p.dot(Vec2::new(12.9898, 78.233)).sin() * 43758.547)
+}
+
 impl EffectModifier for Wave {
     /// Replaces the wave of the last sub-effect added.
     fn apply(&self, builder: &mut EffectBuilder) {
         match builder.last_effect {
             Some(LastEffect::Color(idx)) => builder.colors[idx].as_mut().unwrap().wave = *self,
-            Some(LastEffect::Alpha) => builder.alpha.as_mut().unwrap().wave = *self,
+            Some(LastEffect::Alpha(idx)) => builder.alphas[idx].as_mut().unwrap().wave = *self,
             Some(LastEffect::Spatial(kind)) => builder.spatial[kind].as_mut().unwrap().wave = *self,
+            Some(LastEffect::Fragment(kind)) => {
+                builder.fragment[kind].as_mut().unwrap().wave = *self
+            }
             None => warn!("No previous sub-effect to modify."),
         }
     }
@@ -326,12 +456,15 @@ impl EffectModifier for WavePhase {
             Some(LastEffect::Color(idx)) => {
                 builder.colors[idx].as_mut().unwrap().wave.phase = self.0;
             }
-            Some(LastEffect::Alpha) => {
-                builder.alpha.as_mut().unwrap().wave.phase = self.0;
+            Some(LastEffect::Alpha(idx)) => {
+                builder.alphas[idx].as_mut().unwrap().wave.phase = self.0;
             }
             Some(LastEffect::Spatial(kind)) => {
                 builder.spatial[kind].as_mut().unwrap().wave.phase = self.0;
             }
+            Some(LastEffect::Fragment(kind)) => {
+                builder.fragment[kind].as_mut().unwrap().wave.phase = self.0;
+            }
             None => warn!("Cannot apply WavePhase: No previous effect to modify."),
         }
     }
@@ -346,10 +479,15 @@ impl EffectModifier for WavePhaseCenter {
             Some(LastEffect::Color(idx)) => {
                 builder.colors[idx].as_mut().unwrap().wave.center_phase()
             }
-            Some(LastEffect::Alpha) => builder.alpha.as_mut().unwrap().wave.center_phase(),
+            Some(LastEffect::Alpha(idx)) => {
+                builder.alphas[idx].as_mut().unwrap().wave.center_phase()
+            }
             Some(LastEffect::Spatial(kind)) => {
                 builder.spatial[kind].as_mut().unwrap().wave.center_phase()
             }
+            Some(LastEffect::Fragment(kind)) => {
+                builder.fragment[kind].as_mut().unwrap().wave.center_phase()
+            }
             None => warn!("No previous sub-effect to modify."),
         }
     }
@@ -368,12 +506,15 @@ impl EffectModifier for Bias {
             Some(LastEffect::Color(idx)) => {
                 builder.colors[idx].as_mut().unwrap().wave.bias = self.0;
             }
-            Some(LastEffect::Alpha) => {
-                builder.alpha.as_mut().unwrap().wave.bias = self.0;
+            Some(LastEffect::Alpha(idx)) => {
+                builder.alphas[idx].as_mut().unwrap().wave.bias = self.0;
             }
             Some(LastEffect::Spatial(kind)) => {
                 builder.spatial[kind].as_mut().unwrap().wave.bias = self.0;
             }
+            Some(LastEffect::Fragment(kind)) => {
+                builder.fragment[kind].as_mut().unwrap().wave.bias = self.0;
+            }
             None => warn!("Cannot apply Amplitude: No previous effect to modify."),
         }
     }
@@ -407,12 +548,15 @@ impl EffectModifier for Amplitude {
             Some(LastEffect::Color(idx)) => {
                 builder.colors[idx].as_mut().unwrap().wave.amp = self.0;
             }
-            Some(LastEffect::Alpha) => {
-                builder.alpha.as_mut().unwrap().wave.amp = self.0;
+            Some(LastEffect::Alpha(idx)) => {
+                builder.alphas[idx].as_mut().unwrap().wave.amp = self.0;
             }
             Some(LastEffect::Spatial(kind)) => {
                 builder.spatial[kind].as_mut().unwrap().wave.amp = self.0;
             }
+            Some(LastEffect::Fragment(kind)) => {
+                builder.fragment[kind].as_mut().unwrap().wave.amp = self.0;
+            }
             None => warn!("Cannot apply Amplitude: No previous effect to modify."),
         }
     }
@@ -428,12 +572,15 @@ impl EffectModifier for Frequency {
             Some(LastEffect::Color(idx)) => {
                 builder.colors[idx].as_mut().unwrap().wave.freq = self.0;
             }
-            Some(LastEffect::Alpha) => {
-                builder.alpha.as_mut().unwrap().wave.freq = self.0;
+            Some(LastEffect::Alpha(idx)) => {
+                builder.alphas[idx].as_mut().unwrap().wave.freq = self.0;
             }
             Some(LastEffect::Spatial(kind)) => {
                 builder.spatial[kind].as_mut().unwrap().wave.freq = self.0;
             }
+            Some(LastEffect::Fragment(kind)) => {
+                builder.fragment[kind].as_mut().unwrap().wave.freq = self.0;
+            }
             None => warn!("Cannot apply Frequency: No previous effect to modify."),
         }
     }
@@ -6,14 +6,14 @@ pub fn dehydrate_vfx(mut world: DeferredWorld, context: HookContext) {
     // 1. Recycle the ID and Clear GPU Slot
     // We can still access the MeshTag here because the removal command
     // hasn't fully applied the archetype change yet.
-    if let Some(tag) = world.get::<MeshTag>(entity).map(|t| t.0) {
-        let mut storage = world.resource_mut::<EffectStorageData>();
-        if let Some(slot) = storage.effects.get_mut(tag as usize) {
-            slot.clear();
-            slot.tile_index = 0;
-            // Mark dirty so the GPU buffer updates ONCE
-            storage.dirty_slots.insert(tag as usize);
-        }
+    // A slot shared through `VfxRegistry` stays until its last entity goes.
+    if let Some(tag) = world.get::<MeshTag>(entity).map(|t| t.0)
+        && world.resource_mut::<VfxRegistry>().release(tag)
+    {
+        // Mark dirty so the GPU buffer updates ONCE
+        world
+            .resource_mut::<EffectStorageData>()
+            .clear_slot(tag as usize);
 
         world.resource_mut::<MeshTagAllocator>().free_tag(tag);
         info!("Dehydrate → recycled tag {}", tag);
@@ -21,3 +21,37 @@ pub fn dehydrate_vfx(mut world: DeferredWorld, context: HookContext) {
 
     // 2. STOP. Do not call commands().remove() here.
 }
+
+/// Untags a broadcast entity losing its [`BroadcastSprite`], so it draws the stack's sprite again
+pub fn clear_broadcast_sprite(mut world: DeferredWorld, context: HookContext) {
+    if let Some(mut tag) = world.get_mut::<MeshTag>(context.entity) {
+        tag.0 = 0;
+    }
+}
+
+/// Puts a broadcast entity losing its [`BroadcastTint`] back on the shared sprite mesh
+pub fn restore_broadcast_mesh(mut world: DeferredWorld, context: HookContext) {
+    let mesh = world.resource::<VfxMeshHandle>().0.clone();
+    if let Some(mut mesh_comp) = world.get_mut::<Mesh2d>(context.entity) {
+        mesh_comp.0 = mesh;
+    }
+}
+
+/// Gives an entity losing its [`VfxInstanced`] its own mesh and material again
+pub fn restore_vfx_instanced(mut world: DeferredWorld, context: HookContext) {
+    let entity = context.entity;
+    // Never taken off its mesh without `with_instancing`
+    if world.get::<Mesh2d>(entity).is_some() {
+        return;
+    }
+    let mesh = match world.get::<VfxMesh>(entity) {
+        Some(vfx_mesh) => vfx_mesh.0.clone(),
+        None => world.resource::<VfxMeshHandle>().0.clone(),
+    };
+    let material = world.resource::<VfxMaterialHandle>().0.clone();
+    // `try_insert`, the entity may be despawning
+    world
+        .commands()
+        .entity(entity)
+        .try_insert((Mesh2d(mesh), MeshMaterial2d(material)));
+}
@@ -1,17 +1,35 @@
+use crate::HirundoPlugin;
 use crate::internal_prelude::*;
+use bevy::ui_render::prelude::MaterialNode;
 
 pub fn hydrate_vfx(mut world: DeferredWorld, context: HookContext) {
     let entity = context.entity;
 
     // 1. Get our dynamic data
     let tag = world.resource_mut::<MeshTagAllocator>().allocate_tag();
-    let mesh = world.resource::<VfxMeshHandle>().0.clone();
+    world.resource_mut::<VfxRegistry>().acquire(tag.0);
+    let mesh = vfx_mesh(&world, entity);
     let mat = world.resource::<VfxMaterialHandle>().0.clone();
     let sprite_val = world
         .get::<Vfx>(entity)
         .map(|v| v.sprite_index)
         .unwrap_or(0);
 
+    // Push any on-spawn effects, with their start times as offsets from now
+    if let Some(on_spawn) = world
+        .get_mut::<VfxOnSpawn>(entity)
+        .map(|mut on_spawn| std::mem::take(&mut on_spawn.0))
+    {
+        let now = world.resource::<Time>().elapsed_secs();
+        if let Some(mut vfx) = world.get_mut::<Vfx>(entity) {
+            for effect in on_spawn {
+                let start = effect.lifetime.start_time;
+                vfx.push_effect_now(effect, now + start);
+            }
+        }
+        world.commands().entity(entity).remove::<VfxOnSpawn>();
+    }
+
     // 2. Mark slot as dirty
     world
         .resource_mut::<EffectStorageData>()
@@ -38,3 +56,126 @@ pub fn hydrate_vfx(mut world: DeferredWorld, context: HookContext) {
         s.0 = sprite_val;
     }
 }
+
+/// Moves a [`Vfx3d`] entity from the 2D mesh and material `Vfx` requires to the 3D ones
+pub fn hydrate_vfx_3d(mut world: DeferredWorld, context: HookContext) {
+    let entity = context.entity;
+    let Some(material) = world
+        .get_resource::<VfxMaterial3dHandle>()
+        .map(|handle| handle.0.clone())
+    else {
+        warn!("Vfx3d on {entity} needs HirundoPlugin::with_3d, it won't be drawn");
+        return;
+    };
+    let mesh = vfx_mesh(&world, entity);
+
+    if let Some(mut m) = world.get_mut::<Mesh3d>(entity) {
+        m.0 = mesh;
+    }
+    if let Some(mut mat_comp) = world.get_mut::<MeshMaterial3d<VfxMaterial3d>>(entity) {
+        mat_comp.0 = material;
+    }
+    world
+        .commands()
+        .entity(entity)
+        .remove::<(Mesh2d, MeshMaterial2d<VfxMaterial>)>();
+}
+
+/// Gives a [`UiVfx`] node its own [`VfxUiMaterial`], following the `Vfx` material
+pub fn hydrate_ui_vfx(mut world: DeferredWorld, context: HookContext) {
+    let entity = context.entity;
+    if world.get_resource::<Assets<VfxUiMaterial>>().is_none() {
+        warn!("UiVfx on {entity} needs HirundoPlugin::with_ui, it won't be drawn");
+        return;
+    }
+    let material_handle = world.resource::<VfxMaterialHandle>().0.clone();
+    let Some(material) = world
+        .resource::<Assets<VfxMaterial>>()
+        .get(&material_handle)
+        .cloned()
+    else {
+        return;
+    };
+    let fx_slots = world
+        .get_resource::<HirundoPlugin>()
+        .map_or(MAX_FX, |plugin_config| plugin_config.fx_slots);
+
+    let Some(effect_stack) = world.get_mut::<UiVfx>(entity).map(|mut ui_vfx| {
+        ui_vfx.effects.limit_slots(fx_slots);
+        ui_vfx.gpu_stack()
+    }) else {
+        return;
+    };
+    let ui_material = world
+        .resource_mut::<Assets<VfxUiMaterial>>()
+        .add(VfxUiMaterial::new(&material, effect_stack));
+    if let Some(mut mat_comp) = world.get_mut::<MaterialNode<VfxUiMaterial>>(entity) {
+        mat_comp.0 = ui_material;
+    }
+}
+
+/// Puts a [`VfxMesh`] on the entity's `Mesh2d` or `Mesh3d`
+pub fn apply_vfx_mesh(mut world: DeferredWorld, context: HookContext) {
+    let Some(mesh) = world.get::<VfxMesh>(context.entity).map(|m| m.0.clone()) else {
+        return;
+    };
+    set_vfx_mesh(&mut world, context.entity, mesh);
+}
+
+/// Puts the entity back on the shared sprite quad when its [`VfxMesh`] is removed
+pub fn restore_vfx_mesh(mut world: DeferredWorld, context: HookContext) {
+    let mesh = world.resource::<VfxMeshHandle>().0.clone();
+    set_vfx_mesh(&mut world, context.entity, mesh);
+}
+
+/// The entity's [`VfxMesh`], or the shared sprite quad
+fn vfx_mesh(world: &DeferredWorld, entity: Entity) -> Handle<Mesh> {
+    match world.get::<VfxMesh>(entity) {
+        Some(vfx_mesh) => vfx_mesh.0.clone(),
+        None => world.resource::<VfxMeshHandle>().0.clone(),
+    }
+}
+
+fn set_vfx_mesh(world: &mut DeferredWorld, entity: Entity, mesh: Handle<Mesh>) {
+    if let Some(mut m) = world.get_mut::<Mesh2d>(entity) {
+        m.0 = mesh.clone();
+    }
+    if let Some(mut m) = world.get_mut::<Mesh3d>(entity) {
+        m.0 = mesh;
+    }
+}
+
+/// Re-uploads the stack of an entity losing a component stored in it ([`SpriteRect`],
+/// [`VfxTint`], [`VfxIntensity`]), so it draws without it again
+pub fn refresh_vfx_stack(mut world: DeferredWorld, context: HookContext) {
+    if let Some(mut vfx) = world.get_mut::<Vfx>(context.entity) {
+        vfx.set_changed();
+    }
+}
+
+/// Keeps every inserted `Vfx` (including re-inserts, e.g. by the [`VfxPool`]) within the
+/// stack slots configured with `HirundoPlugin::with_max_fx`
+pub fn limit_vfx_slots(mut world: DeferredWorld, context: HookContext) {
+    let Some(fx_slots) = world
+        .get_resource::<HirundoPlugin>()
+        .map(|plugin_config| plugin_config.fx_slots)
+    else {
+        return;
+    };
+    if let Some(mut vfx) = world.get_mut::<Vfx>(context.entity) {
+        vfx.effects.limit_slots(fx_slots);
+    }
+}
+
+/// Moves a [`VfxInstanced`] entity off its own mesh and material, onto the batch mesh
+pub fn hydrate_vfx_instanced(mut world: DeferredWorld, context: HookContext) {
+    let entity = context.entity;
+    if world.get_resource::<VfxInstanceBatch>().is_none() {
+        warn!("VfxInstanced on {entity} needs HirundoPlugin::with_instancing, drawing it alone");
+        return;
+    }
+    world
+        .commands()
+        .entity(entity)
+        .remove::<(Mesh2d, MeshMaterial2d<VfxMaterial>)>();
+}
@@ -4,14 +4,9 @@ use crate::internal_prelude::*;
 pub fn control_broadcast_fx(
     time: Res<Time>,
     input: Res<ButtonInput<KeyCode>>,
-    broadcast_mat_handle: Res<VfxBroadcastMaterialHandle>,
+    mut broadcast: ResMut<BroadcastFx>,
     mut sprite_query: Query<&mut SpriteIndex>,
-    mut materials: ResMut<Assets<VfxBroadcastMaterial>>,
 ) {
-    let Some(material) = materials.get_mut(&broadcast_mat_handle.0) else {
-        return;
-    };
-
     if input.just_pressed(KeyCode::KeyP) {
         info!("P - Adding pulsing color effect to ALL entities");
         let random_color = LinearRgba::rgb(
@@ -20,7 +15,7 @@ pub fn control_broadcast_fx(
             rand::rng().random_range(0.0..1.0),
         );
 
-        material.effect_stack.push(
+        broadcast.push_effect(
             EffectBuilder::looping(time.elapsed_secs(), 1.0)
                 .color(random_color)
                 .with(Wave::sine(1.0, -0.5, 0.5))
@@ -31,7 +26,7 @@ pub fn control_broadcast_fx(
         info!("O - Adding squash effect to ALL entities");
         let random_squash = rand::rng().random_range(-0.5..0.0);
 
-        material.effect_stack.push(
+        broadcast.push_effect(
             EffectBuilder::one_shot(time.elapsed_secs(), 0.5)
                 .scale_y(-1.0)
                 .with(Wave::sine(1.0, -random_squash, random_squash))
@@ -42,7 +37,7 @@ pub fn control_broadcast_fx(
         );
     } else if input.just_pressed(KeyCode::KeyT) {
         info!("T - Changing sprite for ALL entities");
-        material.effect_stack.tile_index = rand::rng().random_range(0..625);
+        broadcast.set_tile(rand::rng().random_range(0..625));
     } else if input.just_pressed(KeyCode::KeyI) {
         info!("I - Randomizing sprite of all Vfx entities.");
         let mut rng = rand::rng();
@@ -54,7 +49,7 @@ pub fn control_broadcast_fx(
         let rotations: [f32; 3] = [360.0, 720.0, 1080.0];
         let random_degrees = *rotations.choose(&mut rand::rng()).unwrap();
 
-        material.effect_stack.push(
+        broadcast.push_effect(
             EffectBuilder::one_shot(time.elapsed_secs(), 2.0)
                 .rotate(random_degrees)
                 .with(Wave::rotate_continuous(1.0, random_degrees))
@@ -66,7 +61,7 @@ pub fn control_broadcast_fx(
         let skews: [f32; 3] = [0.3, 0.6, 1.0];
         let skew = *skews.choose(&mut rand::rng()).unwrap();
 
-        material.effect_stack.push(
+        broadcast.push_effect(
             EffectBuilder::one_shot(time.elapsed_secs(), 1.0)
                 .skew_x(skew)
                 .with(Wave::triangle(3.0, skew, 0.0))
@@ -77,10 +72,10 @@ pub fn control_broadcast_fx(
         );
     } else if input.just_pressed(KeyCode::KeyC) {
         info!("C - Clearing all effects");
-        material.effect_stack.clear();
+        broadcast.clear_effects();
     } else if input.just_pressed(KeyCode::KeyR) {
         info!("R - Blue wave effect!");
-        material.effect_stack.push(
+        broadcast.push_effect(
             EffectBuilder::looping(time.elapsed_secs(), 3.0)
                 .color(LinearRgba::BLUE)
                 .with(Wave::sine(1.0, 0.0, 360.0))
@@ -88,7 +83,7 @@ pub fn control_broadcast_fx(
         );
     } else if input.just_pressed(KeyCode::KeyT) {
         info!("T - Fade in/out effect!");
-        material.effect_stack.push(
+        broadcast.push_effect(
             EffectBuilder::looping(time.elapsed_secs(), 2.0)
                 .alpha(0.0)
                 .with(Wave::sine(1.0, 0.0, 1.0))
@@ -35,15 +35,15 @@ pub fn control_unique_fx(
     } else if input.just_pressed(KeyCode::KeyO) {
         let mut rng = rand::rng();
         for mut vfx in &mut query {
-            let random_squash = rng.random_range(-0.5..0.0);
+            let random_squash = rng.random_range(0.0..1.0);
             vfx.push_effect(
-                EffectBuilder::one_shot(time.elapsed_secs(), 0.5)
-                    .scale_y(-1.0)
-                    .with(Wave::sine(1.0, -random_squash, random_squash))
-                    .with(Anchor::BottomCenter)
-                    .scale_x(1.0)
-                    .with(Wave::sine(1.0, random_squash, -random_squash))
-                    .build(),
+                EffectBuilder::squash(
+                    time.elapsed_secs(),
+                    random_squash,
+                    0.5,
+                    Anchor::BottomCenter,
+                )
+                .build(),
             );
         }
     } else if input.just_pressed(KeyCode::KeyI) {
@@ -85,6 +85,96 @@ pub fn control_unique_fx(
                     .build(),
             );
         }
+    } else if input.just_pressed(KeyCode::KeyF) {
+        info!("KeyF - Hit flash on all Vfx entities.");
+        for mut vfx in &mut query {
+            vfx.push_effect(
+                EffectBuilder::flash(time.elapsed_secs(), LinearRgba::WHITE, 0.2).build(),
+            );
+        }
+    } else if input.just_pressed(KeyCode::KeyG) {
+        info!("KeyG - Red/blue warning pulse on all Vfx entities.");
+        for mut vfx in &mut query {
+            vfx.push_effect(
+                EffectBuilder::one_shot(time.elapsed_secs(), 2.0)
+                    .color_lerp(LinearRgba::RED, LinearRgba::BLUE)
+                    .with(Wave::triangle(2.0, 0.5, 0.5))
+                    .build(),
+            );
+        }
+    } else if input.just_pressed(KeyCode::KeyH) {
+        info!("KeyH - Pulsing selection outline on all Vfx entities.");
+        for mut vfx in &mut query {
+            vfx.push_effect(
+                EffectBuilder::one_shot(time.elapsed_secs(), 2.0)
+                    .outline(LinearRgba::rgb(1.0, 0.9, 0.2), 1.0)
+                    .with(Wave::sine(4.0, 0.5, 0.5))
+                    .build(),
+            );
+        }
+    } else if input.just_pressed(KeyCode::KeyJ) {
+        info!("KeyJ - Dissolve and reform all Vfx entities.");
+        for mut vfx in &mut query {
+            vfx.push_effect(
+                EffectBuilder::one_shot(time.elapsed_secs(), 2.0)
+                    .dissolve(LinearRgba::rgb(1.0, 0.5, 0.1), 0.1)
+                    .with(Wave::triangle(1.0, -0.5, 0.5))
+                    .build(),
+            );
+        }
+    } else if input.just_pressed(KeyCode::KeyL) {
+        info!("KeyL - Pixelate in and out on all Vfx entities.");
+        for mut vfx in &mut query {
+            vfx.push_effect(
+                EffectBuilder::one_shot(time.elapsed_secs(), 1.0)
+                    .pixelate(8.0)
+                    .with(Wave::triangle(1.0, -0.5, 0.5))
+                    .build(),
+            );
+        }
+    } else if input.just_pressed(KeyCode::KeyN) {
+        info!("KeyN - Glitch all Vfx entities.");
+        for mut vfx in &mut query {
+            vfx.push_effect(
+                EffectBuilder::one_shot(time.elapsed_secs(), 1.0)
+                    .glitch(8.0, 0.15, 1.0)
+                    .build(),
+            );
+        }
+    } else if input.just_pressed(KeyCode::KeyE) {
+        info!("KeyE - Overlay pulse on all Vfx entities.");
+        for mut vfx in &mut query {
+            vfx.push_effect(
+                EffectBuilder::one_shot(time.elapsed_secs(), 1.5)
+                    .overlay(LinearRgba::new(0.6, 0.9, 1.0, 1.0), BlendMode::Screen)
+                    .with(Wave::triangle(1.0, 0.5, 0.5))
+                    .with_overlay_scroll(Vec2::new(0.0, 0.5))
+                    .build(),
+            );
+        }
+    } else if input.just_pressed(KeyCode::KeyQ) {
+        info!("KeyQ - Hologram on all Vfx entities.");
+        for mut vfx in &mut query {
+            vfx.push_effect(
+                EffectBuilder::one_shot(time.elapsed_secs(), 3.0)
+                    .hologram(LinearRgba::new(0.3, 0.9, 1.0, 0.8), 24.0, 0.6)
+                    .build(),
+            );
+        }
+    } else if input.just_pressed(KeyCode::KeyR) {
+        info!("KeyR - Flickering fade-out on all Vfx entities.");
+        for mut vfx in &mut query {
+            vfx.push_effect(
+                EffectBuilder::one_shot(time.elapsed_secs(), 1.5)
+                    .alpha_zero()
+                    .with(Wave::saw(1.0, 0.5, 0.5))
+                    .with(AlphaClip::Dither)
+                    .alpha(0.3)
+                    .with(Wave::square(12.0, 0.5, 0.5))
+                    .with(AlphaComposite::Multiply)
+                    .build(),
+            );
+        }
     } else if input.just_pressed(KeyCode::KeyC) {
         for mut vfx in &mut query {
             vfx.clear_effects();
@@ -105,7 +195,7 @@ pub fn control_unique_fx(
         const SPACING: f32 = 50.0;
 
         let cols: usize = (COUNT as f32).sqrt().ceil() as usize;
-        let rows: usize = (COUNT + cols - 1) / cols;
+        let rows: usize = COUNT.div_ceil(cols);
         let total_w = (cols as f32 - 1.0) * SPACING;
         let total_h = (rows as f32 - 1.0) * SPACING;
         let start_x = -total_w * 0.5;
@@ -1,25 +1,102 @@
 // New module structure
+pub mod assets;
+pub mod commands;
 pub mod components;
+pub mod editor;
 pub mod effects;
 pub mod hooks;
 pub mod input;
 pub mod materials;
+pub mod messages;
+#[cfg(any(feature = "avian", feature = "rapier"))]
+pub mod physics;
 mod preludes;
 pub mod resources;
 pub mod spawners;
 pub mod systems;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 // Internal prelude is truly private - users should never need it
 use crate::preludes::internal as internal_prelude;
 pub use crate::preludes::user as prelude;
 
 use crate::internal_prelude::*;
+use bevy::asset::{AssetEventSystems, load_internal_asset};
+use bevy::camera::{CameraUpdateSystems, visibility::VisibilitySystems};
+use bevy::core_pipeline::fullscreen_material::FullscreenMaterialPlugin;
+use bevy::diagnostic::{Diagnostic, RegisterDiagnostic};
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::render::{
+    ExtractSchedule, Render, RenderApp, RenderSystems, renderer::RenderDevice,
+    storage::ShaderStorageBuffer,
+};
+use bevy::text::Text2dUpdateSystems;
+use bevy::ui_render::prelude::UiMaterialPlugin;
+#[cfg(feature = "inspector")]
+use bevy_inspector_egui::bevy_egui::{EguiPlugin, EguiPrimaryContextPass};
+#[cfg(feature = "inspector")]
+use editor::draw_vfx_inspector;
 
 #[derive(Resource)]
 pub struct HirundoPlugin {
     pub texture_path: String,
     pub atlas_dimensions: AtlasDimensions,
     pub with_camera: bool,
+    /// Spawn the built-in camera as HDR with `Bloom`, for [`Emissive`](effects::Emissive) effects
+    pub with_bloom: bool,
+    /// Noise texture for the dissolve effect, `None` uses a generated value noise
+    pub noise_texture_path: Option<String>,
+    /// Texture for the overlay effect (ice, fire, shields...)
+    pub overlay_texture_path: Option<String>,
+    /// WGSL module run at the end of both fragment shaders, see [`Self::with_custom_fragment`]
+    pub custom_fragment_path: Option<String>,
+    /// `Vfx` entities the effect storage holds
+    pub max_entities: usize,
+    /// Effects per stack (1 to `MAX_FX`)
+    pub fx_slots: usize,
+    /// Color slots the shaders evaluate per effect (1 to `MAX_COLOR_FX`)
+    pub color_slots: usize,
+    /// Alpha slots the shaders evaluate per effect (1 to `MAX_ALPHA_FX`)
+    pub alpha_slots: usize,
+    /// Spatial slots the shaders evaluate per effect (1 to `MAX_SPATIAL_FX`)
+    pub spatial_slots: usize,
+    /// Fragment slots the shaders evaluate per effect (1 to `MAX_FRAGMENT_FX`)
+    pub fragment_slots: usize,
+    /// TexturePacker/Aseprite JSON describing the atlas, replaces `atlas_dimensions` once loaded
+    pub atlas_json_path: Option<String>,
+    /// Load LDtk/Tiled levels as [`VfxLevel`](assets::VfxLevel)s, see [`Self::with_level_import`]
+    pub level_import: bool,
+    /// Hidden `Vfx` entities pre-spawned into the [`VfxPool`](resources::VfxPool)
+    pub vfx_pool_size: usize,
+    /// Keys to freeze and scrub the [`VfxTimeline`](resources::VfxTimeline)
+    pub timeline_scrub: bool,
+    /// Draw anchors, offsets and rotation arcs of spatial effects with gizmos
+    pub vfx_gizmos: bool,
+    /// Run the sync and prune systems in `FixedUpdate` instead of `Update`
+    pub fixed_update: bool,
+    /// Let `Vfx` entities with identical effect stacks share one storage slot
+    pub slot_dedup: bool,
+    /// Hold back stack uploads of `Vfx` entities culled from every view until they're visible
+    pub upload_culling: bool,
+    /// Convert every `Sprite` with a `TextureAtlas` into a `Vfx`, see [`Self::with_sprite_bridge`]
+    pub sprite_bridge: bool,
+    /// Seconds between expiry passes over `Vfx` stacks (0 = every tick)
+    pub prune_interval: f32,
+    /// How both materials blend, `Vfx` entities can override it with a [`VfxAlphaMode`]
+    pub alpha_mode: VfxAlphaMode,
+    /// Derive sprite depth from world Y in the shaders, see [`Self::with_y_sort`]
+    pub y_sort: Option<VfxYSort>,
+    /// Draw [`Vfx3d`](components::Vfx3d) entities as billboards with [`VfxMaterial3d`](materials::VfxMaterial3d)
+    pub with_3d: bool,
+    /// Draw [`VfxInstanced`](components::VfxInstanced) entities as one batch mesh
+    pub instancing: bool,
+    /// Draw [`UiVfx`](components::UiVfx) nodes with [`VfxUiMaterial`](materials::VfxUiMaterial)
+    pub with_ui: bool,
+    /// Full-screen pass on 2D cameras for the [`ScreenVfx`](resources::ScreenVfx) stack
+    pub screen_vfx: bool,
+    /// egui window over `Vfx` entities and their effects (`inspector` feature)
+    pub inspector: bool,
 }
 
 impl Plugin for HirundoPlugin {
@@ -29,31 +106,422 @@ impl Plugin for HirundoPlugin {
             texture_path: self.texture_path.clone(),
             atlas_dimensions: self.atlas_dimensions.clone(),
             with_camera: self.with_camera,
+            with_bloom: self.with_bloom,
+            noise_texture_path: self.noise_texture_path.clone(),
+            overlay_texture_path: self.overlay_texture_path.clone(),
+            custom_fragment_path: self.custom_fragment_path.clone(),
+            max_entities: self.max_entities,
+            fx_slots: self.fx_slots,
+            color_slots: self.color_slots,
+            alpha_slots: self.alpha_slots,
+            spatial_slots: self.spatial_slots,
+            fragment_slots: self.fragment_slots,
+            atlas_json_path: self.atlas_json_path.clone(),
+            level_import: self.level_import,
+            vfx_pool_size: self.vfx_pool_size,
+            timeline_scrub: self.timeline_scrub,
+            vfx_gizmos: self.vfx_gizmos,
+            fixed_update: self.fixed_update,
+            slot_dedup: self.slot_dedup,
+            upload_culling: self.upload_culling,
+            sprite_bridge: self.sprite_bridge,
+            prune_interval: self.prune_interval,
+            alpha_mode: self.alpha_mode,
+            y_sort: self.y_sort,
+            with_3d: self.with_3d,
+            instancing: self.instancing,
+            with_ui: self.with_ui,
+            screen_vfx: self.screen_vfx,
+            inspector: self.inspector,
         });
 
         // Core resources
         app.init_resource::<MeshTagAllocator>();
+        app.init_resource::<VfxRegistry>();
         app.init_resource::<EffectStorageData>();
+        app.init_resource::<EffectStorageWrites>();
+        app.init_resource::<EffectTemplates>();
+        app.init_resource::<SpriteNames>();
+        app.init_resource::<SpriteRects>();
+        app.init_resource::<VfxPool>();
+        app.init_resource::<VfxTimeline>();
+        app.init_resource::<VfxStats>();
+        app.init_resource::<VfxAlphaModeMaterials>();
+        app.init_resource::<VfxAccessibility>();
+        app.init_resource::<VfxBroadcasts>();
+        app.init_resource::<BroadcastFx>();
+        app.init_resource::<BroadcastTintMeshes>();
+        app.init_resource::<VfxLod>();
+        app.init_resource::<VfxScheduler>();
+        app.init_resource::<VfxAudioDriver>();
+        app.init_resource::<VfxClock>();
+        app.init_resource::<DamageNumbers>();
+        app.add_message::<AnimationFrameEvent>();
+        app.add_message::<VfxStorageGrown>();
+        app.add_message::<BroadcastEffectFinished>();
+        app.add_message::<PlayVfx>();
+
+        // Reflection, for inspectors and scene serialization
+        app.register_type::<Vfx>()
+            .register_type::<SpriteIndex>()
+            .register_type::<VfxBroadcast>()
+            .register_type::<VfxBroadcastGroup>()
+            .register_type::<BroadcastSprite>()
+            .register_type::<BroadcastTint>()
+            .register_type::<EffectStack>()
+            .register_type::<Effect>()
+            .register_type::<Lifetime>()
+            .register_type::<Phase>()
+            .register_type::<Wave>()
+            .register_type::<Envelope>()
+            .register_type::<ColorEffect>()
+            .register_type::<AlphaEffect>()
+            .register_type::<SpatialEffect>()
+            .register_type::<FragmentEffect>()
+            .register_type::<TileCycleEffect>()
+            .register_type::<EffectAssetPlayer>()
+            .register_type::<SpriteAnimation>()
+            .register_type::<AnimationClipPlayer>()
+            .register_type::<DirectionalSprite>()
+            .register_type::<DespawnWhenEffectsFinish>()
+            .register_type::<PooledVfx>()
+            .register_type::<VfxOnSpawn>()
+            .register_type::<VfxAlphaMode>()
+            .register_type::<Vfx3d>()
+            .register_type::<VfxInstanced>()
+            .register_type::<UiVfx>()
+            .register_type::<VfxMesh>()
+            .register_type::<SpriteRect>()
+            .register_type::<VfxTint>()
+            .register_type::<SpriteToVfx>()
+            .register_type::<VfxIntensity>()
+            .register_type::<AtlasDimensions>()
+            .register_type::<SpriteNames>()
+            .register_type::<SpriteRects>()
+            .register_type::<VfxTimeline>()
+            .register_type::<VfxAccessibility>()
+            .register_type::<VfxLod>()
+            .register_type::<VfxAudioDriver>()
+            .register_type::<VfxClock>()
+            .register_type::<DamageNumbers>()
+            .register_type::<VfxStats>();
+
         app.init_asset::<ShaderStorageBuffer>();
+        app.init_resource::<VfxStorageKind>();
+        app.init_resource::<VfxStorageLimit>();
+        app.init_asset::<EffectAsset>();
+        app.init_asset_loader::<EffectAssetLoader>();
+        app.init_asset::<EffectLibrary>();
+        app.init_asset_loader::<EffectLibraryLoader>();
+        app.init_asset::<AnimationClips>();
+        app.init_asset_loader::<AnimationClipsLoader>();
+        app.init_asset::<VfxAtlasLayout>();
+        app.init_asset_loader::<AtlasJsonLoader>();
         app.insert_resource(VfxMeshHandle(Handle::default()));
         app.insert_resource(VfxMaterialHandle(Handle::default()));
+        app.insert_resource(VfxNoiseTexture(Handle::default()));
+        app.add_systems(PreStartup, (setup_noise_texture, setup_sprite_rects_buffer));
+
+        // Shaders ship inside the crate, nothing to copy into the user's assets folder
+        load_internal_asset!(
+            app,
+            VFX_SHADER_HANDLE,
+            "../assets/shaders/vfx.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            VFX_BROADCAST_SHADER_HANDLE,
+            "../assets/shaders/vfx_broadcast.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            VFX_COMMON_SHADER_HANDLE,
+            "../assets/shaders/vfx_common.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            USER_FRAGMENT_SHADER_HANDLE,
+            "../assets/shaders/user_fragment.wgsl",
+            Shader::from_wgsl
+        );
+        // The user's module only has to be loaded for the shaders to find its import path
+        if let Some(path) = &self.custom_fragment_path {
+            let shader = app.world().resource::<AssetServer>().load::<Shader>(path);
+            app.insert_resource(VfxCustomFragmentShader(shader));
+        }
 
         // Per-entity VFX material (unique effects)
         app.add_plugins(Material2dPlugin::<VfxMaterial>::default());
-        app.add_systems(PreStartup, setup_vfx_assets);
+        app.add_systems(
+            PreStartup,
+            setup_vfx_assets
+                .after(setup_noise_texture)
+                .after(setup_sprite_rects_buffer),
+        );
+        app.configure_sets(
+            Update,
+            (HirundoSet::Sync, HirundoSet::Upload, HirundoSet::Prune).chain(),
+        );
+        if self.fixed_update {
+            app.configure_sets(FixedUpdate, (HirundoSet::Sync, HirundoSet::Prune).chain());
+        }
+        app.add_systems(Update, upload_sprite_rects.in_set(HirundoSet::Upload));
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            // Dirty effect slots are written into the storage buffer in place
+            render_app
+                .init_resource::<EffectStorageWrites>()
+                .add_systems(ExtractSchedule, extract_effect_storage_writes)
+                .add_systems(
+                    Render,
+                    write_effect_storage.in_set(RenderSystems::PrepareResources),
+                );
+        }
+        // Effect logic, in `FixedUpdate` for lockstep games; the upload always runs per frame
+        let logic_schedule = if self.fixed_update {
+            FixedUpdate.intern()
+        } else {
+            Update.intern()
+        };
+        app.add_systems(
+            logic_schedule,
+            (
+                (
+                    update_directional_sprites,
+                    play_animation_clips,
+                    advance_sprite_animations,
+                    play_effect_assets,
+                    advance_vfx_crossfades,
+                    sync_vfx_to_internal,
+                )
+                    .chain()
+                    .in_set(HirundoSet::Sync),
+                prune_expired_effects
+                    .run_if(any_with_component::<Vfx>)
+                    .run_if(prune_interval_elapsed)
+                    .in_set(HirundoSet::Prune),
+            ),
+        );
+        // Effects are stamped and drawn on the virtual clock, so finished entities go on
+        // it too: the fixed clock lags, and some frames have no fixed step at all
+        app.add_systems(
+            Update,
+            (despawn_finished_vfx, reclaim_pooled_vfx)
+                .chain()
+                .in_set(HirundoSet::Prune),
+        );
         app.add_systems(
             Update,
             (
-                sync_vfx_to_internal,
-                update_effect_storage_buffer,
-                prune_expired_effects,
-            )
-                .chain(),
+                show_pooled_vfx.in_set(HirundoSet::Sync),
+                refresh_vfx_accessibility
+                    .run_if(resource_changed::<VfxAccessibility>)
+                    .in_set(HirundoSet::Sync),
+                update_effect_storage_buffer
+                    .run_if(effect_storage_needs_update)
+                    .in_set(HirundoSet::Upload),
+                update_vfx_stats.after(HirundoSet::Prune),
+            ),
         );
 
+        // Bevy sprites adopted into Vfx, ahead of the systems reading them
+        app.add_systems(Update, convert_sprites_to_vfx.before(HirundoSet::Sync));
+
+        // Optional: Skip uploads for culled entities
+        if self.upload_culling {
+            app.add_systems(
+                Update,
+                upload_revealed_vfx
+                    .run_if(any_with_component::<VfxCulledUpload>)
+                    .in_set(HirundoSet::Sync),
+            );
+        }
+        app.register_diagnostic(Diagnostic::new(VfxStats::ENTITIES))
+            .register_diagnostic(Diagnostic::new(VfxStats::ACTIVE_EFFECTS))
+            .register_diagnostic(Diagnostic::new(VfxStats::DIRTY_SLOTS))
+            .register_diagnostic(Diagnostic::new(VfxStats::BYTES_UPLOADED).with_suffix(" B"))
+            .register_diagnostic(Diagnostic::new(VfxStats::BATCHES));
+
         // Broadcast VFX material (shared effects) - always available
         app.add_plugins(Material2dPlugin::<VfxBroadcastMaterial>::default());
-        app.add_systems(PreStartup, setup_broadcast_material);
+        app.add_systems(
+            PreStartup,
+            setup_broadcast_material
+                .after(setup_noise_texture)
+                .after(setup_sprite_rects_buffer),
+        );
+        app.add_systems(
+            Update,
+            (
+                (sync_broadcast_sprites, sync_broadcast_tints).in_set(HirundoSet::Sync),
+                apply_broadcast_fx.in_set(HirundoSet::Upload),
+                (update_broadcast_effect_stack, prune_expired_broadcasts).in_set(HirundoSet::Prune),
+            ),
+        );
+        app.add_systems(PostUpdate, sync_broadcast_groups.before(AssetEventSystems));
+
+        // Per-entity alpha modes, drawn with copies of the material kept in step with it
+        app.add_systems(Update, apply_vfx_alpha_modes.in_set(HirundoSet::Sync));
+        app.add_systems(
+            PostUpdate,
+            sync_alpha_mode_materials.before(AssetEventSystems),
+        );
+
+        // Optional: Billboarded `Vfx3d` entities in 3D scenes
+        if self.with_3d {
+            app.add_plugins(MaterialPlugin::<VfxMaterial3d>::default());
+            app.add_systems(PreStartup, setup_vfx_material_3d.after(setup_vfx_assets));
+            app.add_systems(PostUpdate, sync_vfx_material_3d.before(AssetEventSystems));
+        }
+
+        // Optional: `VfxInstanced` entities drawn as one batch mesh
+        if self.instancing {
+            app.add_systems(PreStartup, setup_vfx_instance_batch.after(setup_vfx_assets));
+            app.add_systems(
+                PostUpdate,
+                (
+                    update_vfx_instance_batch
+                        .after(TransformSystems::Propagate)
+                        .after(VisibilitySystems::VisibilityPropagate),
+                    sync_vfx_instanced_material,
+                )
+                    .before(AssetEventSystems),
+            );
+        }
+
+        // Optional: Effects on UI nodes
+        if self.with_ui {
+            app.add_plugins(UiMaterialPlugin::<VfxUiMaterial>::default());
+            app.add_systems(
+                Update,
+                prune_expired_ui_vfx
+                    .run_if(any_with_component::<UiVfx>)
+                    .in_set(HirundoSet::Prune),
+            );
+            app.add_systems(PostUpdate, sync_ui_vfx_materials.before(AssetEventSystems));
+        }
+
+        // Optional: Full-screen effects
+        if self.screen_vfx {
+            load_internal_asset!(
+                app,
+                VFX_SCREEN_SHADER_HANDLE,
+                "../assets/shaders/vfx_screen.wgsl",
+                Shader::from_wgsl
+            );
+            app.add_plugins(FullscreenMaterialPlugin::<ScreenVfxUniform>::default());
+            app.init_resource::<ScreenVfx>();
+            app.add_systems(
+                Update,
+                (
+                    add_screen_vfx_to_cameras.in_set(HirundoSet::Sync),
+                    prune_expired_screen_vfx.in_set(HirundoSet::Prune),
+                ),
+            );
+            app.add_systems(PostUpdate, update_screen_vfx.after(CameraUpdateSystems));
+        }
+
+        // Effects on `Text2d`, evaluated on the CPU
+        app.add_systems(
+            Update,
+            prune_expired_text_vfx
+                .run_if(any_with_component::<TextVfx>)
+                .in_set(HirundoSet::Prune),
+        );
+        app.add_systems(
+            PostUpdate,
+            (
+                apply_text_vfx.before(TransformSystems::Propagate),
+                apply_text_vfx_glyphs.after(Text2dUpdateSystems),
+            )
+                .run_if(any_with_component::<TextVfx>),
+        );
+
+        // Frozen effect time, for inspecting frames while authoring
+        app.add_systems(
+            Update,
+            apply_vfx_timeline
+                .run_if(resource_changed::<VfxTimeline>)
+                .in_set(HirundoSet::Upload),
+        );
+
+        // Effects triggered with `PlayVfx` messages, `PlayEffect` events and the `VfxScheduler`
+        app.add_systems(
+            Update,
+            (run_vfx_scheduler, play_vfx_messages)
+                .chain()
+                .in_set(HirundoSet::Sync),
+        );
+        app.add_observer(play_effect_observer);
+
+        // Impacts on colliding `Vfx` entities, with the `avian` or `rapier` feature
+        #[cfg(any(feature = "avian", feature = "rapier"))]
+        app.init_resource::<physics::CollisionVfx>();
+        #[cfg(feature = "avian")]
+        app.add_systems(
+            Update,
+            physics::play_avian_collision_vfx
+                .run_if(resource_exists::<Messages<avian2d::prelude::CollisionStart>>)
+                .before(HirundoSet::Sync),
+        );
+        #[cfg(feature = "rapier")]
+        app.add_systems(
+            Update,
+            physics::play_rapier_collision_vfx
+                .run_if(resource_exists::<Messages<bevy_rapier2d::prelude::CollisionEvent>>)
+                .before(HirundoSet::Sync),
+        );
+
+        // Cheaper shading when zoomed out
+        app.add_systems(Update, apply_vfx_lod.in_set(HirundoSet::Upload));
+
+        // Audio-reactive waves
+        app.add_systems(Update, apply_vfx_audio_driver.in_set(HirundoSet::Upload));
+
+        // Optional: Atlas layout from a TexturePacker/Aseprite export
+        if self.atlas_json_path.is_some() {
+            app.add_systems(PreStartup, load_atlas_layout);
+            app.add_systems(Update, apply_atlas_layout.in_set(HirundoSet::Sync));
+        }
+
+        // Optional: Decorations from LDtk/Tiled levels
+        if self.level_import {
+            app.init_asset::<VfxLevel>();
+            app.init_asset_loader::<LdtkLevelLoader>();
+            app.init_asset_loader::<TiledLevelLoader>();
+            app.register_type::<VfxLevelRoot>();
+            app.add_systems(Update, spawn_vfx_levels.in_set(HirundoSet::Sync));
+        }
+
+        // Optional: Pre-spawned pool for short-lived effects
+        if self.vfx_pool_size > 0 {
+            app.add_systems(Startup, spawn_vfx_pool);
+        }
+
+        // Optional: Timeline scrubbing keys
+        if self.timeline_scrub {
+            app.add_systems(Update, control_timeline_scrub.before(apply_vfx_timeline));
+        }
+
+        // Optional: Debug overlay for spatial effects
+        if self.vfx_gizmos {
+            app.init_gizmo_group::<VfxGizmos>();
+            app.add_systems(
+                PostUpdate,
+                draw_vfx_gizmos.after(TransformSystems::Propagate),
+            );
+        }
+
+        // Optional: egui window over Vfx entities
+        #[cfg(feature = "inspector")]
+        if self.inspector {
+            if !app.is_plugin_added::<EguiPlugin>() {
+                app.add_plugins(EguiPlugin::default());
+            }
+            app.add_systems(EguiPrimaryContextPass, draw_vfx_inspector);
+        }
 
         // Optional: Camera spawn and controls
         if self.with_camera {
@@ -61,6 +529,30 @@ impl Plugin for HirundoPlugin {
             app.add_systems(Update, control_2d_camera);
         }
     }
+
+    fn finish(&self, app: &mut App) {
+        // The render device exists by now, before the `PreStartup` setup creates the
+        // storage: without storage buffers (WebGL2) effects go in data textures
+        if let Some(render_device) = app.world().get_resource::<RenderDevice>() {
+            let kind = VfxStorageKind::for_device(render_device);
+            if kind == VfxStorageKind::Textures && !cfg!(feature = "data_textures") {
+                info!("This adapter has no storage buffers, storing effects in data textures");
+            }
+            let limit = VfxStorageLimit::for_device(render_device, kind);
+            app.insert_resource(kind).insert_resource(limit);
+            // A data texture row per stack, no taller than the device allows
+            let mut storage_data = app.world_mut().resource_mut::<EffectStorageData>();
+            if storage_data.effects.len() > limit.0 {
+                warn!(
+                    "HirundoPlugin::with_max_entities({}) is past this device's {} effect \
+                     stacks, clamping it",
+                    storage_data.effects.len(),
+                    limit.0
+                );
+                storage_data.effects.truncate(limit.0);
+            }
+        }
+    }
 }
 
 impl Default for HirundoPlugin {
@@ -72,8 +564,36 @@ impl Default for HirundoPlugin {
                 cell_size: Vec2::new(40.0, 40.0),
                 sprite_size: Vec2::new(32.0, 32.0),
                 padding: Vec2::new(4.0, 4.0),
+                uv_inset: Vec2::ZERO,
             },
             with_camera: false,
+            with_bloom: false,
+            noise_texture_path: None,
+            overlay_texture_path: None,
+            custom_fragment_path: None,
+            max_entities: MAX_VFX_ENTITIES,
+            fx_slots: MAX_FX,
+            color_slots: MAX_COLOR_FX,
+            alpha_slots: MAX_ALPHA_FX,
+            spatial_slots: MAX_SPATIAL_FX,
+            fragment_slots: MAX_FRAGMENT_FX,
+            atlas_json_path: None,
+            level_import: false,
+            vfx_pool_size: 0,
+            timeline_scrub: false,
+            vfx_gizmos: false,
+            fixed_update: false,
+            slot_dedup: false,
+            upload_culling: false,
+            sprite_bridge: false,
+            prune_interval: 0.0,
+            alpha_mode: VfxAlphaMode::Blend,
+            y_sort: None,
+            with_3d: false,
+            instancing: false,
+            with_ui: false,
+            screen_vfx: false,
+            inspector: false,
         }
     }
 }
@@ -85,6 +605,266 @@ impl HirundoPlugin {
         self
     }
 
+    /// Render the built-in camera in HDR with bloom, so emissive effects glow.
+    ///
+    /// Implies [`Self::with_camera`]. With your own camera, add `Bloom` to it instead.
+    pub fn with_bloom(mut self) -> Self {
+        self.with_camera = true;
+        self.with_bloom = true;
+        self
+    }
+
+    /// Use a custom noise texture for dissolve effects (red channel is the threshold source)
+    pub fn with_noise_texture(mut self, path: &str) -> Self {
+        self.noise_texture_path = Some(path.to_string());
+        self
+    }
+
+    /// Run your own WGSL at the end of both fragment shaders, after every built-in effect.
+    ///
+    /// The module at `path` (in your assets folder) declares
+    /// `#define_import_path hirundo::custom_fragment` and defines
+    /// `fn custom_fragment(input: UserFragmentInput) -> vec4<f32>`, importing the input from
+    /// `hirundo::user_fragment`. It's compiled with the same shader defs as the VFX shaders,
+    /// plus `HIRUNDO_USER_FRAGMENT`.
+    pub fn with_custom_fragment(mut self, path: &str) -> Self {
+        self.custom_fragment_path = Some(path.to_string());
+        self
+    }
+
+    /// Texture composited over sprites by the overlay effect.
+    ///
+    /// The whole texture is stretched over each sprite; make it tileable if you scroll it.
+    pub fn with_overlay_texture(mut self, path: &str) -> Self {
+        self.overlay_texture_path = Some(path.to_string());
+        self
+    }
+
+    /// Size of the effect storage, i.e. how many `Vfx` entities can exist at once
+    /// (default `MAX_VFX_ENTITIES`). Each one costs a full `EffectStack` of GPU memory.
+    pub fn with_max_entities(mut self, entities: usize) -> Self {
+        self.max_entities = entities.max(1);
+        self
+    }
+
+    /// Effects a stack holds at once, clamped to `1..=MAX_FX`.
+    ///
+    /// Pushing onto a full stack overwrites its first slot, and the shaders only loop
+    /// over this many effects per sprite.
+    pub fn with_max_fx(mut self, effects: usize) -> Self {
+        self.fx_slots = effects.clamp(1, MAX_FX);
+        self
+    }
+
+    /// Color sub-effects evaluated per effect, clamped to `1..=MAX_COLOR_FX`.
+    /// Effects layering more colors drop the later ones.
+    pub fn with_max_color_fx(mut self, slots: usize) -> Self {
+        self.color_slots = slots.clamp(1, MAX_COLOR_FX);
+        self
+    }
+
+    /// Alpha sub-effects evaluated per effect, clamped to `1..=MAX_ALPHA_FX`.
+    /// Effects layering more alphas drop the later ones.
+    pub fn with_max_alpha_fx(mut self, slots: usize) -> Self {
+        self.alpha_slots = slots.clamp(1, MAX_ALPHA_FX);
+        self
+    }
+
+    /// Fragment sub-effects evaluated per effect, clamped to `1..=MAX_FRAGMENT_FX`.
+    /// Effects using more fragment kinds drop the later ones.
+    pub fn with_max_fragment_fx(mut self, slots: usize) -> Self {
+        self.fragment_slots = slots.clamp(1, MAX_FRAGMENT_FX);
+        self
+    }
+
+    /// Spatial sub-effects evaluated per effect, clamped to `1..=MAX_SPATIAL_FX`.
+    ///
+    /// Every slot costs vertex work for every sprite, so games that never stack many
+    /// spatial kinds can lower this. Effects using more kinds than this keep the first
+    /// ones in [`SpatialKind`](effects::SpatialKind) order and drop the rest.
+    pub fn with_max_spatial_fx(mut self, slots: usize) -> Self {
+        self.spatial_slots = slots.clamp(1, MAX_SPATIAL_FX);
+        self
+    }
+
+    #[deprecated(note = "renamed to `with_max_spatial_fx`")]
+    pub fn with_spatial_slots(self, slots: usize) -> Self {
+        self.with_max_spatial_fx(slots)
+    }
+
+    /// Read the atlas grid and sprite names from a TexturePacker or Aseprite JSON export
+    /// (hash or array format) instead of hand-configuring `atlas_dimensions`.
+    /// Aseprite's animation tags are loaded too.
+    ///
+    /// Frames that are equally sized and laid out on a grid configure the uniform grid;
+    /// otherwise every frame gets its own rect in [`SpriteRects`](resources::SpriteRects).
+    /// Until the file has loaded, the configured `atlas_dimensions` are used. Names, without their image
+    /// extension, are looked up through the [`SpriteNames`](resources::SpriteNames) resource.
+    pub fn with_atlas_json(mut self, path: &str) -> Self {
+        self.atlas_json_path = Some(path.to_string());
+        self
+    }
+
+    /// Load `.ldtk` projects and `.tmj` Tiled maps as [`VfxLevel`](assets::VfxLevel)s and
+    /// spawn their entity layers under [`VfxLevelRoot`](components::VfxLevelRoot)s, each
+    /// entity or object a `Vfx` (or broadcast) decoration with its sprite and effect
+    /// template taken from its `sprite`, `effect` and `broadcast` fields.
+    pub fn with_level_import(mut self) -> Self {
+        self.level_import = true;
+        self
+    }
+
+    /// Pre-spawn `size` hidden `Vfx` entities into the [`VfxPool`](resources::VfxPool),
+    /// for effects spawned too often to pay for a new entity each time
+    pub fn with_vfx_pool(mut self, size: usize) -> Self {
+        self.vfx_pool_size = size;
+        self
+    }
+
+    /// Scrub effect time from the keyboard: F8 freezes/resumes the
+    /// [`VfxTimeline`](resources::VfxTimeline), `,`/`.` step one frame (ten with Shift)
+    pub fn with_timeline_scrub(mut self) -> Self {
+        self.timeline_scrub = true;
+        self
+    }
+
+    /// Run the CPU-side effect logic (animation and sprite sync, freeing expired slots)
+    /// in `FixedUpdate`, so it follows the fixed clock rather than the render framerate.
+    /// Uploads to the GPU, despawning finished entities and pool reclaim still happen
+    /// every `Update`, on the clock the shaders draw with.
+    ///
+    /// `HirundoSet::Sync` and `HirundoSet::Prune` are then configured in `FixedUpdate` too.
+    pub fn with_fixed_update(mut self) -> Self {
+        self.fixed_update = true;
+        self
+    }
+
+    /// Let `Vfx` entities whose stacks (effects, start times and sprite) are identical
+    /// share one storage slot and `MeshTag`, tracked by the [`VfxRegistry`](resources::VfxRegistry).
+    /// A crowd playing the same effect then uploads it once; an entity changing its
+    /// stack moves to a slot of its own again.
+    pub fn with_slot_dedup(mut self) -> Self {
+        self.slot_dedup = true;
+        self
+    }
+
+    /// Don't upload effect changes of `Vfx` entities that were culled from every view
+    /// (`ViewVisibility`) last frame; they're uploaded once they come back into view,
+    /// drawing their old stack for that one frame. Cuts buffer writes in large scrolling
+    /// worlds where most entities are off-screen while their effects play and expire.
+    ///
+    /// Entities just spawned and [`VfxPool`](resources::VfxPool) entities always upload.
+    pub fn with_upload_culling(mut self) -> Self {
+        self.upload_culling = true;
+        self
+    }
+
+    /// Convert every `Sprite` spawned with a `TextureAtlas` into a `Vfx`, as if it were
+    /// marked [`SpriteToVfx`](components::SpriteToVfx). For projects whose sprite sheet is
+    /// the Hirundo atlas; without this only marked sprites are converted.
+    pub fn with_sprite_bridge(mut self) -> Self {
+        self.sprite_bridge = true;
+        self
+    }
+
+    /// Only free the slots of expired effects every `seconds` instead of every tick.
+    /// `DespawnWhenEffectsFinish` and pool reclaim still check expiry every tick: an
+    /// ended one-shot draws as identity, so waiting would show a faded sprite again.
+    pub fn with_prune_interval(mut self, seconds: f32) -> Self {
+        self.prune_interval = seconds.max(0.0);
+        self
+    }
+
+    /// Blend both materials with `mode` instead of straight alpha, e.g.
+    /// [`VfxAlphaMode::Additive`] for glows. Single `Vfx` entities can insert a
+    /// [`VfxAlphaMode`] component to differ from this.
+    pub fn with_alpha_mode(mut self, mode: VfxAlphaMode) -> Self {
+        self.alpha_mode = mode;
+        self
+    }
+
+    /// Y-sort sprites for top-down games: the vertex shaders add `offset + scale * y` to
+    /// each sprite's Z, `y` being the world Y of its bottom edge after offsets and other
+    /// spatial effects, so a sprite bobbing down passes in front of its neighbours.
+    ///
+    /// Use a small negative `scale` (e.g. `-0.001`) and keep the resulting Z inside the
+    /// camera's near/far range. Both materials then write depth, since that is what
+    /// orders blended sprites; fully transparent pixels are discarded, but soft edges
+    /// hide what's behind them rather than blend over it.
+    pub fn with_y_sort(mut self, scale: f32, offset: f32) -> Self {
+        self.y_sort = Some(VfxYSort { scale, offset });
+        self
+    }
+
+    /// Drive billboarded quads in 3D scenes from the same effect stacks: `Vfx3d` entities
+    /// are `Vfx` entities drawn with [`VfxMaterial3d`](materials::VfxMaterial3d), facing the
+    /// camera from their origin. The quad is the sprite's size in pixels, so scale the
+    /// `Transform` to your world units (e.g. `1.0 / 32.0` for one unit per 32px sprite).
+    pub fn with_3d(mut self) -> Self {
+        self.with_3d = true;
+        self
+    }
+
+    /// Draw every [`VfxInstanced`](components::VfxInstanced) entity in one draw: their
+    /// sprites are packed into a single mesh, each vertex carrying its sprite's transform
+    /// and `MeshTag`, instead of each entity being extracted, culled and sorted on its
+    /// own. For thousands of unique `Vfx` entities that don't need a [`VfxMesh`] or their
+    /// own [`VfxAlphaMode`]; the mesh is rebuilt on frames where one of them moves.
+    pub fn with_instancing(mut self) -> Self {
+        self.instancing = true;
+        self
+    }
+
+    /// Put effects on UI nodes: a [`UiVfx`](components::UiVfx) node draws an atlas sprite
+    /// over itself with its own effect stack, so buttons and HUD icons can flash, pulse
+    /// and shake with the same builders as world sprites.
+    pub fn with_ui(mut self) -> Self {
+        self.with_ui = true;
+        self
+    }
+
+    /// Put effects on the whole screen: the [`ScreenVfx`](resources::ScreenVfx) stack is
+    /// drawn by a full-screen pass on every `Camera2d`, so flashes, fades to black, shake
+    /// and chromatic aberration use the same builders as sprites.
+    pub fn with_screen_vfx(mut self) -> Self {
+        self.screen_vfx = true;
+        self
+    }
+
+    /// Draw each `Vfx` entity's spatial anchors, current offset vector and rotation arcs
+    /// with gizmos, to see which pivot an effect actually turns around.
+    /// Toggle the [`VfxGizmos`](systems::VfxGizmos) group to hide them at runtime.
+    pub fn with_vfx_gizmos(mut self) -> Self {
+        self.vfx_gizmos = true;
+        self
+    }
+
+    /// Open an egui window listing each `Vfx` entity with its active effects, the time
+    /// one-shots have left, and sliders for their wave amplitude and frequency.
+    /// Adds `EguiPlugin` unless the app already has it.
+    #[cfg(feature = "inspector")]
+    pub fn with_inspector(mut self) -> Self {
+        self.inspector = true;
+        self
+    }
+
+    /// Shader specialization for the VFX materials, reading `storage`
+    pub(crate) fn shader_key(&self, storage: VfxStorageKind) -> VfxShaderKey {
+        VfxShaderKey {
+            fx_slots: self.fx_slots as u32,
+            color_slots: self.color_slots as u32,
+            alpha_slots: self.alpha_slots as u32,
+            spatial_slots: self.spatial_slots as u32,
+            fragment_slots: self.fragment_slots as u32,
+            user_fragment: self.custom_fragment_path.is_some(),
+            alpha_mode: self.alpha_mode,
+            y_sort: self.y_sort,
+            billboard: false,
+            instanced: false,
+            data_textures: storage == VfxStorageKind::Textures,
+        }
+    }
+
     pub fn with_texture(mut self, path: &str) -> Self {
         self.texture_path = path.to_string();
         self
@@ -114,4 +894,11 @@ impl HirundoPlugin {
         self.atlas_dimensions.padding = size;
         self
     }
+
+    /// Sample sprites `inset` pixels in from their edges, see [`AtlasDimensions::uv_inset`].
+    /// Kept when an atlas JSON layout is applied.
+    pub fn with_uv_inset(mut self, inset: Vec2) -> Self {
+        self.atlas_dimensions.uv_inset = inset;
+        self
+    }
 }
@@ -0,0 +1,68 @@
+use crate::internal_prelude::*;
+use bevy::render::render_resource::{BlendComponent, BlendFactor, BlendOperation, BlendState};
+use bevy::sprite_render::AlphaMode2d;
+use std::hash::{Hash, Hasher};
+
+/// How VFX sprites are composited over what's behind them.
+///
+/// Set for both materials with `HirundoPlugin::with_alpha_mode`, per material through
+/// [`VfxShaderKey::alpha_mode`], or per `Vfx` entity by inserting it as a component
+/// (the entity then draws with a copy of the material in that mode).
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub enum VfxAlphaMode {
+    /// Straight alpha blending
+    #[default]
+    Blend,
+    /// No blending, alpha is ignored
+    Opaque,
+    /// Pixels below the cutoff alpha are discarded, the rest drawn opaque
+    Mask(f32),
+    /// Color times alpha is added to the target: glows, sparks, light
+    Additive,
+    /// Colors are already multiplied by alpha (premultiplied textures, custom fragments)
+    Premultiplied,
+}
+
+impl VfxAlphaMode {
+    /// Render phase the material is queued in; additive and premultiplied sort as blended
+    pub fn alpha_mode_2d(self) -> AlphaMode2d {
+        match self {
+            Self::Opaque => AlphaMode2d::Opaque,
+            Self::Mask(cutoff) => AlphaMode2d::Mask(cutoff),
+            Self::Blend | Self::Additive | Self::Premultiplied => AlphaMode2d::Blend,
+        }
+    }
+
+    /// Blend state replacing the one `AlphaMode2d` gives the pipeline, if it has none for this mode
+    pub fn blend_state(self) -> Option<BlendState> {
+        match self {
+            Self::Additive => Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::SrcAlpha,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            }),
+            Self::Premultiplied => Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+            Self::Blend | Self::Opaque | Self::Mask(_) => None,
+        }
+    }
+}
+
+// Pipelines are keyed on the mode, the mask cutoff by its bits
+impl Eq for VfxAlphaMode {}
+
+impl Hash for VfxAlphaMode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let Self::Mask(cutoff) = self {
+            cutoff.to_bits().hash(state);
+        }
+    }
+}
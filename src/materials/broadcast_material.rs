@@ -1,4 +1,18 @@
 use crate::internal_prelude::*;
+use bevy::asset::uuid_handle;
+use bevy::{
+    ecs::system::SystemParamItem,
+    render::{
+        render_resource::{
+            AsBindGroupError, BindGroupLayout, BindGroupLayoutEntry, UnpreparedBindGroup,
+        },
+        renderer::RenderDevice,
+    },
+};
+
+/// `assets/shaders/vfx_broadcast.wgsl`, embedded in the crate by `HirundoPlugin`
+pub const VFX_BROADCAST_SHADER_HANDLE: Handle<Shader> =
+    uuid_handle!("4c0cd8c3-5f3d-48b1-80a1-66db2afe42ac");
 
 /// Material for broadcasting a single shared EffectStack to many entities.
 /// Unlike VfxMaterial which uses a storage buffer indexed by mesh tag,
@@ -6,27 +20,138 @@ use crate::internal_prelude::*;
 ///
 /// Use this when you want 10,000+ entities to animate with the same effect,
 /// achieving better performance through uniform memory access patterns.
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+#[derive(Asset, TypePath, Debug, Clone)]
 pub struct VfxBroadcastMaterial {
+    pub texture: Handle<Image>,
+
+    pub effect_stack: EffectStack,
+
+    pub atlas_dimensions: AtlasDimensions,
+
+    pub noise_texture: Handle<Image>,
+
+    pub overlay_texture: Option<Handle<Image>>,
+
+    /// Per-sprite pixel rects, see [`SpriteRects`]
+    pub sprite_rects: VfxStorage,
+    /// Frozen effect time, see [`VfxTimeline`]
+    pub timeline: VfxTimelineUniform,
+    /// Effects skipped when zoomed out, see [`VfxLod`]
+    pub lod: VfxLodUniform,
+    /// Levels of audio waves, see [`VfxAudioDriver`]
+    pub audio: VfxAudioUniform,
+    /// Shader specialization, see [`VfxShaderKey`]
+    pub shader_key: VfxShaderKey,
+}
+
+/// Bindings of [`VfxBroadcastMaterial`] and [`VfxUiMaterial`] but the sprite rects
+/// [`VfxStorage`] (8), whose binding type depends on the adapter
+#[derive(AsBindGroup)]
+pub(crate) struct VfxStackBindings {
     #[texture(0)]
     #[sampler(1)]
     pub texture: Handle<Image>,
-
     #[uniform(2)]
     pub effect_stack: EffectStack,
-
     #[uniform(3)]
     pub atlas_dimensions: AtlasDimensions,
+    #[texture(4)]
+    #[sampler(5)]
+    pub noise_texture: Handle<Image>,
+    #[texture(6)]
+    #[sampler(7)]
+    pub overlay_texture: Option<Handle<Image>>,
+    #[uniform(9)]
+    pub timeline: VfxTimelineUniform,
+    #[uniform(10)]
+    pub lod: VfxLodUniform,
+    #[uniform(11)]
+    pub audio: VfxAudioUniform,
+}
+
+impl From<&VfxBroadcastMaterial> for VfxStackBindings {
+    fn from(material: &VfxBroadcastMaterial) -> Self {
+        Self {
+            texture: material.texture.clone(),
+            effect_stack: material.effect_stack.clone(),
+            atlas_dimensions: material.atlas_dimensions.clone(),
+            noise_texture: material.noise_texture.clone(),
+            overlay_texture: material.overlay_texture.clone(),
+            timeline: material.timeline,
+            lod: material.lod,
+            audio: material.audio,
+        }
+    }
+}
+
+impl AsBindGroup for VfxBroadcastMaterial {
+    type Data = VfxShaderKey;
+    type Param = VfxBindGroupParam;
+
+    fn label() -> &'static str {
+        "vfx_broadcast_material"
+    }
+
+    fn bind_group_data(&self) -> Self::Data {
+        self.shader_key
+    }
+
+    fn unprepared_bind_group(
+        &self,
+        layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        param: &mut SystemParamItem<'_, '_, Self::Param>,
+        force_no_bindless: bool,
+    ) -> Result<UnpreparedBindGroup, AsBindGroupError> {
+        let mut bind_group = VfxStackBindings::from(self).unprepared_bind_group(
+            layout,
+            render_device,
+            param,
+            force_no_bindless,
+        )?;
+        bind_group
+            .bindings
+            .push(storage_binding(8, &self.sprite_rects, param)?);
+        Ok(bind_group)
+    }
+
+    fn bind_group_layout_entries(
+        render_device: &RenderDevice,
+        force_no_bindless: bool,
+    ) -> Vec<BindGroupLayoutEntry> {
+        let mut entries =
+            VfxStackBindings::bind_group_layout_entries(render_device, force_no_bindless);
+        entries.push(storage_layout_entry(
+            render_device,
+            8,
+            VfxStorageContents::SpriteRects,
+        ));
+        entries
+    }
+}
+
+impl From<&VfxBroadcastMaterial> for VfxShaderKey {
+    fn from(material: &VfxBroadcastMaterial) -> Self {
+        material.shader_key
+    }
 }
 
 impl Material2d for VfxBroadcastMaterial {
     fn vertex_shader() -> bevy::shader::ShaderRef {
-        "shaders/vfx_broadcast.wgsl".into()
+        VFX_BROADCAST_SHADER_HANDLE.into()
     }
     fn fragment_shader() -> bevy::shader::ShaderRef {
-        "shaders/vfx_broadcast.wgsl".into()
+        VFX_BROADCAST_SHADER_HANDLE.into()
     }
     fn alpha_mode(&self) -> bevy::sprite_render::AlphaMode2d {
-        bevy::sprite_render::AlphaMode2d::Blend
+        self.shader_key.alpha_mode.alpha_mode_2d()
+    }
+    fn specialize(
+        descriptor: &mut bevy::render::render_resource::RenderPipelineDescriptor,
+        _layout: &bevy::mesh::MeshVertexBufferLayoutRef,
+        key: bevy::sprite_render::Material2dKey<Self>,
+    ) -> Result<(), bevy::render::render_resource::SpecializedMeshPipelineError> {
+        key.bind_group_data.apply(descriptor);
+        Ok(())
     }
 }
@@ -1,5 +1,17 @@
 mod vfx_material;
 mod broadcast_material;
+mod shader_key;
+mod vfx_storage;
+mod alpha_mode;
+mod vfx_material_3d;
+mod ui_material;
+mod screen_material;
 
 pub use vfx_material::*;
 pub use broadcast_material::*;
+pub use shader_key::*;
+pub use vfx_storage::*;
+pub use alpha_mode::*;
+pub use vfx_material_3d::*;
+pub use ui_material::*;
+pub use screen_material::*;
@@ -0,0 +1,88 @@
+use crate::internal_prelude::*;
+use bevy::asset::uuid_handle;
+use bevy::core_pipeline::core_2d::graph::Node2d;
+use bevy::core_pipeline::fullscreen_material::FullscreenMaterial;
+use bevy::ecs::query::QueryItem;
+use bevy::math::Affine2;
+use bevy::render::extract_component::ExtractComponent;
+use bevy::render::render_graph::{InternedRenderLabel, RenderLabel};
+use bevy::shader::ShaderRef;
+
+/// `assets/shaders/vfx_screen.wgsl`, embedded in the crate by `HirundoPlugin`
+pub const VFX_SCREEN_SHADER_HANDLE: Handle<Shader> =
+    uuid_handle!("b3a4f0d2-7c61-4e0b-9f3a-2d85c6e1a947");
+
+/// The [`ScreenVfx`] stack as the full-screen pass reads it, on every `Camera2d`.
+///
+/// The stack is evaluated on the CPU each frame, like the vertex stage does for
+/// sprites, so the pass only has one uniform to read. Cameras with nothing playing
+/// aren't extracted, which skips the pass altogether.
+#[derive(Component, Clone, Copy, Debug, Default, ShaderType, PartialEq)]
+pub struct ScreenVfxUniform {
+    pub(crate) tint: TintTerms,
+    pub(crate) fragments: [FragmentTerm; MAX_FRAGMENT_FX],
+    /// Inverse of the spatial effects, from where a point is drawn back to where it was
+    pub(crate) source_axes: Vec4,
+    pub(crate) source_offset: Vec2,
+    /// Logical size of the viewport
+    pub(crate) size: Vec2,
+    pub(crate) time: f32,
+    pub(crate) enabled: u32,
+}
+
+impl ScreenVfxUniform {
+    /// `effects` at `now` over a viewport `size` logical pixels big
+    pub(crate) fn new(
+        effects: &EffectStack,
+        now: f32,
+        size: Vec2,
+        audio: &VfxAudioUniform,
+    ) -> Self {
+        let affine = effects.spatial_affine(now, size, audio);
+        // A screen scaled to nothing shows nothing, rather than a smear of one texel
+        let source = if affine.matrix2.determinant().abs() < 1e-6 {
+            Affine2::ZERO
+        } else {
+            affine.inverse()
+        };
+        Self {
+            tint: effects.tint_terms(now, audio),
+            fragments: effects.fragment_terms(now, audio),
+            source_axes: Vec4::new(
+                source.matrix2.x_axis.x,
+                source.matrix2.x_axis.y,
+                source.matrix2.y_axis.x,
+                source.matrix2.y_axis.y,
+            ),
+            source_offset: source.translation,
+            size,
+            time: now,
+            enabled: 1,
+        }
+    }
+}
+
+impl ExtractComponent for ScreenVfxUniform {
+    type QueryData = &'static Self;
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(uniform: QueryItem<'_, '_, Self::QueryData>) -> Option<Self> {
+        (uniform.enabled != 0).then_some(*uniform)
+    }
+}
+
+impl FullscreenMaterial for ScreenVfxUniform {
+    fn fragment_shader() -> ShaderRef {
+        VFX_SCREEN_SHADER_HANDLE.into()
+    }
+
+    // After tonemapping, so flashes and fades work on the colors the screen shows
+    fn node_edges() -> Vec<InternedRenderLabel> {
+        vec![
+            Node2d::Tonemapping.intern(),
+            Self::node_label().intern(),
+            Node2d::EndMainPassPostProcessing.intern(),
+        ]
+    }
+}
@@ -0,0 +1,148 @@
+use crate::internal_prelude::*;
+use bevy::{
+    asset::uuid_handle, render::render_resource::RenderPipelineDescriptor, shader::ShaderDefVal,
+};
+use std::hash::{Hash, Hasher};
+
+/// `assets/shaders/user_fragment.wgsl`: the `hirundo::user_fragment` import with
+/// `UserFragmentInput`, for shaders plugged in with `HirundoPlugin::with_custom_fragment`
+pub const USER_FRAGMENT_SHADER_HANDLE: Handle<Shader> =
+    uuid_handle!("51610190-5c40-4cb5-983c-7a4c34d141e8");
+
+/// `assets/shaders/vfx_common.wgsl`: the `hirundo::vfx_common` import holding the effect
+/// stack layout, the shared material bindings and the effect helpers of both VFX shaders
+pub const VFX_COMMON_SHADER_HANDLE: Handle<Shader> =
+    uuid_handle!("9b1f3c2e-6d4a-4f7e-b8a5-2c0e71d94f36");
+
+/// Pipeline specialization shared by the VFX materials.
+///
+/// Carries the plugin's runtime limits into the WGSL as shader defs, so the shaders
+/// only loop over the slots that are actually in use.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct VfxShaderKey {
+    /// Effects evaluated per stack (`FX_SLOTS`)
+    pub fx_slots: u32,
+    /// Color slots evaluated per effect (`COLOR_SLOTS`)
+    pub color_slots: u32,
+    /// Alpha slots evaluated per effect (`ALPHA_SLOTS`)
+    pub alpha_slots: u32,
+    /// Spatial slots evaluated per effect (`SPATIAL_SLOTS`)
+    pub spatial_slots: u32,
+    /// Fragment slots evaluated per effect (`FRAGMENT_SLOTS`)
+    pub fragment_slots: u32,
+    /// A custom fragment shader is plugged in (`HIRUNDO_USER_FRAGMENT`)
+    pub user_fragment: bool,
+    /// Blending of the material (`VFX_ALPHA_CUTOFF` for masks)
+    pub alpha_mode: VfxAlphaMode,
+    /// Depth from world Y (`VFX_Y_SORT_SCALE`, `VFX_Y_SORT_OFFSET`)
+    pub y_sort: Option<VfxYSort>,
+    /// Camera-facing quads in 3D, for [`VfxMaterial3d`] (`VFX_3D`)
+    pub billboard: bool,
+    /// Sprites batched into one mesh, for [`VfxInstanced`] (`VFX_INSTANCED`)
+    pub instanced: bool,
+    /// Effect stacks and sprite rects are data textures, see [`VfxStorageKind`]
+    /// (`VFX_DATA_TEXTURES`)
+    pub data_textures: bool,
+}
+
+/// Y-sorting set with `HirundoPlugin::with_y_sort`: the shaders add
+/// `offset + scale * y` to each sprite's Z, `y` being the world Y of its bottom edge
+/// after spatial effects moved it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VfxYSort {
+    /// Z per world unit of Y, negative so lower sprites draw in front
+    pub scale: f32,
+    /// Z added on top, to keep the sorted range apart from other layers
+    pub offset: f32,
+}
+
+impl Eq for VfxYSort {}
+
+impl Hash for VfxYSort {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.scale.to_bits().hash(state);
+        self.offset.to_bits().hash(state);
+    }
+}
+
+impl VfxShaderKey {
+    /// Shader defs both VFX shaders (and any custom fragment module) are compiled with
+    pub fn shader_defs(&self) -> Vec<ShaderDefVal> {
+        let mut shader_defs = vec![
+            ShaderDefVal::UInt("FX_SLOTS".into(), self.fx_slots),
+            ShaderDefVal::UInt("COLOR_SLOTS".into(), self.color_slots),
+            ShaderDefVal::UInt("ALPHA_SLOTS".into(), self.alpha_slots),
+            ShaderDefVal::UInt("SPATIAL_SLOTS".into(), self.spatial_slots),
+            ShaderDefVal::UInt("FRAGMENT_SLOTS".into(), self.fragment_slots),
+        ];
+        if self.data_textures {
+            shader_defs.push("VFX_DATA_TEXTURES".into());
+        }
+        if self.billboard {
+            shader_defs.push("VFX_3D".into());
+        }
+        if self.instanced {
+            shader_defs.push("VFX_INSTANCED".into());
+        }
+        if self.user_fragment {
+            shader_defs.push("HIRUNDO_USER_FRAGMENT".into());
+        }
+        // The cutoff goes in as its bits, shader defs have no floats
+        if let VfxAlphaMode::Mask(cutoff) = self.alpha_mode {
+            shader_defs.push(ShaderDefVal::UInt(
+                "VFX_ALPHA_CUTOFF".into(),
+                cutoff.to_bits(),
+            ));
+        }
+        if let Some(y_sort) = self.y_sort {
+            shader_defs.push(ShaderDefVal::UInt(
+                "VFX_Y_SORT_SCALE".into(),
+                y_sort.scale.to_bits(),
+            ));
+            shader_defs.push(ShaderDefVal::UInt(
+                "VFX_Y_SORT_OFFSET".into(),
+                y_sort.offset.to_bits(),
+            ));
+        }
+        shader_defs
+    }
+
+    pub(crate) fn apply(&self, descriptor: &mut RenderPipelineDescriptor) {
+        // Blended sprites are drawn in Transform Z order, so only the depth test
+        // can order them by the Z the shader derives from Y
+        if self.y_sort.is_some()
+            && let Some(depth_stencil) = descriptor.depth_stencil.as_mut()
+        {
+            depth_stencil.depth_write_enabled = true;
+        }
+        let shader_defs = self.shader_defs();
+        descriptor.vertex.shader_defs.extend(shader_defs.clone());
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader_defs.extend(shader_defs);
+            if let Some(blend) = self.alpha_mode.blend_state() {
+                for target in fragment.targets.iter_mut().flatten() {
+                    target.blend = Some(blend);
+                }
+            }
+        }
+    }
+}
+
+impl Default for VfxShaderKey {
+    fn default() -> Self {
+        Self {
+            fx_slots: MAX_FX as u32,
+            color_slots: MAX_COLOR_FX as u32,
+            alpha_slots: MAX_ALPHA_FX as u32,
+            spatial_slots: MAX_SPATIAL_FX as u32,
+            fragment_slots: MAX_FRAGMENT_FX as u32,
+            user_fragment: false,
+            alpha_mode: VfxAlphaMode::Blend,
+            y_sort: None,
+            billboard: false,
+            instanced: false,
+            data_textures: VfxStorageKind::default() == VfxStorageKind::Textures,
+        }
+    }
+}
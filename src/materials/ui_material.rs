@@ -0,0 +1,154 @@
+use crate::internal_prelude::*;
+use bevy::{
+    ecs::system::SystemParamItem,
+    render::{
+        render_resource::{
+            AsBindGroupError, BindGroupLayout, BindGroupLayoutEntry, RenderPipelineDescriptor,
+            UnpreparedBindGroup,
+        },
+        renderer::RenderDevice,
+    },
+    shader::ShaderDefVal,
+    ui_render::prelude::{UiMaterial, UiMaterialKey},
+};
+
+/// Material of a [`UiVfx`] node: the node's own [`EffectStack`] as a uniform, like
+/// [`VfxBroadcastMaterial`], drawn by `vfx_broadcast.wgsl` compiled with `VFX_UI`.
+///
+/// One per node, created by the `UiVfx` add hook. The atlas, noise, overlay and
+/// timeline follow the `Vfx` material.
+#[derive(Asset, TypePath, Debug, Clone, PartialEq)]
+pub struct VfxUiMaterial {
+    pub texture: Handle<Image>,
+    pub effect_stack: EffectStack,
+    pub atlas_dimensions: AtlasDimensions,
+    pub noise_texture: Handle<Image>,
+    pub overlay_texture: Option<Handle<Image>>,
+    pub sprite_rects: VfxStorage,
+    pub timeline: VfxTimelineUniform,
+    pub lod: VfxLodUniform,
+    pub audio: VfxAudioUniform,
+    /// Shader specialization, see [`VfxShaderKey`]
+    pub shader_key: VfxShaderKey,
+}
+
+impl From<&VfxUiMaterial> for VfxStackBindings {
+    fn from(material: &VfxUiMaterial) -> Self {
+        Self {
+            texture: material.texture.clone(),
+            effect_stack: material.effect_stack.clone(),
+            atlas_dimensions: material.atlas_dimensions.clone(),
+            noise_texture: material.noise_texture.clone(),
+            overlay_texture: material.overlay_texture.clone(),
+            timeline: material.timeline,
+            lod: material.lod,
+            audio: material.audio,
+        }
+    }
+}
+
+/// Same bindings as [`VfxBroadcastMaterial`]
+impl AsBindGroup for VfxUiMaterial {
+    type Data = VfxShaderKey;
+    type Param = VfxBindGroupParam;
+
+    fn label() -> &'static str {
+        "vfx_ui_material"
+    }
+
+    fn bind_group_data(&self) -> Self::Data {
+        self.shader_key
+    }
+
+    fn unprepared_bind_group(
+        &self,
+        layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        param: &mut SystemParamItem<'_, '_, Self::Param>,
+        force_no_bindless: bool,
+    ) -> Result<UnpreparedBindGroup, AsBindGroupError> {
+        let mut bind_group = VfxStackBindings::from(self).unprepared_bind_group(
+            layout,
+            render_device,
+            param,
+            force_no_bindless,
+        )?;
+        bind_group
+            .bindings
+            .push(storage_binding(8, &self.sprite_rects, param)?);
+        Ok(bind_group)
+    }
+
+    fn bind_group_layout_entries(
+        render_device: &RenderDevice,
+        force_no_bindless: bool,
+    ) -> Vec<BindGroupLayoutEntry> {
+        VfxBroadcastMaterial::bind_group_layout_entries(render_device, force_no_bindless)
+    }
+}
+
+impl VfxUiMaterial {
+    /// `effect_stack` drawn with the atlas, textures and timeline of `material`
+    pub fn new(material: &VfxMaterial, effect_stack: EffectStack) -> Self {
+        Self {
+            texture: material.texture.clone(),
+            effect_stack,
+            atlas_dimensions: material.atlas_dimensions.clone(),
+            noise_texture: material.noise_texture.clone(),
+            overlay_texture: material.overlay_texture.clone(),
+            sprite_rects: material.sprite_rects.clone(),
+            timeline: material.timeline,
+            // Nodes don't zoom with the camera
+            lod: VfxLodUniform::default(),
+            audio: material.audio,
+            shader_key: Self::shader_key(material),
+        }
+    }
+
+    /// Whether everything but the effect stack still matches `material`
+    pub(crate) fn follows(&self, material: &VfxMaterial) -> bool {
+        self.texture == material.texture
+            && self.atlas_dimensions == material.atlas_dimensions
+            && self.noise_texture == material.noise_texture
+            && self.overlay_texture == material.overlay_texture
+            && self.sprite_rects == material.sprite_rects
+            && self.timeline == material.timeline
+            && self.audio == material.audio
+            && self.shader_key == Self::shader_key(material)
+    }
+
+    fn shader_key(material: &VfxMaterial) -> VfxShaderKey {
+        VfxShaderKey {
+            // Nodes are drawn in UI stack order
+            y_sort: None,
+            ..material.shader_key
+        }
+    }
+}
+
+impl From<&VfxUiMaterial> for VfxShaderKey {
+    fn from(material: &VfxUiMaterial) -> Self {
+        material.shader_key
+    }
+}
+
+impl UiMaterial for VfxUiMaterial {
+    fn vertex_shader() -> bevy::shader::ShaderRef {
+        VFX_BROADCAST_SHADER_HANDLE.into()
+    }
+    fn fragment_shader() -> bevy::shader::ShaderRef {
+        VFX_BROADCAST_SHADER_HANDLE.into()
+    }
+    fn specialize(descriptor: &mut RenderPipelineDescriptor, key: UiMaterialKey<Self>) {
+        key.bind_group_data.apply(descriptor);
+        // UI materials bind at group 1, `Material2d` sets this def itself
+        let shader_defs = [
+            "VFX_UI".into(),
+            ShaderDefVal::UInt("MATERIAL_BIND_GROUP".into(), 1),
+        ];
+        descriptor.vertex.shader_defs.extend(shader_defs.clone());
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader_defs.extend(shader_defs);
+        }
+    }
+}
@@ -1,24 +1,178 @@
 use crate::internal_prelude::*;
+use bevy::{
+    asset::uuid_handle,
+    ecs::system::SystemParamItem,
+    mesh::MeshVertexAttribute,
+    render::{
+        render_resource::{
+            AsBindGroupError, BindGroupLayout, BindGroupLayoutEntry, UnpreparedBindGroup,
+            VertexFormat,
+        },
+        renderer::RenderDevice,
+    },
+};
 
-#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+/// `assets/shaders/vfx.wgsl`, embedded in the crate by `HirundoPlugin`
+pub const VFX_SHADER_HANDLE: Handle<Shader> = uuid_handle!("4283f38a-60e9-4068-8dd6-4dd69ecc9aa3");
+
+/// Batch mesh vertex attribute: the sprite's world X and Y axes (`xy`, `zw`), see
+/// [`VfxInstanced`]
+pub const ATTRIBUTE_INSTANCE_AXES: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vfx_InstanceAxes", 1_473_920_561, VertexFormat::Float32x4);
+
+/// Batch mesh vertex attribute: the sprite's world translation
+pub const ATTRIBUTE_INSTANCE_TRANSLATION: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vfx_InstanceTranslation", 1_473_920_562, VertexFormat::Float32x3);
+
+/// Batch mesh vertex attribute: the sprite's `MeshTag`, its effect stack
+pub const ATTRIBUTE_INSTANCE_TAG: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vfx_InstanceTag", 1_473_920_563, VertexFormat::Uint32);
+
+#[derive(Asset, TypePath, Debug, Clone, PartialEq)]
 pub struct VfxMaterial {
+    pub texture: Handle<Image>,
+    /// Effect stacks indexed by `MeshTag`, see [`VfxStorage`]
+    pub effect_storage: VfxStorage,
+    pub atlas_dimensions: AtlasDimensions,
+    /// Noise sampled by the dissolve fragment effect
+    pub noise_texture: Handle<Image>,
+    /// Texture composited by the overlay fragment effect (optional)
+    pub overlay_texture: Option<Handle<Image>>,
+    /// Per-sprite pixel rects, see [`SpriteRects`]
+    pub sprite_rects: VfxStorage,
+    /// Frozen effect time, see [`VfxTimeline`]
+    pub timeline: VfxTimelineUniform,
+    /// Effects skipped when zoomed out, see [`VfxLod`]
+    pub lod: VfxLodUniform,
+    /// Levels of audio waves, see [`VfxAudioDriver`]
+    pub audio: VfxAudioUniform,
+    /// Shader specialization, see [`VfxShaderKey`]
+    pub shader_key: VfxShaderKey,
+}
+
+/// Bindings of [`VfxMaterial`] and [`VfxMaterial3d`] but the two [`VfxStorage`]s
+/// (2 and 8), whose binding type depends on the adapter
+#[derive(AsBindGroup)]
+pub(crate) struct VfxTaggedBindings {
     #[texture(0)]
     #[sampler(1)]
     pub texture: Handle<Image>,
-    #[storage(2, read_only)]
-    pub effect_storage: Handle<ShaderStorageBuffer>,
     #[uniform(3)]
     pub atlas_dimensions: AtlasDimensions,
+    #[texture(4)]
+    #[sampler(5)]
+    pub noise_texture: Handle<Image>,
+    #[texture(6)]
+    #[sampler(7)]
+    pub overlay_texture: Option<Handle<Image>>,
+    #[uniform(9)]
+    pub timeline: VfxTimelineUniform,
+    #[uniform(10)]
+    pub lod: VfxLodUniform,
+    #[uniform(11)]
+    pub audio: VfxAudioUniform,
+}
+
+impl From<&VfxMaterial> for VfxTaggedBindings {
+    fn from(material: &VfxMaterial) -> Self {
+        Self {
+            texture: material.texture.clone(),
+            atlas_dimensions: material.atlas_dimensions.clone(),
+            noise_texture: material.noise_texture.clone(),
+            overlay_texture: material.overlay_texture.clone(),
+            timeline: material.timeline,
+            lod: material.lod,
+            audio: material.audio,
+        }
+    }
+}
+
+impl AsBindGroup for VfxMaterial {
+    type Data = VfxShaderKey;
+    type Param = VfxBindGroupParam;
+
+    fn label() -> &'static str {
+        "vfx_material"
+    }
+
+    fn bind_group_data(&self) -> Self::Data {
+        self.shader_key
+    }
+
+    fn unprepared_bind_group(
+        &self,
+        layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        param: &mut SystemParamItem<'_, '_, Self::Param>,
+        force_no_bindless: bool,
+    ) -> Result<UnpreparedBindGroup, AsBindGroupError> {
+        let mut bind_group = VfxTaggedBindings::from(self).unprepared_bind_group(
+            layout,
+            render_device,
+            param,
+            force_no_bindless,
+        )?;
+        bind_group
+            .bindings
+            .push(storage_binding(2, &self.effect_storage, param)?);
+        bind_group
+            .bindings
+            .push(storage_binding(8, &self.sprite_rects, param)?);
+        Ok(bind_group)
+    }
+
+    fn bind_group_layout_entries(
+        render_device: &RenderDevice,
+        force_no_bindless: bool,
+    ) -> Vec<BindGroupLayoutEntry> {
+        let mut entries =
+            VfxTaggedBindings::bind_group_layout_entries(render_device, force_no_bindless);
+        entries.push(storage_layout_entry(
+            render_device,
+            2,
+            VfxStorageContents::EffectStacks,
+        ));
+        entries.push(storage_layout_entry(
+            render_device,
+            8,
+            VfxStorageContents::SpriteRects,
+        ));
+        entries
+    }
+}
+
+impl From<&VfxMaterial> for VfxShaderKey {
+    fn from(material: &VfxMaterial) -> Self {
+        material.shader_key
+    }
 }
 
 impl Material2d for VfxMaterial {
     fn vertex_shader() -> bevy::shader::ShaderRef {
-        "shaders/vfx.wgsl".into()
+        VFX_SHADER_HANDLE.into()
     }
     fn fragment_shader() -> bevy::shader::ShaderRef {
-        "shaders/vfx.wgsl".into()
+        VFX_SHADER_HANDLE.into()
     }
     fn alpha_mode(&self) -> bevy::sprite_render::AlphaMode2d {
-        bevy::sprite_render::AlphaMode2d::Blend
+        self.shader_key.alpha_mode.alpha_mode_2d()
+    }
+    fn specialize(
+        descriptor: &mut bevy::render::render_resource::RenderPipelineDescriptor,
+        layout: &bevy::mesh::MeshVertexBufferLayoutRef,
+        key: bevy::sprite_render::Material2dKey<Self>,
+    ) -> Result<(), bevy::render::render_resource::SpecializedMeshPipelineError> {
+        // The batch mesh carries each sprite's transform and tag next to the quad's vertices
+        if key.bind_group_data.instanced {
+            descriptor.vertex.buffers = vec![layout.0.get_layout(&[
+                Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+                Mesh::ATTRIBUTE_UV_0.at_shader_location(2),
+                ATTRIBUTE_INSTANCE_AXES.at_shader_location(3),
+                ATTRIBUTE_INSTANCE_TRANSLATION.at_shader_location(4),
+                ATTRIBUTE_INSTANCE_TAG.at_shader_location(5),
+            ])?];
+        }
+        key.bind_group_data.apply(descriptor);
+        Ok(())
     }
 }
@@ -0,0 +1,149 @@
+use crate::internal_prelude::*;
+use bevy::{
+    ecs::system::SystemParamItem,
+    render::{
+        render_resource::{
+            AsBindGroupError, BindGroupLayout, BindGroupLayoutEntry, UnpreparedBindGroup,
+        },
+        renderer::RenderDevice,
+    },
+};
+
+/// The `Vfx` material for 3D scenes, drawing [`Vfx3d`] entities as camera-facing quads.
+///
+/// A copy of [`VfxMaterial`] (same storage, atlas and timeline, kept in step by
+/// `sync_vfx_material_3d`) compiled with `VFX_3D`.
+#[derive(Asset, TypePath, Debug, Clone, PartialEq)]
+pub struct VfxMaterial3d {
+    pub texture: Handle<Image>,
+    pub effect_storage: VfxStorage,
+    pub atlas_dimensions: AtlasDimensions,
+    pub noise_texture: Handle<Image>,
+    pub overlay_texture: Option<Handle<Image>>,
+    pub sprite_rects: VfxStorage,
+    pub timeline: VfxTimelineUniform,
+    pub lod: VfxLodUniform,
+    pub audio: VfxAudioUniform,
+    /// Shader specialization, see [`VfxShaderKey`]
+    pub shader_key: VfxShaderKey,
+}
+
+impl From<&VfxMaterial3d> for VfxTaggedBindings {
+    fn from(material: &VfxMaterial3d) -> Self {
+        Self {
+            texture: material.texture.clone(),
+            atlas_dimensions: material.atlas_dimensions.clone(),
+            noise_texture: material.noise_texture.clone(),
+            overlay_texture: material.overlay_texture.clone(),
+            timeline: material.timeline,
+            lod: material.lod,
+            audio: material.audio,
+        }
+    }
+}
+
+/// Same bindings as [`VfxMaterial`]
+impl AsBindGroup for VfxMaterial3d {
+    type Data = VfxShaderKey;
+    type Param = VfxBindGroupParam;
+
+    fn label() -> &'static str {
+        "vfx_material_3d"
+    }
+
+    fn bind_group_data(&self) -> Self::Data {
+        self.shader_key
+    }
+
+    fn unprepared_bind_group(
+        &self,
+        layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        param: &mut SystemParamItem<'_, '_, Self::Param>,
+        force_no_bindless: bool,
+    ) -> Result<UnpreparedBindGroup, AsBindGroupError> {
+        let mut bind_group = VfxTaggedBindings::from(self).unprepared_bind_group(
+            layout,
+            render_device,
+            param,
+            force_no_bindless,
+        )?;
+        bind_group
+            .bindings
+            .push(storage_binding(2, &self.effect_storage, param)?);
+        bind_group
+            .bindings
+            .push(storage_binding(8, &self.sprite_rects, param)?);
+        Ok(bind_group)
+    }
+
+    fn bind_group_layout_entries(
+        render_device: &RenderDevice,
+        force_no_bindless: bool,
+    ) -> Vec<BindGroupLayoutEntry> {
+        VfxMaterial::bind_group_layout_entries(render_device, force_no_bindless)
+    }
+}
+
+impl From<&VfxMaterial> for VfxMaterial3d {
+    fn from(material: &VfxMaterial) -> Self {
+        Self {
+            texture: material.texture.clone(),
+            effect_storage: material.effect_storage.clone(),
+            atlas_dimensions: material.atlas_dimensions.clone(),
+            noise_texture: material.noise_texture.clone(),
+            overlay_texture: material.overlay_texture.clone(),
+            sprite_rects: material.sprite_rects.clone(),
+            timeline: material.timeline,
+            lod: material.lod,
+            audio: material.audio,
+            shader_key: VfxShaderKey {
+                billboard: true,
+                // Depth already comes from the camera
+                y_sort: None,
+                ..material.shader_key
+            },
+        }
+    }
+}
+
+impl From<&VfxMaterial3d> for VfxShaderKey {
+    fn from(material: &VfxMaterial3d) -> Self {
+        material.shader_key
+    }
+}
+
+impl Material for VfxMaterial3d {
+    fn vertex_shader() -> bevy::shader::ShaderRef {
+        VFX_SHADER_HANDLE.into()
+    }
+    fn fragment_shader() -> bevy::shader::ShaderRef {
+        VFX_SHADER_HANDLE.into()
+    }
+    fn alpha_mode(&self) -> AlphaMode {
+        match self.shader_key.alpha_mode {
+            VfxAlphaMode::Opaque => AlphaMode::Opaque,
+            VfxAlphaMode::Mask(cutoff) => AlphaMode::Mask(cutoff),
+            // Additive and premultiplied override the blend state in `VfxShaderKey::apply`
+            VfxAlphaMode::Blend | VfxAlphaMode::Additive | VfxAlphaMode::Premultiplied => {
+                AlphaMode::Blend
+            }
+        }
+    }
+    // The billboard only exists in `vfx.wgsl`, the PBR prepass would draw the flat quad
+    fn enable_prepass() -> bool {
+        false
+    }
+    fn enable_shadows() -> bool {
+        false
+    }
+    fn specialize(
+        _pipeline: &bevy::pbr::MaterialPipeline,
+        descriptor: &mut bevy::render::render_resource::RenderPipelineDescriptor,
+        _layout: &bevy::mesh::MeshVertexBufferLayoutRef,
+        key: bevy::pbr::MaterialPipelineKey<Self>,
+    ) -> Result<(), bevy::render::render_resource::SpecializedMeshPipelineError> {
+        key.bind_group_data.apply(descriptor);
+        Ok(())
+    }
+}
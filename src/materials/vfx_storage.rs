@@ -0,0 +1,303 @@
+//! What the materials read effect stacks and sprite rects from.
+//!
+//! Storage buffers where the adapter has them. WebGL2 has none, so there both are data
+//! textures instead (`VFX_DATA_TEXTURES` shader def): one `Rgba32Uint` row per effect
+//! stack, capped at the device's texture height ([`VfxStorageLimit`]), and `Rgba32Float`
+//! sprite rects in rows of [`SPRITE_RECTS_PER_ROW`].
+//! `HirundoPlugin` picks one in `finish`, from the render device, see [`VfxStorageKind`].
+use crate::internal_prelude::*;
+use bevy::{
+    asset::RenderAssetUsages,
+    ecs::system::{SystemParam, SystemParamItem, lifetimeless::SRes},
+    render::{
+        render_asset::RenderAssets,
+        render_resource::{
+            AsBindGroupError, BindGroupLayoutEntry, BindingType, BufferBindingType, BufferUsages,
+            Extent3d, OwnedBindingResource, ShaderSize, ShaderStages, TextureDimension,
+            TextureFormat, TextureSampleType, TextureViewDimension, encase,
+        },
+        renderer::RenderDevice,
+        settings::WgpuLimits,
+        storage::{GpuShaderStorageBuffer, ShaderStorageBuffer},
+        texture::{FallbackImage, GpuImage},
+    },
+};
+
+/// Sprite rects per texture row when stored in a data texture
+pub const SPRITE_RECTS_PER_ROW: usize = 256;
+
+/// Whether the materials read effect stacks and sprite rects from storage buffers or
+/// data textures.
+///
+/// Set by `HirundoPlugin::finish` from the render device: data textures when it has no
+/// storage buffers (WebGL2), or always with the `data_textures` feature.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VfxStorageKind {
+    Buffers,
+    Textures,
+}
+
+impl VfxStorageKind {
+    /// The storage the materials bind on `render_device`
+    pub fn for_device(render_device: &RenderDevice) -> Self {
+        if cfg!(feature = "data_textures")
+            || render_device.limits().max_storage_buffers_per_shader_stage == 0
+        {
+            Self::Textures
+        } else {
+            Self::Buffers
+        }
+    }
+}
+
+/// Without a render device (headless apps), whatever the features ask for
+impl Default for VfxStorageKind {
+    fn default() -> Self {
+        if cfg!(feature = "data_textures") {
+            Self::Textures
+        } else {
+            Self::Buffers
+        }
+    }
+}
+
+/// Most effect stacks the storage grows to: the render device's largest texture height
+/// with data textures (2048 on WebGL2), unbounded with storage buffers.
+///
+/// Set by `HirundoPlugin::finish` along with [`VfxStorageKind`]. `Vfx` entities past it
+/// aren't drawn, with a warning.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VfxStorageLimit(pub usize);
+
+impl VfxStorageLimit {
+    /// The limit of `kind` storage on `render_device`
+    pub fn for_device(render_device: &RenderDevice, kind: VfxStorageKind) -> Self {
+        match kind {
+            VfxStorageKind::Buffers => Self(usize::MAX),
+            VfxStorageKind::Textures => {
+                Self(render_device.limits().max_texture_dimension_2d as usize)
+            }
+        }
+    }
+}
+
+/// Without a render device, WebGL2's limit for data textures
+impl Default for VfxStorageLimit {
+    fn default() -> Self {
+        match VfxStorageKind::default() {
+            VfxStorageKind::Buffers => Self(usize::MAX),
+            VfxStorageKind::Textures => {
+                Self(WgpuLimits::downlevel_webgl2_defaults().max_texture_dimension_2d as usize)
+            }
+        }
+    }
+}
+
+/// Asset behind `effect_storage` and `sprite_rects` on the materials, of the
+/// [`VfxStorageKind`] in use
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum VfxStorage {
+    Buffer(Handle<ShaderStorageBuffer>),
+    Texture(Handle<Image>),
+}
+
+/// Creates and rewrites [`VfxStorage`] assets in the kind the materials bind
+#[derive(SystemParam)]
+pub struct VfxStorages<'w> {
+    kind: Res<'w, VfxStorageKind>,
+    limit: Res<'w, VfxStorageLimit>,
+    buffers: ResMut<'w, Assets<ShaderStorageBuffer>>,
+    images: ResMut<'w, Assets<Image>>,
+}
+
+impl VfxStorages<'_> {
+    /// The kind of storage new assets are created in
+    pub fn kind(&self) -> VfxStorageKind {
+        *self.kind
+    }
+
+    /// Most effect stacks an effect storage holds
+    pub fn max_stacks(&self) -> usize {
+        self.limit.0
+    }
+
+    /// A new effect storage, writable in place for partial uploads
+    pub fn add_effects(&mut self, effects: &[EffectStack]) -> VfxStorage {
+        match *self.kind {
+            VfxStorageKind::Buffers => VfxStorage::Buffer(self.buffers.add(effect_buffer(effects))),
+            VfxStorageKind::Textures => {
+                VfxStorage::Texture(self.images.add(effect_texture(effects)))
+            }
+        }
+    }
+
+    /// Replaces the contents of an effect storage
+    pub fn set_effects(&mut self, storage: &VfxStorage, effects: &[EffectStack]) {
+        match storage {
+            VfxStorage::Buffer(handle) => {
+                if let Some(buffer) = self.buffers.get_mut(handle) {
+                    *buffer = effect_buffer(effects);
+                }
+            }
+            VfxStorage::Texture(handle) => {
+                if let Some(image) = self.images.get_mut(handle) {
+                    *image = effect_texture(effects);
+                }
+            }
+        }
+    }
+
+    /// A new sprite rect table, read by [`SpriteRects`] lookups in the shaders
+    pub fn add_sprite_rects(&mut self, table: Vec<Vec4>) -> VfxStorage {
+        match *self.kind {
+            VfxStorageKind::Buffers => {
+                VfxStorage::Buffer(self.buffers.add(ShaderStorageBuffer::from(table)))
+            }
+            VfxStorageKind::Textures => {
+                VfxStorage::Texture(self.images.add(sprite_rects_texture(table)))
+            }
+        }
+    }
+
+    /// Replaces the contents of a sprite rect table
+    pub fn set_sprite_rects(&mut self, storage: &VfxStorage, table: Vec<Vec4>) {
+        match storage {
+            VfxStorage::Buffer(handle) => {
+                if let Some(buffer) = self.buffers.get_mut(handle) {
+                    *buffer = ShaderStorageBuffer::from(table);
+                }
+            }
+            VfxStorage::Texture(handle) => {
+                if let Some(image) = self.images.get_mut(handle) {
+                    *image = sprite_rects_texture(table);
+                }
+            }
+        }
+    }
+}
+
+/// Bytes uploaded for `stacks` effect stacks, in either kind of storage
+pub(crate) fn effect_storage_bytes(stacks: usize) -> usize {
+    stacks.max(1) * EffectStack::SHADER_SIZE.get() as usize
+}
+
+fn effect_buffer(effects: &[EffectStack]) -> ShaderStorageBuffer {
+    let mut buffer = ShaderStorageBuffer::from(effects.to_vec());
+    buffer.buffer_description.usage |= BufferUsages::COPY_DST;
+    buffer
+}
+
+fn effect_texture(effects: &[EffectStack]) -> Image {
+    let rows = effects.len().max(1);
+    let mut bytes = encase::StorageBuffer::new(Vec::<u8>::new());
+    bytes
+        .write(effects)
+        .expect("effect stacks encode into a Vec");
+    let mut data = bytes.into_inner();
+    data.resize(effect_storage_bytes(rows), 0);
+    Image::new(
+        Extent3d {
+            width: effect_stack_texels(),
+            height: rows as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba32Uint,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// Texels in one effect stack row, every struct in it starts on a texel
+pub(crate) fn effect_stack_texels() -> u32 {
+    (EffectStack::SHADER_SIZE.get() / 16) as u32
+}
+
+fn sprite_rects_texture(mut table: Vec<Vec4>) -> Image {
+    let width = table.len().clamp(1, SPRITE_RECTS_PER_ROW);
+    let rows = table.len().div_ceil(width).max(1);
+    table.resize(width * rows, Vec4::ZERO);
+    Image::new(
+        Extent3d {
+            width: width as u32,
+            height: rows as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        bytemuck::cast_slice(&table).to_vec(),
+        TextureFormat::Rgba32Float,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// `AsBindGroup::Param` of the VFX materials, the one the derive gives their other
+/// bindings, which has the render assets a [`VfxStorage`] binding resolves to as well
+pub(crate) type VfxBindGroupParam = (
+    SRes<RenderAssets<GpuImage>>,
+    SRes<FallbackImage>,
+    SRes<RenderAssets<GpuShaderStorageBuffer>>,
+);
+
+/// What a [`VfxStorage`] binding holds, picking the texture sample type
+#[derive(Clone, Copy)]
+pub(crate) enum VfxStorageContents {
+    /// `Rgba32Uint` effect stack rows
+    EffectStacks,
+    /// `Rgba32Float` sprite rects
+    SpriteRects,
+}
+
+/// Layout entry of a [`VfxStorage`] binding, of the kind `render_device` gets
+pub(crate) fn storage_layout_entry(
+    render_device: &RenderDevice,
+    binding: u32,
+    contents: VfxStorageContents,
+) -> BindGroupLayoutEntry {
+    let ty = match VfxStorageKind::for_device(render_device) {
+        VfxStorageKind::Buffers => BindingType::Buffer {
+            ty: BufferBindingType::Storage { read_only: true },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        VfxStorageKind::Textures => BindingType::Texture {
+            sample_type: match contents {
+                VfxStorageContents::EffectStacks => TextureSampleType::Uint,
+                VfxStorageContents::SpriteRects => TextureSampleType::Float { filterable: false },
+            },
+            view_dimension: TextureViewDimension::D2,
+            multisampled: false,
+        },
+    };
+    BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::VERTEX_FRAGMENT,
+        ty,
+        count: None,
+    }
+}
+
+/// The prepared buffer or texture behind a [`VfxStorage`] binding
+pub(crate) fn storage_binding(
+    binding: u32,
+    storage: &VfxStorage,
+    (images, _, buffers): &SystemParamItem<'_, '_, VfxBindGroupParam>,
+) -> Result<(u32, OwnedBindingResource), AsBindGroupError> {
+    let resource = match storage {
+        VfxStorage::Buffer(handle) => OwnedBindingResource::Buffer(
+            buffers
+                .get(handle)
+                .ok_or(AsBindGroupError::RetryNextUpdate)?
+                .buffer
+                .clone(),
+        ),
+        VfxStorage::Texture(handle) => OwnedBindingResource::TextureView(
+            TextureViewDimension::D2,
+            images
+                .get(handle)
+                .ok_or(AsBindGroupError::RetryNextUpdate)?
+                .texture_view
+                .clone(),
+        ),
+    };
+    Ok((binding, resource))
+}
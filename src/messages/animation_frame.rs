@@ -0,0 +1,29 @@
+use crate::internal_prelude::*;
+
+/// Sent each time a [`SpriteAnimation`] shows a new frame, including its first, so
+/// gameplay can sync footsteps, hit frames and sounds to the animation.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Resource)]
+/// # struct Sounds {
+/// #     footstep: Handle<AudioSource>,
+/// # }
+/// fn footsteps(mut frames: MessageReader<AnimationFrameEvent>, mut commands: Commands, sounds: Res<Sounds>) {
+///     for event in frames.read() {
+///         if event.clip.as_deref() == Some("walk") && matches!(event.frame, 1 | 3) {
+///             commands.spawn(AudioPlayer::new(sounds.footstep.clone()));
+///         }
+///     }
+/// }
+/// ```
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct AnimationFrameEvent {
+    pub entity: Entity,
+    /// Clip playing through the entity's [`AnimationClipPlayer`], if it has one
+    pub clip: Option<String>,
+    /// Position in the animation's frames, from 0
+    pub frame: usize,
+    /// Atlas index of that frame
+    pub sprite_index: u32,
+}
@@ -0,0 +1,30 @@
+use crate::internal_prelude::*;
+
+/// Sent when a one-shot effect on a broadcast stack ends and its slot is freed, so game
+/// logic can chain off shared effects ("after the global flash ends, start the wave").
+///
+/// `group` is the [`VfxBroadcasts`] group, `None` for the default broadcast material.
+/// `effect` is the effect as stored, i.e. after [`VfxAccessibility`] for [`BroadcastFx`] pushes.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Resource)]
+/// # struct Waves;
+/// # impl Waves {
+/// #     fn spawn_next(&mut self) {}
+/// # }
+/// fn start_wave(mut finished: MessageReader<BroadcastEffectFinished>, mut waves: ResMut<Waves>) {
+///     for event in finished.read() {
+///         if event.group.as_deref() == Some("alarm") {
+///             waves.spawn_next();
+///         }
+///     }
+/// }
+/// ```
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct BroadcastEffectFinished {
+    pub group: Option<String>,
+    /// Stack slot the effect played in
+    pub slot: usize,
+    pub effect: Effect,
+}
@@ -0,0 +1,11 @@
+mod animation_frame;
+mod storage_grown;
+mod broadcast_finished;
+mod play_vfx;
+mod play_effect;
+
+pub use animation_frame::*;
+pub use storage_grown::*;
+pub use broadcast_finished::*;
+pub use play_vfx::*;
+pub use play_effect::*;
@@ -0,0 +1,36 @@
+use crate::internal_prelude::*;
+
+/// Observer counterpart of [`PlayVfx`]: triggered on a [`Vfx`], [`UiVfx`] or [`TextVfx`]
+/// entity, the effect is pushed right away by an observer the plugin registers, whatever
+/// schedule or system order the trigger comes from.
+///
+/// An [`EffectAsset`] still loading is handed on as a [`PlayVfx`], which holds it until loaded.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Event)]
+/// # struct Hit {
+/// #     target: Entity,
+/// # }
+/// fn on_hit(hit: On<Hit>, mut commands: Commands) {
+///     commands.entity(hit.target).trigger(PlayEffect::preset("hit_flash"));
+/// }
+/// ```
+#[derive(EntityEvent, Clone, Debug)]
+pub struct PlayEffect {
+    pub entity: Entity,
+    pub effect: EffectSpec,
+}
+
+impl PlayEffect {
+    /// Play `effect`, for `EntityCommands::trigger`
+    pub fn new(effect: impl Into<EffectSpec>) -> impl FnOnce(Entity) -> Self {
+        let effect = effect.into();
+        move |entity| Self { entity, effect }
+    }
+
+    /// Play the [`EffectTemplates`] entry `name`, for `EntityCommands::trigger`
+    pub fn preset(name: impl Into<String>) -> impl FnOnce(Entity) -> Self {
+        Self::new(EffectSpec::Template(name.into()))
+    }
+}
@@ -0,0 +1,128 @@
+use crate::internal_prelude::*;
+
+/// Plays an effect on `target`, a [`Vfx`], [`UiVfx`] or [`TextVfx`] entity, so gameplay code
+/// can trigger effects with a `MessageWriter` instead of querying for the component.
+///
+/// Handled in [`HirundoSet::Sync`]; written before it, the effect is drawn the same frame.
+/// Targets that were despawned in the meantime are skipped, as are assets that failed
+/// to load.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Message)]
+/// # struct Damage {
+/// #     target: Entity,
+/// # }
+/// fn on_damage(mut damage: MessageReader<Damage>, mut play: MessageWriter<PlayVfx>) {
+///     for hit in damage.read() {
+///         play.write(PlayVfx::new(hit.target, "hit_flash"));
+///     }
+/// }
+/// ```
+#[derive(Message, Clone, Debug)]
+pub struct PlayVfx {
+    pub target: Entity,
+    pub effect: EffectSpec,
+}
+
+impl PlayVfx {
+    pub fn new(target: Entity, effect: impl Into<EffectSpec>) -> Self {
+        Self {
+            target,
+            effect: effect.into(),
+        }
+    }
+}
+
+/// What a [`PlayVfx`] plays
+#[derive(Clone, Debug)]
+pub enum EffectSpec {
+    /// A built effect, pushed as is (it starts at the time it was built with)
+    Effect(Box<Effect>),
+    /// Several built effects, e.g. the steps of an [`EffectSequence`]
+    Effects(Vec<Effect>),
+    /// The [`EffectTemplates`] entry of this name, starting when the message is handled
+    Template(String),
+    /// An [`EffectAsset`], starting when the message is handled or, if it's still loading,
+    /// once it has loaded
+    Asset(Handle<EffectAsset>),
+}
+
+impl EffectSpec {
+    /// Built effects start `delay` seconds later; templates and assets are built when played
+    pub(crate) fn delayed(mut self, delay: f32) -> Self {
+        match &mut self {
+            EffectSpec::Effect(effect) => effect.lifetime.start_time += delay,
+            EffectSpec::Effects(effects) => {
+                for effect in effects {
+                    effect.lifetime.start_time += delay;
+                }
+            }
+            EffectSpec::Template(_) | EffectSpec::Asset(_) => {}
+        }
+        self
+    }
+
+    /// The effects to push at `now`, `None` while an asset is still loading
+    pub(crate) fn build(
+        &self,
+        templates: &EffectTemplates,
+        assets: &Assets<EffectAsset>,
+        now: f32,
+    ) -> Option<Vec<Effect>> {
+        match self {
+            EffectSpec::Effect(effect) => Some(vec![**effect]),
+            EffectSpec::Effects(effects) => Some(effects.clone()),
+            EffectSpec::Template(name) => Some(match templates.build(name, now) {
+                Some(effect) => vec![effect],
+                None => {
+                    warn!("No effect template named \"{}\"", name);
+                    Vec::new()
+                }
+            }),
+            EffectSpec::Asset(handle) => assets.get(handle).map(|asset| vec![asset.build(now)]),
+        }
+    }
+}
+
+impl From<Effect> for EffectSpec {
+    fn from(effect: Effect) -> Self {
+        EffectSpec::Effect(Box::new(effect))
+    }
+}
+
+impl From<EffectBuilder> for EffectSpec {
+    fn from(builder: EffectBuilder) -> Self {
+        builder.build().into()
+    }
+}
+
+impl From<Vec<Effect>> for EffectSpec {
+    fn from(effects: Vec<Effect>) -> Self {
+        EffectSpec::Effects(effects)
+    }
+}
+
+impl From<EffectSequence> for EffectSpec {
+    fn from(sequence: EffectSequence) -> Self {
+        EffectSpec::Effects(sequence.build())
+    }
+}
+
+impl From<&str> for EffectSpec {
+    fn from(name: &str) -> Self {
+        EffectSpec::Template(name.to_string())
+    }
+}
+
+impl From<String> for EffectSpec {
+    fn from(name: String) -> Self {
+        EffectSpec::Template(name)
+    }
+}
+
+impl From<Handle<EffectAsset>> for EffectSpec {
+    fn from(handle: Handle<EffectAsset>) -> Self {
+        EffectSpec::Asset(handle)
+    }
+}
@@ -0,0 +1,10 @@
+use crate::internal_prelude::*;
+
+/// Sent when more `Vfx` entities exist than the effect storage holds, after it has been
+/// grown to fit them. Each growth re-uploads the whole storage, so raise
+/// [`HirundoPlugin::with_max_entities`](crate::HirundoPlugin::with_max_entities) if this shows up.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VfxStorageGrown {
+    pub previous_capacity: usize,
+    pub capacity: usize,
+}
@@ -0,0 +1,35 @@
+use super::{CollisionVfx, vfx_target};
+use crate::internal_prelude::*;
+use avian2d::prelude::{CollisionStart, Sensor};
+
+/// Plays [`CollisionVfx`] on both sides of each avian `CollisionStart`
+pub fn play_avian_collision_vfx(
+    time: Res<Time>,
+    config: Res<CollisionVfx>,
+    mut collisions: MessageReader<CollisionStart>,
+    mut play: MessageWriter<PlayVfx>,
+    sensors: Query<(), With<Sensor>>,
+    vfxs: Query<(), With<Vfx>>,
+) {
+    if !config.enabled {
+        collisions.clear();
+        return;
+    }
+    let now = time.elapsed_secs();
+    for collision in collisions.read() {
+        if !config.sensors
+            && (sensors.contains(collision.collider1) || sensors.contains(collision.collider2))
+        {
+            continue;
+        }
+        let sides = [
+            (collision.collider1, collision.body1),
+            (collision.collider2, collision.body2),
+        ];
+        for (collider, body) in sides {
+            if let Some(target) = vfx_target([Some(collider), body], &vfxs) {
+                play.write(PlayVfx::new(target, config.effect(now)));
+            }
+        }
+    }
+}
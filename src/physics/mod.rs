@@ -0,0 +1,76 @@
+//! Glue between physics engines and effects: [`CollisionVfx`] plays an impact on `Vfx`
+//! entities as they start colliding. Enabled by the `avian` (avian2d) and `rapier`
+//! (bevy_rapier2d) features, which add the system reading that engine's collision messages.
+#[cfg(feature = "avian")]
+mod avian;
+#[cfg(feature = "rapier")]
+mod rapier;
+
+#[cfg(feature = "avian")]
+pub use avian::*;
+#[cfg(feature = "rapier")]
+pub use rapier::*;
+
+use crate::internal_prelude::*;
+
+/// What plays on a `Vfx` entity when one of its colliders starts touching another: by
+/// default [`EffectBuilder::impact`], a white flash and a 3px shake over 0.2 seconds.
+///
+/// Only colliders that report collisions do (`CollisionEventsEnabled` with avian,
+/// `ActiveEvents::COLLISION_EVENTS` with rapier). The effect plays on the collider's entity
+/// if it has a `Vfx`, otherwise on its rigid body (avian) or parent (rapier).
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let mut app = App::new();
+/// app.insert_resource(CollisionVfx {
+///     template: Some("crunch".into()),
+///     ..default()
+/// });
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct CollisionVfx {
+    pub enabled: bool,
+    /// [`EffectTemplates`] entry played instead of the impact
+    pub template: Option<String>,
+    pub color: LinearRgba,
+    /// Largest shake offset in pixels
+    pub shake: f32,
+    pub duration: f32,
+    /// Also play when sensors (triggers) start overlapping
+    pub sensors: bool,
+}
+
+impl Default for CollisionVfx {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            template: None,
+            color: LinearRgba::WHITE,
+            shake: 3.0,
+            duration: 0.2,
+            sensors: false,
+        }
+    }
+}
+
+impl CollisionVfx {
+    /// The effect to play on an entity hit at `now`
+    pub(crate) fn effect(&self, now: f32) -> EffectSpec {
+        match &self.template {
+            Some(name) => EffectSpec::Template(name.clone()),
+            None => EffectBuilder::impact(now, self.color, self.shake, self.duration).into(),
+        }
+    }
+}
+
+/// The first of `candidates` with a `Vfx`
+fn vfx_target(
+    candidates: impl IntoIterator<Item = Option<Entity>>,
+    vfxs: &Query<(), With<Vfx>>,
+) -> Option<Entity> {
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|&entity| vfxs.contains(entity))
+}
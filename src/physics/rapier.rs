@@ -0,0 +1,34 @@
+use super::{CollisionVfx, vfx_target};
+use crate::internal_prelude::*;
+use bevy_rapier2d::prelude::CollisionEvent;
+use bevy_rapier2d::rapier::geometry::CollisionEventFlags;
+
+/// Plays [`CollisionVfx`] on both sides of each rapier `CollisionEvent::Started`
+pub fn play_rapier_collision_vfx(
+    time: Res<Time>,
+    config: Res<CollisionVfx>,
+    mut collisions: MessageReader<CollisionEvent>,
+    mut play: MessageWriter<PlayVfx>,
+    parents: Query<&ChildOf>,
+    vfxs: Query<(), With<Vfx>>,
+) {
+    if !config.enabled {
+        collisions.clear();
+        return;
+    }
+    let now = time.elapsed_secs();
+    for collision in collisions.read() {
+        let CollisionEvent::Started(collider1, collider2, flags) = *collision else {
+            continue;
+        };
+        if !config.sensors && flags.contains(CollisionEventFlags::SENSOR) {
+            continue;
+        }
+        for collider in [collider1, collider2] {
+            let parent = parents.get(collider).ok().map(ChildOf::parent);
+            if let Some(target) = vfx_target([Some(collider), parent], &vfxs) {
+                play.write(PlayVfx::new(target, config.effect(now)));
+            }
+        }
+    }
+}
@@ -1,8 +1,11 @@
 // Internal prelude for use within the crate
 pub mod internal {
+    pub use crate::assets::*;
+    pub use crate::commands::*;
     pub use crate::components::*;
     pub use crate::effects::*;
     pub use crate::materials::*;
+    pub use crate::messages::*;
     pub use crate::resources::*;
     pub use crate::systems::*;
 
@@ -11,25 +14,26 @@ pub mod internal {
         ecs::lifecycle::HookContext,
         ecs::world::DeferredWorld,
         log::*,
-        mesh::{MeshTag, RectangleMeshBuilder},
+        mesh::MeshTag,
         platform::collections::HashMap,
         prelude::*,
-        render::{
-            render_resource::{AsBindGroup, ShaderType},
-            storage::ShaderStorageBuffer,
-        },
+        render::render_resource::{AsBindGroup, ShaderType},
         sprite_render::{Material2d, Material2dPlugin},
     };
     pub use derive_more::From;
     pub use enum_map::{Enum, EnumMap};
     pub use rand::prelude::*;
+    pub use serde::{Deserialize, Serialize};
     pub use std::collections::{HashSet, VecDeque};
     pub use std::f32::{self};
 
     // Constants should definitely live here
     pub const MAX_FX: usize = 6;
-    pub const MAX_SPATIAL_FX: usize = 3;
+    pub const MAX_SPATIAL_FX: usize = 6;
     pub const MAX_COLOR_FX: usize = 3;
+    pub const MAX_ALPHA_FX: usize = 2;
+    pub const MAX_FRAGMENT_FX: usize = 2;
+    pub const MAX_PATH_POINTS: usize = 8;
     pub const MAX_VFX_ENTITIES: usize = 500;
 }
 
@@ -37,22 +41,65 @@ pub mod internal {
 pub mod user {
     // Plugin
     pub use crate::HirundoPlugin;
+    pub use crate::editor::{HirundoEditorPlugin, VfxEditor};
+
+    // Data-driven effects
+    pub use crate::assets::{
+        AnimationClip, AnimationClips, AtlasTag, EffectAsset, EffectLibrary, LevelEntity,
+        LevelSprite, TagDirection, VfxAtlasLayout, VfxLevel,
+    };
+
+    // Commands
+    pub use crate::commands::{
+        DamageNumberCommands, DemoteToBroadcast, PromoteToVfx, SpawnDamageNumber, SwapVfxAtlas,
+    };
 
     // Core components
-    pub use crate::components::{Vfx, VfxBroadcast, VfxBundle};
+    pub use crate::components::{
+        AnimationClipPlayer, AnimationMode, BroadcastSprite, BroadcastTint,
+        DespawnWhenEffectsFinish, DirectionalSprite, DirectionalTarget, EffectAssetPlayer,
+        EffectHandle, SpriteAnimation, SpriteRect, SpriteToVfx, TextVfx, TextVfxMode, UiVfx, Vfx,
+        Vfx3d, VfxBroadcast, VfxBroadcastGroup, VfxBundle, VfxInstanced, VfxIntensity,
+        VfxLevelRoot, VfxMesh, VfxOnSpawn, VfxTint,
+    };
+
+    // Material blending, plugin-wide or per `Vfx` entity
+    pub use crate::materials::VfxAlphaMode;
+
+    // Messages
+    pub use crate::messages::{
+        AnimationFrameEvent, BroadcastEffectFinished, EffectSpec, PlayEffect, PlayVfx,
+        VfxStorageGrown,
+    };
 
     // Effects API (builders, modifiers, enums)
     pub use crate::effects::{
-        AlphaEffect, Anchor, BlendMode, ColorEffect, CompositeMode, Effect, EffectBuilder,
-        EffectModifier, EffectStack, Envelope, Lifetime, Phase, SpatialEffect, Wave, WaveKind,
+        AlphaClip, AlphaComposite, AlphaEffect, Anchor, BlendMode, ColorEffect, CompositeMode,
+        Effect, EffectBuilder, EffectError, EffectModifier, EffectSequence, EffectStack,
+        EffectTarget, Emissive, Envelope, FragmentEffect, FragmentKind, LastEffect, Lifetime,
+        Phase, Reverse, SpatialEffect, SpatialKind, TileCycleEffect, Wave, WaveKind,
     };
 
     // Resources (only what users might need to access)
-    pub use crate::resources::{AtlasDimensions, VfxBroadcastMaterialHandle};
+    pub use crate::resources::{
+        AtlasDimensions, BroadcastFx, DamageNumbers, EffectTemplates, ScreenVfx, SpriteNames,
+        SpriteRects, VfxAccessibility, VfxAudioDriver, VfxAudioUniform, VfxBroadcastMaterialHandle,
+        VfxBroadcasts, VfxClock, VfxLod, VfxPool, VfxScheduler, VfxStats, VfxTimeline,
+    };
+
+    // System sets, for ordering effect pushes before the upload
+    pub use crate::systems::HirundoSet;
 
     // Optional: Broadcast update system (if users want manual control)
     pub use crate::systems::update_broadcast_effect_stack;
 
+    // Optional: Gizmo group of the `with_vfx_gizmos` overlay
+    pub use crate::systems::VfxGizmos;
+
+    // Optional: Collision impacts (`avian` / `rapier` features)
+    #[cfg(any(feature = "avian", feature = "rapier"))]
+    pub use crate::physics::CollisionVfx;
+
     // Optional: Demo input systems (for testing/examples)
     pub use crate::input::{control_broadcast_fx, control_unique_fx};
 
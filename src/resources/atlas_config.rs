@@ -1,9 +1,14 @@
 use crate::internal_prelude::*;
 
-#[derive(Clone, ShaderType, Debug)]
+#[derive(Clone, ShaderType, Debug, PartialEq, Reflect)]
 pub struct AtlasDimensions {
     pub texture_size: Vec2,
     pub cell_size: Vec2,
     pub sprite_size: Vec2,
     pub padding: Vec2,
+    /// Pixels left out of every side of a sprite when sampling it, so linear filtering,
+    /// scaling and rotation don't pull in the neighbouring sprites of atlases without
+    /// padding. `Vec2::splat(0.5)` is the usual half-texel correction; the sprite is
+    /// still drawn at its full size.
+    pub uv_inset: Vec2,
 }
@@ -0,0 +1,52 @@
+use crate::internal_prelude::*;
+
+/// Edits to the default broadcast material, queued from any system and applied to the
+/// material behind [`VfxBroadcastMaterialHandle`] in [`HirundoSet::Upload`], so pushing a
+/// shared effect doesn't need `ResMut<Assets<VfxBroadcastMaterial>>`.
+///
+/// Pushed effects go through [`VfxAccessibility`] on the way in.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// fn on_hit(mut broadcast: ResMut<BroadcastFx>, time: Res<Time>) {
+///     broadcast.push_effect(EffectBuilder::flash(time.elapsed_secs(), LinearRgba::RED, 0.3).build());
+/// }
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct BroadcastFx {
+    /// Effects pushed since the last upload
+    pub(crate) pending: EffectStack,
+    /// Whether the material's own effects are cleared before `pending` is pushed
+    pub(crate) clear: bool,
+    pub(crate) tile_index: Option<u32>,
+}
+
+impl BroadcastFx {
+    /// Draw every broadcast entity with atlas tile `tile_index`
+    pub fn set_tile(&mut self, tile_index: u32) {
+        self.tile_index = Some(tile_index);
+    }
+
+    /// Whether edits are waiting for the next `HirundoSet::Upload`
+    pub fn is_pending(&self) -> bool {
+        self.clear || self.tile_index.is_some() || self.pending.active_count() > 0
+    }
+}
+
+/// [`EffectTarget::effects`] holds the effects pushed since the last upload, not the
+/// material's
+impl EffectTarget for BroadcastFx {
+    fn stack(&self) -> &EffectStack {
+        &self.pending
+    }
+
+    fn stack_mut(&mut self) -> &mut EffectStack {
+        &mut self.pending
+    }
+
+    /// Remove every effect on the material, including ones pushed earlier this frame
+    fn clear_effects(&mut self) {
+        self.pending.clear();
+        self.clear = true;
+    }
+}
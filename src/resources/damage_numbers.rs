@@ -0,0 +1,42 @@
+use crate::internal_prelude::*;
+
+/// Where the digit sprites live in the atlas and how
+/// [`DamageNumberCommands::spawn_damage_number`] lays out and animates them.
+///
+/// The digits '0' to '9' must sit at consecutive atlas indices starting at `first_digit`.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let mut app = App::new();
+/// app.insert_resource(DamageNumbers {
+///     first_digit: 48,
+///     spacing: 7.0,
+///     ..default()
+/// });
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct DamageNumbers {
+    /// Atlas index of the '0' sprite
+    pub first_digit: u32,
+    /// Pixels between the centers of neighbouring digits
+    pub spacing: f32,
+    /// Pixels a number rises before fading out
+    pub rise: f32,
+    /// Seconds a number stays on screen
+    pub duration: f32,
+    /// Z of the spawned digits, to draw above the sprites they pop from
+    pub z: f32,
+}
+
+impl Default for DamageNumbers {
+    fn default() -> Self {
+        Self {
+            first_digit: 0,
+            spacing: 8.0,
+            rise: 24.0,
+            duration: 0.8,
+            z: 10.0,
+        }
+    }
+}
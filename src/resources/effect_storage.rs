@@ -1,16 +1,56 @@
+use crate::HirundoPlugin;
 use crate::internal_prelude::*;
 
 #[derive(Resource)]
 pub struct EffectStorageData {
     pub effects: Vec<EffectStack>,
     pub dirty_slots: HashSet<usize>,
+    /// Slots included in the most recent buffer upload
+    pub last_uploaded: HashSet<usize>,
+    /// Size of the most recent buffer upload, 0 if there was none this frame
+    pub last_upload_bytes: usize,
 }
 
 impl FromWorld for EffectStorageData {
-    fn from_world(_world: &mut World) -> Self {
+    fn from_world(world: &mut World) -> Self {
+        let max_entities = world
+            .get_resource::<HirundoPlugin>()
+            .map_or(MAX_VFX_ENTITIES, |plugin_config| plugin_config.max_entities);
         Self {
-            effects: vec![EffectStack::default(); MAX_VFX_ENTITIES],
+            effects: vec![EffectStack::default(); max_entities],
             dirty_slots: HashSet::new(),
+            last_uploaded: HashSet::new(),
+            last_upload_bytes: 0,
         }
     }
 }
+
+impl EffectStorageData {
+    /// Empty `slot` and mark it for upload, once no `Vfx` entity draws from it
+    pub(crate) fn clear_slot(&mut self, slot: usize) {
+        if let Some(stack) = self.effects.get_mut(slot) {
+            stack.clear();
+            stack.tile_index = 0;
+            stack.hidden = 0;
+            stack.sprite_rect = Vec4::ZERO;
+            stack.base_tint = Vec4::ONE;
+            stack.intensity = 1.0;
+            self.dirty_slots.insert(slot);
+        } else {
+            // A tag past the storage, released before its first upload ever grew it
+            self.dirty_slots.remove(&slot);
+        }
+    }
+}
+
+/// Encoded runs of dirty slots, written into the effect storage buffer at their
+/// byte offsets instead of re-uploading the whole buffer.
+///
+/// The main world replaces the runs whenever slots were dirty; the render world
+/// queues every extracted run until the GPU buffer exists, then writes and drops them.
+#[derive(Resource, Default, Clone)]
+pub struct EffectStorageWrites {
+    pub(crate) buffer: Option<VfxStorage>,
+    /// `(byte offset, encoded stacks)` per contiguous run of slots
+    pub(crate) runs: Vec<(u64, Vec<u8>)>,
+}
@@ -0,0 +1,60 @@
+use crate::internal_prelude::*;
+
+type Template = Box<dyn Fn(f32) -> EffectBuilder + Send + Sync>;
+
+/// Named effect recipes, registered once and played anywhere by name.
+///
+/// Each template receives `now` and returns the builder, so every play starts fresh.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// fn register_effects(mut templates: ResMut<EffectTemplates>) {
+///     templates.insert("hit_flash", |now| EffectBuilder::flash(now, LinearRgba::WHITE, 0.15));
+///     templates.insert("spin", |now| EffectBuilder::one_shot(now, 0.5).rotate(360.0));
+/// }
+///
+/// fn on_hit(time: Res<Time>, templates: Res<EffectTemplates>, mut query: Query<&mut Vfx>) {
+///     for mut vfx in &mut query {
+///         vfx.play(&templates, "hit_flash", time.elapsed_secs());
+///     }
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct EffectTemplates {
+    templates: HashMap<String, Template>,
+}
+
+impl EffectTemplates {
+    /// Register `template` under `name`, replacing any template already using it.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        template: impl Fn(f32) -> EffectBuilder + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.templates.insert(name.into(), Box::new(template));
+        self
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.templates.remove(name).is_some()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.templates.contains_key(name)
+    }
+
+    /// Registered template names, in no particular order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    /// The builder of template `name` starting at `now`, for further tweaks before `build()`
+    pub fn builder(&self, name: &str, now: f32) -> Option<EffectBuilder> {
+        self.templates.get(name).map(|template| template(now))
+    }
+
+    /// Build template `name` starting at `now`, e.g. to push it to a broadcast [`EffectStack`]
+    pub fn build(&self, name: &str, now: f32) -> Option<Effect> {
+        self.builder(name, now).map(EffectBuilder::build)
+    }
+}
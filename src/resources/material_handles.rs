@@ -3,9 +3,47 @@ use crate::internal_prelude::*;
 #[derive(Resource, Deref, DerefMut)]
 pub struct VfxMaterialHandle(pub Handle<VfxMaterial>);
 
+/// Copies of the `Vfx` material in other [`VfxAlphaMode`]s, for entities carrying one
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct VfxAlphaModeMaterials(pub HashMap<VfxAlphaMode, Handle<VfxMaterial>>);
+
+/// The [`VfxMaterial3d`] every `Vfx3d` entity draws with, see `HirundoPlugin::with_3d`
+#[derive(Resource, Deref, DerefMut)]
+pub struct VfxMaterial3dHandle(pub Handle<VfxMaterial3d>);
+
+/// The mesh every [`VfxInstanced`] sprite is batched into, drawn by `entity` with a copy
+/// of the `Vfx` material, see `HirundoPlugin::with_instancing`
+#[derive(Resource)]
+pub struct VfxInstanceBatch {
+    pub entity: Entity,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<VfxMaterial>,
+}
+
 #[derive(Resource, Deref, DerefMut)]
 pub struct VfxMeshHandle(pub Handle<Mesh>);
 
+/// Copies of the sprite mesh with a vertex color, one per [`BroadcastTint`] color in use,
+/// keyed by the bits of its linear RGBA
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct BroadcastTintMeshes(pub HashMap<[u32; 4], Handle<Mesh>>);
+
+/// Noise texture shared by both materials (dissolve threshold source)
+#[derive(Resource, Deref, DerefMut)]
+pub struct VfxNoiseTexture(pub Handle<Image>);
+
+/// Module loaded for `HirundoPlugin::with_custom_fragment`, kept alive for its import path
+#[derive(Resource, Deref, DerefMut)]
+pub struct VfxCustomFragmentShader(pub Handle<Shader>);
+
+/// Storage both materials read [`SpriteRects`] from
+#[derive(Resource, Deref, DerefMut)]
+pub struct VfxSpriteRectsBuffer(pub VfxStorage);
+
+/// Atlas description loaded with `HirundoPlugin::with_atlas_json`
+#[derive(Resource, Deref, DerefMut)]
+pub struct VfxAtlasLayoutHandle(pub Handle<VfxAtlasLayout>);
+
 /// Resource holding the broadcast material handle
 #[derive(Resource, Deref, DerefMut)]
 pub struct VfxBroadcastMaterialHandle(pub Handle<VfxBroadcastMaterial>);
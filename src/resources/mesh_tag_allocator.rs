@@ -1,6 +1,6 @@
 use crate::internal_prelude::*;
 
-#[derive(Resource, FromWorld)]
+#[derive(Resource, Default)]
 pub struct MeshTagAllocator {
     pub next_tag: u32,
     pub free_list: VecDeque<u32>,
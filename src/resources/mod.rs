@@ -3,9 +3,39 @@ mod effect_storage;
 mod material_handles;
 mod vfx_registry;
 mod atlas_config;
+mod effect_templates;
+mod sprite_names;
+mod sprite_rects;
+mod vfx_pool;
+mod vfx_timeline;
+mod vfx_stats;
+mod screen_vfx;
+mod vfx_accessibility;
+mod vfx_broadcasts;
+mod broadcast_fx;
+mod vfx_lod;
+mod vfx_scheduler;
+mod vfx_audio_driver;
+mod vfx_clock;
+mod damage_numbers;
 
 pub use mesh_tag_allocator::*;
 pub use effect_storage::*;
 pub use material_handles::*;
 pub use vfx_registry::*;
 pub use atlas_config::*;
+pub use effect_templates::*;
+pub use sprite_names::*;
+pub use sprite_rects::*;
+pub use vfx_pool::*;
+pub use vfx_timeline::*;
+pub use vfx_stats::*;
+pub use screen_vfx::*;
+pub use vfx_accessibility::*;
+pub use vfx_broadcasts::*;
+pub use broadcast_fx::*;
+pub use vfx_lod::*;
+pub use vfx_scheduler::*;
+pub use vfx_audio_driver::*;
+pub use vfx_clock::*;
+pub use damage_numbers::*;
@@ -0,0 +1,33 @@
+use crate::internal_prelude::*;
+
+/// Effects on the whole screen (hit flashes, fades to black, camera shake, chromatic
+/// aberration), built with the same [`EffectBuilder`] waves, envelopes and presets as
+/// sprites. Needs `HirundoPlugin::with_screen_vfx`.
+///
+/// Drawn by a full-screen pass after tonemapping on every `Camera2d`, as if the screen
+/// were one big sprite: spatial effects move the image (in logical pixels, anchors
+/// relative to the viewport), color effects tint it and alpha effects fade it to black.
+/// Of the fragment effects, only pixelate and glitch apply.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// fn on_hit(mut screen: ResMut<ScreenVfx>, time: Res<Time>) {
+///     let now = time.elapsed_secs();
+///     screen.push_effect(EffectBuilder::flash(now, LinearRgba::WHITE, 0.15).build());
+///     screen.push_effect(EffectBuilder::shake(now, 6.0, 0.3).build());
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct ScreenVfx {
+    pub(crate) effects: EffectStack,
+}
+
+impl EffectTarget for ScreenVfx {
+    fn stack(&self) -> &EffectStack {
+        &self.effects
+    }
+
+    fn stack_mut(&mut self) -> &mut EffectStack {
+        &mut self.effects
+    }
+}
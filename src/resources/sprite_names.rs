@@ -0,0 +1,63 @@
+use crate::internal_prelude::*;
+
+/// Sprite names to atlas indices, filled from the atlas loaded with
+/// [`HirundoPlugin::with_atlas_json`](crate::HirundoPlugin::with_atlas_json),
+/// along with its Aseprite animation tags.
+///
+/// Without an atlas description, names can be registered by hand with [`Self::insert`].
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// fn spawn_goblin(mut commands: Commands, names: Res<SpriteNames>) {
+///     commands.spawn(Vfx::with_sprite_name("goblin_idle_0", &names));
+/// }
+/// ```
+#[derive(Resource, Default, Debug, Clone, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SpriteNames {
+    pub(crate) names: HashMap<String, u32>,
+    pub(crate) tags: HashMap<String, AtlasTag>,
+}
+
+impl SpriteNames {
+    /// Atlas index of sprite `name`
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.names.get(name).copied()
+    }
+
+    /// Register `name` for atlas index `index`, replacing any previous index.
+    ///
+    /// Names from an atlas description replace all names whenever it (re)loads.
+    pub fn insert(&mut self, name: impl Into<String>, index: u32) -> &mut Self {
+        self.names.insert(name.into(), index);
+        self
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<u32> {
+        self.names.remove(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Aseprite animation tag `name`
+    pub fn tag(&self, name: &str) -> Option<&AtlasTag> {
+        self.tags.get(name)
+    }
+
+    /// All names and their indices, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.names
+            .iter()
+            .map(|(name, index)| (name.as_str(), *index))
+    }
+}
@@ -0,0 +1,68 @@
+use crate::internal_prelude::*;
+
+/// Per-sprite pixel rects for atlases whose sprites don't share one size.
+///
+/// A sprite index with a rect here is sampled from that rect (in pixels, origin top-left)
+/// and drawn at its size, instead of from its cell on the uniform [`AtlasDimensions`] grid.
+/// Indices without a rect keep using the grid, so the two can be mixed. Filled from the
+/// atlas loaded with [`HirundoPlugin::with_atlas_json`](crate::HirundoPlugin::with_atlas_json)
+/// when its frames aren't on a grid, or set by hand:
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// fn setup_boss(mut rects: ResMut<SpriteRects>) {
+///     rects.insert(600, Rect::new(0.0, 800.0, 96.0, 928.0));
+/// }
+/// ```
+#[derive(Resource, Default, Debug, Clone, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SpriteRects {
+    pub(crate) rects: Vec<Option<Rect>>,
+}
+
+impl SpriteRects {
+    /// Sample sprite `index` from `rect`, replacing any previous rect.
+    pub fn insert(&mut self, index: u32, rect: Rect) -> &mut Self {
+        let index = index as usize;
+        if index >= self.rects.len() {
+            self.rects.resize(index + 1, None);
+        }
+        self.rects[index] = Some(rect);
+        self
+    }
+
+    /// Put sprite `index` back on the uniform grid
+    pub fn remove(&mut self, index: u32) -> Option<Rect> {
+        self.rects.get_mut(index as usize)?.take()
+    }
+
+    pub fn get(&self, index: u32) -> Option<Rect> {
+        self.rects.get(index as usize).copied().flatten()
+    }
+
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rects.iter().all(Option::is_none)
+    }
+
+    /// The GPU table: `(x, y, width, height)` per index, zero for grid sprites.
+    /// Never empty, since storage buffers can't be zero-sized.
+    pub(crate) fn gpu_table(&self) -> Vec<Vec4> {
+        let mut table: Vec<Vec4> = self
+            .rects
+            .iter()
+            .map(|rect| {
+                rect.map_or(Vec4::ZERO, |rect| {
+                    Vec4::new(rect.min.x, rect.min.y, rect.width(), rect.height())
+                })
+            })
+            .collect();
+        if table.is_empty() {
+            table.push(Vec4::ZERO);
+        }
+        table
+    }
+}
@@ -0,0 +1,118 @@
+use crate::internal_prelude::*;
+use std::borrow::Cow;
+
+/// Photosensitivity and reduced-motion settings, applied to every effect stack as it's
+/// uploaded (`Vfx`, [`UiVfx`], [`ScreenVfx`], [`TextVfx`]), so games can honor them
+/// without rewriting their effects. The default changes nothing.
+///
+/// [`VfxBroadcasts`] groups and [`BroadcastFx`] pushes are covered too; effects written
+/// straight into the default broadcast material's stack should go through [`Self::apply`].
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Resource)]
+/// # struct GameSettings {
+/// #     reduce_motion: bool,
+/// # }
+/// fn apply_settings(settings: Res<GameSettings>, mut accessibility: ResMut<VfxAccessibility>) {
+///     *accessibility = if settings.reduce_motion { VfxAccessibility::reduced() } else { default() };
+/// }
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct VfxAccessibility {
+    /// Scale of color effect strengths (flashes, tints, HSV shifts, emissive), 1.0 for as
+    /// built. Gradients keep their colors.
+    pub flash_intensity: f32,
+    /// Largest distance in pixels an oscillating offset (shakes, jitters, bobbing) moves
+    /// a sprite, `None` for no cap. Steady offsets and motion along arcs or paths are kept.
+    pub max_shake: Option<f32>,
+    /// Square waves on color, alpha and fragment effects cycling faster than this many
+    /// times per second are held at their middle value instead of strobing, `None` for
+    /// no limit
+    pub max_strobe_hz: Option<f32>,
+}
+
+impl Default for VfxAccessibility {
+    fn default() -> Self {
+        Self {
+            flash_intensity: 1.0,
+            max_shake: None,
+            max_strobe_hz: None,
+        }
+    }
+}
+
+impl VfxAccessibility {
+    /// Flashes at half strength, shakes of at most 2px and no strobing above 3 Hz, the
+    /// flash rate photosensitivity guidelines (WCAG 2.3.1) stay under
+    pub fn reduced() -> Self {
+        Self {
+            flash_intensity: 0.5,
+            max_shake: Some(2.0),
+            max_strobe_hz: Some(3.0),
+        }
+    }
+
+    /// Tone down `stack` according to these settings
+    pub fn apply(&self, stack: &mut EffectStack) {
+        for eff in &mut stack.effects {
+            self.apply_effect(eff);
+        }
+    }
+
+    /// Tone down a single effect, see [`Self::apply`]
+    pub fn apply_effect(&self, eff: &mut Effect) {
+        let duration = eff.lifetime.duration;
+        for color in &mut eff.color_effects {
+            if color.blend_mode != BlendMode::Gradient as u32 {
+                color.wave.amp *= self.flash_intensity;
+                color.wave.bias *= self.flash_intensity;
+            }
+            self.hold_strobe(&mut color.wave, &color.phase, duration);
+        }
+        for alpha in &mut eff.alpha_effects {
+            self.hold_strobe(&mut alpha.wave, &alpha.phase, duration);
+        }
+        for frag in &mut eff.fragment_effects {
+            self.hold_strobe(&mut frag.wave, &frag.phase, duration);
+        }
+
+        let Some(max_shake) = self.max_shake else {
+            return;
+        };
+        for spatial in &mut eff.spatial_effects {
+            let offset = spatial.manipulation == SpatialKind::OffsetX as u32
+                || spatial.manipulation == SpatialKind::OffsetY as u32;
+            let oscillating =
+                WaveKind::from_raw(spatial.wave.kind).is_some_and(WaveKind::oscillates);
+            let peak = (spatial.wave.amp * spatial.intensity).abs();
+            if offset && oscillating && peak > max_shake {
+                spatial.wave.amp *= max_shake / peak;
+            }
+        }
+    }
+
+    /// `stack` with [`Self::apply`], borrowed as is while nothing is enabled
+    pub(crate) fn adjust<'a>(&self, stack: &'a EffectStack) -> Cow<'a, EffectStack> {
+        if *self == Self::default() {
+            return Cow::Borrowed(stack);
+        }
+        let mut adjusted = stack.clone();
+        self.apply(&mut adjusted);
+        Cow::Owned(adjusted)
+    }
+
+    /// A square `wave` over `phase` of a `duration` long effect, held at its middle
+    /// value if it cycles faster than `max_strobe_hz`
+    fn hold_strobe(&self, wave: &mut Wave, phase: &Phase, duration: f32) {
+        let Some(max_hz) = self.max_strobe_hz else {
+            return;
+        };
+        let seconds = duration * (phase.end.clamp(0.0, 1.0) - phase.start.clamp(0.0, 1.0));
+        if wave.kind == WaveKind::Square as u32 && seconds > 0.0 && wave.freq / seconds > max_hz {
+            wave.kind = WaveKind::Constant as u32;
+            wave.amp = 0.0;
+        }
+    }
+}
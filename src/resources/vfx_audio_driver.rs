@@ -0,0 +1,103 @@
+use crate::internal_prelude::*;
+
+/// Audio levels that [`WaveKind::Audio`] and [`WaveKind::Beat`] waves follow, so effects
+/// pulse to music without editing amplitudes every frame.
+///
+/// Feed it from your audio code (an analyzer on the mixer, a beat map, bevy_kira_audio's
+/// playback position...). The beat jumps to 1.0 on [`Self::trigger_beat`] and falls back
+/// to 0.0 over `beat_decay` seconds. Applies to every material, [`UiVfx`], [`TextVfx`]
+/// and [`ScreenVfx`] included.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Resource)]
+/// # struct MusicAnalysis {
+/// #     rms: f32,
+/// #     on_beat: bool,
+/// # }
+/// fn feed_music(mut driver: ResMut<VfxAudioDriver>, music: Res<MusicAnalysis>) {
+///     driver.set_amplitude(music.rms);
+///     if music.on_beat {
+///         driver.trigger_beat();
+///     }
+/// }
+///
+/// # let now = 0.0;
+/// // A speaker that swells with the music and kicks on every beat
+/// EffectBuilder::looping(now, 1.0)
+///     .scale_uniform(0.2)
+///     .with(Wave::audio(0.2, 0.0))
+///     .color(LinearRgba::WHITE)
+///     .with(BlendMode::Add)
+///     .with(Wave::beat(0.6, 0.0))
+///     .build();
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct VfxAudioDriver {
+    /// Loudness, 0.0 (silence) to 1.0
+    pub amplitude: f32,
+    /// 1.0 on a beat, fading to 0.0
+    pub beat: f32,
+    /// Seconds a beat takes to fade out
+    pub beat_decay: f32,
+    /// What the shaders read this frame, see [`Self::levels`]
+    #[reflect(ignore)]
+    pub(crate) levels: VfxAudioUniform,
+}
+
+impl Default for VfxAudioDriver {
+    fn default() -> Self {
+        Self {
+            amplitude: 0.0,
+            beat: 0.0,
+            beat_decay: 0.25,
+            levels: VfxAudioUniform::default(),
+        }
+    }
+}
+
+impl VfxAudioDriver {
+    /// Set the loudness, clamped to 0.0 - 1.0
+    pub fn set_amplitude(&mut self, amplitude: f32) {
+        self.amplitude = amplitude.clamp(0.0, 1.0);
+    }
+
+    /// Start a beat: `beat` jumps to 1.0 and fades out over `beat_decay` seconds
+    pub fn trigger_beat(&mut self) {
+        self.beat = 1.0;
+    }
+
+    /// Fade the beat by `dt` seconds
+    pub(crate) fn decay(&mut self, dt: f32) {
+        if self.beat > 0.0 {
+            self.beat = (self.beat - dt / self.beat_decay.max(f32::EPSILON)).max(0.0);
+        }
+    }
+
+    /// The levels the shaders draw with this frame, for the CPU mirrors of their math
+    /// ([`Wave::evaluate`], [`EffectStack::tint`], [`Effect::spatial_value`]...).
+    ///
+    /// Set when the levels are handed to the materials in [`HirundoSet::Upload`], before
+    /// the beat fades, so read it after that set to follow what is drawn.
+    pub fn levels(&self) -> VfxAudioUniform {
+        self.levels
+    }
+
+    /// What the shaders read
+    pub(crate) fn uniform(&self) -> VfxAudioUniform {
+        VfxAudioUniform {
+            amplitude: self.amplitude.clamp(0.0, 1.0),
+            beat: self.beat.clamp(0.0, 1.0),
+            _pad: Vec2::ZERO,
+        }
+    }
+}
+
+/// [`VfxAudioDriver`] as seen by the shaders
+#[derive(Clone, Copy, ShaderType, Debug, Default, PartialEq)]
+pub struct VfxAudioUniform {
+    pub amplitude: f32,
+    pub beat: f32,
+    pub _pad: Vec2,
+}
@@ -0,0 +1,73 @@
+use crate::HirundoPlugin;
+use crate::internal_prelude::*;
+
+/// Named broadcast groups ("enemies", "pickups", "water tiles"), each drawn with its own
+/// [`VfxBroadcastMaterial`] so the whole group shares one effect stack.
+///
+/// Entities join a group with [`VfxBroadcastGroup`]. A group's material is created the
+/// first time it's pushed to or joined, from the settings of the default broadcast
+/// material (atlas, overlay, alpha mode), and changed stacks are copied into it in
+/// `PostUpdate`. The default material behind [`VfxBroadcastMaterialHandle`] stays as it was.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// fn on_alarm(mut broadcasts: ResMut<VfxBroadcasts>, time: Res<Time>) {
+///     let now = time.elapsed_secs();
+///     broadcasts
+///         .group("enemies")
+///         .push_effect(EffectBuilder::flash(now, LinearRgba::RED, 0.3).build());
+/// }
+/// ```
+#[derive(Resource, Debug)]
+pub struct VfxBroadcasts {
+    pub(crate) stacks: HashMap<String, EffectStack>,
+    pub(crate) materials: HashMap<String, Handle<VfxBroadcastMaterial>>,
+    /// `HirundoPlugin::fx_slots`, for the stacks of new groups
+    fx_slots: usize,
+}
+
+impl FromWorld for VfxBroadcasts {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            stacks: default(),
+            materials: default(),
+            fx_slots: world
+                .get_resource::<HirundoPlugin>()
+                .map_or(MAX_FX, |plugin_config| plugin_config.fx_slots),
+        }
+    }
+}
+
+impl VfxBroadcasts {
+    /// Draw every entity of `group` with atlas sprite `sprite_index`
+    pub fn set_sprite(&mut self, group: &str, sprite_index: u32) {
+        self.group(group).tile_index = sprite_index;
+    }
+
+    /// The effect stack of `group`, `None` until it's first used
+    pub fn effects(&self, group: &str) -> Option<&EffectStack> {
+        self.stacks.get(group)
+    }
+
+    /// The material `group` is drawn with, `None` until the first `PostUpdate` after it's
+    /// first used
+    pub fn material(&self, group: &str) -> Option<&Handle<VfxBroadcastMaterial>> {
+        self.materials.get(group)
+    }
+
+    /// Names of the groups used so far
+    pub fn groups(&self) -> impl Iterator<Item = &str> {
+        self.stacks.keys().map(String::as_str)
+    }
+
+    /// The stack of `group`, created empty on first use, to push effects to through
+    /// [`EffectTarget`]
+    pub fn group(&mut self, group: &str) -> &mut EffectStack {
+        let fx_slots = self.fx_slots;
+        self.stacks.entry_ref(group).or_insert_with(|| {
+            let mut stack = EffectStack::default();
+            stack.limit_slots(fx_slots);
+            stack
+        })
+    }
+}
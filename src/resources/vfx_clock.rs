@@ -0,0 +1,85 @@
+use crate::internal_prelude::*;
+
+/// Musical tempo for effects that loop in time with the beat, so rhythm games keep every
+/// entity's loops phase-aligned no matter when it was spawned.
+///
+/// Beats fall every `60 / bpm` seconds from `offset` (elapsed seconds of any beat, e.g.
+/// when the song started). [`EffectBuilder::looping_synced`] starts a loop on the next one.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[allow(non_upper_case_globals)]
+/// # const dancer: u32 = 3;
+/// fn start_song(mut clock: ResMut<VfxClock>, time: Res<Time>) {
+///     *clock = VfxClock::new(128.0).starting_at(time.elapsed_secs());
+/// }
+///
+/// fn spawn_dancer(mut commands: Commands, clock: Res<VfxClock>, time: Res<Time>) {
+///     let mut vfx = Vfx::with_sprite(dancer);
+///     // Bobs once per beat, in step with every other dancer
+///     vfx.push_effect(
+///         EffectBuilder::looping_synced(time.elapsed_secs(), &clock, 1.0)
+///             .offset_y(4.0)
+///             .with(Wave::sine(1.0, 4.0, 0.0))
+///             .build(),
+///     );
+///     commands.spawn(vfx);
+/// }
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct VfxClock {
+    /// Beats per minute
+    pub bpm: f32,
+    /// Elapsed seconds at which a beat falls
+    pub offset: f32,
+}
+
+impl Default for VfxClock {
+    fn default() -> Self {
+        Self::new(120.0)
+    }
+}
+
+impl VfxClock {
+    /// A clock at `bpm` beats per minute, with a beat at 0.0 seconds
+    pub fn new(bpm: f32) -> Self {
+        Self { bpm, offset: 0.0 }
+    }
+
+    /// Move the beats so one falls at `time`
+    pub fn starting_at(mut self, time: f32) -> Self {
+        self.offset = time;
+        self
+    }
+
+    /// Seconds between beats, 0.0 for a stopped clock (`bpm` of 0.0 or less)
+    pub fn beat_length(&self) -> f32 {
+        if self.bpm > 0.0 { 60.0 / self.bpm } else { 0.0 }
+    }
+
+    /// Seconds `beats` beats last
+    pub fn beats(&self, beats: f32) -> f32 {
+        beats * self.beat_length()
+    }
+
+    /// Beats since `offset` at `now`, fractional between beats
+    pub fn beat_at(&self, now: f32) -> f32 {
+        let length = self.beat_length();
+        if length > 0.0 {
+            (now - self.offset) / length
+        } else {
+            0.0
+        }
+    }
+
+    /// Time of the first beat at or after `now`; `now` itself for a stopped clock
+    pub fn next_beat(&self, now: f32) -> f32 {
+        let length = self.beat_length();
+        if length > 0.0 {
+            self.offset + self.beat_at(now).ceil() * length
+        } else {
+            now
+        }
+    }
+}
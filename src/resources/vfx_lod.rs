@@ -0,0 +1,97 @@
+use crate::internal_prelude::*;
+
+/// Level of detail for zoomed-out views: once the camera's orthographic scale passes
+/// `zoom_threshold`, the shaders skip fragment-heavy effects and evaluate fewer spatial
+/// effects, which are too small to see at that distance anyway.
+///
+/// Applies to `Vfx`, `Vfx3d` and broadcast materials; [`UiVfx`] nodes don't zoom and keep
+/// every effect. The default never kicks in.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let mut app = App::new();
+/// app.insert_resource(VfxLod::beyond(3.0));
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct VfxLod {
+    /// Orthographic scale (1.0 unzoomed, larger zoomed out) past which the LOD applies,
+    /// `None` to never apply it
+    pub zoom_threshold: Option<f32>,
+    pub skip_outline: bool,
+    pub skip_dissolve: bool,
+    /// Skip HSV shifts, the only color effect with a per-pixel conversion
+    pub skip_hsv: bool,
+    /// Effect slots whose spatial effects are still evaluated, in slot order; 0 keeps
+    /// every sprite still
+    pub spatial_slots: u32,
+}
+
+impl Default for VfxLod {
+    fn default() -> Self {
+        Self {
+            zoom_threshold: None,
+            skip_outline: true,
+            skip_dissolve: true,
+            skip_hsv: true,
+            spatial_slots: 2,
+        }
+    }
+}
+
+impl VfxLod {
+    /// The default reductions, applied past orthographic scale `zoom_threshold`
+    pub fn beyond(zoom_threshold: f32) -> Self {
+        Self {
+            zoom_threshold: Some(zoom_threshold),
+            ..default()
+        }
+    }
+
+    /// Whether the LOD applies to a camera at orthographic scale `zoom`
+    pub fn applies_at(&self, zoom: f32) -> bool {
+        self.zoom_threshold
+            .is_some_and(|threshold| zoom > threshold)
+    }
+
+    /// What the shaders skip at orthographic scale `zoom`
+    pub(crate) fn uniform(&self, zoom: f32) -> VfxLodUniform {
+        if !self.applies_at(zoom) {
+            return VfxLodUniform::default();
+        }
+        let mut skip_fragments = 0;
+        if self.skip_outline {
+            skip_fragments |= 1 << FragmentKind::Outline as u32;
+        }
+        if self.skip_dissolve {
+            skip_fragments |= 1 << FragmentKind::Dissolve as u32;
+        }
+        VfxLodUniform {
+            skip_fragments,
+            skip_hsv: self.skip_hsv as u32,
+            spatial_slots: self.spatial_slots,
+            _pad: 0,
+        }
+    }
+}
+
+/// [`VfxLod`] as seen by the shaders
+#[derive(Clone, Copy, ShaderType, Debug, PartialEq)]
+pub struct VfxLodUniform {
+    /// Bit `1 << kind` set for each skipped [`FragmentKind`]
+    pub skip_fragments: u32,
+    pub skip_hsv: u32,
+    pub spatial_slots: u32,
+    pub _pad: u32,
+}
+
+impl Default for VfxLodUniform {
+    fn default() -> Self {
+        Self {
+            skip_fragments: 0,
+            skip_hsv: 0,
+            spatial_slots: MAX_FX as u32,
+            _pad: 0,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::internal_prelude::*;
+
+/// Pre-spawned, hidden [`Vfx`] entities handed out for short-lived effects.
+///
+/// Spawning and despawning a `Vfx` entity runs the tag hooks and moves it through
+/// several archetypes; for effects played many times a second (hit sparks, muzzle
+/// flashes) the pool reuses the same entities instead. Size it with
+/// [`HirundoPlugin::with_vfx_pool`](crate::HirundoPlugin::with_vfx_pool).
+///
+/// A handed out entity returns to the pool, hidden, once its last effect expires.
+/// Clear its effects to return it early, e.g. one holding a looping effect.
+/// Pooled entities keep their `MeshTag` while idle, so they count towards the plugin's `max_entities`.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[allow(non_upper_case_globals)]
+/// # const spark: u32 = 7;
+/// # #[allow(non_upper_case_globals)]
+/// # const hit: Vec3 = Vec3::ZERO;
+/// fn on_hit(mut commands: Commands, mut pool: ResMut<VfxPool>, time: Res<Time>) {
+///     let mut vfx = Vfx::with_sprite(spark);
+///     vfx.push_effect(EffectBuilder::fade_out(time.elapsed_secs(), 0.3).build());
+///     pool.spawn(&mut commands, vfx, Transform::from_translation(hit));
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct VfxPool {
+    pub(crate) free: Vec<Entity>,
+    pub(crate) size: usize,
+}
+
+impl VfxPool {
+    /// Hand out an idle entity showing `vfx` at `transform`.
+    ///
+    /// When every entity is in use the pool grows by one, spawning it like any other `Vfx`.
+    /// Don't despawn or remove components from the returned entity; let it expire instead.
+    ///
+    /// A reused entity carries its slot generations over, so [`EffectHandle`]s kept from
+    /// its previous use don't resolve against `vfx`'s effects. Handles from pushes onto
+    /// `vfx` before this call are shifted with them; take them from the entity's `Vfx`
+    /// once it's spawned instead.
+    pub fn spawn(&mut self, commands: &mut Commands, vfx: Vfx, transform: Transform) -> Entity {
+        let pooled = PooledVfx {
+            in_use: true,
+            shown: false,
+        };
+        match self.free.pop() {
+            Some(entity) => {
+                commands
+                    .entity(entity)
+                    .queue(move |mut entity: EntityWorldMut| {
+                        let mut vfx = vfx;
+                        if let Some(previous) = entity.get::<Vfx>() {
+                            vfx.follow_generations(previous);
+                        }
+                        entity.insert((vfx, transform, pooled));
+                    });
+                entity
+            }
+            None => {
+                self.size += 1;
+                commands.spawn((vfx, transform, pooled)).id()
+            }
+        }
+    }
+
+    /// Entities spawned by the pool, idle or not
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Idle entities ready to be handed out
+    pub fn available(&self) -> usize {
+        self.free.len()
+    }
+}
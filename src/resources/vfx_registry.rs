@@ -1,8 +1,83 @@
 use crate::internal_prelude::*;
+use bevy::platform::hash::FixedHasher;
+use bevy::render::render_resource::encase;
+use std::hash::BuildHasher;
 
+/// Effect storage slots shared between `Vfx` entities with identical stacks,
+/// see [`HirundoPlugin::with_slot_dedup`](crate::HirundoPlugin::with_slot_dedup).
+///
+/// Every `Vfx` entity's `MeshTag` is counted here; without dedup each slot has one user.
 #[derive(Resource, Default)]
 pub struct VfxRegistry {
     // Maps a hash of an EffectStack to a specific buffer index
     pub active_effects: HashMap<u64, u32>,
     pub slot_ref_counts: Vec<usize>, // Track how many entities use each slot
+    /// Hash each slot is registered under in `active_effects`
+    pub slot_hashes: Vec<Option<u64>>,
+}
+
+impl VfxRegistry {
+    /// Hash of a stack as the shaders see it, so identical stacks land on the same slot
+    pub fn stack_hash(stack: &EffectStack) -> u64 {
+        let mut bytes = encase::StorageBuffer::new(Vec::<u8>::new());
+        bytes.write(stack).expect("effect stacks encode into a Vec");
+        FixedHasher.hash_one(bytes.into_inner())
+    }
+
+    /// Slot currently holding a stack with this hash
+    pub fn slot_for(&self, hash: u64) -> Option<u32> {
+        self.active_effects.get(&hash).copied()
+    }
+
+    /// Number of `Vfx` entities drawing from `slot`
+    pub fn ref_count(&self, slot: u32) -> usize {
+        self.slot_ref_counts
+            .get(slot as usize)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Count one more entity drawing from `slot`
+    pub(crate) fn acquire(&mut self, slot: u32) {
+        let index = slot as usize;
+        if index >= self.slot_ref_counts.len() {
+            self.slot_ref_counts.resize(index + 1, 0);
+            self.slot_hashes.resize(index + 1, None);
+        }
+        self.slot_ref_counts[index] += 1;
+    }
+
+    /// Count one entity fewer on `slot`. Returns true once nothing uses it any more,
+    /// the slot is then unregistered and its tag should be cleared and freed.
+    pub(crate) fn release(&mut self, slot: u32) -> bool {
+        let Some(count) = self.slot_ref_counts.get_mut(slot as usize) else {
+            return true;
+        };
+        *count = count.saturating_sub(1);
+        if *count > 0 {
+            return false;
+        }
+        self.unregister(slot);
+        true
+    }
+
+    /// Record that `slot` now holds a stack hashing to `hash`
+    pub(crate) fn register(&mut self, slot: u32, hash: u64) {
+        self.unregister(slot);
+        if let Some(slot_hash) = self.slot_hashes.get_mut(slot as usize) {
+            *slot_hash = Some(hash);
+            self.active_effects.insert(hash, slot);
+        }
+    }
+
+    fn unregister(&mut self, slot: u32) {
+        if let Some(hash) = self
+            .slot_hashes
+            .get_mut(slot as usize)
+            .and_then(Option::take)
+            && self.active_effects.get(&hash) == Some(&slot)
+        {
+            self.active_effects.remove(&hash);
+        }
+    }
 }
@@ -0,0 +1,63 @@
+use crate::internal_prelude::*;
+
+/// Effects queued to play on an entity after a delay, for staggering hits, chains and
+/// reveals without a timer component per entity. Due entries are pushed in
+/// [`HirundoSet::Sync`] like [`PlayVfx`] messages.
+///
+/// Delays count down with `Time` and pause while the [`VfxTimeline`] is frozen.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # #[derive(Resource)]
+/// # struct Chain {
+/// #     targets: Vec<Entity>,
+/// # }
+/// fn chain_lightning(mut scheduler: ResMut<VfxScheduler>, chain: Res<Chain>) {
+///     for (i, &target) in chain.targets.iter().enumerate() {
+///         scheduler.schedule(i as f32 * 0.1, target, "shock");
+///     }
+/// }
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct VfxScheduler {
+    pub(crate) queue: Vec<ScheduledVfx>,
+}
+
+/// A [`VfxScheduler`] entry
+#[derive(Debug)]
+pub(crate) struct ScheduledVfx {
+    /// Seconds left until it plays
+    pub(crate) delay: f32,
+    pub(crate) play: PlayVfx,
+}
+
+impl VfxScheduler {
+    /// Play `effect` on `target` in `delay` seconds. Templates and assets are built when it
+    /// plays; built effects are delayed along with it, so build them with the current time.
+    pub fn schedule(&mut self, delay: f32, target: Entity, effect: impl Into<EffectSpec>) {
+        let delay = delay.max(0.0);
+        self.queue.push(ScheduledVfx {
+            delay,
+            play: PlayVfx::new(target, effect.into().delayed(delay)),
+        });
+    }
+
+    /// Drop every entry still waiting for `target`
+    pub fn cancel(&mut self, target: Entity) {
+        self.queue.retain(|entry| entry.play.target != target);
+    }
+
+    /// Drop every waiting entry
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    /// Entries still waiting
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
@@ -0,0 +1,32 @@
+use crate::internal_prelude::*;
+use bevy::diagnostic::DiagnosticPath;
+
+/// Per-frame VFX counters, refreshed after the storage upload.
+///
+/// The same values are recorded as Bevy diagnostics under the `hirundo/` paths below,
+/// so `LogDiagnosticsPlugin` (or any diagnostics overlay) shows them next to frame time.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct VfxStats {
+    /// Live `Vfx` entities
+    pub entities: usize,
+    /// Enabled effects across all `Vfx` entities
+    pub active_effects: usize,
+    /// Storage slots written by the last upload, 0 when nothing was uploaded
+    pub dirty_slots: usize,
+    /// Bytes sent to the effect storage buffer in the last frame
+    pub bytes_uploaded: usize,
+    /// Distinct mesh and material pairs among 2D `Vfx` entities: the fewest draws they
+    /// take, as Bevy instances entities sharing both into one draw when they sort next to
+    /// each other. Custom meshes and per-entity alpha modes each add one, and all
+    /// [`VfxInstanced`] entities together add one.
+    pub batches: usize,
+}
+
+impl VfxStats {
+    pub const ENTITIES: DiagnosticPath = DiagnosticPath::const_new("hirundo/entities");
+    pub const ACTIVE_EFFECTS: DiagnosticPath = DiagnosticPath::const_new("hirundo/active_effects");
+    pub const DIRTY_SLOTS: DiagnosticPath = DiagnosticPath::const_new("hirundo/dirty_slots");
+    pub const BYTES_UPLOADED: DiagnosticPath = DiagnosticPath::const_new("hirundo/bytes_uploaded");
+    pub const BATCHES: DiagnosticPath = DiagnosticPath::const_new("hirundo/batches");
+}
@@ -0,0 +1,70 @@
+use crate::internal_prelude::*;
+
+/// Freezes effect time, so the exact frames of an effect can be inspected while authoring.
+///
+/// While frozen, both materials evaluate effects at the frozen time instead of Bevy's
+/// clock, and expired effects aren't pruned. Scrub by moving the frozen time, from code or
+/// with the keys of [`HirundoPlugin::with_timeline_scrub`](crate::HirundoPlugin::with_timeline_scrub).
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # let (mut timeline, mut vfx) = (VfxTimeline::default(), Vfx::default());
+/// # let flash = vfx.push_effect(EffectBuilder::flash(0.0, LinearRgba::WHITE, 0.2).build());
+/// // Halfway through the hit flash
+/// timeline.scrub_to(vfx.effect(flash).unwrap(), 0.5);
+/// ```
+#[derive(Resource, Default, Debug, Clone, Copy, Reflect)]
+#[reflect(Resource, Default)]
+pub struct VfxTimeline {
+    frozen_at: Option<f32>,
+}
+
+impl VfxTimeline {
+    /// Stop effect time at `time` (seconds, on the same clock as `Time::elapsed_secs`)
+    pub fn freeze(&mut self, time: f32) {
+        self.frozen_at = Some(time);
+    }
+
+    /// Let effects follow Bevy's clock again
+    pub fn resume(&mut self) {
+        self.frozen_at = None;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_at.is_some()
+    }
+
+    /// Effect time: the frozen time, or `now` while running
+    pub fn now(&self, now: f32) -> f32 {
+        self.frozen_at.unwrap_or(now)
+    }
+
+    /// Move the frozen time by `dt` seconds; does nothing while running
+    pub fn step(&mut self, dt: f32) {
+        if let Some(time) = &mut self.frozen_at {
+            *time += dt;
+        }
+    }
+
+    /// Freeze at `progress` (0.0 - 1.0) through `effect`'s lifetime, or its first period
+    pub fn scrub_to(&mut self, effect: &Effect, progress: f32) {
+        let lifetime = effect.lifetime;
+        self.freeze(lifetime.start_time + progress.clamp(0.0, 1.0) * lifetime.duration);
+    }
+
+    pub(crate) fn uniform(&self) -> VfxTimelineUniform {
+        VfxTimelineUniform {
+            frozen: self.frozen_at.is_some() as u32,
+            time: self.frozen_at.unwrap_or_default(),
+            _pad: Vec2::ZERO,
+        }
+    }
+}
+
+/// [`VfxTimeline`] as seen by the shaders
+#[derive(Clone, Copy, ShaderType, Debug, Default, PartialEq)]
+pub struct VfxTimelineUniform {
+    pub frozen: u32,
+    pub time: f32,
+    pub _pad: Vec2,
+}
@@ -1,4 +1,5 @@
 use crate::internal_prelude::*;
+use std::ops::Range;
 
 /// Helper to spawn a broadcast VFX entity
 pub fn spawn_broadcast_entity(
@@ -13,13 +14,13 @@ pub fn spawn_broadcast_entity(
             Mesh2d(mesh_handle.clone()),
             MeshMaterial2d(material_handle.clone()),
             transform,
-            SpriteIndex(sprite_index),
-            VfxBroadcast,
+            BroadcastSprite(sprite_index),
             Visibility::default(),
         ))
         .id()
 }
 
+/// Demo system: a 20,000 entity grid on the default broadcast material
 pub fn spawn_broadcast_entities(
     mut commands: Commands,
     mesh_handle: Res<VfxMeshHandle>,
@@ -27,31 +28,216 @@ pub fn spawn_broadcast_entities(
 ) {
     const COUNT: usize = 20_000;
     info!("Spawning {COUNT} broadcast VFX entities...");
-    const SPACING: f32 = 50.0;
-
-    // Grid dims (near-square) calculation
-    let cols: usize = (COUNT as f32).sqrt().ceil() as usize;
-    let rows: usize = (COUNT + cols - 1) / cols;
-
-    let total_w = (cols as f32 - 1.0) * SPACING;
-    let total_h = (rows as f32 - 1.0) * SPACING;
-    let start_x = -total_w * 0.5;
-    let start_y = -total_h * 0.5;
-
-    let _random_sprite_index = rand::rng().random_range(0..625);
-
-    for i in 0..COUNT {
-        let col = i % cols;
-        let row = i / cols;
-
-        let x = start_x + (col as f32) * SPACING;
-        let y = start_y + (row as f32) * SPACING;
-        commands.spawn((
-            Mesh2d(mesh_handle.0.clone()),
-            MeshMaterial2d(broadcast_mat_handle.0.clone()), // Shared material!
-            Transform::from_xyz(x, y, 0.0),
-            VfxBroadcast,
-            Visibility::default(),
-        ));
+    BroadcastSpawner::grid(COUNT).spawn(&mut commands, &mesh_handle.0, &broadcast_mat_handle.0);
+}
+
+/// How a [`BroadcastSpawner`] places its entities
+#[derive(Clone, Debug, PartialEq)]
+pub enum BroadcastLayout {
+    /// Near-square grid, `spacing` apart
+    Grid,
+    /// Ring whose neighbours are `spacing` apart along the circumference
+    Circle,
+    /// Row along `direction`, `spacing` apart
+    Line { direction: Vec2 },
+    /// These positions (relative to the center), ignoring count and spacing
+    Positions(Vec<Vec2>),
+}
+
+/// Which atlas sprite each [`BroadcastSpawner`] entity draws
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpriteSelection {
+    /// No [`BroadcastSprite`]; every entity draws the stack's `tile_index`
+    Shared,
+    /// The same sprite for all
+    Fixed(u32),
+    /// A random sprite from the range for each
+    Random(Range<u32>),
+    /// Consecutive sprites from the range, wrapping around
+    Sequence(Range<u32>),
+}
+
+/// Builder spawning many broadcast entities at once, laid out in a grid, circle, line or at
+/// given positions, centered on `center`.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # use bevy_hirundo::resources::VfxMeshHandle;
+/// # use bevy_hirundo::spawners::{BroadcastSpawner, SpriteSelection};
+/// fn spawn_crowd(mut commands: Commands, mesh: Res<VfxMeshHandle>, material: Res<VfxBroadcastMaterialHandle>) {
+///     BroadcastSpawner::circle(64)
+///         .with_spacing(40.0)
+///         .with_jitter(6.0)
+///         .with_sprites(SpriteSelection::Random(0..16))
+///         .spawn(&mut commands, &mesh.0, &material.0);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BroadcastSpawner {
+    pub layout: BroadcastLayout,
+    pub count: usize,
+    pub spacing: f32,
+    /// Largest random offset on each axis, 0.0 for exact positions
+    pub jitter: f32,
+    pub center: Vec2,
+    pub z: f32,
+    pub sprites: SpriteSelection,
+}
+
+impl BroadcastSpawner {
+    pub fn new(layout: BroadcastLayout, count: usize) -> Self {
+        Self {
+            layout,
+            count,
+            spacing: 50.0,
+            jitter: 0.0,
+            center: Vec2::ZERO,
+            z: 0.0,
+            sprites: SpriteSelection::Shared,
+        }
+    }
+
+    pub fn grid(count: usize) -> Self {
+        Self::new(BroadcastLayout::Grid, count)
+    }
+
+    pub fn circle(count: usize) -> Self {
+        Self::new(BroadcastLayout::Circle, count)
+    }
+
+    /// A horizontal row, see [`Self::with_direction`]
+    pub fn line(count: usize) -> Self {
+        Self::new(BroadcastLayout::Line { direction: Vec2::X }, count)
+    }
+
+    /// One entity per position
+    pub fn positions(positions: impl IntoIterator<Item = Vec2>) -> Self {
+        let positions: Vec<Vec2> = positions.into_iter().collect();
+        let count = positions.len();
+        Self::new(BroadcastLayout::Positions(positions), count)
+    }
+
+    pub fn with_count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: f32) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn with_center(mut self, center: Vec2) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn with_z(mut self, z: f32) -> Self {
+        self.z = z;
+        self
+    }
+
+    /// Direction of a [`BroadcastLayout::Line`], ignored by other layouts
+    pub fn with_direction(mut self, direction: Vec2) -> Self {
+        if let BroadcastLayout::Line { direction: line } = &mut self.layout {
+            *line = direction.normalize_or(Vec2::X);
+        }
+        self
+    }
+
+    pub fn with_sprites(mut self, sprites: SpriteSelection) -> Self {
+        self.sprites = sprites;
+        self
+    }
+
+    /// The position of every entity, before jitter
+    pub fn layout_positions(&self) -> Vec<Vec2> {
+        let count = self.count;
+        let offsets: Vec<Vec2> = match &self.layout {
+            BroadcastLayout::Grid => {
+                let cols = ((count as f32).sqrt().ceil() as usize).max(1);
+                let rows = count.div_ceil(cols);
+                let start = -Vec2::new(cols as f32 - 1.0, rows as f32 - 1.0) * self.spacing * 0.5;
+                (0..count)
+                    .map(|i| start + Vec2::new((i % cols) as f32, (i / cols) as f32) * self.spacing)
+                    .collect()
+            }
+            BroadcastLayout::Circle => {
+                let radius = if count > 1 {
+                    count as f32 * self.spacing / f32::consts::TAU
+                } else {
+                    0.0
+                };
+                (0..count)
+                    .map(|i| Vec2::from_angle(i as f32 / count as f32 * f32::consts::TAU) * radius)
+                    .collect()
+            }
+            BroadcastLayout::Line { direction } => {
+                let start = -(count as f32 - 1.0).max(0.0) * self.spacing * 0.5;
+                (0..count)
+                    .map(|i| *direction * (start + i as f32 * self.spacing))
+                    .collect()
+            }
+            BroadcastLayout::Positions(positions) => positions.clone(),
+        };
+        offsets
+            .into_iter()
+            .map(|offset| self.center + offset)
+            .collect()
+    }
+
+    /// Spawn the entities with `mesh` and `material`, in layout order
+    pub fn spawn(
+        &self,
+        commands: &mut Commands,
+        mesh: &Handle<Mesh>,
+        material: &Handle<VfxBroadcastMaterial>,
+    ) -> Vec<Entity> {
+        let mut rng = rand::rng();
+        self.layout_positions()
+            .into_iter()
+            .enumerate()
+            .map(|(i, position)| {
+                let jitter = if self.jitter > 0.0 {
+                    Vec2::new(
+                        rng.random_range(-self.jitter..=self.jitter),
+                        rng.random_range(-self.jitter..=self.jitter),
+                    )
+                } else {
+                    Vec2::ZERO
+                };
+                let mut entity = commands.spawn((
+                    Mesh2d(mesh.clone()),
+                    MeshMaterial2d(material.clone()),
+                    Transform::from_translation((position + jitter).extend(self.z)),
+                    VfxBroadcast,
+                    Visibility::default(),
+                ));
+                if let Some(sprite) = self.sprite(i, &mut rng) {
+                    entity.insert(BroadcastSprite(sprite));
+                }
+                entity.id()
+            })
+            .collect()
+    }
+
+    /// The sprite of the `i`th entity, `None` for the stack's
+    fn sprite(&self, i: usize, rng: &mut impl Rng) -> Option<u32> {
+        match &self.sprites {
+            SpriteSelection::Shared => None,
+            SpriteSelection::Fixed(sprite) => Some(*sprite),
+            SpriteSelection::Random(range) if !range.is_empty() => {
+                Some(rng.random_range(range.clone()))
+            }
+            SpriteSelection::Sequence(range) if !range.is_empty() => {
+                Some(range.start + (i as u32 % (range.end - range.start)))
+            }
+            SpriteSelection::Random(range) | SpriteSelection::Sequence(range) => Some(range.start),
+        }
     }
 }
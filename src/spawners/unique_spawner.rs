@@ -6,7 +6,7 @@ pub fn spawn_unique_entities(mut commands: Commands) {
 
     // Grid dims (near-square) calculation
     let cols: usize = (COUNT as f32).sqrt().ceil() as usize;
-    let rows: usize = (COUNT + cols - 1) / cols;
+    let rows: usize = COUNT.div_ceil(cols);
 
     let total_w = (cols as f32 - 1.0) * SPACING;
     let total_h = (rows as f32 - 1.0) * SPACING;
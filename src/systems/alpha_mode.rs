@@ -0,0 +1,72 @@
+use crate::internal_prelude::*;
+
+/// `Vfx` entities whose mode or material handle changed
+type AlphaModeChanged = (
+    With<Vfx>,
+    Or<(Changed<VfxAlphaMode>, Changed<MeshMaterial2d<VfxMaterial>>)>,
+);
+
+/// Points `Vfx` entities carrying a [`VfxAlphaMode`] at a copy of the material in that
+/// mode (created on first use), and entities that dropped it back at the shared one
+pub fn apply_vfx_alpha_modes(
+    material_handle: Res<VfxMaterialHandle>,
+    mut variants: ResMut<VfxAlphaModeMaterials>,
+    mut materials: ResMut<Assets<VfxMaterial>>,
+    mut with_mode: Query<(&VfxAlphaMode, &mut MeshMaterial2d<VfxMaterial>), AlphaModeChanged>,
+    mut without_mode: Query<&mut MeshMaterial2d<VfxMaterial>, (With<Vfx>, Without<VfxAlphaMode>)>,
+    mut removed: RemovedComponents<VfxAlphaMode>,
+) {
+    let Some(base) = materials.get(&material_handle.0).cloned() else {
+        return;
+    };
+
+    for (mode, mut material) in &mut with_mode {
+        let handle = if *mode == base.shader_key.alpha_mode {
+            material_handle.0.clone()
+        } else {
+            variants
+                .entry(*mode)
+                .or_insert_with(|| {
+                    let mut variant = base.clone();
+                    variant.shader_key.alpha_mode = *mode;
+                    materials.add(variant)
+                })
+                .clone()
+        };
+        // Only write on a real change, the `Changed` filter would see our own write again
+        if material.0 != handle {
+            material.0 = handle;
+        }
+    }
+
+    for entity in removed.read() {
+        if let Ok(mut material) = without_mode.get_mut(entity)
+            && material.0 != material_handle.0
+        {
+            material.0 = material_handle.0.clone();
+        }
+    }
+}
+
+/// Copies every change to the shared `Vfx` material (storage growth, atlas swaps, the
+/// timeline) into the [`VfxAlphaModeMaterials`], in the same frame it happens
+pub fn sync_alpha_mode_materials(
+    material_handle: Res<VfxMaterialHandle>,
+    variants: Res<VfxAlphaModeMaterials>,
+    mut materials: ResMut<Assets<VfxMaterial>>,
+) {
+    let Some(base) = materials.get(&material_handle.0).cloned() else {
+        return;
+    };
+    for (mode, handle) in variants.iter() {
+        let mut variant = base.clone();
+        variant.shader_key.alpha_mode = *mode;
+        // Reading first, `get_mut` alone would re-prepare the material every frame
+        if materials.get(handle) == Some(&variant) {
+            continue;
+        }
+        if let Some(material) = materials.get_mut(handle) {
+            *material = variant;
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::internal_prelude::*;
+
+/// Writes the current frame of each [`SpriteAnimation`] to [`Vfx::sprite_index`], sending
+/// an [`AnimationFrameEvent`] when it changes, then advances it
+pub fn advance_sprite_animations(
+    time: Res<Time>,
+    mut frame_events: MessageWriter<AnimationFrameEvent>,
+    mut query: Query<(
+        Entity,
+        &mut SpriteAnimation,
+        &mut Vfx,
+        Option<&AnimationClipPlayer>,
+    )>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut animation, mut vfx, player) in &mut query {
+        if let Some(position) = animation.frame_position()
+            && animation.shown != Some(position)
+        {
+            animation.shown = Some(position);
+            let frame = animation.frames[position];
+            frame_events.write(AnimationFrameEvent {
+                entity,
+                clip: player.and_then(|player| player.current.clone()),
+                frame: position,
+                sprite_index: frame,
+            });
+            // Only touch Vfx on an actual frame change, it triggers a storage upload
+            if vfx.sprite_index != frame {
+                vfx.sprite_index = frame;
+            }
+        }
+        if !animation.is_finished() {
+            animation.elapsed += delta;
+        }
+    }
+}
+
+/// Switches [`AnimationClipPlayer`]s to their requested clip once the clips have loaded
+pub fn play_animation_clips(
+    time: Res<Time>,
+    clips: Res<Assets<AnimationClips>>,
+    mut query: Query<(&mut AnimationClipPlayer, &mut SpriteAnimation, &mut Vfx)>,
+) {
+    let now = time.elapsed_secs();
+    for (mut player, mut animation, mut vfx) in &mut query {
+        let Some((name, crossfade)) = player.requested.take() else {
+            continue;
+        };
+        let Some(clip_set) = clips.get(&player.clips) else {
+            player.requested = Some((name, crossfade));
+            continue;
+        };
+        let Some(clip) = clip_set.get(&name) else {
+            warn!("No animation clip named \"{}\"", name);
+            continue;
+        };
+
+        *animation = clip.animation();
+        if crossfade > 0.0 {
+            vfx.push_effect(
+                EffectBuilder::one_shot(now, crossfade)
+                    .alpha(0.5)
+                    .with(Envelope::amplitude(0.0, 0.0, 1.0))
+                    .build(),
+            );
+        }
+        player.current = Some(name);
+    }
+}
+
+/// Applies the sprite or clip of each changed [`DirectionalSprite`]'s facing
+pub fn update_directional_sprites(
+    mut query: Query<
+        (
+            &DirectionalSprite,
+            &mut Vfx,
+            Option<&mut AnimationClipPlayer>,
+        ),
+        Changed<DirectionalSprite>,
+    >,
+) {
+    for (directional, mut vfx, player) in &mut query {
+        match directional.current() {
+            // Only touch Vfx on an actual change, it triggers a storage upload
+            Some(DirectionalTarget::Sprite(sprite)) if vfx.sprite_index != *sprite => {
+                vfx.sprite_index = *sprite;
+            }
+            Some(DirectionalTarget::Clip(clip)) => match player {
+                Some(mut player) => player.play_clip(clip),
+                None => warn!(
+                    "DirectionalSprite clip \"{}\" needs an AnimationClipPlayer",
+                    clip
+                ),
+            },
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::HirundoPlugin;
+use crate::internal_prelude::*;
+
+/// Starts loading the atlas description configured with `HirundoPlugin::with_atlas_json`
+pub fn load_atlas_layout(
+    plugin_config: Res<HirundoPlugin>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    if let Some(path) = &plugin_config.atlas_json_path {
+        commands.insert_resource(VfxAtlasLayoutHandle(asset_server.load(path)));
+    }
+}
+
+/// Applies the atlas layout to both materials, the sprite mesh and [`SpriteNames`]
+/// whenever it (re)loads
+pub fn apply_atlas_layout(
+    mut events: MessageReader<AssetEvent<VfxAtlasLayout>>,
+    layout_handle: Res<VfxAtlasLayoutHandle>,
+    layouts: Res<Assets<VfxAtlasLayout>>,
+    mut commands: Commands,
+) {
+    let id = layout_handle.id();
+    let changed = events.read().any(|event| match event {
+        AssetEvent::LoadedWithDependencies { id: loaded } | AssetEvent::Modified { id: loaded } => {
+            *loaded == id
+        }
+        _ => false,
+    });
+    if !changed {
+        return;
+    }
+    if let Some(layout) = layouts.get(id) {
+        commands.queue(SwapVfxAtlas::layout_only(layout));
+    }
+}
+
+/// Writes [`SpriteRects`] to the buffer both materials read whenever it changes
+pub fn upload_sprite_rects(
+    sprite_rects: Res<SpriteRects>,
+    buffer_handle: Res<VfxSpriteRectsBuffer>,
+    mut storages: VfxStorages,
+) {
+    if !sprite_rects.is_changed() {
+        return;
+    }
+    storages.set_sprite_rects(&buffer_handle.0, sprite_rects.gpu_table());
+}
@@ -0,0 +1,34 @@
+use crate::internal_prelude::*;
+
+/// Passes [`VfxAudioDriver`] levels on to the materials (broadcast groups included) and
+/// [`VfxAudioDriver::levels`] for CPU-evaluated waves, then fades its beat for the next frame
+#[allow(clippy::too_many_arguments)]
+pub fn apply_vfx_audio_driver(
+    time: Res<Time>,
+    mut driver: ResMut<VfxAudioDriver>,
+    material_handle: Res<VfxMaterialHandle>,
+    broadcast_handle: Option<Res<VfxBroadcastMaterialHandle>>,
+    broadcasts: Res<VfxBroadcasts>,
+    mut materials: ResMut<Assets<VfxMaterial>>,
+    mut broadcast_materials: ResMut<Assets<VfxBroadcastMaterial>>,
+    mut applied: Local<VfxAudioUniform>,
+) {
+    let uniform = driver.uniform();
+    driver.levels = uniform;
+    driver.decay(time.delta_secs());
+    // Silence stays silent, only changes need writing
+    if *applied == uniform {
+        return;
+    }
+    *applied = uniform;
+
+    if let Some(material) = materials.get_mut(&material_handle.0) {
+        material.audio = uniform;
+    }
+    let broadcast_handles = broadcast_handle.iter().map(|handle| &handle.0);
+    for handle in broadcast_handles.chain(broadcasts.materials.values()) {
+        if let Some(material) = broadcast_materials.get_mut(handle) {
+            material.audio = uniform;
+        }
+    }
+}
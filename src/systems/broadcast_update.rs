@@ -1,18 +1,180 @@
+use crate::HirundoPlugin;
 use crate::internal_prelude::*;
 
-/// System to update the broadcast effect stack
+/// System to update the broadcast effect stack, sending a [`BroadcastEffectFinished`] for
+/// each one-shot it prunes (skipped while the timeline is frozen)
 pub fn update_broadcast_effect_stack(
     broadcast_mat_handle: Res<VfxBroadcastMaterialHandle>,
     mut materials: ResMut<Assets<VfxBroadcastMaterial>>,
     time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    mut finished: MessageWriter<BroadcastEffectFinished>,
     // You can add your own logic here to determine what effects to broadcast
     // For example, query for a controller entity or resource
 ) {
-    if let Some(material) = materials.get_mut(&broadcast_mat_handle.0) {
-        // Example: You could update the effect stack here based on game state
-        // material.effect_stack = new_effect_stack;
+    // Example: You could update the effect stack here based on game state
+    // material.effect_stack = new_effect_stack;
 
-        // Or prune expired effects
-        material.effect_stack.expire(time.elapsed_secs());
+    // Or prune expired effects, touching the material (and its uniform) only when one ended
+    if timeline.is_frozen() {
+        return;
+    }
+    let now = time.elapsed_secs();
+    let expired = materials
+        .get(&broadcast_mat_handle.0)
+        .is_some_and(|material| material.effect_stack.has_expired(now));
+    if expired && let Some(material) = materials.get_mut(&broadcast_mat_handle.0) {
+        for (slot, effect) in material.effect_stack.take_expired(now) {
+            finished.write(BroadcastEffectFinished {
+                group: None,
+                slot,
+                effect,
+            });
+        }
+    }
+}
+
+/// Points changed [`BroadcastTint`] entities at the sprite mesh carrying their color,
+/// creating it the first time the color is used
+pub fn sync_broadcast_tints(
+    plugin: Res<HirundoPlugin>,
+    mut tint_meshes: ResMut<BroadcastTintMeshes>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut query: Query<(&BroadcastTint, &mut Mesh2d), Changed<BroadcastTint>>,
+) {
+    for (tint, mut mesh) in &mut query {
+        let color = tint.0.to_linear();
+        let handle = tint_meshes
+            .entry(color.to_f32_array().map(f32::to_bits))
+            .or_insert_with(|| {
+                meshes.add(tinted_sprite_mesh(
+                    plugin.atlas_dimensions.sprite_size,
+                    color,
+                ))
+            });
+        if mesh.0 != *handle {
+            mesh.0 = handle.clone();
+        }
+    }
+}
+
+/// Applies the edits queued on [`BroadcastFx`] to the default broadcast material
+pub fn apply_broadcast_fx(
+    accessibility: Res<VfxAccessibility>,
+    broadcast_mat_handle: Res<VfxBroadcastMaterialHandle>,
+    mut broadcast: ResMut<BroadcastFx>,
+    mut materials: ResMut<Assets<VfxBroadcastMaterial>>,
+) {
+    // Checked through `Deref` so an idle queue never marks the resource changed
+    if !broadcast.is_pending() {
+        return;
+    }
+    let Some(material) = materials.get_mut(&broadcast_mat_handle.0) else {
+        return;
+    };
+    let stack = &mut material.effect_stack;
+    if std::mem::take(&mut broadcast.clear) {
+        stack.clear();
+    }
+    for mut effect in broadcast.pending.iter_active().copied() {
+        accessibility.apply_effect(&mut effect);
+        stack.push(effect);
+    }
+    broadcast.pending.clear();
+    if let Some(tile_index) = broadcast.tile_index.take() {
+        stack.tile_index = tile_index;
+    }
+}
+
+/// Writes changed [`BroadcastSprite`]s into their entity's `MeshTag`, read by the broadcast
+/// shader as the instance's sprite
+pub fn sync_broadcast_sprites(
+    mut query: Query<(&BroadcastSprite, &mut MeshTag), Changed<BroadcastSprite>>,
+) {
+    for (sprite, mut tag) in &mut query {
+        tag.set_if_neq(MeshTag(sprite.0 + 1));
+    }
+}
+
+/// A [`VfxBroadcastGroup`] entity's group and the material it's drawn with
+type BroadcastMember = (
+    &'static VfxBroadcastGroup,
+    &'static mut MeshMaterial2d<VfxBroadcastMaterial>,
+);
+
+/// Creates the material of every [`VfxBroadcasts`] group on first use, copies changed
+/// group stacks into them and points [`VfxBroadcastGroup`] entities at their group's
+pub fn sync_broadcast_groups(
+    accessibility: Res<VfxAccessibility>,
+    default_handle: Res<VfxBroadcastMaterialHandle>,
+    mut broadcasts: ResMut<VfxBroadcasts>,
+    mut materials: ResMut<Assets<VfxBroadcastMaterial>>,
+    mut members: Query<BroadcastMember, Changed<VfxBroadcastGroup>>,
+) {
+    // Groups only joined so far start out empty
+    for (group, _) in &members {
+        if !broadcasts.stacks.contains_key(&group.0) {
+            broadcasts.group(&group.0);
+        }
+    }
+
+    if broadcasts.is_changed() || accessibility.is_changed() {
+        let Some(template) = materials.get(&default_handle.0).cloned() else {
+            return;
+        };
+        let broadcasts = broadcasts.bypass_change_detection();
+        for (name, effects) in &broadcasts.stacks {
+            let stack = accessibility.adjust(effects);
+            let Some(handle) = broadcasts.materials.get(name) else {
+                let material = materials.add(VfxBroadcastMaterial {
+                    effect_stack: stack.into_owned(),
+                    ..template.clone()
+                });
+                broadcasts.materials.insert(name.clone(), material);
+                continue;
+            };
+            // Reading first, `get_mut` alone would re-prepare every group's material
+            if materials
+                .get(handle)
+                .is_some_and(|material| material.effect_stack != *stack)
+                && let Some(material) = materials.get_mut(handle)
+            {
+                material.effect_stack = stack.into_owned();
+            }
+        }
+    }
+
+    for (group, mut material) in &mut members {
+        if let Some(handle) = broadcasts.materials.get(&group.0)
+            && material.0 != *handle
+        {
+            material.0 = handle.clone();
+        }
+    }
+}
+
+/// Frees the slots of ended one-shots on [`VfxBroadcasts`] groups, without re-uploading,
+/// sending a [`BroadcastEffectFinished`] for each (skipped while the timeline is frozen,
+/// like `prune_expired_effects`)
+pub fn prune_expired_broadcasts(
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    mut broadcasts: ResMut<VfxBroadcasts>,
+    mut finished: MessageWriter<BroadcastEffectFinished>,
+) {
+    if timeline.is_frozen() {
+        return;
+    }
+    let now = time.elapsed_secs();
+    for (group, stack) in &mut broadcasts.bypass_change_detection().stacks {
+        if stack.has_expired(now) {
+            for (slot, effect) in stack.take_expired(now) {
+                finished.write(BroadcastEffectFinished {
+                    group: Some(group.clone()),
+                    slot,
+                    effect,
+                });
+            }
+        }
     }
 }
@@ -1,7 +1,12 @@
+use crate::HirundoPlugin;
 use crate::internal_prelude::*;
+use bevy::post_process::bloom::Bloom;
 
-pub fn spawn_camera(mut commands: Commands) {
-    commands.spawn(Camera2d);
+pub fn spawn_camera(mut commands: Commands, plugin_config: Res<HirundoPlugin>) {
+    let mut camera = commands.spawn(Camera2d);
+    if plugin_config.with_bloom {
+        camera.insert(Bloom::default());
+    }
 }
 
 /// Pan (WASD) and zoom (Z/X)
@@ -0,0 +1,41 @@
+use crate::internal_prelude::*;
+
+/// Push [`EffectAssetPlayer`] effects once their asset loads, and rebuild them
+/// whenever it is modified
+pub fn play_effect_assets(
+    time: Res<Time>,
+    mut events: MessageReader<AssetEvent<EffectAsset>>,
+    assets: Res<Assets<EffectAsset>>,
+    mut query: Query<(&mut EffectAssetPlayer, &mut Vfx)>,
+) {
+    let modified: HashSet<AssetId<EffectAsset>> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    let now = time.elapsed_secs();
+
+    for (mut player, mut vfx) in &mut query {
+        let reloaded = modified.contains(&player.handle.id());
+        if player.pushed.is_some() && !reloaded {
+            continue;
+        }
+        let Some(asset) = assets.get(&player.handle) else {
+            continue;
+        };
+
+        // Still running: swap it out in place, keeping its start time. Going through the
+        // handle API retires handles to the old effect, so they can't touch the new one
+        if let Some(handle) = player.pushed
+            && let Some(pushed) = vfx.effect(handle)
+        {
+            let effect = asset.build(pushed.lifetime.start_time);
+            player.pushed = Some(vfx.replace_effect(handle, effect));
+            continue;
+        }
+
+        player.pushed = Some(vfx.push_effect(asset.build(now)));
+    }
+}
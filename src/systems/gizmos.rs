@@ -0,0 +1,78 @@
+use crate::HirundoPlugin;
+use crate::internal_prelude::*;
+use bevy::color::palettes::css::{FUCHSIA, TURQUOISE, YELLOW};
+
+/// Segments of a full turn of the rotation arc
+const ARC_RESOLUTION: f32 = 32.0;
+
+/// Gizmo group of the [`HirundoPlugin::with_vfx_gizmos`] overlay, toggle it at runtime
+/// through `GizmoConfigStore`:
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # fn toggle_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+/// config_store.config_mut::<VfxGizmos>().0.enabled ^= true;
+/// # }
+/// ```
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct VfxGizmos;
+
+/// Draws, for every visible `Vfx` entity, the anchor of each spatial sub-effect (yellow),
+/// the current OffsetX/OffsetY vector from the sprite's center (turquoise), and the arc
+/// swept by Rotation effects around their anchor (fuchsia)
+pub fn draw_vfx_gizmos(
+    mut gizmos: Gizmos<VfxGizmos>,
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    plugin_config: Res<HirundoPlugin>,
+    sprite_rects: Res<SpriteRects>,
+    audio: Res<VfxAudioDriver>,
+    query: Query<(&Vfx, &GlobalTransform, &InheritedVisibility)>,
+) {
+    let now = timeline.now(time.elapsed_secs());
+    let audio = audio.levels();
+    for (vfx, transform, visibility) in &query {
+        if !visibility.get() || vfx.is_hidden() {
+            continue;
+        }
+        let sprite_px = sprite_rects
+            .get(vfx.sprite_index)
+            .map_or(plugin_config.atlas_dimensions.sprite_size, |rect| {
+                rect.size()
+            });
+        let to_world = |local: Vec2| transform.transform_point(local.extend(0.0)).truncate();
+
+        let mut offset = Vec2::ZERO;
+        for effect in vfx.effects().iter_active() {
+            for (spatial, value) in effect.spatial_values(now, &audio) {
+                let anchor = (spatial.anchor - Vec2::splat(0.5)) * sprite_px;
+                gizmos.cross_2d(Isometry2d::from_translation(to_world(anchor)), 4.0, YELLOW);
+
+                if spatial.manipulation == SpatialKind::OffsetX as u32 {
+                    offset.x += value;
+                } else if spatial.manipulation == SpatialKind::OffsetY as u32 {
+                    offset.y += value;
+                } else if spatial.manipulation == SpatialKind::Rotation as u32 && value != 0.0 {
+                    let radius = sprite_px.min_element() * 0.5;
+                    let segments = (value.abs() / f32::consts::TAU * ARC_RESOLUTION)
+                        .ceil()
+                        .max(2.0);
+                    let arc = (0..=segments as u32).map(|i| {
+                        let angle = value * i as f32 / segments;
+                        to_world(anchor + radius * Vec2::from_angle(angle))
+                    });
+                    gizmos.linestrip_2d(
+                        std::iter::once(to_world(anchor))
+                            .chain(arc)
+                            .chain(std::iter::once(to_world(anchor))),
+                        FUCHSIA,
+                    );
+                }
+            }
+        }
+
+        if offset != Vec2::ZERO {
+            gizmos.arrow_2d(to_world(Vec2::ZERO), to_world(offset), TURQUOISE);
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use crate::internal_prelude::*;
+use bevy::{
+    camera::visibility::NoFrustumCulling,
+    math::Affine3A,
+    mesh::{Indices, VertexAttributeValues},
+};
+
+/// `VfxInstanced` entities whose sprite in the batch mesh changed
+type BatchChanged = (
+    With<VfxInstanced>,
+    Or<(
+        Changed<GlobalTransform>,
+        Changed<MeshTag>,
+        Changed<InheritedVisibility>,
+    )>,
+);
+
+/// Creates the [`VfxInstanceBatch`]: its mesh, the copy of the `Vfx` material drawing it
+/// and the entity holding both
+pub fn setup_vfx_instance_batch(
+    material_handle: Res<VfxMaterialHandle>,
+    mesh_handle: Res<VfxMeshHandle>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<VfxMaterial>>,
+    mut commands: Commands,
+) {
+    // Never empty, the entity stays hidden until a real sprite is batched
+    let (Some(material), Some(mesh)) = (
+        materials.get(&material_handle.0).map(instanced_material),
+        meshes
+            .get(&mesh_handle.0)
+            .and_then(|quad| batch_mesh(quad, &[(Affine3A::IDENTITY, 0)])),
+    ) else {
+        return;
+    };
+    let mesh = meshes.add(mesh);
+    let material = materials.add(material);
+    let entity = commands
+        .spawn((
+            Mesh2d(mesh.clone()),
+            MeshMaterial2d(material.clone()),
+            Transform::default(),
+            Visibility::Hidden,
+            // The sprites are placed by their own transforms in the vertices
+            NoFrustumCulling,
+        ))
+        .id();
+    commands.insert_resource(VfxInstanceBatch {
+        entity,
+        mesh,
+        material,
+    });
+}
+
+/// Copies every change to the `Vfx` material (storage growth, atlas swaps, the timeline, LOD)
+/// into the [`VfxInstanceBatch`] material, in the same frame it happens
+pub fn sync_vfx_instanced_material(
+    material_handle: Res<VfxMaterialHandle>,
+    batch: Option<Res<VfxInstanceBatch>>,
+    mut materials: ResMut<Assets<VfxMaterial>>,
+) {
+    let (Some(synced), Some(batch)) = (
+        materials.get(&material_handle.0).map(instanced_material),
+        batch,
+    ) else {
+        return;
+    };
+    // Reading first, `get_mut` alone would re-prepare the material every frame
+    if materials.get(&batch.material) == Some(&synced) {
+        return;
+    }
+    if let Some(material) = materials.get_mut(&batch.material) {
+        *material = synced;
+    }
+}
+
+/// Rebuilds the [`VfxInstanceBatch`] mesh from the visible [`VfxInstanced`] sprites, in
+/// Z order, on frames where one of them changed
+pub fn update_vfx_instance_batch(
+    batch: Option<Res<VfxInstanceBatch>>,
+    mesh_handle: Res<VfxMeshHandle>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut visibility: Query<&mut Visibility>,
+    instanced: Query<
+        (Entity, &GlobalTransform, &MeshTag, &InheritedVisibility),
+        With<VfxInstanced>,
+    >,
+    changed: Query<(), BatchChanged>,
+    mut removed: RemovedComponents<VfxInstanced>,
+) {
+    // Read every frame, or old removals would trigger a later rebuild
+    let any_removed = removed.read().count() > 0;
+    let Some(batch) = batch else {
+        return;
+    };
+    if changed.is_empty() && !any_removed {
+        return;
+    }
+
+    let mut sprites: Vec<_> = instanced
+        .iter()
+        .filter(|(.., inherited)| inherited.get())
+        .map(|(entity, transform, tag, _)| (entity, transform.affine(), tag.0))
+        .collect();
+    // Back to front, drawn in vertex order within the one draw
+    sprites.sort_by(|a, b| {
+        a.1.translation
+            .z
+            .total_cmp(&b.1.translation.z)
+            .then(a.0.cmp(&b.0))
+    });
+
+    let Ok(mut batch_visibility) = visibility.get_mut(batch.entity) else {
+        return;
+    };
+    if sprites.is_empty() {
+        batch_visibility.set_if_neq(Visibility::Hidden);
+        return;
+    }
+    batch_visibility.set_if_neq(Visibility::Inherited);
+
+    let instances: Vec<_> = sprites
+        .into_iter()
+        .map(|(_, affine, tag)| (affine, tag))
+        .collect();
+    let Some(mesh) = meshes
+        .get(&mesh_handle.0)
+        .and_then(|quad| batch_mesh(quad, &instances))
+    else {
+        return;
+    };
+    if let Some(batch_mesh) = meshes.get_mut(&batch.mesh) {
+        *batch_mesh = mesh;
+    }
+}
+
+/// Copy of the `Vfx` material reading transforms and tags from the batch mesh
+fn instanced_material(material: &VfxMaterial) -> VfxMaterial {
+    let mut instanced = material.clone();
+    instanced.shader_key.instanced = true;
+    instanced
+}
+
+/// One copy of `quad` per instance, each vertex carrying its sprite's transform and tag.
+/// `None` if the quad isn't indexed with positions and UVs like [`sprite_mesh`]'s.
+fn batch_mesh(quad: &Mesh, instances: &[(Affine3A, u32)]) -> Option<Mesh> {
+    let (
+        Some(VertexAttributeValues::Float32x3(quad_positions)),
+        Some(VertexAttributeValues::Float32x2(quad_uvs)),
+        Some(Indices::U32(quad_indices)),
+    ) = (
+        quad.attribute(Mesh::ATTRIBUTE_POSITION),
+        quad.attribute(Mesh::ATTRIBUTE_UV_0),
+        quad.indices(),
+    )
+    else {
+        return None;
+    };
+
+    let vertices = quad_positions.len() * instances.len();
+    let mut positions = Vec::with_capacity(vertices);
+    let mut uvs = Vec::with_capacity(vertices);
+    let mut axes = Vec::with_capacity(vertices);
+    let mut translations = Vec::with_capacity(vertices);
+    let mut tags = Vec::with_capacity(vertices);
+    let mut indices = Vec::with_capacity(quad_indices.len() * instances.len());
+    for (affine, tag) in instances {
+        let first = positions.len() as u32;
+        let x = affine.matrix3.x_axis;
+        let y = affine.matrix3.y_axis;
+        positions.extend_from_slice(quad_positions);
+        uvs.extend_from_slice(quad_uvs);
+        axes.extend(std::iter::repeat_n(
+            [x.x, x.y, y.x, y.y],
+            quad_positions.len(),
+        ));
+        translations.extend(std::iter::repeat_n(
+            affine.translation.to_array(),
+            quad_positions.len(),
+        ));
+        tags.extend(std::iter::repeat_n(*tag, quad_positions.len()));
+        indices.extend(quad_indices.iter().map(|index| first + index));
+    }
+
+    Some(
+        Mesh::new(quad.primitive_topology(), quad.asset_usage)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+            .with_inserted_attribute(ATTRIBUTE_INSTANCE_AXES, axes)
+            .with_inserted_attribute(ATTRIBUTE_INSTANCE_TRANSLATION, translations)
+            .with_inserted_attribute(ATTRIBUTE_INSTANCE_TAG, tags)
+            .with_inserted_indices(Indices::U32(indices)),
+    )
+}
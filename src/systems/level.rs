@@ -0,0 +1,71 @@
+use crate::internal_prelude::*;
+
+/// Spawns the decorations of each [`VfxLevelRoot`] whose [`VfxLevel`] has loaded
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_vfx_levels(
+    mut commands: Commands,
+    time: Res<Time>,
+    levels: Res<Assets<VfxLevel>>,
+    templates: Res<EffectTemplates>,
+    names: Res<SpriteNames>,
+    mesh_handle: Res<VfxMeshHandle>,
+    broadcast_handle: Option<Res<VfxBroadcastMaterialHandle>>,
+    mut broadcasts: ResMut<VfxBroadcasts>,
+    mut roots: Query<(Entity, &mut VfxLevelRoot)>,
+) {
+    let now = time.elapsed_secs();
+    for (root, mut level_root) in &mut roots {
+        if level_root.spawned {
+            continue;
+        }
+        let Some(level) = levels.get(&level_root.handle) else {
+            continue;
+        };
+        level_root.spawned = true;
+        for decoration in &level.entities {
+            let sprite_index = match &decoration.sprite {
+                LevelSprite::Index(index) => *index,
+                LevelSprite::Name(name) => names.get(name).unwrap_or_else(|| {
+                    warn!(
+                        "No sprite named \"{}\" for level entity {}",
+                        name, decoration.name
+                    );
+                    0
+                }),
+            };
+            let mut entity = commands.spawn((
+                Name::new(decoration.name.clone()),
+                Transform::from_translation(decoration.position.extend(0.0)),
+                ChildOf(root),
+            ));
+            if !decoration.broadcast {
+                let mut vfx = Vfx::with_sprite(sprite_index);
+                if let Some(effect) = &decoration.effect {
+                    match templates.build(effect, now) {
+                        Some(effect) => {
+                            vfx.push_effect(effect);
+                        }
+                        None => warn!("No effect template \"{}\"", effect),
+                    }
+                }
+                entity.insert(vfx);
+                continue;
+            }
+            entity.insert((Mesh2d(mesh_handle.0.clone()), BroadcastSprite(sprite_index)));
+            match &decoration.effect {
+                Some(group) => {
+                    // The group plays its template once, for every decoration in it
+                    if broadcasts.effects(group).is_none() {
+                        broadcasts.group(group).play(&templates, group, now);
+                    }
+                    entity.insert(VfxBroadcastGroup::new(group.clone()));
+                }
+                None => {
+                    if let Some(broadcast_handle) = &broadcast_handle {
+                        entity.insert(MeshMaterial2d(broadcast_handle.0.clone()));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::internal_prelude::*;
+
+/// Passes [`VfxLod`] on to the materials (broadcast groups included) as the `Camera2d`
+/// zooms past its threshold, writing them only when what's skipped changes
+#[allow(clippy::too_many_arguments)]
+pub fn apply_vfx_lod(
+    lod: Res<VfxLod>,
+    cameras: Query<(&Camera, &Projection), With<Camera2d>>,
+    material_handle: Res<VfxMaterialHandle>,
+    broadcast_handle: Option<Res<VfxBroadcastMaterialHandle>>,
+    broadcasts: Res<VfxBroadcasts>,
+    mut materials: ResMut<Assets<VfxMaterial>>,
+    mut broadcast_materials: ResMut<Assets<VfxBroadcastMaterial>>,
+    mut applied: Local<Option<VfxLodUniform>>,
+) {
+    // First active orthographic camera; anything else counts as unzoomed
+    let zoom = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .find_map(|(_, projection)| match projection {
+            Projection::Orthographic(ortho) => Some(ortho.scale),
+            _ => None,
+        })
+        .unwrap_or(1.0);
+    let uniform = lod.uniform(zoom);
+    // New groups copy the default material, so only changes need writing
+    if *applied == Some(uniform) {
+        return;
+    }
+    *applied = Some(uniform);
+
+    if let Some(material) = materials.get_mut(&material_handle.0) {
+        material.lod = uniform;
+    }
+    let broadcast_handles = broadcast_handle.iter().map(|handle| &handle.0);
+    for handle in broadcast_handles.chain(broadcasts.materials.values()) {
+        if let Some(material) = broadcast_materials.get_mut(handle) {
+            material.lod = uniform;
+        }
+    }
+}
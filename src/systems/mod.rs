@@ -4,6 +4,25 @@ mod pruning;
 mod setup;
 mod broadcast_update;
 mod camera;
+mod effect_assets;
+mod atlas;
+mod animation;
+mod pool;
+mod timeline;
+mod gizmos;
+mod stats;
+mod sets;
+mod alpha_mode;
+mod vfx_3d;
+mod instancing;
+mod ui_vfx;
+mod text_vfx;
+mod screen_vfx;
+mod lod;
+mod play_vfx;
+mod sprite_bridge;
+mod level;
+mod audio;
 
 pub use sync::*;
 pub use storage::*;
@@ -11,3 +30,22 @@ pub use pruning::*;
 pub use setup::*;
 pub use broadcast_update::*;
 pub use camera::*;
+pub use effect_assets::*;
+pub use atlas::*;
+pub use animation::*;
+pub use pool::*;
+pub use timeline::*;
+pub use gizmos::*;
+pub use stats::*;
+pub use sets::*;
+pub use alpha_mode::*;
+pub use vfx_3d::*;
+pub use instancing::*;
+pub use ui_vfx::*;
+pub use text_vfx::*;
+pub use screen_vfx::*;
+pub use lod::*;
+pub use play_vfx::*;
+pub use sprite_bridge::*;
+pub use level::*;
+pub use audio::*;
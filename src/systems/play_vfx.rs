@@ -0,0 +1,99 @@
+use crate::internal_prelude::*;
+
+/// Components a [`PlayVfx`] can push to
+type PlayTarget = (
+    Option<&'static mut Vfx>,
+    Option<&'static mut UiVfx>,
+    Option<&'static mut TextVfx>,
+);
+
+/// Pushes the effects of [`PlayVfx`] messages onto their targets. Messages playing an
+/// [`EffectAsset`] that's still loading are held until it has loaded, and dropped with a
+/// warning if it fails to load or their target is despawned in the meantime.
+pub fn play_vfx_messages(
+    time: Res<Time>,
+    templates: Res<EffectTemplates>,
+    assets: Res<Assets<EffectAsset>>,
+    asset_server: Res<AssetServer>,
+    mut messages: MessageReader<PlayVfx>,
+    mut loading: Local<Vec<PlayVfx>>,
+    mut targets: Query<PlayTarget>,
+) {
+    let now = time.elapsed_secs();
+    let pending = std::mem::take(&mut *loading);
+    for play in pending.into_iter().chain(messages.read().cloned()) {
+        if let Some(effects) = play.effect.build(&templates, &assets, now) {
+            push_to_target(&mut targets, play.target, effects);
+        } else if !targets.contains(play.target) {
+            warn!(
+                "Dropping an effect for despawned entity {} whose asset was still loading",
+                play.target
+            );
+        } else if let EffectSpec::Asset(handle) = &play.effect
+            && asset_server.load_state(handle.id()).is_failed()
+        {
+            warn!(
+                "Dropping an effect for entity {}: its asset failed to load",
+                play.target
+            );
+        } else {
+            loading.push(play);
+        }
+    }
+}
+
+/// Pushes a [`PlayEffect`]'s effects onto its entity as it's triggered
+pub fn play_effect_observer(
+    event: On<PlayEffect>,
+    time: Res<Time>,
+    templates: Res<EffectTemplates>,
+    assets: Res<Assets<EffectAsset>>,
+    mut loading: MessageWriter<PlayVfx>,
+    mut targets: Query<PlayTarget>,
+) {
+    let PlayEffect { entity, effect } = event.event().clone();
+    match effect.build(&templates, &assets, time.elapsed_secs()) {
+        Some(effects) => push_to_target(&mut targets, entity, effects),
+        None => {
+            loading.write(PlayVfx::new(entity, effect));
+        }
+    }
+}
+
+/// Counts down [`VfxScheduler`] entries (paused while the [`VfxTimeline`] is frozen) and
+/// hands the due ones to [`play_vfx_messages`]
+pub fn run_vfx_scheduler(
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    mut scheduler: ResMut<VfxScheduler>,
+    mut due: MessageWriter<PlayVfx>,
+) {
+    if timeline.is_frozen() {
+        return;
+    }
+    let dt = time.delta_secs();
+    let elapsed = scheduler.queue.extract_if(.., |entry| {
+        entry.delay -= dt;
+        entry.delay <= 0.0
+    });
+    for entry in elapsed {
+        due.write(entry.play);
+    }
+}
+
+/// Pushes `effects` onto whichever of `Vfx`, `UiVfx` and `TextVfx` `target` has, skipping
+/// despawned targets
+fn push_to_target(targets: &mut Query<PlayTarget>, target: Entity, effects: Vec<Effect>) {
+    let Ok((vfx, ui_vfx, text_vfx)) = targets.get_mut(target) else {
+        return;
+    };
+    if let Some(mut vfx) = vfx {
+        vfx.push_effects(effects);
+    } else if let Some(mut ui_vfx) = ui_vfx {
+        ui_vfx.push_effects(effects);
+    } else if let Some(mut text_vfx) = text_vfx {
+        text_vfx.push_effects(effects);
+    } else {
+        warn!("Effect target {} has no Vfx, UiVfx or TextVfx", target);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::HirundoPlugin;
+use crate::internal_prelude::*;
+
+/// Pre-spawns the [`VfxPool`] entities configured through `HirundoPlugin::with_vfx_pool`
+pub fn spawn_vfx_pool(
+    mut commands: Commands,
+    mut pool: ResMut<VfxPool>,
+    plugin_config: Res<HirundoPlugin>,
+) {
+    for _ in 0..plugin_config.vfx_pool_size {
+        let entity = commands
+            .spawn((Vfx::default(), PooledVfx::default(), Transform::default()))
+            .id();
+        pool.free.push(entity);
+        pool.size += 1;
+    }
+}
+
+/// Reveals pooled entities that were just handed out.
+///
+/// Runs before the storage upload, so they appear in the same frame as their effects.
+pub fn show_pooled_vfx(mut query: Query<(&mut PooledVfx, &mut Visibility)>) {
+    for (mut pooled, mut visibility) in &mut query {
+        if pooled.in_use && !pooled.shown {
+            pooled.shown = true;
+            *visibility = Visibility::Visible;
+        }
+    }
+}
+
+/// Hides pooled entities whose effects have all expired and returns them to the [`VfxPool`].
+/// Like [`despawn_finished_vfx`], checks expiry every tick whatever the prune interval.
+pub fn reclaim_pooled_vfx(
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    mut pool: ResMut<VfxPool>,
+    mut query: Query<(Entity, &mut Vfx, &mut PooledVfx, &mut Visibility)>,
+) {
+    if timeline.is_frozen() {
+        return;
+    }
+    let now = time.elapsed_secs();
+    for (entity, mut vfx, mut pooled, mut visibility) in &mut query {
+        if !pooled.shown || vfx.effects.playing_count(now) > 0 {
+            continue;
+        }
+        vfx.clear_effects();
+        *pooled = PooledVfx::default();
+        *visibility = Visibility::Hidden;
+        pool.free.push(entity);
+    }
+}
@@ -1,9 +1,66 @@
+use crate::HirundoPlugin;
 use crate::internal_prelude::*;
 
-/// System to prune expired effects (optional - keeps effect stacks clean)
-pub fn prune_expired_effects(time: Res<Time>, mut query: Query<&mut Vfx>) {
+/// System to prune expired effects (optional - keeps effect stacks clean).
+/// Paused while the [`VfxTimeline`] is frozen, so scrubbing back can revisit them.
+///
+/// The shaders already draw a one-shot past its end as identity, so pruning only frees
+/// the slot for [`EffectStack::push`] and bypasses change detection: expiry alone never
+/// re-uploads a stack. The GPU copy catches up with the next real change. Despawning and
+/// pool reclaim don't wait for it, see [`despawn_finished_vfx`].
+pub fn prune_expired_effects(
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    mut query: Query<&mut Vfx>,
+) {
+    if timeline.is_frozen() {
+        return;
+    }
+    let _span = info_span!("hirundo::prune_expired_effects").entered();
     let now = time.elapsed_secs();
     for mut vfx in &mut query {
-        vfx.effects.expire(now);
+        if vfx.effects.has_expired(now) {
+            vfx.bypass_change_detection().effects.expire(now);
+        }
+    }
+}
+
+/// Run condition spacing [`prune_expired_effects`] out by `HirundoPlugin::prune_interval`
+pub fn prune_interval_elapsed(
+    plugin: Res<HirundoPlugin>,
+    time: Res<Time>,
+    mut last_prune: Local<Option<f32>>,
+) -> bool {
+    let now = time.elapsed_secs();
+    let due = last_prune.is_none_or(|last| now - last >= plugin.prune_interval);
+    if due {
+        *last_prune = Some(now);
+    }
+    due
+}
+
+/// Despawns [`DespawnWhenEffectsFinish`] entities whose effects have all expired,
+/// recycling their `MeshTag` through the `Vfx` removal hook.
+///
+/// Checks expiry itself every tick rather than waiting for [`prune_expired_effects`]:
+/// an ended fade draws as identity, so a pruning interval would show the sprite again.
+pub fn despawn_finished_vfx(
+    mut commands: Commands,
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    mut query: Query<(Entity, &Vfx, &mut DespawnWhenEffectsFinish)>,
+) {
+    if timeline.is_frozen() {
+        return;
+    }
+    let now = time.elapsed_secs();
+    for (entity, vfx, mut despawn) in &mut query {
+        if vfx.effects.playing_count(now) > 0 {
+            if !despawn.armed {
+                despawn.armed = true;
+            }
+        } else if despawn.armed {
+            commands.entity(entity).despawn();
+        }
     }
 }
@@ -0,0 +1,54 @@
+use crate::internal_prelude::*;
+
+/// Gives new 2D cameras the [`ScreenVfx`] pass
+pub fn add_screen_vfx_to_cameras(
+    mut commands: Commands,
+    cameras: Query<Entity, (Added<Camera2d>, Without<ScreenVfxUniform>)>,
+) {
+    for camera in &cameras {
+        commands.entity(camera).insert(ScreenVfxUniform::default());
+    }
+}
+
+/// Evaluates the [`ScreenVfx`] stack for each 2D camera's viewport. Cameras are left
+/// disabled (and skip the pass) while nothing plays.
+pub fn update_screen_vfx(
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    screen: Res<ScreenVfx>,
+    accessibility: Res<VfxAccessibility>,
+    audio: Res<VfxAudioDriver>,
+    mut cameras: Query<(&Camera, &mut ScreenVfxUniform)>,
+) {
+    let now = timeline.now(time.elapsed_secs());
+    let playing = screen
+        .effects
+        .iter_active()
+        .any(|eff| eff.drawn_progress(now).is_some());
+    let effects = accessibility.adjust(&screen.effects);
+    for (camera, mut uniform) in &mut cameras {
+        let target = match camera.logical_viewport_size() {
+            Some(size) if playing => ScreenVfxUniform::new(&effects, now, size, &audio.levels()),
+            _ => ScreenVfxUniform::default(),
+        };
+        if *uniform != target {
+            *uniform = target;
+        }
+    }
+}
+
+/// Frees the slots of ended one-shots on the [`ScreenVfx`] stack (skipped while the
+/// timeline is frozen, like `prune_expired_effects`)
+pub fn prune_expired_screen_vfx(
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    mut screen: ResMut<ScreenVfx>,
+) {
+    if timeline.is_frozen() {
+        return;
+    }
+    let now = time.elapsed_secs();
+    if screen.effects.has_expired(now) {
+        screen.bypass_change_detection().effects.expire(now);
+    }
+}
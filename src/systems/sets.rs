@@ -0,0 +1,25 @@
+use crate::internal_prelude::*;
+
+/// The plugin's `Update` systems, in the order they run.
+///
+/// With [`HirundoPlugin::with_fixed_update`](crate::HirundoPlugin::with_fixed_update),
+/// the `Sync` systems and the expiry pass run in `FixedUpdate` (in the same sets) instead.
+///
+/// Order systems that push effects or change sprites `.before(HirundoSet::Upload)` so
+/// their changes reach the GPU the same frame:
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_hirundo::prelude::*;
+/// # fn apply_hit_flashes() {}
+/// # let mut app = App::new();
+/// app.add_systems(Update, apply_hit_flashes.before(HirundoSet::Upload));
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HirundoSet {
+    /// Animation, effect asset and sprite index sync into `Vfx`
+    Sync,
+    /// Writes changed `Vfx` stacks, sprite rects and the timeline to the GPU
+    Upload,
+    /// Expires finished effects, despawns and reclaims finished entities, refreshes `VfxStats`
+    Prune,
+}
@@ -1,33 +1,71 @@
 use crate::HirundoPlugin;
 use crate::internal_prelude::*;
+use bevy::{
+    asset::RenderAssetUsages,
+    image::{ImageAddressMode, ImageSampler, ImageSamplerDescriptor},
+    mesh::{Indices, PrimitiveTopology},
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
 
+const NOISE_SIZE: u32 = 64;
+/// Horizontal bands in the sprite quad, so height-weighted effects (sway) can bend it
+const QUAD_ROWS: u32 = 8;
+
+/// Loads the configured noise texture, or generates a tileable value noise
+pub fn setup_noise_texture(
+    plugin_config: Res<HirundoPlugin>,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut noise_res: ResMut<VfxNoiseTexture>,
+) {
+    noise_res.0 = match &plugin_config.noise_texture_path {
+        Some(path) => asset_server.load(path),
+        None => images.add(generate_noise_image(NOISE_SIZE)),
+    };
+}
+
+/// Creates the [`SpriteRects`] table, once `HirundoPlugin::finish` picked the
+/// [`VfxStorageKind`]
+pub fn setup_sprite_rects_buffer(mut storages: VfxStorages, mut commands: Commands) {
+    let table = SpriteRects::default().gpu_table();
+    commands.insert_resource(VfxSpriteRectsBuffer(storages.add_sprite_rects(table)));
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn setup_vfx_assets(
     plugin_config: Res<HirundoPlugin>,
     asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<VfxMaterial>>,
-    mut buffers: ResMut<Assets<ShaderStorageBuffer>>,
+    mut storages: VfxStorages,
     mut mesh_handle_res: ResMut<VfxMeshHandle>,
     mut mat_handle_res: ResMut<VfxMaterialHandle>,
+    noise: Res<VfxNoiseTexture>,
+    sprite_rects: Res<VfxSpriteRectsBuffer>,
 ) {
     // 1. Create Mesh
-    let mesh_handle = meshes.add(RectangleMeshBuilder::new(
-        plugin_config.atlas_dimensions.sprite_size.x,
-        plugin_config.atlas_dimensions.sprite_size.y,
-    ));
+    let mesh_handle = meshes.add(sprite_mesh(plugin_config.atlas_dimensions.sprite_size));
     mesh_handle_res.0 = mesh_handle;
 
     // 2. Create Storage Buffer
-    let buffer_handle = buffers.add(ShaderStorageBuffer::from(vec![
-        EffectStack::default();
-        MAX_VFX_ENTITIES
-    ]));
+    let stacks = plugin_config.max_entities.min(storages.max_stacks());
+    let buffer_handle = storages.add_effects(&vec![EffectStack::default(); stacks]);
 
     // 3. Create Material
     let material_handle = materials.add(VfxMaterial {
         texture: asset_server.load(&plugin_config.texture_path),
         effect_storage: buffer_handle,
         atlas_dimensions: plugin_config.atlas_dimensions.clone(),
+        noise_texture: noise.0.clone(),
+        overlay_texture: plugin_config
+            .overlay_texture_path
+            .as_ref()
+            .map(|path| asset_server.load(path)),
+        sprite_rects: sprite_rects.0.clone(),
+        timeline: VfxTimelineUniform::default(),
+        lod: VfxLodUniform::default(),
+        audio: VfxAudioUniform::default(),
+        shader_key: plugin_config.shader_key(storages.kind()),
     });
     mat_handle_res.0 = material_handle;
 }
@@ -37,13 +75,145 @@ pub fn setup_broadcast_material(
     plugin_config: Res<HirundoPlugin>,
     asset_server: Res<AssetServer>,
     mut materials: ResMut<Assets<VfxBroadcastMaterial>>,
+    noise: Res<VfxNoiseTexture>,
+    sprite_rects: Res<VfxSpriteRectsBuffer>,
+    storage_kind: Res<VfxStorageKind>,
     mut commands: Commands,
 ) {
     let material_handle = materials.add(VfxBroadcastMaterial {
         texture: asset_server.load(&plugin_config.texture_path),
-        effect_stack: EffectStack::default(),
+        effect_stack: EffectStack {
+            fx_slots: plugin_config.fx_slots as u32,
+            ..default()
+        },
         atlas_dimensions: plugin_config.atlas_dimensions.clone(),
+        noise_texture: noise.0.clone(),
+        overlay_texture: plugin_config
+            .overlay_texture_path
+            .as_ref()
+            .map(|path| asset_server.load(path)),
+        sprite_rects: sprite_rects.0.clone(),
+        timeline: VfxTimelineUniform::default(),
+        lod: VfxLodUniform::default(),
+        audio: VfxAudioUniform::default(),
+        shader_key: plugin_config.shader_key(*storage_kind),
     });
 
     commands.insert_resource(VfxBroadcastMaterialHandle(material_handle));
 }
+
+/// Creates the [`VfxMaterial3d`] for `Vfx3d` entities from the `Vfx` material
+pub fn setup_vfx_material_3d(
+    material_handle: Res<VfxMaterialHandle>,
+    materials: Res<Assets<VfxMaterial>>,
+    mut materials_3d: ResMut<Assets<VfxMaterial3d>>,
+    mut commands: Commands,
+) {
+    let Some(material) = materials.get(&material_handle.0) else {
+        return;
+    };
+    let material_handle = materials_3d.add(VfxMaterial3d::from(material));
+    commands.insert_resource(VfxMaterial3dHandle(material_handle));
+}
+
+/// The quad every VFX sprite is drawn on, sized to one atlas sprite
+pub(crate) fn sprite_mesh(sprite_size: Vec2) -> Mesh {
+    sprite_quad_mesh(sprite_size, QUAD_ROWS)
+}
+
+/// [`sprite_mesh`] with `color` in every vertex, for [`BroadcastTint`] entities
+pub(crate) fn tinted_sprite_mesh(sprite_size: Vec2, color: LinearRgba) -> Mesh {
+    let mesh = sprite_mesh(sprite_size);
+    let colors = vec![color.to_f32_array(); mesh.count_vertices()];
+    mesh.with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+}
+
+/// Centered quad of `size`, split into `rows` horizontal bands.
+///
+/// Same layout as a `Rectangle` mesh (UV origin top-left, facing +Z), with
+/// extra vertices along the sides.
+fn sprite_quad_mesh(size: Vec2, rows: u32) -> Mesh {
+    let half = size / 2.0;
+    let mut positions = Vec::with_capacity(((rows + 1) * 2) as usize);
+    let mut uvs = Vec::with_capacity(positions.capacity());
+    for row in 0..=rows {
+        let v = row as f32 / rows as f32;
+        let y = half.y - v * size.y;
+        positions.push([-half.x, y, 0.0]);
+        positions.push([half.x, y, 0.0]);
+        uvs.push([0.0, v]);
+        uvs.push([1.0, v]);
+    }
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+
+    let mut indices = Vec::with_capacity((rows * 6) as usize);
+    for row in 0..rows {
+        let top_left = row * 2;
+        let bottom_left = top_left + 2;
+        indices.extend_from_slice(&[
+            top_left,
+            bottom_left,
+            bottom_left + 1,
+            top_left,
+            bottom_left + 1,
+            top_left + 1,
+        ]);
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// Two octaves of wrapped, smoothstepped value noise, stored in all channels.
+fn generate_noise_image(size: u32) -> Image {
+    let mut rng = rand::rng();
+    let octaves: [(usize, f32); 2] = [(8, 0.65), (16, 0.35)];
+    let lattices: Vec<Vec<f32>> = octaves
+        .iter()
+        .map(|(cells, _)| (0..cells * cells).map(|_| rng.random::<f32>()).collect())
+        .collect();
+
+    let mut data = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let mut value = 0.0;
+            for ((cells, weight), lattice) in octaves.iter().zip(&lattices) {
+                let fx = x as f32 / size as f32 * *cells as f32;
+                let fy = y as f32 / size as f32 * *cells as f32;
+                let (x0, y0) = (fx.floor() as usize % cells, fy.floor() as usize % cells);
+                let (x1, y1) = ((x0 + 1) % cells, (y0 + 1) % cells);
+                let tx = fx.fract() * fx.fract() * (3.0 - 2.0 * fx.fract());
+                let ty = fy.fract() * fy.fract() * (3.0 - 2.0 * fy.fract());
+                let top = lattice[y0 * cells + x0].lerp(lattice[y0 * cells + x1], tx);
+                let bottom = lattice[y1 * cells + x0].lerp(lattice[y1 * cells + x1], tx);
+                value += top.lerp(bottom, ty) * weight;
+            }
+            let v = (value.clamp(0.0, 1.0) * 255.0) as u8;
+            data.extend_from_slice(&[v, v, v, 255]);
+        }
+    }
+
+    let mut image = Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        ..ImageSamplerDescriptor::linear()
+    });
+    image
+}
@@ -0,0 +1,46 @@
+use crate::HirundoPlugin;
+use crate::internal_prelude::*;
+
+/// Sprites (or markers) added this frame to entities that aren't `Vfx` yet
+type NewSprites = (Or<(Added<Sprite>, Added<SpriteToVfx>)>, Without<Vfx>);
+
+/// Turns `Sprite`s marked [`SpriteToVfx`] (or, with the sprite bridge, any `Sprite` with a
+/// `TextureAtlas`) into [`Vfx`] entities
+pub fn convert_sprites_to_vfx(
+    mut commands: Commands,
+    time: Res<Time>,
+    plugin: Res<HirundoPlugin>,
+    material_handle: Res<VfxMaterialHandle>,
+    materials: Res<Assets<VfxMaterial>>,
+    sprites: Query<(Entity, &Sprite, Has<SpriteToVfx>), NewSprites>,
+) {
+    let now = time.elapsed_secs();
+    let atlas_texture = materials
+        .get(&material_handle.0)
+        .map(|material| material.texture.id());
+    for (entity, sprite, marked) in &sprites {
+        let bridged = plugin.sprite_bridge && sprite.texture_atlas.is_some();
+        if !(marked || bridged) {
+            continue;
+        }
+        if atlas_texture.is_some_and(|texture| texture != sprite.image.id()) {
+            warn!(
+                "Sprite {} isn't drawn from the Vfx atlas, its index is used on the atlas anyway",
+                entity
+            );
+        }
+        let index = sprite.texture_atlas.as_ref().map_or(0, |atlas| atlas.index);
+        let mut vfx = Vfx::with_sprite(index as u32);
+        if sprite.flip_x {
+            vfx.push_effect(EffectBuilder::looping(now, 1.0).flip_x().build());
+        }
+        if sprite.flip_y {
+            vfx.push_effect(EffectBuilder::looping(now, 1.0).flip_y().build());
+        }
+        let mut entity = commands.entity(entity);
+        entity.remove::<(Sprite, SpriteToVfx)>().insert(vfx);
+        if sprite.color != Color::WHITE {
+            entity.insert(VfxTint(sprite.color));
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::internal_prelude::*;
+use bevy::diagnostic::Diagnostics;
+
+/// The mesh and material a 2D `Vfx` entity is batched by
+type BatchKey = (&'static Mesh2d, &'static MeshMaterial2d<VfxMaterial>);
+
+/// Refreshes [`VfxStats`] and records it as diagnostics
+pub fn update_vfx_stats(
+    mut stats: ResMut<VfxStats>,
+    mut diagnostics: Diagnostics,
+    storage_data: Res<EffectStorageData>,
+    query: Query<&Vfx>,
+    batch_query: Query<BatchKey, (With<Vfx>, Without<Vfx3d>)>,
+    instance_batch: Option<Res<VfxInstanceBatch>>,
+    visibility: Query<&Visibility>,
+) {
+    let batches: HashSet<_> = batch_query
+        .iter()
+        .map(|(mesh, material)| (mesh.id(), material.id()))
+        .collect();
+    // `VfxInstanced` entities have no mesh of their own, their batch is drawn while shown
+    let instanced = instance_batch.is_some_and(|batch| {
+        visibility
+            .get(batch.entity)
+            .is_ok_and(|visibility| *visibility != Visibility::Hidden)
+    });
+    *stats = VfxStats {
+        entities: query.iter().len(),
+        active_effects: query.iter().map(|vfx| vfx.effects.active_count()).sum(),
+        dirty_slots: storage_data.last_uploaded.len(),
+        bytes_uploaded: storage_data.last_upload_bytes,
+        batches: batches.len() + usize::from(instanced),
+    };
+
+    diagnostics.add_measurement(&VfxStats::ENTITIES, || stats.entities as f64);
+    diagnostics.add_measurement(&VfxStats::ACTIVE_EFFECTS, || stats.active_effects as f64);
+    diagnostics.add_measurement(&VfxStats::DIRTY_SLOTS, || stats.dirty_slots as f64);
+    diagnostics.add_measurement(&VfxStats::BYTES_UPLOADED, || stats.bytes_uploaded as f64);
+    diagnostics.add_measurement(&VfxStats::BATCHES, || stats.batches as f64);
+}
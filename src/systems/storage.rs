@@ -1,45 +1,329 @@
+use crate::HirundoPlugin;
 use crate::internal_prelude::*;
+use bevy::render::{
+    Extract,
+    render_asset::RenderAssets,
+    render_resource::{
+        Extent3d, Origin3d, ShaderSize, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect,
+        encase,
+    },
+    renderer::RenderQueue,
+    storage::GpuShaderStorageBuffer,
+    texture::GpuImage,
+};
+
+/// `Vfx` entities whose stored stack changed
+type StackChanged = Or<(
+    Changed<Vfx>,
+    Changed<SpriteRect>,
+    Changed<VfxTint>,
+    Changed<VfxIntensity>,
+)>;
+
+/// A `Vfx` entity's slot, what goes into its stored stack and whether it's in view
+type StackSource = (
+    Entity,
+    Option<&'static ViewVisibility>,
+    Has<VfxGhostBuffer>,
+    Has<PooledVfx>,
+    Has<VfxInstanced>,
+    &'static mut MeshTag,
+    &'static Vfx,
+    Option<&'static SpriteRect>,
+    Option<&'static VfxTint>,
+    Option<&'static VfxIntensity>,
+);
+
+/// Culled `Vfx` entities with a skipped upload, on the frame they turn visible
+type CulledComingIntoView = (With<VfxCulledUpload>, Changed<ViewVisibility>);
 
 /// System to update the storage buffer when effect stacks or sprite indices change
+#[allow(clippy::too_many_arguments)]
 pub fn update_effect_storage_buffer(
     mut commands: Commands,
+    plugin: Res<HirundoPlugin>,
+    accessibility: Res<VfxAccessibility>,
     material_handle: Res<VfxMaterialHandle>,
     mut storage_data: ResMut<EffectStorageData>,
-    mut query: Query<(&MeshTag, &Vfx), Changed<Vfx>>,
-    mut init_query: Query<(Entity, &mut Visibility), With<VfxGhostBuffer>>,
+    mut registry: ResMut<VfxRegistry>,
+    mut allocator: ResMut<MeshTagAllocator>,
+    mut writes: ResMut<EffectStorageWrites>,
+    mut grown: MessageWriter<VfxStorageGrown>,
+    mut query: Query<StackSource, StackChanged>,
+    mut init_query: Query<(Entity, &mut Visibility, Has<PooledVfx>), With<VfxGhostBuffer>>,
     mut materials: ResMut<Assets<VfxMaterial>>,
-    mut buffers: ResMut<Assets<ShaderStorageBuffer>>,
+    mut storages: VfxStorages,
 ) {
-    // Only process Changed<Vfx> entities that aren't already dirty
-    // (to avoid double-processing entities that were just hydrated)
-    for (tag, vfx) in &mut query {
-        let index = tag.0 as usize;
+    let _span = info_span!("hirundo::update_effect_storage_buffer").entered();
 
-        // Skip if already dirty from hydrate/dehydrate hooks
-        if storage_data.dirty_slots.contains(&index) {
+    // Entities hydrated this frame are Changed too, so their initial effects land here
+    let mut grown_from = None;
+    let mut past_limit = 0;
+    let max_stacks = storages.max_stacks();
+    for (
+        entity,
+        view_visibility,
+        veiled,
+        pooled,
+        instanced,
+        mut tag,
+        vfx,
+        sprite_rect,
+        tint,
+        intensity,
+    ) in &mut query
+    {
+        // Culled last frame: upload once it's back in view. Pooled entities are shown
+        // the frame their effects are pushed, so they can't wait; instanced ones are never
+        // checked against the views, the batch is drawn whole.
+        if plugin.upload_culling
+            && !veiled
+            && !pooled
+            && !instanced
+            && view_visibility.is_some_and(|view| !view.get())
+        {
+            commands.entity(entity).insert(VfxCulledUpload);
             continue;
         }
+        let mut updated_stack = vfx.effects.clone();
+        updated_stack.tile_index = vfx.sprite_index;
+        updated_stack.sprite_rect = sprite_rect.map_or(Vec4::ZERO, SpriteRect::gpu_rect);
+        updated_stack.base_tint = tint.map_or(Vec4::ONE, |tint| tint.0.to_linear().to_vec4());
+        updated_stack.intensity = intensity.map_or(1.0, |intensity| intensity.0);
+        accessibility.apply(&mut updated_stack);
 
-        if index < storage_data.effects.len() {
-            let mut updated_stack = vfx.effects.clone();
-            updated_stack.tile_index = vfx.sprite_index;
-            storage_data.effects[index] = updated_stack;
-            storage_data.dirty_slots.insert(index);
+        if plugin.slot_dedup {
+            let hash = VfxRegistry::stack_hash(&updated_stack);
+            match registry.slot_for(hash) {
+                // Already stored where this entity draws from
+                Some(slot) if slot == tag.0 => continue,
+                // Stored for other entities: share their slot, nothing to upload
+                Some(slot) if storage_data.effects.get(slot as usize) == Some(&updated_stack) => {
+                    registry.acquire(slot);
+                    if registry.release(tag.0) {
+                        storage_data.clear_slot(tag.0 as usize);
+                        allocator.free_tag(tag.0);
+                    }
+                    tag.0 = slot;
+                    continue;
+                }
+                _ => {}
+            }
+            // Others still draw the old stack, so move to a slot of our own
+            if registry.ref_count(tag.0) > 1 {
+                registry.release(tag.0);
+                *tag = allocator.allocate_tag();
+                registry.acquire(tag.0);
+            }
+            registry.register(tag.0, hash);
         }
+
+        // More live `Vfx` than slots: grow the storage rather than leave entities invisible,
+        // up to what the storage can hold
+        let index = tag.0 as usize;
+        let capacity = storage_data.effects.len();
+        if index >= capacity {
+            if index >= max_stacks {
+                past_limit += 1;
+                continue;
+            }
+            grown_from.get_or_insert(capacity);
+            storage_data.effects.resize(
+                (index + 1).max(capacity * 2).min(max_stacks),
+                EffectStack::default(),
+            );
+        }
+        storage_data.effects[index] = updated_stack;
+        storage_data.dirty_slots.insert(index);
+    }
+
+    // Entities past the cap had their slots marked dirty on spawn, with nothing there to write
+    let capacity = storage_data.effects.len();
+    storage_data.dirty_slots.retain(|&slot| slot < capacity);
+    if past_limit > 0 {
+        warn!(
+            "{past_limit} Vfx entities don't fit the effect storage, which is capped at {max_stacks} \
+             stacks on this device; they won't be drawn."
+        );
+    }
+
+    if let Some(previous_capacity) = grown_from {
+        let capacity = storage_data.effects.len();
+        warn!(
+            "More Vfx entities than the effect storage holds ({}), growing it to {}. \
+             Raise HirundoPlugin::with_max_entities to avoid the full re-upload.",
+            previous_capacity, capacity
+        );
+        grown.write(VfxStorageGrown {
+            previous_capacity,
+            capacity,
+        });
     }
 
-    for (entity, mut vis) in &mut init_query {
-        *vis = Visibility::Visible;
+    for (entity, mut vis, pooled) in &mut init_query {
+        // Pooled entities stay hidden until handed out, see `show_pooled_vfx`
+        if !pooled {
+            *vis = Visibility::Visible;
+        }
         commands.entity(entity).remove::<VfxGhostBuffer>();
     }
 
-    // Upload if we have any dirty slots
-    if !storage_data.dirty_slots.is_empty() {
-        if let Some(material) = materials.get_mut(&material_handle.0) {
-            if let Some(buffer) = buffers.get_mut(&material.effect_storage) {
-                buffer.set_data(storage_data.effects.clone());
-                storage_data.dirty_slots.clear();
+    // A grown storage gets a new buffer, so the material's bind group picks it up;
+    // otherwise write only the dirty slots, in contiguous runs
+    if grown_from.is_some()
+        && let Some(material) = materials.get_mut(&material_handle.0)
+    {
+        material.effect_storage = storages.add_effects(&storage_data.effects);
+        // Nothing left to write in place, the new storage holds every slot
+        writes.runs.clear();
+        storage_data.last_upload_bytes = effect_storage_bytes(storage_data.effects.len());
+        storage_data.last_uploaded = std::mem::take(&mut storage_data.dirty_slots);
+    } else if !storage_data.dirty_slots.is_empty()
+        && let Some(material) = materials.get(&material_handle.0)
+    {
+        let _write_span = info_span!(
+            "hirundo::effect_storage_write",
+            dirty_slots = storage_data.dirty_slots.len()
+        )
+        .entered();
+        let dirty = std::mem::take(&mut storage_data.dirty_slots);
+        writes.buffer = Some(material.effect_storage.clone());
+        writes.runs = dirty_runs(&dirty)
+            .map(|(start, end)| {
+                let offset = start as u64 * EffectStack::SHADER_SIZE.get();
+                let mut bytes = encase::StorageBuffer::new(Vec::new());
+                bytes
+                    .write(&storage_data.effects[start..end])
+                    .expect("effect stacks encode into a Vec");
+                (offset, bytes.into_inner())
+            })
+            .collect();
+        storage_data.last_upload_bytes = writes.runs.iter().map(|(_, run)| run.len()).sum();
+        storage_data.last_uploaded = dirty;
+    } else {
+        storage_data.last_uploaded.clear();
+        storage_data.last_upload_bytes = 0;
+    }
+}
+
+/// Run condition for [`update_effect_storage_buffer`]: a `Vfx` changed or a slot is dirty.
+///
+/// The frame after an upload still runs, so the upload counters read by `VfxStats`
+/// and the test harness go back to zero.
+pub fn effect_storage_needs_update(
+    storage_data: Res<EffectStorageData>,
+    changed: Query<(), StackChanged>,
+) -> bool {
+    !storage_data.dirty_slots.is_empty()
+        || !storage_data.last_uploaded.is_empty()
+        || !changed.is_empty()
+}
+
+/// Marks `Vfx` entities whose upload was skipped while culled as changed once they're in
+/// view again, see `HirundoPlugin::with_upload_culling`
+pub fn upload_revealed_vfx(
+    mut commands: Commands,
+    mut culled: Query<(Entity, &ViewVisibility, &mut Vfx), CulledComingIntoView>,
+) {
+    for (entity, view_visibility, mut vfx) in &mut culled {
+        if view_visibility.get() {
+            vfx.set_changed();
+            commands.entity(entity).remove::<VfxCulledUpload>();
+        }
+    }
+}
+
+/// Re-uploads every `Vfx` and [`UiVfx`] stack once [`VfxAccessibility`] changes
+pub fn refresh_vfx_accessibility(mut vfxs: Query<&mut Vfx>, mut ui_vfxs: Query<&mut UiVfx>) {
+    for mut vfx in &mut vfxs {
+        vfx.set_changed();
+    }
+    for mut ui_vfx in &mut ui_vfxs {
+        ui_vfx.set_changed();
+    }
+}
+
+/// Sorted dirty slots merged into half-open `(start, end)` ranges
+fn dirty_runs(dirty: &HashSet<usize>) -> impl Iterator<Item = (usize, usize)> {
+    let mut slots: Vec<usize> = dirty.iter().copied().collect();
+    slots.sort_unstable();
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for slot in slots {
+        match runs.last_mut() {
+            Some((_, end)) if *end == slot => *end += 1,
+            _ => runs.push((slot, slot + 1)),
+        }
+    }
+    runs.into_iter()
+}
+
+/// Queues the main world's [`EffectStorageWrites`] in the render world
+pub fn extract_effect_storage_writes(
+    main_writes: Extract<Res<EffectStorageWrites>>,
+    mut writes: ResMut<EffectStorageWrites>,
+) {
+    if main_writes.is_changed() && !main_writes.runs.is_empty() {
+        // Runs queued for a replaced buffer are already part of its successor's data
+        if writes.buffer != main_writes.buffer {
+            writes.runs.clear();
+        }
+        writes.buffer = main_writes.buffer.clone();
+        writes.runs.extend(main_writes.runs.iter().cloned());
+    }
+}
+
+/// Writes the queued runs into the GPU buffer, or the rows of the GPU texture, once
+/// it has been prepared
+pub fn write_effect_storage(
+    mut writes: ResMut<EffectStorageWrites>,
+    buffers: Res<RenderAssets<GpuShaderStorageBuffer>>,
+    images: Res<RenderAssets<GpuImage>>,
+    render_queue: Res<RenderQueue>,
+) {
+    if writes.runs.is_empty() {
+        return;
+    }
+    let writes = &mut *writes;
+    match &writes.buffer {
+        Some(VfxStorage::Buffer(handle)) => {
+            let Some(gpu_buffer) = buffers.get(handle) else {
+                return;
+            };
+            for (offset, bytes) in writes.runs.drain(..) {
+                render_queue.write_buffer(&gpu_buffer.buffer, offset, &bytes);
+            }
+        }
+        Some(VfxStorage::Texture(handle)) => {
+            let Some(gpu_image) = images.get(handle) else {
+                return;
+            };
+            let row_bytes = EffectStack::SHADER_SIZE.get();
+            for (offset, bytes) in writes.runs.drain(..) {
+                render_queue.write_texture(
+                    TexelCopyTextureInfo {
+                        texture: &gpu_image.texture,
+                        mip_level: 0,
+                        origin: Origin3d {
+                            x: 0,
+                            y: (offset / row_bytes) as u32,
+                            z: 0,
+                        },
+                        aspect: TextureAspect::All,
+                    },
+                    &bytes,
+                    TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(row_bytes as u32),
+                        rows_per_image: None,
+                    },
+                    Extent3d {
+                        width: effect_stack_texels(),
+                        height: (bytes.len() as u64 / row_bytes) as u32,
+                        depth_or_array_layers: 1,
+                    },
+                );
             }
         }
+        None => {}
     }
 }
@@ -2,7 +2,19 @@ use crate::internal_prelude::*;
 
 /// System to sync user-facing Vfx component to internal SpriteIndex component
 pub fn sync_vfx_to_internal(mut query: Query<(&Vfx, &mut SpriteIndex), Changed<Vfx>>) {
+    let _span = info_span!("hirundo::sync_vfx_to_internal").entered();
     for (vfx, mut internal_sprite) in &mut query {
         internal_sprite.0 = vfx.sprite_index;
     }
 }
+
+/// Ramps the effects of [`Vfx::crossfade_effect`] in and out
+pub fn advance_vfx_crossfades(time: Res<Time>, mut query: Query<&mut Vfx>) {
+    let now = time.elapsed_secs();
+    for mut vfx in &mut query {
+        // Only touch crossfading entities, so the rest aren't re-uploaded
+        if !vfx.fades.is_empty() {
+            vfx.advance_fades(now);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use crate::internal_prelude::*;
+use bevy::sprite::Anchor;
+use bevy::text::TextLayoutInfo;
+
+/// Applies [`TextVfx`] spatial effects to the `Transform` (in [`TextVfxMode::Whole`])
+/// and color and alpha effects to the `TextColor`, first taking back what the last
+/// frame applied
+pub fn apply_text_vfx(
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    accessibility: Res<VfxAccessibility>,
+    audio: Res<VfxAudioDriver>,
+    mut texts: Query<(
+        &mut TextVfx,
+        &mut Transform,
+        &mut TextColor,
+        &TextLayoutInfo,
+        &Anchor,
+    )>,
+) {
+    let now = timeline.now(time.elapsed_secs());
+    let audio = audio.levels();
+    for (mut text_vfx, mut transform, mut color, layout, anchor) in &mut texts {
+        if text_vfx.is_idle() {
+            continue;
+        }
+        let text_vfx = text_vfx.bypass_change_detection();
+        let playing = text_vfx
+            .effects
+            .iter_active()
+            .any(|eff| eff.drawn_progress(now).is_some());
+        let effects = accessibility.adjust(&text_vfx.effects);
+
+        // The app may have moved the text since, only the effect's part is taken back
+        let mut target = *transform;
+        if let Some(applied) = text_vfx.applied.transform.take() {
+            target.translation -= applied.translation;
+            target.rotation *= applied.rotation.inverse();
+            target.scale /= applied.scale;
+        }
+        if playing && text_vfx.mode == TextVfxMode::Whole {
+            // Not laid out yet (no camera to pick a scale factor from): a point at the anchor
+            let size = if layout.scale_factor > 0.0 {
+                layout.size / layout.scale_factor
+            } else {
+                Vec2::ZERO
+            };
+            let delta = whole_text_delta(&effects, now, size, anchor.as_vec(), &audio);
+            let applied = Transform {
+                translation: target.rotation * (target.scale * delta.translation),
+                ..delta
+            };
+            target.translation += applied.translation;
+            target.rotation *= applied.rotation;
+            target.scale *= applied.scale;
+            text_vfx.applied.transform = Some(applied);
+        }
+        if *transform != target {
+            *transform = target;
+        }
+
+        // A color other than the last tint was set by the app, and is the new base
+        let base = match text_vfx.applied.color.take() {
+            Some((base, tinted)) if color.0 == tinted => base,
+            _ => color.0,
+        };
+        let mut target = base;
+        if playing {
+            target = effects.tint(now, base.to_linear(), &audio).into();
+            text_vfx.applied.color = Some((base, target));
+        }
+        if color.0 != target {
+            color.0 = target;
+        }
+    }
+}
+
+/// Offsets glyphs in [`TextVfxMode::Glyphs`], each evaluated `stagger` seconds after the
+/// one before it. Runs after the text layout, which resets every glyph when the text changes.
+pub fn apply_text_vfx_glyphs(
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    accessibility: Res<VfxAccessibility>,
+    audio: Res<VfxAudioDriver>,
+    mut texts: Query<(&mut TextVfx, &mut TextLayoutInfo)>,
+) {
+    let now = timeline.now(time.elapsed_secs());
+    let audio = audio.levels();
+    for (mut text_vfx, mut layout) in &mut texts {
+        if text_vfx.is_idle() {
+            continue;
+        }
+        let text_vfx = text_vfx.bypass_change_detection();
+        let effects = accessibility.adjust(&text_vfx.effects);
+        let applied = &mut text_vfx.applied.glyphs;
+
+        // Any glyph not where it was left means a fresh layout, with nothing to take back
+        let relaid = applied.len() != layout.glyphs.len()
+            || layout
+                .glyphs
+                .iter()
+                .zip(applied.iter())
+                .any(|(glyph, (_, left_at))| glyph.position != *left_at);
+        if relaid {
+            *applied = layout
+                .glyphs
+                .iter()
+                .map(|glyph| (Vec2::ZERO, glyph.position))
+                .collect();
+        }
+
+        let scale_factor = layout.scale_factor;
+        let mut moved = false;
+        for (i, (glyph, (offset, left_at))) in
+            layout.glyphs.iter().zip(applied.iter_mut()).enumerate()
+        {
+            let target = match text_vfx.mode {
+                TextVfxMode::Glyphs { stagger } => {
+                    let glyph_now = now - i as f32 * stagger;
+                    let size = glyph.size / scale_factor;
+                    let center = effects.spatial_point(glyph_now, Vec2::ZERO, size, &audio);
+                    // Layout pixels are physical, with Y down
+                    center * Vec2::new(1.0, -1.0) * scale_factor
+                }
+                TextVfxMode::Whole => Vec2::ZERO,
+            };
+            *left_at = glyph.position - *offset + target;
+            *offset = target;
+            moved |= *left_at != glyph.position;
+        }
+        // Writing only on a real move keeps `Changed<TextLayoutInfo>` (bounds) quiet
+        if moved {
+            for (glyph, (_, left_at)) in layout.glyphs.iter_mut().zip(applied.iter()) {
+                glyph.position = *left_at;
+            }
+        }
+    }
+}
+
+/// Frees the slots of ended one-shots on [`TextVfx`] texts, once they've been drawn
+/// at rest (skipped while the timeline is frozen, like `prune_expired_effects`)
+pub fn prune_expired_text_vfx(
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    mut texts: Query<&mut TextVfx>,
+) {
+    if timeline.is_frozen() {
+        return;
+    }
+    let now = time.elapsed_secs();
+    for mut text_vfx in &mut texts {
+        if text_vfx.effects.has_expired(now) {
+            text_vfx.bypass_change_detection().effects.expire(now);
+        }
+    }
+}
+
+/// The whole text's spatial effects as a transform in its local space. The text is a
+/// `size` quad centered `anchor` away from the origin, like the `Text2d` extraction.
+///
+/// Skew is folded into rotation and scale, which is kept off 0.0 so it can be taken back.
+fn whole_text_delta(
+    effects: &EffectStack,
+    now: f32,
+    size: Vec2,
+    anchor: Vec2,
+    audio: &VfxAudioUniform,
+) -> Transform {
+    let center = -anchor * size;
+    let affine = effects.spatial_affine(now, size, audio);
+    let (x_axis, y_axis) = (affine.matrix2.x_axis, affine.matrix2.y_axis);
+
+    let (angle, scale) = if x_axis.length() > f32::EPSILON {
+        let scale_x = x_axis.length();
+        let scale_y = x_axis.perp_dot(y_axis) / scale_x;
+        (x_axis.to_angle(), Vec2::new(scale_x, scale_y))
+    } else {
+        (
+            Vec2::new(y_axis.y, -y_axis.x).to_angle(),
+            Vec2::new(0.0, y_axis.length()),
+        )
+    };
+    let scale = scale.map(|s| {
+        if s.abs() < 1e-3 {
+            1e-3f32.copysign(s)
+        } else {
+            s
+        }
+    });
+
+    // Points move as `center + linear * (point - center) + translation`
+    let linear = Mat2::from_angle(angle) * Mat2::from_diagonal(scale);
+    Transform {
+        translation: (center + affine.translation - linear * center).extend(0.0),
+        rotation: Quat::from_rotation_z(angle),
+        scale: scale.extend(1.0),
+    }
+}
@@ -0,0 +1,53 @@
+use crate::internal_prelude::*;
+
+/// One 60 Hz frame, the step of the scrub keys
+const SCRUB_STEP: f32 = 1.0 / 60.0;
+
+/// Passes [`VfxTimeline`] changes on to the materials, broadcast groups included
+pub fn apply_vfx_timeline(
+    timeline: Res<VfxTimeline>,
+    material_handle: Res<VfxMaterialHandle>,
+    broadcast_handle: Option<Res<VfxBroadcastMaterialHandle>>,
+    broadcasts: Res<VfxBroadcasts>,
+    mut materials: ResMut<Assets<VfxMaterial>>,
+    mut broadcast_materials: ResMut<Assets<VfxBroadcastMaterial>>,
+) {
+    if let Some(material) = materials.get_mut(&material_handle.0) {
+        material.timeline = timeline.uniform();
+    }
+    let broadcast_handles = broadcast_handle.iter().map(|handle| &handle.0);
+    for handle in broadcast_handles.chain(broadcasts.materials.values()) {
+        if let Some(material) = broadcast_materials.get_mut(handle) {
+            material.timeline = timeline.uniform();
+        }
+    }
+}
+
+/// Timeline scrubbing keys: F8 freezes/resumes effect time, `,` and `.` step it
+/// one frame back/forward (ten with Shift)
+pub fn control_timeline_scrub(
+    time: Res<Time>,
+    input: Res<ButtonInput<KeyCode>>,
+    mut timeline: ResMut<VfxTimeline>,
+) {
+    if input.just_pressed(KeyCode::F8) {
+        if timeline.is_frozen() {
+            timeline.resume();
+            info!("Timeline resumed");
+        } else {
+            timeline.freeze(time.elapsed_secs());
+            info!("Timeline frozen at {:.3}s", time.elapsed_secs());
+        }
+    }
+
+    let frames = if input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight) {
+        10.0
+    } else {
+        1.0
+    };
+    if input.just_pressed(KeyCode::Comma) {
+        timeline.step(-frames * SCRUB_STEP);
+    } else if input.just_pressed(KeyCode::Period) {
+        timeline.step(frames * SCRUB_STEP);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::internal_prelude::*;
+use bevy::ui_render::prelude::MaterialNode;
+
+/// Uploads changed [`UiVfx`] stacks to their nodes' materials, and copies changes to
+/// the `Vfx` material (atlas swaps, the timeline) into them in the same frame
+pub fn sync_ui_vfx_materials(
+    accessibility: Res<VfxAccessibility>,
+    material_handle: Res<VfxMaterialHandle>,
+    materials: Res<Assets<VfxMaterial>>,
+    mut ui_materials: ResMut<Assets<VfxUiMaterial>>,
+    nodes: Query<(Ref<UiVfx>, &MaterialNode<VfxUiMaterial>)>,
+) {
+    let Some(material) = materials.get(&material_handle.0) else {
+        return;
+    };
+    for (ui_vfx, node_material) in &nodes {
+        // Reading first, `get_mut` alone would re-prepare every node's material each frame
+        if !ui_vfx.is_changed()
+            && ui_materials
+                .get(&node_material.0)
+                .is_some_and(|ui_material| ui_material.follows(material))
+        {
+            continue;
+        }
+        if let Some(ui_material) = ui_materials.get_mut(&node_material.0) {
+            let mut stack = ui_vfx.gpu_stack();
+            accessibility.apply(&mut stack);
+            *ui_material = VfxUiMaterial::new(material, stack);
+        }
+    }
+}
+
+/// Frees the slots of ended one-shots on [`UiVfx`] nodes, without re-uploading: like
+/// `prune_expired_effects`, the shader already draws them as identity
+pub fn prune_expired_ui_vfx(
+    time: Res<Time>,
+    timeline: Res<VfxTimeline>,
+    mut nodes: Query<&mut UiVfx>,
+) {
+    if timeline.is_frozen() {
+        return;
+    }
+    let now = time.elapsed_secs();
+    for mut ui_vfx in &mut nodes {
+        if ui_vfx.effects.has_expired(now) {
+            ui_vfx.bypass_change_detection().effects.expire(now);
+        }
+    }
+}
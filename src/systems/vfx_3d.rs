@@ -0,0 +1,24 @@
+use crate::internal_prelude::*;
+
+/// Copies every change to the `Vfx` material (storage growth, atlas swaps, the timeline, LOD)
+/// into the [`VfxMaterial3d`], in the same frame it happens
+pub fn sync_vfx_material_3d(
+    material_handle: Res<VfxMaterialHandle>,
+    material_3d_handle: Option<Res<VfxMaterial3dHandle>>,
+    materials: Res<Assets<VfxMaterial>>,
+    mut materials_3d: ResMut<Assets<VfxMaterial3d>>,
+) {
+    let (Some(material), Some(material_3d_handle)) =
+        (materials.get(&material_handle.0), material_3d_handle)
+    else {
+        return;
+    };
+    let synced = VfxMaterial3d::from(material);
+    // Reading first, `get_mut` alone would re-prepare the material every frame
+    if materials_3d.get(&material_3d_handle.0) == Some(&synced) {
+        return;
+    }
+    if let Some(material_3d) = materials_3d.get_mut(&material_3d_handle.0) {
+        *material_3d = synced;
+    }
+}
@@ -0,0 +1,149 @@
+//! Headless harness for integration tests in downstream crates, behind the `testing`
+//! feature (enable it in `[dev-dependencies]`).
+//!
+//! ```
+//! use bevy_hirundo::{prelude::*, testing::*};
+//!
+//! let mut app = test_app();
+//! let entity = app.world_mut().spawn(Vfx::default()).id();
+//! app.advance(0.016);
+//!
+//! let now = app.elapsed_secs();
+//! app.world_mut()
+//!     .get_mut::<Vfx>(entity)
+//!     .unwrap()
+//!     .push_effect(EffectBuilder::one_shot(now, 0.5).alpha_zero().build());
+//! app.advance(0.016);
+//! assert!(app.uploaded_slots().contains(&app.vfx_slot(entity).unwrap()));
+//! ```
+use std::{ops::Range, time::Duration};
+
+use bevy::{
+    asset::AssetPlugin, image::Image, render::render_resource::ShaderSize, shader::Shader,
+    time::TimeUpdateStrategy,
+};
+
+use crate::HirundoPlugin;
+use crate::internal_prelude::*;
+
+/// Fixed step used when a test calls `App::update` directly instead of [`VfxTestApp::advance`].
+pub const TEST_TIMESTEP: f32 = 1.0 / 60.0;
+
+/// Builds a headless `App` with [`HirundoPlugin`] and no renderer.
+///
+/// Asset types the plugin touches (`Image`, `Mesh`, `Shader`) are registered directly, so the
+/// atlas texture handle is never backed by a real file. Time is driven manually: each update
+/// advances the clock by [`TEST_TIMESTEP`] unless [`VfxTestApp::advance`] is used.
+/// The first update runs `PreStartup`/`Startup` and only starts the clock, so elapsed time
+/// stays at zero until the second one.
+pub fn test_app() -> App {
+    test_app_with(HirundoPlugin::default())
+}
+
+/// Same as [`test_app`], but with a custom plugin configuration.
+pub fn test_app_with(plugin: HirundoPlugin) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Image>()
+        .init_asset::<Mesh>()
+        .init_asset::<Shader>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            TEST_TIMESTEP,
+        )))
+        .add_plugins(plugin);
+
+    // Large manual steps should not be clamped by the virtual clock
+    app.world_mut()
+        .resource_mut::<Time<Virtual>>()
+        .set_max_delta(Duration::MAX);
+
+    app
+}
+
+/// Time control for apps built with [`test_app`].
+pub trait VfxTestApp {
+    /// Advances time by `dt` seconds and runs a single update.
+    fn advance(&mut self, dt: f32) -> &mut Self;
+
+    /// Advances time in steps of `dt` until `total` seconds have elapsed.
+    ///
+    /// Panics if `dt` isn't positive, it would never get there.
+    fn advance_by(&mut self, total: f32, dt: f32) -> &mut Self;
+
+    /// Elapsed virtual time in seconds, as seen by the plugin's systems.
+    fn elapsed_secs(&self) -> f32;
+
+    /// Storage slot (the `MeshTag`) assigned to a `Vfx` entity.
+    fn vfx_slot(&self, entity: Entity) -> Option<usize>;
+
+    /// Slots written to the storage buffer during the last update.
+    fn uploaded_slots(&self) -> &HashSet<usize>;
+
+    /// Slot ranges written in place during the last update, one per contiguous run of
+    /// uploaded slots. Empty when nothing was uploaded, or the storage grew and was
+    /// uploaded whole.
+    fn uploaded_runs(&self) -> Vec<Range<usize>>;
+
+    /// CPU-side copy of the stack stored for `entity`, as last synced.
+    fn stored_stack(&self, entity: Entity) -> Option<&EffectStack>;
+}
+
+impl VfxTestApp for App {
+    fn advance(&mut self, dt: f32) -> &mut Self {
+        self.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            dt,
+        )));
+        self.update();
+        self
+    }
+
+    fn advance_by(&mut self, total: f32, dt: f32) -> &mut Self {
+        assert!(dt > 0.0, "advance_by needs a positive step, got {dt}");
+        let mut elapsed = 0.0;
+        while elapsed < total {
+            let step = dt.min(total - elapsed);
+            self.advance(step);
+            elapsed += step;
+        }
+        self
+    }
+
+    fn elapsed_secs(&self) -> f32 {
+        self.world().resource::<Time>().elapsed_secs()
+    }
+
+    fn vfx_slot(&self, entity: Entity) -> Option<usize> {
+        self.world()
+            .get::<MeshTag>(entity)
+            .filter(|_| self.world().get::<Vfx>(entity).is_some())
+            .map(|tag| tag.0 as usize)
+    }
+
+    fn uploaded_slots(&self) -> &HashSet<usize> {
+        &self.world().resource::<EffectStorageData>().last_uploaded
+    }
+
+    fn uploaded_runs(&self) -> Vec<Range<usize>> {
+        if self.uploaded_slots().is_empty() {
+            return Vec::new();
+        }
+        let stack_size = EffectStack::SHADER_SIZE.get() as usize;
+        self.world()
+            .resource::<EffectStorageWrites>()
+            .runs
+            .iter()
+            .map(|(offset, bytes)| {
+                let start = *offset as usize / stack_size;
+                start..start + bytes.len() / stack_size
+            })
+            .collect()
+    }
+
+    fn stored_stack(&self, entity: Entity) -> Option<&EffectStack> {
+        let slot = self.vfx_slot(entity)?;
+        self.world()
+            .resource::<EffectStorageData>()
+            .effects
+            .get(slot)
+    }
+}
@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+use bevy_hirundo::{assets::AtlasLayoutError, prelude::*};
+
+fn frame(name: &str, x: f32, y: f32, size: f32) -> (String, Rect) {
+    (name.to_string(), Rect::new(x, y, x + size, y + size))
+}
+
+#[test]
+fn grid_atlases_infer_cell_size_and_padding() {
+    let layout =
+        VfxAtlasLayout::from_json(include_bytes!("fixtures/atlas_grid_hash.json")).unwrap();
+    let dimensions = layout.dimensions;
+    assert_eq!(dimensions.texture_size, Vec2::splat(64.0));
+    assert_eq!(dimensions.sprite_size, Vec2::splat(16.0));
+    assert_eq!(dimensions.cell_size, Vec2::splat(18.0));
+    assert_eq!(dimensions.padding, Vec2::ONE);
+    assert!(layout.rects.is_empty());
+
+    // Three 18 pixel columns fit in 64 pixels; names lose their extension
+    assert_eq!(layout.names["slime_0"], 0);
+    assert_eq!(layout.names["slime_1"], 1);
+    assert_eq!(layout.names["bat_0"], 3);
+    assert_eq!(layout.names["bat_1"], 5);
+}
+
+#[test]
+fn padding_wraps_the_origin_into_the_first_cell() {
+    let layout = VfxAtlasLayout::from_frames(
+        Vec2::splat(64.0),
+        [frame("a", 19.0, 19.0, 16.0), frame("b", 37.0, 37.0, 16.0)],
+    )
+    .unwrap();
+    assert_eq!(layout.dimensions.cell_size, Vec2::splat(18.0));
+    assert_eq!(layout.dimensions.padding, Vec2::ONE);
+    assert_eq!(layout.names["a"], 4);
+    assert_eq!(layout.names["b"], 8);
+}
+
+#[test]
+fn a_single_row_or_column_steps_by_the_sprite_size() {
+    let layout = VfxAtlasLayout::from_frames(
+        Vec2::new(64.0, 16.0),
+        [frame("a", 0.0, 0.0, 16.0), frame("b", 32.0, 0.0, 16.0)],
+    )
+    .unwrap();
+    assert_eq!(layout.dimensions.cell_size, Vec2::new(32.0, 16.0));
+    assert_eq!(layout.names["b"], 1);
+
+    let single =
+        VfxAtlasLayout::from_frames(Vec2::splat(16.0), [frame("a", 0.0, 0.0, 8.0)]).unwrap();
+    assert_eq!(single.dimensions.cell_size, Vec2::splat(8.0));
+}
+
+#[test]
+fn frames_off_the_grid_are_rejected_by_grid_inference() {
+    let uneven = VfxAtlasLayout::from_frames(
+        Vec2::splat(64.0),
+        [frame("a", 0.0, 0.0, 16.0), frame("b", 16.0, 0.0, 8.0)],
+    );
+    assert!(matches!(uneven, Err(AtlasLayoutError::NonUniformFrames(name)) if name == "b"));
+
+    let off_grid = VfxAtlasLayout::from_frames(
+        Vec2::splat(64.0),
+        [
+            frame("a", 0.0, 0.0, 8.0),
+            frame("b", 16.0, 0.0, 8.0),
+            frame("c", 40.0, 0.0, 8.0),
+        ],
+    );
+    assert!(matches!(off_grid, Err(AtlasLayoutError::NotAGrid(name)) if name == "c"));
+
+    assert!(matches!(
+        VfxAtlasLayout::from_frames(Vec2::splat(64.0), []),
+        Err(AtlasLayoutError::Empty)
+    ));
+}
+
+#[test]
+fn frames_sharing_a_name_are_rejected() {
+    let frames = [
+        frame("coin.png", 0.0, 0.0, 8.0),
+        frame("gem.png", 8.0, 0.0, 8.0),
+        frame("coin.jpg", 16.0, 0.0, 8.0),
+    ];
+    let on_grid = VfxAtlasLayout::from_frames(Vec2::splat(64.0), frames.clone());
+    assert!(matches!(on_grid, Err(AtlasLayoutError::DuplicateName(name)) if name == "coin"));
+
+    let packed = VfxAtlasLayout::from_rects(Vec2::splat(64.0), frames);
+    assert!(matches!(packed, Err(AtlasLayoutError::DuplicateName(name)) if name == "coin"));
+}
+
+#[test]
+fn packed_atlases_fall_back_to_per_sprite_rects() {
+    let layout = VfxAtlasLayout::from_json(include_bytes!("fixtures/atlas_packed.json")).unwrap();
+    assert_eq!(layout.names["tree"], 0);
+    assert_eq!(layout.names["rock"], 1);
+    assert_eq!(layout.names["bush"], 2);
+    assert_eq!(
+        layout.rects,
+        [
+            Rect::new(0.0, 0.0, 32.0, 48.0),
+            Rect::new(32.0, 0.0, 48.0, 16.0),
+            Rect::new(32.0, 16.0, 56.0, 32.0),
+        ]
+    );
+    // The grid only sizes the quad, after the first frame
+    assert_eq!(layout.dimensions.cell_size, Vec2::new(32.0, 48.0));
+    assert_eq!(layout.dimensions.padding, Vec2::ZERO);
+}
+
+#[test]
+fn hash_and_array_exports_read_the_same() {
+    let hash = VfxAtlasLayout::from_json(include_bytes!("fixtures/atlas_grid_hash.json")).unwrap();
+    let array =
+        VfxAtlasLayout::from_json(include_bytes!("fixtures/atlas_grid_array.json")).unwrap();
+    assert_eq!(hash.names, array.names);
+    assert_eq!(hash.dimensions.cell_size, array.dimensions.cell_size);
+    assert_eq!(hash.dimensions.padding, array.dimensions.padding);
+}
+
+#[test]
+fn rotated_frames_are_rejected() {
+    let layout = VfxAtlasLayout::from_json(include_bytes!("fixtures/atlas_rotated.json"));
+    assert!(matches!(layout, Err(AtlasLayoutError::RotatedFrame(name)) if name == "coin_1.png"));
+}
+
+#[test]
+fn malformed_exports_are_rejected() {
+    assert!(matches!(
+        VfxAtlasLayout::from_json(br#"{"frames": {}, "meta": {"size": {"w": 8, "h": 8}}}"#),
+        Err(AtlasLayoutError::Empty)
+    ));
+    assert!(matches!(
+        VfxAtlasLayout::from_json(br#"{"frames": 3, "meta": {"size": {"w": 8, "h": 8}}}"#),
+        Err(AtlasLayoutError::Json(_))
+    ));
+}
+
+#[test]
+fn aseprite_tags_map_to_atlas_indices_and_seconds() {
+    let layout =
+        VfxAtlasLayout::from_json(include_bytes!("fixtures/knight.aseprite.json")).unwrap();
+    assert_eq!(layout.names["knight 3"], 3);
+    assert_eq!(
+        layout.tags["idle"],
+        AtlasTag {
+            frames: vec![0, 1],
+            durations: vec![0.2, 0.1],
+            direction: TagDirection::Forward,
+        }
+    );
+    assert_eq!(
+        layout.tags["swing"],
+        AtlasTag {
+            frames: vec![1, 2, 3],
+            // Frames without a duration show for Aseprite's default 100 ms
+            durations: vec![0.1, 0.05, 0.1],
+            direction: TagDirection::PingPong,
+        }
+    );
+    assert_eq!(layout.tags["hurt"].direction, TagDirection::Forward);
+    assert_eq!(layout.tags.len(), 3);
+}
+
+#[test]
+fn aseprite_tags_on_packed_frames_map_to_frame_order() {
+    let layout = VfxAtlasLayout::from_json(
+        br#"{
+            "frames": [
+                {"filename": "slash 0", "frame": {"x": 0, "y": 0, "w": 32, "h": 16}},
+                {"filename": "slash 1", "frame": {"x": 32, "y": 0, "w": 24, "h": 24}},
+                {"filename": "slash 2", "frame": {"x": 0, "y": 24, "w": 16, "h": 16}}
+            ],
+            "meta": {
+                "size": {"w": 64, "h": 64},
+                "frameTags": [{"name": "swing", "from": 1, "to": 2, "direction": "reverse"}]
+            }
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(layout.rects.len(), 3);
+    assert_eq!(layout.tags["swing"].frames, [1, 2]);
+    assert_eq!(layout.tags["swing"].direction, TagDirection::Reverse);
+}
+
+#[test]
+fn aseprite_tags_past_the_last_frame_are_rejected() {
+    let layout = VfxAtlasLayout::from_json(include_bytes!("fixtures/knight_bad_tag.aseprite.json"));
+    assert!(matches!(layout, Err(AtlasLayoutError::TagOutOfRange(name)) if name == "walk"));
+}
+
+#[test]
+fn texture_packer_exports_have_no_tags() {
+    let layout =
+        VfxAtlasLayout::from_json(include_bytes!("fixtures/atlas_grid_hash.json")).unwrap();
+    assert!(layout.tags.is_empty());
+}
@@ -0,0 +1,236 @@
+//! The CPU mirrors of the shader math, checked against values worked out by hand from
+//! `eval_wave`, `eval_envelope_integral` and `master_lifetime` in vfx_common.wgsl
+use bevy_hirundo::prelude::*;
+
+const EPSILON: f32 = 1e-4;
+
+fn assert_near(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < EPSILON,
+        "expected {expected}, got {actual}"
+    );
+}
+
+fn silence() -> VfxAudioUniform {
+    VfxAudioUniform::default()
+}
+
+fn amp_envelope(attack: f32, hold: f32, release: f32) -> Envelope {
+    Envelope::amplitude(attack, hold, release).0
+}
+
+#[test]
+fn sine_waves_start_at_their_peak() {
+    let wave = Wave::sine(1.0, 1.0, 0.0);
+    assert_near(wave.evaluate(0.0, &silence()), 1.0);
+    assert_near(wave.evaluate(0.25, &silence()), 0.0);
+    assert_near(wave.evaluate(0.5, &silence()), -1.0);
+    assert_near(wave.evaluate(1.0, &silence()), 1.0);
+    // Amplitude scales around the bias
+    assert_near(Wave::sine(1.0, 2.0, 0.5).evaluate(0.5, &silence()), -1.5);
+}
+
+#[test]
+fn square_waves_flip_after_half_a_cycle() {
+    let wave = Wave::square(1.0, 1.0, 0.0);
+    assert_near(wave.evaluate(0.25, &silence()), -1.0);
+    assert_near(wave.evaluate(0.5, &silence()), -1.0);
+    assert_near(wave.evaluate(0.75, &silence()), 1.0);
+    assert_near(Wave::square(2.0, 1.0, 0.0).evaluate(0.3, &silence()), 1.0);
+}
+
+#[test]
+fn triangle_and_saw_waves_ramp_through_each_cycle() {
+    let triangle = Wave::triangle(1.0, 1.0, 0.0);
+    assert_near(triangle.evaluate(0.0, &silence()), 1.0);
+    assert_near(triangle.evaluate(0.25, &silence()), 0.0);
+    assert_near(triangle.evaluate(0.5, &silence()), -1.0);
+    assert_near(triangle.evaluate(0.75, &silence()), 0.0);
+
+    let saw = Wave::saw(1.0, 1.0, 0.0);
+    assert_near(saw.evaluate(0.0, &silence()), -1.0);
+    assert_near(saw.evaluate(0.5, &silence()), 0.0);
+    assert_near(saw.evaluate(0.75, &silence()), 0.5);
+}
+
+#[test]
+fn phases_wrap_like_wgsl_fract() {
+    let saw = Wave::saw(1.0, 1.0, 0.0);
+    assert_near(saw.with_phase(0.75).evaluate(0.5, &silence()), -0.5);
+    // Never negative, unlike f32::fract
+    assert_near(saw.with_phase(-0.25).evaluate(0.0, &silence()), 0.5);
+    assert_near(
+        Wave::sine(1.0, 1.0, 0.0)
+            .with_center_phase()
+            .evaluate(0.0, &silence()),
+        0.0,
+    );
+}
+
+#[test]
+fn constant_waves_hold_their_value() {
+    let wave = Wave::constant(0.3).with_bias(0.1);
+    for t in [0.0, 0.3, 1.0, 2.5] {
+        assert_near(wave.evaluate(t, &silence()), 0.4);
+    }
+}
+
+#[test]
+fn noise_stays_in_range_and_is_seeded_by_phase() {
+    let wave = Wave::noise(8.0, 1.0, 0.0);
+    let samples: Vec<f32> = (0..=100)
+        .map(|i| wave.evaluate(i as f32 / 100.0, &silence()))
+        .collect();
+    assert!(samples.iter().all(|v| (-1.0..=1.0).contains(v)));
+    assert!(samples.iter().any(|v| (v - samples[0]).abs() > EPSILON));
+    assert_eq!(wave.evaluate(0.37, &silence()), samples[37]);
+
+    let reseeded = wave.with_phase(0.5);
+    assert!(
+        (0..=100).any(
+            |i| (reseeded.evaluate(i as f32 / 100.0, &silence()) - samples[i]).abs() > EPSILON
+        )
+    );
+}
+
+#[test]
+fn audio_and_beat_waves_follow_the_levels() {
+    let levels = VfxAudioUniform {
+        amplitude: 0.8,
+        beat: 0.5,
+        ..silence()
+    };
+    assert_near(Wave::audio(0.5, 0.25).evaluate(0.3, &levels), 0.65);
+    assert_near(Wave::beat(2.0, 0.0).evaluate(0.9, &levels), 1.0);
+    assert_near(Wave::audio(0.5, 0.25).evaluate(0.3, &silence()), 0.25);
+}
+
+#[test]
+fn linear_envelopes_ramp_through_attack_hold_and_release() {
+    let envelope = amp_envelope(0.25, 0.5, 0.25);
+    for (t, level) in [
+        (-0.5, 0.0),
+        (0.0, 0.0),
+        (0.125, 0.5),
+        (0.25, 1.0),
+        (0.5, 1.0),
+        (0.75, 1.0),
+        (0.875, 0.5),
+        (1.0, 0.0),
+        (1.5, 0.0),
+    ] {
+        assert_near(envelope.evaluate(t), level);
+    }
+    // Timings are relative to each other, not required to sum to 1.0
+    assert_near(amp_envelope(1.0, 0.0, 1.0).evaluate(0.25), 0.5);
+}
+
+#[test]
+fn eased_envelopes_curve_attack_and_release() {
+    // (e^1 - 1) / (e^2 - 1) halfway through either ramp
+    let halfway = (1f32.exp() - 1.0) / (2f32.exp() - 1.0);
+
+    let ease_in = Envelope::amplitude(0.5, 0.0, 0.5).with_ease_in(2.0).0;
+    assert_near(ease_in.evaluate(0.0), 0.0);
+    assert_near(ease_in.evaluate(0.25), halfway);
+    assert_near(ease_in.evaluate(0.5), 1.0);
+    assert_near(ease_in.evaluate(0.75), 0.5);
+
+    let ease_out = Envelope::amplitude(0.5, 0.0, 0.5).with_ease_out(2.0).0;
+    assert_near(ease_out.evaluate(0.25), 0.5);
+    assert_near(ease_out.evaluate(0.5), 1.0);
+    assert_near(ease_out.evaluate(0.75), halfway);
+    assert_near(ease_out.evaluate(1.0), 0.0);
+}
+
+#[test]
+fn disabled_and_empty_envelopes_pass_through() {
+    for t in [0.0, 0.4, 1.0] {
+        assert_near(Envelope::default().evaluate(t), 1.0);
+        assert_near(amp_envelope(0.0, 0.0, 0.0).evaluate(t), 1.0);
+    }
+}
+
+#[test]
+fn amplitude_envelopes_scale_the_wave_but_not_its_bias() {
+    let wave = Wave::constant(2.0)
+        .with_bias(1.0)
+        .with_amp_envelope(0.5, 0.0, 0.5);
+    assert_near(wave.evaluate(0.0, &silence()), 1.0);
+    assert_near(wave.evaluate(0.25, &silence()), 2.0);
+    assert_near(wave.evaluate(0.5, &silence()), 3.0);
+}
+
+#[test]
+fn frequency_envelopes_advance_the_cycle_by_their_area() {
+    // Half the area is under the attack, which reaches only an eighth of a cycle by
+    // its midpoint
+    let saw = Wave::saw(1.0, 1.0, 0.0).with_freq_envelope(0.5, 0.0, 0.5);
+    assert_near(saw.evaluate(0.25, &silence()), -0.75);
+    assert_near(saw.evaluate(0.5, &silence()), 0.0);
+    assert_near(saw.evaluate(0.75, &silence()), 0.75);
+    assert_near(saw.evaluate(0.9, &silence()), 0.96);
+    // The full area completes exactly one cycle, back to the start of the ramp
+    assert_near(saw.evaluate(1.0, &silence()), -1.0);
+
+    // A hold-only envelope runs at the wave's own frequency
+    let held = Wave::saw(1.0, 1.0, 0.0).with_freq_envelope(0.0, 1.0, 0.0);
+    assert_near(held.evaluate(0.75, &silence()), 0.5);
+}
+
+/// Offset drawn at `now`: 8 pixels whenever the shaders draw the effect, the phase
+/// mirrored so it is nonzero from the first frame of each period.
+fn drawn_offset(effect: &Effect, now: f32) -> f32 {
+    effect
+        .spatial_value(SpatialKind::OffsetX, now, &silence())
+        .unwrap()
+}
+
+fn offset_effect(builder: EffectBuilder) -> Effect {
+    builder.offset_x(8.0).build().reversed()
+}
+
+#[test]
+fn one_shots_are_drawn_between_their_start_and_end() {
+    let effect = offset_effect(EffectBuilder::one_shot(10.0, 2.0));
+    assert_eq!(drawn_offset(&effect, 9.9), 0.0); // Queued
+    assert_eq!(drawn_offset(&effect, 10.0), 0.0); // Master lifetime 0.0 is skipped
+    assert_near(drawn_offset(&effect, 10.5), 8.0);
+    assert_near(drawn_offset(&effect, 11.99), 8.0);
+    assert_eq!(drawn_offset(&effect, 12.0), 0.0); // Played out
+    assert_eq!(drawn_offset(&effect, 20.0), 0.0);
+}
+
+#[test]
+fn loops_wrap_at_each_period() {
+    let effect = offset_effect(EffectBuilder::looping(10.0, 2.0));
+    assert_eq!(drawn_offset(&effect, 9.9), 0.0); // Queued
+    assert_near(drawn_offset(&effect, 10.0), 8.0);
+    assert_near(drawn_offset(&effect, 12.0), 8.0);
+    assert_near(drawn_offset(&effect, 15.0), 8.0);
+
+    // The wave restarts with each period
+    let saw = EffectBuilder::looping(10.0, 2.0)
+        .offset_x(1.0)
+        .with(Wave::saw(1.0, 1.0, 0.0))
+        .build();
+    assert_near(
+        saw.spatial_value(SpatialKind::OffsetX, 13.5, &silence())
+            .unwrap(),
+        0.5,
+    );
+}
+
+#[test]
+fn non_positive_durations_stay_on_the_first_frame() {
+    for duration in [0.0, -1.0] {
+        let one_shot = offset_effect(EffectBuilder::one_shot(10.0, duration));
+        assert_eq!(drawn_offset(&one_shot, 10.0), 0.0);
+        assert_eq!(drawn_offset(&one_shot, 11.0), 0.0);
+
+        let looping = offset_effect(EffectBuilder::looping(10.0, duration));
+        assert_eq!(drawn_offset(&looping, 9.0), 0.0);
+        assert_near(drawn_offset(&looping, 10.0), 8.0);
+        assert_near(drawn_offset(&looping, 11.0), 8.0);
+    }
+}
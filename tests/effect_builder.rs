@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+use bevy_hirundo::{assets::EffectAssetError, prelude::*};
+
+/// `MAX_SPATIAL_FX`, the spatial slots of an effect
+const SPATIAL_SLOTS: usize = 6;
+
+/// One-shot builder with all spatial slots taken
+fn full_spatial() -> EffectBuilder {
+    EffectBuilder::one_shot(0.0, 1.0)
+        .offset_xy(1.0, 2.0)
+        .scale_uniform(1.5)
+        .rotate(10.0)
+        .sway(3.0)
+}
+
+#[test]
+fn spatial_effects_past_the_slots_are_reported() {
+    let spatial_overflow = Err(EffectError::SlotOverflow {
+        effects: "spatial",
+        max: SPATIAL_SLOTS,
+    });
+    assert!(full_spatial().build_checked().is_ok());
+    assert_eq!(full_spatial().skew_x(0.2).validate(), spatial_overflow);
+    assert_eq!(full_spatial().orbit(4.0, 1.0).validate(), spatial_overflow);
+    assert_eq!(full_spatial().arc(8.0, 4.0).validate(), spatial_overflow);
+    assert_eq!(
+        full_spatial().path(&[Vec2::ZERO, Vec2::X]).validate(),
+        spatial_overflow
+    );
+}
+
+#[test]
+fn replacing_a_spatial_kind_needs_no_new_slot() {
+    assert!(
+        full_spatial()
+            .sway(5.0)
+            .rotate(20.0)
+            .build_checked()
+            .is_ok()
+    );
+}
+
+#[test]
+fn paths_with_fewer_than_two_points_are_reported() {
+    let single = EffectBuilder::one_shot(0.0, 1.0).path(&[Vec2::X]);
+    assert_eq!(
+        single.validate(),
+        Err(EffectError::PathTooShort { points: 1 })
+    );
+    let empty = EffectBuilder::one_shot(0.0, 1.0).polyline(&[]);
+    assert_eq!(
+        empty.validate(),
+        Err(EffectError::PathTooShort { points: 0 })
+    );
+}
+
+#[test]
+fn modifying_a_missing_sub_effect_is_reported() {
+    let target = LastEffect::Alpha(0);
+    let builder = EffectBuilder::one_shot(0.0, 1.0)
+        .color(LinearRgba::RED)
+        .modify(target, Wave::sine(2.0, 0.5, 0.5));
+    assert_eq!(
+        builder.validate(),
+        Err(EffectError::MissingTarget { target })
+    );
+    assert!(
+        EffectBuilder::one_shot(0.0, 1.0)
+            .color(LinearRgba::RED)
+            .modify_color(0, Wave::sine(2.0, 0.5, 0.5))
+            .build_checked()
+            .is_ok()
+    );
+}
+
+#[test]
+fn synced_loops_on_a_stopped_clock_fall_back_to_120_bpm() {
+    for bpm in [0.0, -90.0, f32::NAN] {
+        let clock = VfxClock::new(bpm);
+        let builder = EffectBuilder::looping_synced(1.25, &clock, 2.0);
+        assert!(matches!(
+            builder.validate(),
+            Err(EffectError::StoppedClock { .. })
+        ));
+        // Two beats at 120 bpm, from `now` rather than a beat
+        let effect = builder.build();
+        assert_eq!(effect.progress(1.75), 0.5);
+        assert_eq!(effect.progress(2.25), 0.0);
+    }
+
+    let clock = VfxClock::new(60.0);
+    let effect = EffectBuilder::looping_synced(1.25, &clock, 2.0)
+        .build_checked()
+        .unwrap();
+    // Queued until the next beat at 2.0, then a 2 second period
+    assert_eq!(effect.progress(1.9), 0.0);
+    assert_eq!(effect.progress(3.0), 0.5);
+}
+
+#[test]
+fn asset_paths_build_like_builder_paths() {
+    let points = [Vec2::ZERO, Vec2::new(8.0, 4.0), Vec2::new(16.0, 0.0)];
+    let built = EffectBuilder::one_shot(0.0, 1.0).polyline(&points).build();
+    let asset = EffectAsset::from_effect(&built);
+    assert_eq!(asset.path, points);
+    assert_eq!(asset.build(0.0), built);
+
+    let short = EffectAsset {
+        path: vec![Vec2::ZERO],
+        ..asset
+    };
+    assert!(matches!(
+        short.validate(),
+        Err(EffectAssetError::Invalid(EffectError::PathTooShort {
+            points: 1
+        }))
+    ));
+}
+
+#[test]
+fn asset_rejects_repeated_kinds() {
+    let asset = EffectAsset::from_effect(
+        &EffectBuilder::one_shot(0.0, 1.0)
+            .offset_x(4.0)
+            .outline(LinearRgba::WHITE, 1.0)
+            .build(),
+    );
+    assert!(asset.validate().is_ok());
+
+    let mut spatial = asset.clone();
+    spatial.spatial.push(spatial.spatial[0]);
+    assert!(matches!(
+        spatial.validate(),
+        Err(EffectAssetError::DuplicateKind {
+            effects: "spatial",
+            ..
+        })
+    ));
+
+    let mut fragment = asset;
+    fragment.fragment.push(fragment.fragment[0]);
+    assert!(matches!(
+        fragment.validate(),
+        Err(EffectAssetError::DuplicateKind {
+            effects: "fragment",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn assets_write_kinds_by_name() {
+    let asset = EffectAsset::from_effect(
+        &EffectBuilder::one_shot(0.0, 1.0)
+            .color(LinearRgba::RED)
+            .with(BlendMode::Add)
+            .alpha(0.0)
+            .with(AlphaClip::Cutout(0.25))
+            .scale_y(0.2)
+            .with(WaveKind::Sine)
+            .outline(LinearRgba::WHITE, 1.0)
+            .build(),
+    );
+    let ron = asset.to_ron().unwrap();
+    for field in [
+        "blend_mode: Add",
+        "clip: Cutout(0.25)",
+        "manipulation: ScaleY",
+        "kind: Sine",
+        "kind: Outline",
+    ] {
+        assert!(ron.contains(field), "no `{field}` in {ron}");
+    }
+    let loaded: EffectAsset = ron::de::from_str(&ron).unwrap();
+    assert_eq!(loaded.build(0.0), asset.build(0.0));
+
+    assert!(ron::de::from_str::<EffectAsset>("(colors: [(blend_mode: Glow)])").is_err());
+    assert!(ron::de::from_str::<EffectAsset>("(colors: [(blend_mode: 1)])").is_err());
+}
+
+#[test]
+fn shipped_effect_assets_load() {
+    let hit: EffectAsset =
+        ron::de::from_str(include_str!("../assets/effects/hit.effect.ron")).unwrap();
+    assert!(hit.validate().is_ok());
+    let library: EffectLibrary =
+        ron::de::from_str(include_str!("../assets/effects/vfx_library.vfxlib.ron")).unwrap();
+    assert!(library.validate().is_ok());
+}
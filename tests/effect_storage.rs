@@ -0,0 +1,226 @@
+use bevy::{ecs::message::Messages, prelude::*, render::render_resource::ShaderSize};
+use bevy_hirundo::{
+    materials::{VfxMaterial, VfxStorage, VfxStorageLimit},
+    prelude::*,
+    resources::{EffectStorageData, MeshTagAllocator, VfxMaterialHandle, VfxRegistry},
+    testing::*,
+};
+
+fn ref_count(app: &App, slot: usize) -> usize {
+    app.world().resource::<VfxRegistry>().ref_count(slot as u32)
+}
+
+fn push_effect(app: &mut App, entity: Entity, effect: Effect) {
+    app.world_mut()
+        .get_mut::<Vfx>(entity)
+        .unwrap()
+        .push_effect(effect);
+}
+
+fn grown(app: &App) -> Vec<VfxStorageGrown> {
+    app.world()
+        .resource::<Messages<VfxStorageGrown>>()
+        .iter_current_update_messages()
+        .copied()
+        .collect()
+}
+
+fn effect_storage(app: &App) -> VfxStorage {
+    let handle = &app.world().resource::<VfxMaterialHandle>().0;
+    let materials = app.world().resource::<Assets<VfxMaterial>>();
+    materials.get(handle).unwrap().effect_storage.clone()
+}
+
+/// Two entities playing the same looping glow, spawned on one frame
+fn spawn_twins(app: &mut App) -> (Entity, Entity) {
+    let now = app.elapsed_secs();
+    let glow = EffectBuilder::looping(now, 1.0).alpha_zero().build();
+    let mut twins = [Entity::PLACEHOLDER; 2];
+    for twin in &mut twins {
+        let mut vfx = Vfx::default();
+        vfx.push_effect(glow);
+        *twin = app.world_mut().spawn(vfx).id();
+    }
+    app.advance(TEST_TIMESTEP);
+    (twins[0], twins[1])
+}
+
+#[test]
+fn identical_stacks_share_one_slot() {
+    let mut app = test_app_with(HirundoPlugin::default().with_slot_dedup());
+    app.advance(TEST_TIMESTEP);
+    let (a, b) = spawn_twins(&mut app);
+
+    let slot = app.vfx_slot(a).unwrap();
+    assert_eq!(app.vfx_slot(b), Some(slot));
+    assert_eq!(ref_count(&app, slot), 2);
+    assert_eq!(app.stored_stack(b).unwrap().active_count(), 1);
+
+    // The tag the second entity was spawned with went back to the allocator
+    let allocator = app.world().resource::<MeshTagAllocator>();
+    assert_eq!(allocator.free_list.len(), 1);
+    assert_ne!(allocator.free_list[0] as usize, slot);
+}
+
+#[test]
+fn a_changed_stack_moves_out_of_the_shared_slot() {
+    let mut app = test_app_with(HirundoPlugin::default().with_slot_dedup());
+    app.advance(TEST_TIMESTEP);
+    let (a, b) = spawn_twins(&mut app);
+    let shared = app.vfx_slot(a).unwrap();
+
+    let now = app.elapsed_secs();
+    push_effect(
+        &mut app,
+        a,
+        EffectBuilder::one_shot(now, 1.0).offset_x(4.0).build(),
+    );
+    app.advance(TEST_TIMESTEP);
+
+    let own = app.vfx_slot(a).unwrap();
+    assert_ne!(own, shared);
+    assert_eq!(app.vfx_slot(b), Some(shared));
+    assert_eq!((ref_count(&app, own), ref_count(&app, shared)), (1, 1));
+    assert_eq!(app.stored_stack(a).unwrap().active_count(), 2);
+    assert_eq!(app.stored_stack(b).unwrap().active_count(), 1);
+    // Only the new slot is written, the shared one still holds what `b` draws
+    assert!(app.uploaded_slots().contains(&own));
+    assert!(!app.uploaded_slots().contains(&shared));
+}
+
+#[test]
+fn a_shared_slot_is_cleared_and_freed_on_its_last_release() {
+    let mut app = test_app_with(HirundoPlugin::default().with_slot_dedup());
+    app.advance(TEST_TIMESTEP);
+    let (a, b) = spawn_twins(&mut app);
+    let shared = app.vfx_slot(a).unwrap();
+
+    app.world_mut().despawn(b);
+    app.advance(TEST_TIMESTEP);
+    assert_eq!(ref_count(&app, shared), 1);
+    assert_eq!(app.stored_stack(a).unwrap().active_count(), 1);
+    assert!(!app.uploaded_slots().contains(&shared));
+
+    app.world_mut().despawn(a);
+    app.advance(TEST_TIMESTEP);
+    assert_eq!(ref_count(&app, shared), 0);
+    assert!(app.uploaded_slots().contains(&shared));
+    let storage = app.world().resource::<EffectStorageData>();
+    assert_eq!(storage.effects[shared].active_count(), 0);
+    assert!(
+        app.world()
+            .resource::<MeshTagAllocator>()
+            .free_list
+            .contains(&(shared as u32))
+    );
+}
+
+#[test]
+fn more_entities_than_slots_grow_the_storage() {
+    let mut app = test_app_with(HirundoPlugin::default().with_max_entities(2));
+    app.advance(TEST_TIMESTEP);
+    let initial_storage = effect_storage(&app);
+
+    let entities: Vec<Entity> = (0..3)
+        .map(|_| app.world_mut().spawn(Vfx::default()).id())
+        .collect();
+    app.advance(TEST_TIMESTEP);
+    assert_eq!(
+        grown(&app),
+        [VfxStorageGrown {
+            previous_capacity: 2,
+            capacity: 4,
+        }]
+    );
+    let storage = app.world().resource::<EffectStorageData>();
+    assert_eq!(storage.effects.len(), 4);
+    for entity in entities {
+        assert!(app.vfx_slot(entity).unwrap() < 4);
+    }
+
+    // The material binds a new storage holding every slot, written in full
+    assert_ne!(effect_storage(&app), initial_storage);
+    assert_eq!(
+        storage.last_upload_bytes,
+        4 * EffectStack::SHADER_SIZE.get() as usize
+    );
+
+    // Within the grown capacity, nothing more to grow
+    app.world_mut().spawn(Vfx::default());
+    app.advance(TEST_TIMESTEP);
+    assert!(grown(&app).is_empty());
+    assert_eq!(app.world().resource::<EffectStorageData>().effects.len(), 4);
+}
+
+#[test]
+fn growth_stops_at_the_storage_limit() {
+    let mut app = test_app_with(HirundoPlugin::default().with_max_entities(2));
+    // A data texture 3 rows tall at most
+    app.insert_resource(VfxStorageLimit(3));
+    app.advance(TEST_TIMESTEP);
+
+    let entities: Vec<Entity> = (0..5)
+        .map(|_| app.world_mut().spawn(Vfx::default()).id())
+        .collect();
+    app.advance(TEST_TIMESTEP);
+    assert_eq!(
+        grown(&app),
+        [VfxStorageGrown {
+            previous_capacity: 2,
+            capacity: 3,
+        }]
+    );
+    assert_eq!(app.world().resource::<EffectStorageData>().effects.len(), 3);
+    let stored = entities
+        .iter()
+        .filter(|&&entity| app.stored_stack(entity).is_some())
+        .count();
+    assert_eq!(stored, 3);
+
+    app.world_mut().spawn(Vfx::default());
+    app.advance(TEST_TIMESTEP);
+    assert!(grown(&app).is_empty());
+    assert_eq!(app.world().resource::<EffectStorageData>().effects.len(), 3);
+}
+
+#[test]
+fn dirty_slots_are_written_in_contiguous_runs() {
+    let mut app = test_app();
+    app.advance(TEST_TIMESTEP);
+    let entities: Vec<Entity> = (0..5)
+        .map(|_| app.world_mut().spawn(Vfx::default()).id())
+        .collect();
+    app.advance(TEST_TIMESTEP);
+    let slots: Vec<usize> = entities
+        .iter()
+        .map(|&entity| app.vfx_slot(entity).unwrap())
+        .collect();
+    let first = slots[0];
+    assert_eq!(slots, (first..first + 5).collect::<Vec<_>>());
+    assert_eq!(app.uploaded_runs(), vec![first..first + 5]);
+
+    // A gap splits the write, adjacent slots share one
+    let now = app.elapsed_secs();
+    for entity in [entities[0], entities[1], entities[3]] {
+        push_effect(
+            &mut app,
+            entity,
+            EffectBuilder::one_shot(now, 1.0).alpha_zero().build(),
+        );
+    }
+    app.advance(TEST_TIMESTEP);
+    assert_eq!(
+        app.uploaded_runs(),
+        [first..first + 2, first + 3..first + 4]
+    );
+    assert_eq!(
+        app.world()
+            .resource::<EffectStorageData>()
+            .last_upload_bytes,
+        3 * EffectStack::SHADER_SIZE.get() as usize
+    );
+
+    // Nothing dirty, nothing written
+    app.advance(TEST_TIMESTEP);
+    assert!(app.uploaded_runs().is_empty());
+}
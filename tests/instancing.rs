@@ -0,0 +1,131 @@
+use bevy::{camera::visibility::VisibilityPlugin, mesh::VertexAttributeValues, prelude::*};
+use bevy_hirundo::{
+    materials::{ATTRIBUTE_INSTANCE_TAG, ATTRIBUTE_INSTANCE_TRANSLATION, VfxMaterial},
+    prelude::*,
+    resources::{VfxInstanceBatch, VfxMeshHandle},
+    testing::*,
+};
+
+/// App with instancing, transforms and visibility propagated like in a real app
+fn instancing_app() -> App {
+    let mut app = test_app_with(HirundoPlugin::default().with_instancing());
+    app.add_plugins((TransformPlugin, VisibilityPlugin));
+    app
+}
+
+fn batch_mesh(app: &App) -> &Mesh {
+    let batch = app.world().resource::<VfxInstanceBatch>();
+    app.world()
+        .resource::<Assets<Mesh>>()
+        .get(&batch.mesh)
+        .unwrap()
+}
+
+fn quad_vertices(app: &App) -> usize {
+    let quad = &app.world().resource::<VfxMeshHandle>().0;
+    app.world()
+        .resource::<Assets<Mesh>>()
+        .get(quad)
+        .unwrap()
+        .count_vertices()
+}
+
+/// Tag and translation Z of each sprite in the batch, in draw order
+fn batched_sprites(app: &App) -> Vec<(u32, f32)> {
+    let mesh = batch_mesh(app);
+    let per_quad = quad_vertices(app);
+    let (
+        Some(VertexAttributeValues::Uint32(tags)),
+        Some(VertexAttributeValues::Float32x3(translations)),
+    ) = (
+        mesh.attribute(ATTRIBUTE_INSTANCE_TAG),
+        mesh.attribute(ATTRIBUTE_INSTANCE_TRANSLATION),
+    )
+    else {
+        panic!("the batch mesh carries tags and translations");
+    };
+    tags.iter()
+        .zip(translations)
+        .step_by(per_quad)
+        .map(|(tag, translation)| (*tag, translation[2]))
+        .collect()
+}
+
+fn batch_visibility(app: &App) -> Visibility {
+    let batch = app.world().resource::<VfxInstanceBatch>().entity;
+    *app.world().get::<Visibility>(batch).unwrap()
+}
+
+#[test]
+fn instanced_entities_are_drawn_from_one_batch_mesh() {
+    let mut app = instancing_app();
+    app.advance(TEST_TIMESTEP);
+    assert_eq!(batch_visibility(&app), Visibility::Hidden);
+
+    let front = app
+        .world_mut()
+        .spawn((VfxInstanced, Transform::from_xyz(10.0, 0.0, 2.0)))
+        .id();
+    let back = app
+        .world_mut()
+        .spawn((VfxInstanced, Transform::from_xyz(-10.0, 0.0, 1.0)))
+        .id();
+    app.advance(TEST_TIMESTEP);
+
+    // No mesh of their own, both in the batch back to front
+    for entity in [front, back] {
+        assert!(app.world().get::<Mesh2d>(entity).is_none());
+    }
+    let (front_tag, back_tag) = (
+        app.vfx_slot(front).unwrap() as u32,
+        app.vfx_slot(back).unwrap() as u32,
+    );
+    assert_eq!(batched_sprites(&app), [(back_tag, 1.0), (front_tag, 2.0)]);
+    assert_eq!(batch_visibility(&app), Visibility::Inherited);
+
+    // Moving one in front of the other reorders the batch
+    app.world_mut()
+        .get_mut::<Transform>(back)
+        .unwrap()
+        .translation
+        .z = 3.0;
+    app.advance(TEST_TIMESTEP);
+    assert_eq!(batched_sprites(&app), [(front_tag, 2.0), (back_tag, 3.0)]);
+
+    // Hidden and despawned sprites leave it, an empty batch is hidden
+    *app.world_mut().get_mut::<Visibility>(front).unwrap() = Visibility::Hidden;
+    app.advance(TEST_TIMESTEP);
+    assert_eq!(batched_sprites(&app), [(back_tag, 3.0)]);
+    app.world_mut().despawn(back);
+    app.advance(TEST_TIMESTEP);
+    assert_eq!(batch_visibility(&app), Visibility::Hidden);
+}
+
+#[test]
+fn removing_vfx_instanced_restores_the_entity_mesh() {
+    let mut app = instancing_app();
+    app.advance(TEST_TIMESTEP);
+    let entity = app.world_mut().spawn(VfxInstanced).id();
+    app.advance(TEST_TIMESTEP);
+    assert!(app.world().get::<Mesh2d>(entity).is_none());
+
+    app.world_mut().entity_mut(entity).remove::<VfxInstanced>();
+    app.advance(TEST_TIMESTEP);
+    let quad = app.world().resource::<VfxMeshHandle>().0.clone();
+    assert_eq!(app.world().get::<Mesh2d>(entity).unwrap().0, quad);
+    assert!(
+        app.world()
+            .get::<MeshMaterial2d<VfxMaterial>>(entity)
+            .is_some()
+    );
+    assert_eq!(batch_visibility(&app), Visibility::Hidden);
+}
+
+#[test]
+fn instanced_entities_keep_their_mesh_without_instancing() {
+    let mut app = test_app();
+    app.advance(TEST_TIMESTEP);
+    let entity = app.world_mut().spawn(VfxInstanced).id();
+    app.advance(TEST_TIMESTEP);
+    assert!(app.world().get::<Mesh2d>(entity).is_some());
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use bevy_hirundo::{assets::VfxLevelError, prelude::*};
+
+fn entity(level: &VfxLevel, name: &str) -> LevelEntity {
+    level
+        .entities
+        .iter()
+        .find(|entity| entity.name == name)
+        .cloned()
+        .unwrap_or_else(|| panic!("no entity named {name}"))
+}
+
+#[test]
+fn ldtk_entities_are_centered_below_the_level_origin() {
+    let level = VfxLevel::from_ldtk(include_bytes!("fixtures/village.ldtk")).unwrap();
+    assert_eq!(level.entities.len(), 3);
+
+    // Pivoted at its bottom center, its tile in the second column of the third row
+    assert_eq!(
+        entity(&level, "Torch"),
+        LevelEntity {
+            name: "Torch".into(),
+            position: Vec2::new(136.0, -90.0),
+            sprite: LevelSprite::Index(9),
+            effect: Some("flicker".into()),
+            broadcast: true,
+        }
+    );
+    assert_eq!(
+        entity(&level, "Sign"),
+        LevelEntity {
+            name: "Sign".into(),
+            position: Vec2::new(112.0, -66.0),
+            sprite: LevelSprite::Name("sign_glow".into()),
+            effect: None,
+            broadcast: false,
+        }
+    );
+}
+
+#[test]
+fn ldtk_sprite_fields_override_the_tile() {
+    let level = VfxLevel::from_ldtk(include_bytes!("fixtures/village.ldtk")).unwrap();
+    let crate_entity = entity(&level, "Crate");
+    assert_eq!(crate_entity.position, Vec2::new(168.0, -58.0));
+    assert_eq!(crate_entity.sprite, LevelSprite::Index(12));
+    // An empty effect field plays nothing
+    assert_eq!(crate_entity.effect, None);
+}
+
+#[test]
+fn ldtk_levels_in_separate_files_are_rejected() {
+    let level = VfxLevel::from_ldtk(include_bytes!("fixtures/external_levels.ldtk"));
+    assert!(matches!(level, Err(VfxLevelError::ExternalLevels)));
+}
+
+#[test]
+fn tiled_objects_are_read_from_object_and_group_layers() {
+    let level = VfxLevel::from_tiled(include_bytes!("fixtures/village.tmj")).unwrap();
+    assert_eq!(level.entities.len(), 3);
+
+    // A tile object, placed by its bottom-left corner, flipped, from the second tileset
+    assert_eq!(
+        entity(&level, "lamp"),
+        LevelEntity {
+            name: "lamp".into(),
+            position: Vec2::new(50.0, -56.0),
+            sprite: LevelSprite::Index(5),
+            effect: Some("glow".into()),
+            broadcast: false,
+        }
+    );
+    // Unnamed objects go by their class
+    assert_eq!(
+        entity(&level, "Spark"),
+        LevelEntity {
+            name: "Spark".into(),
+            position: Vec2::new(14.0, -4.0),
+            sprite: LevelSprite::Index(3),
+            effect: None,
+            broadcast: true,
+        }
+    );
+    // Group offsets add up; a point object without a tile or sprite field
+    assert_eq!(
+        entity(&level, "bird"),
+        LevelEntity {
+            name: "bird".into(),
+            position: Vec2::new(105.0, -25.0),
+            sprite: LevelSprite::Index(0),
+            effect: None,
+            broadcast: false,
+        }
+    );
+}
+
+#[test]
+fn malformed_levels_are_rejected() {
+    assert!(matches!(
+        VfxLevel::from_tiled(br#"{"layers": 3}"#),
+        Err(VfxLevelError::Json(_))
+    ));
+    assert!(matches!(
+        VfxLevel::from_ldtk(br#"{"levels": []}"#),
+        Err(VfxLevelError::Json(_))
+    ));
+}
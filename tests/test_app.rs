@@ -0,0 +1,322 @@
+use bevy::prelude::*;
+use bevy_hirundo::{
+    materials::{VfxMaterial, VfxStorage, VfxStorageKind},
+    prelude::*,
+    resources::{BroadcastTintMeshes, VfxMaterialHandle, VfxMeshHandle},
+    testing::*,
+};
+
+fn active_effects(app: &App, entity: Entity) -> usize {
+    app.world()
+        .get::<Vfx>(entity)
+        .unwrap()
+        .effects()
+        .active_count()
+}
+
+/// The first active effect on `entity`
+fn active_effect(app: &App, entity: Entity) -> Effect {
+    *app.world()
+        .get::<Vfx>(entity)
+        .unwrap()
+        .effects()
+        .iter_active()
+        .next()
+        .unwrap()
+}
+
+#[test]
+fn pushed_effect_uploads_its_slot_and_expires_on_schedule() {
+    let mut app = test_app();
+    let entity = app.world_mut().spawn(Vfx::default()).id();
+    app.advance(TEST_TIMESTEP);
+    let slot = app
+        .vfx_slot(entity)
+        .expect("Vfx entities get a storage slot");
+
+    let now = app.elapsed_secs();
+    app.world_mut()
+        .get_mut::<Vfx>(entity)
+        .unwrap()
+        .push_effect(EffectBuilder::one_shot(now, 0.5).alpha_zero().build());
+    app.advance(TEST_TIMESTEP);
+    assert!(app.uploaded_slots().contains(&slot));
+    assert_eq!(app.stored_stack(entity).unwrap().active_count(), 1);
+
+    // Still playing just before the end
+    app.advance_by(0.4, TEST_TIMESTEP);
+    assert_eq!(active_effects(&app, entity), 1);
+
+    // Pruned once past its duration, without re-uploading the slot
+    app.advance_by(0.2, TEST_TIMESTEP);
+    assert_eq!(active_effects(&app, entity), 0);
+    assert!(!app.uploaded_slots().contains(&slot));
+}
+
+#[test]
+#[should_panic(expected = "positive step")]
+fn advance_by_rejects_a_zero_step() {
+    test_app().advance_by(1.0, 0.0);
+}
+
+#[test]
+fn finished_fades_despawn_without_waiting_for_the_prune_interval() {
+    let mut app = test_app_with(HirundoPlugin::default().with_prune_interval(10.0));
+    app.advance(TEST_TIMESTEP);
+    let now = app.elapsed_secs();
+    let mut vfx = Vfx::default();
+    vfx.push_effect(EffectBuilder::fade_out(now, 0.2).build());
+    let entity = app
+        .world_mut()
+        .spawn((vfx, DespawnWhenEffectsFinish::default()))
+        .id();
+
+    app.advance_by(0.1, TEST_TIMESTEP);
+    assert!(app.world().get_entity(entity).is_ok());
+
+    // Long before the next prune pass
+    app.advance_by(0.15, TEST_TIMESTEP);
+    assert!(app.world().get_entity(entity).is_err());
+}
+
+#[test]
+fn finished_fades_despawn_between_fixed_steps() {
+    let mut app = test_app_with(HirundoPlugin::default().with_fixed_update());
+    app.world_mut()
+        .resource_mut::<Time<Fixed>>()
+        .set_timestep_seconds(10.0);
+    app.advance(TEST_TIMESTEP);
+    let now = app.elapsed_secs();
+    let mut vfx = Vfx::default();
+    vfx.push_effect(EffectBuilder::fade_out(now, 0.2).build());
+    let entity = app
+        .world_mut()
+        .spawn((vfx, DespawnWhenEffectsFinish::default()))
+        .id();
+
+    // No fixed step runs before the fade ends
+    app.advance_by(0.25, TEST_TIMESTEP);
+    assert!(app.world().get_entity(entity).is_err());
+}
+
+#[test]
+fn effect_asset_players_retire_stale_handles() {
+    let mut app = test_app();
+    let asset = EffectAsset::from_effect(&EffectBuilder::looping(0.0, 1.0).alpha_zero().build());
+    let asset = app
+        .world_mut()
+        .resource_mut::<Assets<EffectAsset>>()
+        .add(asset);
+    let entity = app.world_mut().spawn(Vfx::default()).id();
+    app.advance(TEST_TIMESTEP);
+
+    // A short effect whose slot the asset's effect reuses once it's pruned
+    let now = app.elapsed_secs();
+    let stale = app
+        .world_mut()
+        .get_mut::<Vfx>(entity)
+        .unwrap()
+        .push_effect(EffectBuilder::one_shot(now, 0.1).alpha_zero().build());
+    app.advance_by(0.2, TEST_TIMESTEP);
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(EffectAssetPlayer::new(asset.clone()));
+    app.advance(TEST_TIMESTEP);
+    let vfx = app.world().get::<Vfx>(entity).unwrap();
+    assert_eq!(vfx.effects().active_count(), 1);
+    assert!(vfx.effect(stale).is_none());
+    let start_time = active_effect(&app, entity).lifetime().start_time;
+
+    // Hot reload swaps the effect in place
+    app.world_mut()
+        .resource_mut::<Assets<EffectAsset>>()
+        .get_mut(&asset)
+        .unwrap()
+        .duration = 2.0;
+    // `AssetEvent`s are sent in `PostUpdate`, the player reads them the frame after
+    app.advance(TEST_TIMESTEP);
+    app.advance(TEST_TIMESTEP);
+    assert_eq!(active_effects(&app, entity), 1);
+    let reloaded = active_effect(&app, entity);
+    assert_eq!(reloaded.lifetime().duration, 2.0);
+    assert_eq!(reloaded.lifetime().start_time, start_time);
+}
+
+#[test]
+fn storage_kind_picked_before_startup_decides_the_bindings() {
+    let mut app = test_app();
+    // What `HirundoPlugin::finish` inserts on an adapter without storage buffers
+    app.insert_resource(VfxStorageKind::Textures);
+    let entity = app.world_mut().spawn(Vfx::default()).id();
+    app.advance(TEST_TIMESTEP);
+
+    let handle = app.world().resource::<VfxMaterialHandle>().0.clone();
+    let materials = app.world().resource::<Assets<VfxMaterial>>();
+    let material = materials.get(&handle).unwrap();
+    assert!(matches!(material.effect_storage, VfxStorage::Texture(_)));
+    assert!(matches!(material.sprite_rects, VfxStorage::Texture(_)));
+    assert!(material.shader_key.data_textures);
+
+    // Uploads still go through, as texture rows
+    let now = app.elapsed_secs();
+    app.world_mut()
+        .get_mut::<Vfx>(entity)
+        .unwrap()
+        .push_effect(EffectBuilder::one_shot(now, 0.5).alpha_zero().build());
+    app.advance(TEST_TIMESTEP);
+    assert!(
+        app.uploaded_slots()
+            .contains(&app.vfx_slot(entity).unwrap())
+    );
+}
+
+#[test]
+fn cpu_audio_waves_follow_the_driver_levels_drawn_this_frame() {
+    let mut app = test_app();
+    app.advance(TEST_TIMESTEP);
+    {
+        let mut driver = app.world_mut().resource_mut::<VfxAudioDriver>();
+        driver.set_amplitude(0.5);
+        driver.trigger_beat();
+    }
+    app.advance(TEST_TIMESTEP);
+
+    // The beat already faded for the next frame, the levels keep what was drawn
+    let driver = app.world().resource::<VfxAudioDriver>();
+    assert!(driver.beat < 1.0);
+    let levels = driver.levels();
+    assert_eq!(Wave::audio(2.0, 0.1).evaluate(0.5, &levels), 1.1);
+    assert_eq!(Wave::beat(1.0, 0.0).evaluate(0.5, &levels), 1.0);
+    assert_eq!(
+        Wave::audio(2.0, 0.1).evaluate(0.5, &VfxAudioUniform::default()),
+        0.1
+    );
+}
+
+#[test]
+fn broadcast_tints_carry_over_promotion_and_demotion() {
+    let mut app = test_app();
+    app.advance(TEST_TIMESTEP);
+    let red = Color::srgb(1.0, 0.3, 0.3);
+    let mesh = app.world().resource::<VfxMeshHandle>().0.clone();
+    let material = app.world().resource::<VfxBroadcastMaterialHandle>().0.clone();
+    let tile = app
+        .world_mut()
+        .spawn((Mesh2d(mesh), MeshMaterial2d(material), BroadcastTint(red)))
+        .id();
+    app.advance(TEST_TIMESTEP);
+
+    app.world_mut()
+        .commands()
+        .entity(tile)
+        .queue(PromoteToVfx::new());
+    app.advance(TEST_TIMESTEP);
+    let promoted = app.world().entity(tile);
+    assert!(!promoted.contains::<BroadcastTint>());
+    assert!(!promoted.contains::<VfxBroadcast>());
+    assert_eq!(promoted.get::<VfxTint>(), Some(&VfxTint(red)));
+    assert_eq!(app.stored_stack(tile).unwrap().base_tint, red.to_linear().to_vec4());
+
+    app.world_mut()
+        .commands()
+        .entity(tile)
+        .queue(DemoteToBroadcast::new());
+    app.advance(TEST_TIMESTEP);
+    let demoted = app.world().entity(tile);
+    assert!(!demoted.contains::<VfxTint>());
+    assert_eq!(demoted.get::<BroadcastTint>(), Some(&BroadcastTint(red)));
+    let tint_mesh = app.world().resource::<BroadcastTintMeshes>()
+        [&red.to_linear().to_f32_array().map(f32::to_bits)]
+        .clone();
+    assert_eq!(demoted.get::<Mesh2d>().unwrap().0, tint_mesh);
+}
+
+#[test]
+fn demotion_drops_vfx_only_components() {
+    let mut app = test_app();
+    app.advance(TEST_TIMESTEP);
+    let now = app.elapsed_secs();
+    let tile = app
+        .world_mut()
+        .spawn((
+            Vfx::default(),
+            SpriteAnimation::range(0, 4, 8.0),
+            VfxOnSpawn::from(EffectBuilder::looping(now, 1.0).alpha_zero().build()),
+            DespawnWhenEffectsFinish::default(),
+        ))
+        .id();
+    app.advance(TEST_TIMESTEP);
+
+    app.world_mut()
+        .commands()
+        .entity(tile)
+        .queue(DemoteToBroadcast::new());
+    app.advance_by(0.5, TEST_TIMESTEP);
+    let demoted = app.world().entity(tile);
+    assert!(demoted.contains::<VfxBroadcast>());
+    assert!(!demoted.contains::<Vfx>());
+    assert!(!demoted.contains::<SpriteAnimation>());
+    assert!(!demoted.contains::<VfxOnSpawn>());
+    assert!(!demoted.contains::<DespawnWhenEffectsFinish>());
+}
+
+#[test]
+fn pooled_and_3d_entities_are_not_demoted() {
+    let mut app = test_app_with(HirundoPlugin::default().with_vfx_pool(1));
+    app.advance(TEST_TIMESTEP);
+    let pooled = app
+        .world_mut()
+        .resource_scope(|world, mut pool: Mut<VfxPool>| {
+            pool.spawn(&mut world.commands(), Vfx::default(), Transform::default())
+        });
+    let billboard = app.world_mut().spawn(Vfx3d).id();
+    app.advance(TEST_TIMESTEP);
+
+    for entity in [pooled, billboard] {
+        app.world_mut()
+            .commands()
+            .entity(entity)
+            .queue(DemoteToBroadcast::new());
+    }
+    app.advance(TEST_TIMESTEP);
+    for entity in [pooled, billboard] {
+        let entity = app.world().entity(entity);
+        assert!(entity.contains::<Vfx>());
+        assert!(!entity.contains::<VfxBroadcast>());
+    }
+    assert!(app.world().entity(billboard).contains::<Mesh3d>());
+}
+
+#[test]
+fn pooled_entities_retire_handles_from_their_previous_use() {
+    let mut app = test_app_with(HirundoPlugin::default().with_vfx_pool(1));
+    app.advance(TEST_TIMESTEP);
+    let spawn = |app: &mut App, vfx: Vfx| {
+        let entity = app
+            .world_mut()
+            .resource_scope(|world, mut pool: Mut<VfxPool>| {
+                pool.spawn(&mut world.commands(), vfx, Transform::default())
+            });
+        app.world_mut().flush();
+        entity
+    };
+
+    let now = app.elapsed_secs();
+    let entity = spawn(&mut app, Vfx::default());
+    let old = app
+        .world_mut()
+        .get_mut::<Vfx>(entity)
+        .unwrap()
+        .push_effect(EffectBuilder::one_shot(now, 0.1).alpha_zero().build());
+    // Expired and handed back to the pool
+    app.advance_by(0.2, TEST_TIMESTEP);
+    assert_eq!(app.world().resource::<VfxPool>().available(), 1);
+
+    let now = app.elapsed_secs();
+    let mut vfx = Vfx::default();
+    vfx.push_effect(EffectBuilder::looping(now, 1.0).alpha_zero().build());
+    assert_eq!(spawn(&mut app, vfx), entity);
+    let vfx = app.world().get::<Vfx>(entity).unwrap();
+    assert_eq!(vfx.effects().active_count(), 1);
+    assert!(vfx.effect(old).is_none());
+}